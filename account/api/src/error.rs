@@ -32,6 +32,12 @@ pub enum AccountError {
     // DecryptPrivateKeyError(anyhow::Error),
     #[error("no private key data associate with address {0}")]
     AccountPrivateKeyMissing(AccountAddress),
+    #[error("hd wallet already exists")]
+    HdWalletAlreadyExist,
+    #[error("hd wallet not exists")]
+    HdWalletNotExist,
+    #[error("invalid mnemonic: {0:?}")]
+    InvalidMnemonic(anyhow::Error),
     #[error("account vault store error, {0:?}")]
     StoreError(#[from] anyhow::Error),
 }
@@ -3,10 +3,12 @@
 
 pub mod error;
 pub mod message;
+mod multisig;
 mod provider;
 mod rich_wallet;
 mod service;
 mod types;
+pub use multisig::*;
 pub use provider::*;
 pub use rich_wallet::*;
 pub use service::*;
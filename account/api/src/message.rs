@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::AccountInfo;
+use crate::{AccountInfo, MnemonicAccountInfo, MultisigTransaction};
 use anyhow::Result;
 use starcoin_service_registry::ServiceRequest;
 use starcoin_types::account_address::AccountAddress;
@@ -13,6 +13,16 @@ use std::time::Duration;
 #[derive(Debug, Clone)]
 pub enum AccountRequest {
     CreateAccount(String),
+    CreateMnemonicAccount {
+        password: String,
+        word_count: usize,
+    },
+    ImportMnemonic {
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    },
+    DeriveAccount(String),
     GetDefaultAccount(),
     SetDefaultAccount(AccountAddress),
     RemoveAccount(AccountAddress, Option<String>),
@@ -26,6 +36,7 @@ pub enum AccountRequest {
         signer: AccountAddress,
         message: SigningMessage,
     },
+    SignMultisigTxn(Box<MultisigTransaction>),
     AccountAcceptedTokens {
         address: AccountAddress,
     },
@@ -58,11 +69,13 @@ impl ServiceRequest for AccountRequest {
 pub enum AccountResponse {
     AccountInfo(Box<AccountInfo>),
     AccountInfoOption(Box<Option<AccountInfo>>),
+    MnemonicAccountInfo(Box<MnemonicAccountInfo>),
     AccountList(Vec<AccountInfo>),
     SignedTxn(Box<SignedUserTransaction>),
     UnlockAccountResponse,
     ExportAccountResponse(Vec<u8>),
     AcceptedTokens(Vec<TokenCode>),
     SignedMessage(Box<SignedMessage>),
+    MultisigTxn(Box<MultisigTransaction>),
     None,
 }
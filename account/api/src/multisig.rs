@@ -0,0 +1,277 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The container of a partially signed multisig transaction, passed between the participants of
+//! a multisig account until enough signatures are collected.
+//!
+//! The container is encoded as BCS (the default, usually in a `.multisig` file) or as JSON,
+//! both encode the same fields:
+//!
+//! - `version`: format version of the container, currently `1`.
+//! - `raw_txn`: the `RawUserTransaction` to sign, its sender is the multisig account.
+//! - `signers`: the ed25519 public keys of the multisig account, in the order of the
+//!   account's `MultiEd25519PublicKey`.
+//! - `threshold`: number of signatures required to submit the transaction.
+//! - `signatures`: collected signatures of the raw txn, keyed by the index of the signer in `signers`.
+
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use starcoin_crypto::multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature};
+use starcoin_crypto::traits::Signature;
+use starcoin_crypto::ValidCryptoMaterial;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use std::collections::BTreeMap;
+
+pub const MULTISIG_TXN_VERSION: u8 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MultisigTransaction {
+    version: u8,
+    raw_txn: RawUserTransaction,
+    signers: Vec<Ed25519PublicKey>,
+    threshold: u8,
+    signatures: BTreeMap<u8, Ed25519Signature>,
+}
+
+impl MultisigTransaction {
+    pub fn new(raw_txn: RawUserTransaction, public_key: &MultiEd25519PublicKey) -> Self {
+        Self {
+            version: MULTISIG_TXN_VERSION,
+            raw_txn,
+            signers: public_key.public_keys().clone(),
+            threshold: *public_key.threshold(),
+            signatures: BTreeMap::new(),
+        }
+    }
+
+    /// Convert a multisig txn signed by some participants, such as the files generated by
+    /// `account sign-multisig-txn`, into a container.
+    pub fn from_signed_txn(txn: &SignedUserTransaction) -> Result<Self> {
+        match txn.authenticator() {
+            TransactionAuthenticator::MultiEd25519 { public_key, .. } => {
+                let mut container = Self::new(txn.raw_txn().clone(), &public_key);
+                container.add_signed_txn(txn)?;
+                Ok(container)
+            }
+            TransactionAuthenticator::Ed25519 { .. } => {
+                bail!("Txn {} is not a multisig txn", txn.id())
+            }
+        }
+    }
+
+    /// Decode the container from BCS or JSON bytes.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let is_json = bytes
+            .iter()
+            .find(|b| !b.is_ascii_whitespace())
+            .map(|b| *b == b'{')
+            .unwrap_or(false);
+        let container: Self = if is_json {
+            serde_json::from_slice(bytes)?
+        } else {
+            bcs_ext::from_bytes(bytes)?
+        };
+        container.verify()?;
+        Ok(container)
+    }
+
+    pub fn to_bcs(&self) -> Result<Vec<u8>> {
+        bcs_ext::to_bytes(self)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn raw_txn(&self) -> &RawUserTransaction {
+        &self.raw_txn
+    }
+
+    pub fn sender(&self) -> AccountAddress {
+        self.raw_txn.sender()
+    }
+
+    pub fn signers(&self) -> &[Ed25519PublicKey] {
+        self.signers.as_slice()
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn multi_public_key(&self) -> Result<MultiEd25519PublicKey> {
+        Ok(MultiEd25519PublicKey::new(
+            self.signers.clone(),
+            self.threshold,
+        )?)
+    }
+
+    /// The signers who have signed the txn.
+    pub fn signed(&self) -> Vec<Ed25519PublicKey> {
+        self.signatures
+            .keys()
+            .map(|index| self.signers[*index as usize].clone())
+            .collect()
+    }
+
+    /// The signers who have not signed the txn yet.
+    pub fn unsigned(&self) -> Vec<Ed25519PublicKey> {
+        self.signers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.signatures.contains_key(&(*index as u8)))
+            .map(|(_, signer)| signer.clone())
+            .collect()
+    }
+
+    pub fn signature_count(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_enough(&self) -> bool {
+        self.signatures.len() >= self.threshold as usize
+    }
+
+    pub fn is_signer(&self, public_key: &Ed25519PublicKey) -> bool {
+        self.signer_index(public_key).is_some()
+    }
+
+    fn signer_index(&self, public_key: &Ed25519PublicKey) -> Option<u8> {
+        self.signers
+            .iter()
+            .position(|signer| signer == public_key)
+            .map(|index| index as u8)
+    }
+
+    /// Add the signature of `public_key`, return false if the signer has signed already.
+    pub fn add_signature(
+        &mut self,
+        public_key: &Ed25519PublicKey,
+        signature: Ed25519Signature,
+    ) -> Result<bool> {
+        let index = self.signer_index(public_key).ok_or_else(|| {
+            format_err!(
+                "Public key {} is not a signer of the multisig txn",
+                hex::encode(public_key.to_bytes())
+            )
+        })?;
+        if self.signatures.contains_key(&index) {
+            return Ok(false);
+        }
+        signature.verify(&self.raw_txn, public_key)?;
+        self.signatures.insert(index, signature);
+        Ok(true)
+    }
+
+    /// Add the signatures of a txn signed by a signer, or by some signers of the multisig account.
+    /// Return the count of new signatures.
+    pub fn add_signed_txn(&mut self, txn: &SignedUserTransaction) -> Result<usize> {
+        ensure!(
+            txn.raw_txn() == &self.raw_txn,
+            "Txn {} does not match the multisig txn",
+            txn.id()
+        );
+        match txn.authenticator() {
+            TransactionAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            } => Ok(self.add_signature(&public_key, signature)? as usize),
+            TransactionAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            } => {
+                ensure!(
+                    public_key.public_keys() == &self.signers
+                        && *public_key.threshold() == self.threshold,
+                    "Txn {} is signed by another multisig account",
+                    txn.id()
+                );
+                self.add_multi_signature(&signature)
+            }
+        }
+    }
+
+    /// Add the signatures in a multi signature, the signer of a signature is found by verifying it.
+    fn add_multi_signature(&mut self, signature: &MultiEd25519Signature) -> Result<usize> {
+        let mut added = 0;
+        for signature in signature.signatures() {
+            let signer = self
+                .unsigned()
+                .into_iter()
+                .find(|signer| signature.verify(&self.raw_txn, signer).is_ok());
+            if let Some(signer) = signer {
+                self.add_signature(&signer, signature.clone())?;
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Merge the signatures collected by another container of the same txn.
+    /// Return the count of new signatures.
+    pub fn merge(&mut self, other: &MultisigTransaction) -> Result<usize> {
+        ensure!(
+            other.raw_txn == self.raw_txn,
+            "Can not merge containers of different txns"
+        );
+        ensure!(
+            other.signers == self.signers && other.threshold == self.threshold,
+            "Can not merge containers of different multisig accounts"
+        );
+        let mut added = 0;
+        for (index, signature) in &other.signatures {
+            let signer = other.signers[*index as usize].clone();
+            if self.add_signature(&signer, signature.clone())? {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Check the container is well formed and every signature is valid.
+    pub fn verify(&self) -> Result<()> {
+        ensure!(
+            self.version == MULTISIG_TXN_VERSION,
+            "Unsupported multisig txn version {}",
+            self.version
+        );
+        self.multi_public_key()?;
+        for (index, signature) in &self.signatures {
+            let signer = self
+                .signers
+                .get(*index as usize)
+                .ok_or_else(|| format_err!("Signature index {} out of signers range", index))?;
+            signature.verify(&self.raw_txn, signer)?;
+        }
+        Ok(())
+    }
+
+    /// Build the txn to submit, require the threshold is met.
+    pub fn into_signed_txn(self) -> Result<SignedUserTransaction> {
+        ensure!(
+            self.is_enough(),
+            "Multisig txn requires {} signatures, but only {} collected",
+            self.threshold,
+            self.signatures.len()
+        );
+        let public_key = self.multi_public_key()?;
+        let signature = MultiEd25519Signature::new(
+            self.signatures
+                .into_iter()
+                .map(|(index, signature)| (signature, index))
+                .collect(),
+        )?;
+        Ok(SignedUserTransaction::multi_ed25519(
+            self.raw_txn,
+            public_key,
+            signature,
+        ))
+    }
+}
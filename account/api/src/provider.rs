@@ -1,4 +1,4 @@
-use crate::AccountInfo;
+use crate::{AccountInfo, MnemonicAccountInfo, MultisigTransaction};
 use anyhow::Result;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
@@ -19,6 +19,24 @@ impl Default for AccountProviderStrategy {
 pub trait AccountProvider {
     fn create_account(&self, password: String) -> Result<AccountInfo>;
 
+    /// Create the HD wallet with a new mnemonic of `word_count` words, and the first account of it.
+    fn create_mnemonic_account(
+        &self,
+        password: String,
+        word_count: usize,
+    ) -> Result<MnemonicAccountInfo>;
+
+    /// Recover the HD wallet from the mnemonic, and restore the first `account_count` accounts.
+    fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    ) -> Result<Vec<AccountInfo>>;
+
+    /// Derive the next account of the HD wallet.
+    fn derive_account(&self, password: String) -> Result<AccountInfo>;
+
     fn get_default_account(&self) -> Result<Option<AccountInfo>>;
     fn set_default_account(&self, address: AccountAddress) -> Result<AccountInfo>;
     fn get_accounts(&self) -> Result<Vec<AccountInfo>>;
@@ -37,6 +55,10 @@ pub trait AccountProvider {
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> Result<SignedUserTransaction>;
+
+    /// Co-sign the multisig txn with the unlocked accounts which hold keys of its signers.
+    fn sign_multisig_txn(&self, txn: MultisigTransaction) -> Result<MultisigTransaction>;
+
     fn unlock_account(
         &self,
         address: AccountAddress,
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::message::{AccountRequest, AccountResponse};
-use crate::AccountInfo;
+use crate::{AccountInfo, MnemonicAccountInfo, MultisigTransaction};
 use anyhow::Result;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
 use starcoin_types::account_address::AccountAddress;
@@ -16,6 +16,24 @@ pub trait AccountAsyncService:
 {
     async fn create_account(&self, password: String) -> Result<AccountInfo>;
 
+    /// Create the HD wallet with a new mnemonic of `word_count` words, and the first account of it.
+    async fn create_mnemonic_account(
+        &self,
+        password: String,
+        word_count: usize,
+    ) -> Result<MnemonicAccountInfo>;
+
+    /// Recover the HD wallet from the mnemonic, and restore the first `account_count` accounts.
+    async fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    ) -> Result<Vec<AccountInfo>>;
+
+    /// Derive the next account of the HD wallet.
+    async fn derive_account(&self, password: String) -> Result<AccountInfo>;
+
     async fn get_default_account(&self) -> Result<Option<AccountInfo>>;
     async fn set_default_account(&self, address: AccountAddress) -> Result<AccountInfo>;
     async fn get_accounts(&self) -> Result<Vec<AccountInfo>>;
@@ -34,6 +52,10 @@ pub trait AccountAsyncService:
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> Result<SignedUserTransaction>;
+
+    /// Co-sign the multisig txn with the unlocked local accounts which hold keys of its signers.
+    async fn sign_multisig_txn(&self, txn: MultisigTransaction) -> Result<MultisigTransaction>;
+
     async fn unlock_account(
         &self,
         address: AccountAddress,
@@ -88,6 +110,53 @@ where
         }
     }
 
+    async fn create_mnemonic_account(
+        &self,
+        password: String,
+        word_count: usize,
+    ) -> Result<MnemonicAccountInfo> {
+        let response = self
+            .send(AccountRequest::CreateMnemonicAccount {
+                password,
+                word_count,
+            })
+            .await??;
+        if let AccountResponse::MnemonicAccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    ) -> Result<Vec<AccountInfo>> {
+        let response = self
+            .send(AccountRequest::ImportMnemonic {
+                mnemonic,
+                password,
+                account_count,
+            })
+            .await??;
+        if let AccountResponse::AccountList(accounts) = response {
+            Ok(accounts)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn derive_account(&self, password: String) -> Result<AccountInfo> {
+        let response = self.send(AccountRequest::DeriveAccount(password)).await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn get_default_account(&self) -> Result<Option<AccountInfo>> {
         let response = self.send(AccountRequest::GetDefaultAccount()).await??;
         if let AccountResponse::AccountInfoOption(account) = response {
@@ -161,6 +230,17 @@ where
         }
     }
 
+    async fn sign_multisig_txn(&self, txn: MultisigTransaction) -> Result<MultisigTransaction> {
+        let response = self
+            .send(AccountRequest::SignMultisigTxn(Box::new(txn)))
+            .await??;
+        if let AccountResponse::MultisigTxn(txn) = response {
+            Ok(*txn)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn unlock_account(
         &self,
         address: AccountAddress,
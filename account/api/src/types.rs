@@ -68,6 +68,16 @@ impl From<&Account> for AccountInfo {
     }
 }
 
+pub const DEFAULT_MNEMONIC_WORD_COUNT: usize = 24;
+
+/// The first account of a new HD wallet, with the mnemonic of the wallet.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MnemonicAccountInfo {
+    /// The mnemonic should be backed up by the user, it is only returned once.
+    pub mnemonic: String,
+    pub account: AccountInfo,
+}
+
 #[derive(Clone, Debug)]
 pub struct DefaultAccountChangeEvent {
     pub new_account: AccountInfo,
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use starcoin_account::{account_storage::AccountStorage, AccountManager};
-use starcoin_account_api::{AccountInfo, AccountProvider};
+use starcoin_account_api::{
+    AccountInfo, AccountProvider, MnemonicAccountInfo, MultisigTransaction,
+};
 use starcoin_config::RocksdbConfig;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
@@ -30,6 +32,40 @@ impl AccountProvider for AccountLocalProvider {
         Ok(account_info)
     }
 
+    fn create_mnemonic_account(
+        &self,
+        password: String,
+        word_count: usize,
+    ) -> anyhow::Result<MnemonicAccountInfo> {
+        let (mnemonic, account) = self
+            .manager
+            .create_mnemonic_account(password.as_str(), word_count)?;
+        Ok(MnemonicAccountInfo {
+            mnemonic,
+            account: account.info(),
+        })
+    }
+
+    fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    ) -> anyhow::Result<Vec<AccountInfo>> {
+        let accounts =
+            self.manager
+                .import_mnemonic(mnemonic.as_str(), password.as_str(), account_count)?;
+        Ok(accounts.iter().map(|account| account.info()).collect())
+    }
+
+    fn derive_account(&self, password: String) -> anyhow::Result<AccountInfo> {
+        let account_info = self
+            .manager
+            .derive_account(password.as_str())
+            .map(|account| account.info())?;
+        Ok(account_info)
+    }
+
     fn get_default_account(&self) -> anyhow::Result<Option<AccountInfo>> {
         self.manager.default_account_info().map_err(|e| e.into())
     }
@@ -68,6 +104,10 @@ impl AccountProvider for AccountLocalProvider {
             .map_err(|e| e.into())
     }
 
+    fn sign_multisig_txn(&self, txn: MultisigTransaction) -> anyhow::Result<MultisigTransaction> {
+        self.manager.sign_multisig_txn(txn).map_err(|e| e.into())
+    }
+
     fn unlock_account(
         &self,
         address: AccountAddress,
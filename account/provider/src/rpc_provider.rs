@@ -1,5 +1,5 @@
-use starcoin_account_api::AccountInfo;
 use starcoin_account_api::AccountProvider;
+use starcoin_account_api::{AccountInfo, MnemonicAccountInfo, MultisigTransaction};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
@@ -24,6 +24,28 @@ impl AccountProvider for AccountRpcProvider {
         self.rpc.account_create(password)
     }
 
+    fn create_mnemonic_account(
+        &self,
+        password: String,
+        word_count: usize,
+    ) -> anyhow::Result<MnemonicAccountInfo> {
+        self.rpc.account_create_mnemonic(password, word_count)
+    }
+
+    fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    ) -> anyhow::Result<Vec<AccountInfo>> {
+        self.rpc
+            .account_import_mnemonic(mnemonic, password, account_count)
+    }
+
+    fn derive_account(&self, password: String) -> anyhow::Result<AccountInfo> {
+        self.rpc.account_derive(password)
+    }
+
     fn get_default_account(&self) -> anyhow::Result<Option<AccountInfo>> {
         self.rpc.account_default()
     }
@@ -57,6 +79,10 @@ impl AccountProvider for AccountRpcProvider {
         self.rpc.account_sign_txn(raw_txn)
     }
 
+    fn sign_multisig_txn(&self, txn: MultisigTransaction) -> anyhow::Result<MultisigTransaction> {
+        self.rpc.account_sign_multisig_txn(txn)
+    }
+
     fn unlock_account(
         &self,
         address: AccountAddress,
@@ -57,6 +57,10 @@ impl EventHandler<Self, ContractEventNotification> for AccountEventService {
         }
 
         for i in item.0 .1.as_ref() {
+            // the accepted token is not rolled back by the removed events.
+            if i.removed {
+                continue;
+            }
             if watched_keys.contains(i.contract_event.key()) {
                 if let Err(e) = self.handle_contract_event(&i.contract_event) {
                     error!(
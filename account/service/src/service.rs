@@ -4,7 +4,7 @@
 use anyhow::Result;
 use starcoin_account::{account_storage::AccountStorage, AccountManager};
 use starcoin_account_api::message::{AccountRequest, AccountResponse};
-use starcoin_account_api::DefaultAccountChangeEvent;
+use starcoin_account_api::{DefaultAccountChangeEvent, MnemonicAccountInfo};
 use starcoin_config::NodeConfig;
 use starcoin_crypto::ValidCryptoMaterial;
 use starcoin_logger::prelude::*;
@@ -108,6 +108,32 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
             AccountRequest::CreateAccount(password) => AccountResponse::AccountInfo(Box::new(
                 self.manager.create_account(password.as_str())?.info(),
             )),
+            AccountRequest::CreateMnemonicAccount {
+                password,
+                word_count,
+            } => {
+                let (mnemonic, account) = self
+                    .manager
+                    .create_mnemonic_account(password.as_str(), word_count)?;
+                AccountResponse::MnemonicAccountInfo(Box::new(MnemonicAccountInfo {
+                    mnemonic,
+                    account: account.info(),
+                }))
+            }
+            AccountRequest::ImportMnemonic {
+                mnemonic,
+                password,
+                account_count,
+            } => AccountResponse::AccountList(
+                self.manager
+                    .import_mnemonic(mnemonic.as_str(), password.as_str(), account_count)?
+                    .iter()
+                    .map(|account| account.info())
+                    .collect(),
+            ),
+            AccountRequest::DeriveAccount(password) => AccountResponse::AccountInfo(Box::new(
+                self.manager.derive_account(password.as_str())?.info(),
+            )),
             AccountRequest::GetDefaultAccount() => {
                 AccountResponse::AccountInfoOption(Box::new(self.manager.default_account_info()?))
             }
@@ -144,6 +170,9 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
             AccountRequest::SignMessage { message, signer } => AccountResponse::SignedMessage(
                 Box::new(self.manager.sign_message(signer, message)?),
             ),
+            AccountRequest::SignMultisigTxn(txn) => {
+                AccountResponse::MultisigTxn(Box::new(self.manager.sign_multisig_txn(*txn)?))
+            }
             AccountRequest::UnlockAccount(address, password, duration) => {
                 let account_info =
                     self.manager
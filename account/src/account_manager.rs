@@ -2,14 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account::Account;
-use crate::account_storage::AccountStorage;
+use crate::account_storage::{AccountStorage, HdWallet};
+use crate::hd_wallet::{derive_private_key, generate_mnemonic, mnemonic_to_seed, DerivationPath};
 use anyhow::format_err;
 use parking_lot::RwLock;
 use rand::prelude::*;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::{AccountInfo, AccountPrivateKey, AccountPublicKey, AccountResult};
+use starcoin_account_api::{
+    AccountInfo, AccountPrivateKey, AccountPublicKey, AccountResult, MultisigTransaction,
+};
 use starcoin_crypto::ed25519::Ed25519PrivateKey;
 use starcoin_crypto::{Uniform, ValidCryptoMaterial};
+use starcoin_decrypt::encrypt;
 use starcoin_logger::prelude::*;
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
@@ -18,6 +22,7 @@ use starcoin_types::{
     account_config::token_code::TokenCode,
     transaction::{RawUserTransaction, SignedUserTransaction},
 };
+use std::cmp;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ops::Add;
@@ -84,6 +89,79 @@ impl AccountManager {
         )
     }
 
+    /// Create the HD wallet with a new mnemonic, and create the first account of the wallet.
+    /// Return the mnemonic and the account, the mnemonic is not stored, only the seed of it.
+    pub fn create_mnemonic_account(
+        &self,
+        password: &str,
+        word_count: usize,
+    ) -> AccountResult<(String, Account)> {
+        if self.store.hd_wallet()?.is_some() {
+            return Err(AccountError::HdWalletAlreadyExist);
+        }
+        let mnemonic = generate_mnemonic(word_count).map_err(AccountError::InvalidMnemonic)?;
+        let mut accounts = self.import_mnemonic(mnemonic.as_str(), password, 1)?;
+        Ok((mnemonic, accounts.remove(0)))
+    }
+
+    /// Recover the HD wallet from the mnemonic, and restore the first `account_count` accounts
+    /// of the wallet, the accounts already in the account storage are skipped.
+    pub fn import_mnemonic(
+        &self,
+        mnemonic: &str,
+        password: &str,
+        account_count: u32,
+    ) -> AccountResult<Vec<Account>> {
+        if self.store.hd_wallet()?.is_some() {
+            return Err(AccountError::HdWalletAlreadyExist);
+        }
+        let seed = mnemonic_to_seed(mnemonic).map_err(AccountError::InvalidMnemonic)?;
+        let mut accounts = vec![];
+        for index in 0..cmp::max(account_count, 1) {
+            let (address, private_key) = derive_account_key(&seed, index)?;
+            if self.contains(&address)? {
+                info!("The account {} of the mnemonic already exists.", address);
+                continue;
+            }
+            accounts.push(self.save_account(
+                address,
+                private_key.public_key(),
+                Some((private_key, password.to_string())),
+            )?);
+        }
+        self.store.update_hd_wallet(HdWallet {
+            encrypted_seed: encrypt(password.as_bytes(), &seed),
+            next_index: cmp::max(account_count, 1),
+        })?;
+        Ok(accounts)
+    }
+
+    /// Derive the next account of the HD wallet, the account is encrypted by the wallet password.
+    pub fn derive_account(&self, password: &str) -> AccountResult<Account> {
+        let mut wallet = self
+            .store
+            .hd_wallet()?
+            .ok_or(AccountError::HdWalletNotExist)?;
+        let seed = self
+            .store
+            .decrypt_hd_wallet_seed(password)?
+            .ok_or(AccountError::HdWalletNotExist)?;
+        // skip the accounts already restored or imported.
+        let (address, private_key) = loop {
+            let (address, private_key) = derive_account_key(&seed, wallet.next_index)?;
+            wallet.next_index += 1;
+            if !self.contains(&address)? {
+                break (address, private_key);
+            }
+        };
+        self.store.update_hd_wallet(wallet)?;
+        self.save_account(
+            address,
+            private_key.public_key(),
+            Some((private_key, password.to_string())),
+        )
+    }
+
     pub fn unlock_account(
         &self,
         address: AccountAddress,
@@ -252,6 +330,40 @@ impl AccountManager {
         }
     }
 
+    /// Co-sign the multisig txn with every unlocked local account which holds keys of the signers:
+    /// the multisig account itself if imported with its key shards, and single key accounts
+    /// whose public key is one of the signers. Locked and readonly accounts are skipped.
+    pub fn sign_multisig_txn(
+        &self,
+        mut txn: MultisigTransaction,
+    ) -> AccountResult<MultisigTransaction> {
+        for account_info in self.list_account_infos()? {
+            let can_sign = match &account_info.public_key {
+                AccountPublicKey::Single(public_key) => txn.is_signer(public_key),
+                AccountPublicKey::Multi(_) => account_info.address == txn.sender(),
+            };
+            if !can_sign || account_info.is_readonly {
+                continue;
+            }
+            let address = account_info.address;
+            let pass = match self.key_cache.write().get_pass(&address) {
+                Some(pass) => pass,
+                None => {
+                    debug!("Skip co-signing multisig txn by locked account {}", address);
+                    continue;
+                }
+            };
+            let account = Account::load(address, Some(pass), self.store.clone())?
+                .ok_or(AccountError::AccountNotExist(address))?;
+            let signed_txn = account
+                .sign_txn(txn.raw_txn().clone())
+                .map_err(AccountError::TransactionSignError)?;
+            txn.add_signed_txn(&signed_txn)
+                .map_err(AccountError::TransactionSignError)?;
+        }
+        Ok(txn)
+    }
+
     pub fn set_default_account(&self, address: AccountAddress) -> AccountResult<AccountInfo> {
         let mut account_info = self
             .account_info(address)?
@@ -351,6 +463,16 @@ impl AccountManager {
     }
 }
 
+/// Derive the key and address of the account at `index` of the HD wallet.
+fn derive_account_key(
+    seed: &[u8],
+    index: u32,
+) -> AccountResult<(AccountAddress, AccountPrivateKey)> {
+    let path = DerivationPath::for_account(index)?;
+    let private_key = AccountPrivateKey::Single(derive_private_key(seed, &path)?);
+    Ok((private_key.public_key().derived_address(), private_key))
+}
+
 pub(crate) fn gen_private_key() -> Ed25519PrivateKey {
     let mut seed_rng = rand::rngs::OsRng;
     let seed_buf: [u8; 32] = seed_rng.gen();
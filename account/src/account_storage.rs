@@ -26,6 +26,7 @@ pub const ENCRYPTED_PRIVATE_KEY_PREFIX_NAME: ColumnFamilyName = "encrypted_priva
 pub const PUBLIC_KEY_PREFIX_NAME: ColumnFamilyName = "public_key";
 pub const ACCEPTED_TOKEN_PREFIX_NAME: ColumnFamilyName = "accepted_token";
 pub const GLOBAL_PREFIX_NAME: ColumnFamilyName = "global";
+pub const HD_WALLET_PREFIX_NAME: ColumnFamilyName = "hd_wallet";
 
 define_storage!(
     AccountSettingStore,
@@ -61,6 +62,8 @@ define_storage!(
     ACCEPTED_TOKEN_PREFIX_NAME
 );
 
+define_storage!(HdWalletStore, HdWalletKey, HdWallet, HD_WALLET_PREFIX_NAME);
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct AcceptedTokens(pub Vec<TokenCode>);
 
@@ -106,6 +109,41 @@ impl ValueCodec for GlobalValue {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum HdWalletKey {
+    /// The wallet is unique for now.
+    Default,
+}
+
+impl KeyCodec for HdWalletKey {
+    fn encode_key(&self) -> Result<Vec<u8>, Error> {
+        self.encode()
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self, Error> {
+        HdWalletKey::decode(data)
+    }
+}
+
+/// The HD wallet, the accounts of the wallet are derived from the seed of the mnemonic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HdWallet {
+    /// The BIP39 seed encrypted by the wallet password.
+    pub encrypted_seed: Vec<u8>,
+    /// The account index of the next derived account.
+    pub next_index: u32,
+}
+
+impl ValueCodec for HdWallet {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        HdWallet::decode(data)
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct AccountAddressWrapper(AccountAddress);
 impl From<AccountAddress> for AccountAddressWrapper {
@@ -186,6 +224,7 @@ pub struct AccountStorage {
     public_key_store: PublicKeyStore,
     global_value_store: GlobalSettingStore,
     accepted_token_store: AcceptedTokenStore,
+    hd_wallet_store: HdWalletStore,
 }
 
 impl AccountStorage {
@@ -198,6 +237,7 @@ impl AccountStorage {
                 PUBLIC_KEY_PREFIX_NAME,
                 ACCEPTED_TOKEN_PREFIX_NAME,
                 GLOBAL_PREFIX_NAME,
+                HD_WALLET_PREFIX_NAME,
             ],
             false,
             rocksdb_config,
@@ -214,6 +254,7 @@ impl AccountStorage {
             private_key_store: PrivateKeyStore::new(store.clone()),
             public_key_store: PublicKeyStore::new(store.clone()),
             accepted_token_store: AcceptedTokenStore::new(store.clone()),
+            hd_wallet_store: HdWalletStore::new(store.clone()),
             global_value_store: GlobalSettingStore::new(store),
         }
     }
@@ -381,4 +422,23 @@ impl AccountStorage {
         }
         Ok(())
     }
+
+    pub fn hd_wallet(&self) -> Result<Option<HdWallet>> {
+        self.hd_wallet_store.get(HdWalletKey::Default)
+    }
+
+    pub fn update_hd_wallet(&self, wallet: HdWallet) -> Result<()> {
+        self.hd_wallet_store.put(HdWalletKey::Default, wallet)
+    }
+
+    /// Decrypt the seed of the HD wallet, return None if the wallet not exists.
+    pub fn decrypt_hd_wallet_seed(&self, password: impl AsRef<str>) -> Result<Option<Vec<u8>>> {
+        match self.hd_wallet()? {
+            None => Ok(None),
+            Some(wallet) => Ok(Some(decrypt(
+                password.as_ref().as_bytes(),
+                &wallet.encrypted_seed,
+            )?)),
+        }
+    }
 }
@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_storage::AccountStorage;
+use crate::hd_wallet::{derive_private_key, DerivationPath};
 use crate::Account;
 use crate::AccountManager;
 use anyhow::Result;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::AccountPublicKey;
+use starcoin_account_api::{AccountPublicKey, MultisigTransaction};
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::keygen::KeyGen;
+use starcoin_crypto::multi_ed25519::MultiEd25519PublicKey;
 use starcoin_crypto::{SigningKey, ValidCryptoMaterial};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
@@ -267,3 +269,110 @@ pub fn test_wallet_account() -> Result<()> {
     println!("txn hash is {:?}", stxn.id());
     Ok(())
 }
+
+#[test]
+pub fn test_slip10_ed25519_derivation() -> Result<()> {
+    // test vector 1 of SLIP-0010 for ed25519.
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f")?;
+    for (path, private_key) in [
+        (
+            "m",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+        ),
+        (
+            "m/0'",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        ),
+        (
+            "m/0'/1'",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        ),
+        (
+            "m/0'/1'/2'",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+        ),
+    ] {
+        let path = DerivationPath::from_str(path)?;
+        let key = derive_private_key(seed.as_slice(), &path)?;
+        assert_eq!(hex::encode(key.to_bytes()), private_key);
+    }
+    assert!(DerivationPath::from_str("m/44'/0").is_err());
+    assert_eq!(
+        DerivationPath::for_account(1)?.to_string(),
+        "m/44'/101010'/1'/0'/0'"
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_mnemonic_account() -> Result<()> {
+    let manager = AccountManager::new(AccountStorage::mock(), ChainId::test())?;
+    let (mnemonic, first) = manager.create_mnemonic_account("hello", 12)?;
+    assert_eq!(mnemonic.split_whitespace().count(), 12);
+    assert!(matches!(
+        manager.create_mnemonic_account("hello", 12),
+        Err(AccountError::HdWalletAlreadyExist)
+    ));
+    assert!(manager.derive_account("wrong password").is_err());
+    let second = manager.derive_account("hello")?;
+
+    // recover the wallet in another storage, the accounts are the same.
+    let recovered_manager = AccountManager::new(AccountStorage::mock(), ChainId::test())?;
+    assert!(matches!(
+        recovered_manager.import_mnemonic("invalid mnemonic", "abc", 1),
+        Err(AccountError::InvalidMnemonic(_))
+    ));
+    let recovered = recovered_manager.import_mnemonic(mnemonic.as_str(), "abc", 2)?;
+    assert_eq!(
+        recovered.iter().map(|a| *a.address()).collect::<Vec<_>>(),
+        vec![*first.address(), *second.address()]
+    );
+    assert_eq!(recovered[0].private_key(), first.private_key());
+    let third = recovered_manager.derive_account("abc")?;
+    assert_eq!(
+        *third.address(),
+        *manager.derive_account("hello")?.address()
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_sign_multisig_txn() -> Result<()> {
+    let manager = AccountManager::new(AccountStorage::mock(), ChainId::test())?;
+    let alice = manager.create_account("alice")?;
+    let bob = manager.create_account("bob")?;
+    let (_private_key, carol) = KeyGen::from_os_rng().generate_keypair();
+    let signer_key = |account: &Account| match account.public_key() {
+        AccountPublicKey::Single(public_key) => public_key,
+        AccountPublicKey::Multi(_) => unreachable!(),
+    };
+    let multi_public_key =
+        MultiEd25519PublicKey::new(vec![signer_key(&alice), signer_key(&bob), carol], 2)?;
+    let sender = AccountPublicKey::Multi(multi_public_key.clone()).derived_address();
+    let raw_txn = RawUserTransaction::mock_by_sender(sender);
+    let txn = MultisigTransaction::new(raw_txn, &multi_public_key);
+
+    // locked accounts do not sign.
+    let txn = manager.sign_multisig_txn(txn)?;
+    assert_eq!(txn.signature_count(), 0);
+
+    manager.unlock_account(*alice.address(), "alice", Duration::from_secs(100))?;
+    let alice_signed = manager.sign_multisig_txn(txn.clone())?;
+    assert_eq!(alice_signed.signed(), vec![signer_key(&alice)]);
+    assert!(!alice_signed.is_enough());
+
+    manager.lock_account(*alice.address())?;
+    manager.unlock_account(*bob.address(), "bob", Duration::from_secs(100))?;
+    let bob_signed =
+        MultisigTransaction::decode(manager.sign_multisig_txn(txn)?.to_json()?.as_bytes())?;
+    assert_eq!(bob_signed.signed(), vec![signer_key(&bob)]);
+
+    let mut merged = MultisigTransaction::decode(alice_signed.to_bcs()?.as_slice())?;
+    assert_eq!(merged.merge(&bob_signed)?, 1);
+    assert_eq!(merged.merge(&bob_signed)?, 0);
+    assert!(merged.is_enough());
+    let signed_txn = merged.clone().into_signed_txn()?;
+    assert_eq!(MultisigTransaction::from_signed_txn(&signed_txn)?, merged);
+    signed_txn.check_signature()?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! BIP39 mnemonic and SLIP-0010 Ed25519 key derivation.
+
+use anyhow::{bail, ensure, format_err, Result};
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha512;
+use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// The registered coin type of Starcoin in SLIP-0044.
+pub const STARCOIN_COIN_TYPE: u32 = 101010;
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// A SLIP-0010 derivation path, Ed25519 only supports hardened derivation,
+/// so every index of the path is hardened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The path of the account at `index` of the wallet: `m/44'/101010'/{index}'/0'/0'`.
+    pub fn for_account(index: u32) -> Result<Self> {
+        ensure!(
+            index < HARDENED_OFFSET,
+            "Account index {} is too large",
+            index
+        );
+        Ok(Self(vec![44, STARCOIN_COIN_TYPE, index, 0, 0]))
+    }
+
+    pub fn indexes(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            bail!("Derivation path {} should start with m", s);
+        }
+        let indexes = parts
+            .map(|part| {
+                let index = part
+                    .strip_suffix('\'')
+                    .or_else(|| part.strip_suffix('h'))
+                    .ok_or_else(|| {
+                        format_err!("Only hardened derivation is supported by Ed25519: {}", s)
+                    })?;
+                let index = u32::from_str(index)?;
+                ensure!(index < HARDENED_OFFSET, "Invalid index {} in {}", index, s);
+                Ok(index)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self(indexes))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Generate a new English mnemonic with `word_count` words.
+pub fn generate_mnemonic(word_count: usize) -> Result<String> {
+    ensure!(
+        matches!(word_count, 12 | 15 | 18 | 21 | 24),
+        "Invalid mnemonic word count {}, should be one of 12, 15, 18, 21, 24",
+        word_count
+    );
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    rand::rngs::OsRng.fill_bytes(&mut entropy);
+    let mnemonic = Mnemonic::from_entropy(&entropy)
+        .map_err(|e| format_err!("Generate mnemonic error: {}", e))?;
+    Ok(mnemonic.to_string())
+}
+
+/// Validate the mnemonic and return the BIP39 seed of it, the BIP39 passphrase is empty.
+pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Vec<u8>> {
+    let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| format_err!("Invalid mnemonic: {}", e))?;
+    Ok(mnemonic.to_seed("").to_vec())
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC can take key of any size");
+    for d in data {
+        mac.update(d);
+    }
+    let mut result = [0u8; 64];
+    result.copy_from_slice(&mac.finalize().into_bytes());
+    result
+}
+
+/// Derive the Ed25519 private key of the `path` from the `seed` by SLIP-0010.
+pub fn derive_private_key(seed: &[u8], path: &DerivationPath) -> Result<Ed25519PrivateKey> {
+    let mut i = hmac_sha512(ED25519_CURVE_SEED, &[seed]);
+    for index in path.indexes() {
+        let (key, chain_code) = i.split_at(32);
+        i = hmac_sha512(
+            chain_code,
+            &[&[0u8], key, &(index | HARDENED_OFFSET).to_be_bytes()],
+        );
+    }
+    Ed25519PrivateKey::try_from(&i[..32]).map_err(|e| format_err!("{:?}", e))
+}
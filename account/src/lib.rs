@@ -7,6 +7,7 @@ mod account_manager;
 pub use account::Account;
 pub use account_manager::AccountManager;
 pub mod account_storage;
+pub mod hd_wallet;
 
 #[cfg(test)]
 mod account_test;
@@ -3,7 +3,7 @@
 
 use anyhow::Error;
 use network_api::ReputationChange;
-use starcoin_types::block::{Block, BlockHeader};
+use starcoin_types::block::{Block, BlockHeader, BlockNumber};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
@@ -61,6 +61,8 @@ pub enum ConnectBlockError {
     ParentNotExist(Box<BlockHeader>),
     #[error("Verify block {0} failed: {1:?}")]
     VerifyBlockFailed(VerifyBlockField, Error),
+    #[error("Block {0:?} forks at block number {1}, the state before pruned block number {2} has been pruned")]
+    ForkPointPruned(Box<BlockHeader>, BlockNumber, BlockNumber),
 }
 
 impl ConnectBlockError {
@@ -70,12 +72,15 @@ impl ConnectBlockError {
         ReputationChange::new_fatal("ParentNotExist");
     pub const REP_VERIFY_BLOCK_FAILED: ReputationChange =
         ReputationChange::new_fatal("VerifyBlockFailed");
+    // the peer may follow another valid branch, do not change the reputation
+    pub const REP_FORK_POINT_PRUNED: ReputationChange = ReputationChange::new(0, "ForkPointPruned");
 
     pub fn reason(&self) -> &str {
         match self {
             ConnectBlockError::FutureBlock(_) => "FutureBlock",
             ConnectBlockError::ParentNotExist(_) => "ParentNotExist",
             ConnectBlockError::VerifyBlockFailed(_, _) => "VerifyBlockFailed",
+            ConnectBlockError::ForkPointPruned(_, _, _) => "ForkPointPruned",
         }
     }
 
@@ -86,6 +91,7 @@ impl ConnectBlockError {
             ConnectBlockError::VerifyBlockFailed(_, _) => {
                 ConnectBlockError::REP_VERIFY_BLOCK_FAILED
             }
+            ConnectBlockError::ForkPointPruned(_, _, _) => ConnectBlockError::REP_FORK_POINT_PRUNED,
         }
     }
 }
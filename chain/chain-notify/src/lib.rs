@@ -9,12 +9,14 @@ use starcoin_logger::prelude::*;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_storage::{Storage, Store};
 use starcoin_types::block::Block;
-use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::system_events::{ChainReorgEvent, NewHeadBlock};
 use std::sync::Arc;
 
 /// ChainNotify watch `NewHeadBlock` message from bus,
 /// and then reproduce `Notification<ThinBlock>` and `Notification<Arc<[Event]>>` message to bus.
 /// User can subscribe the two notification to watch onchain events.
+/// On `ChainReorgEvent`, the events of retracted blocks are reproduced with the `removed` flag,
+/// and the events of enacted blocks except the new head are reproduced too.
 pub struct ChainNotifyHandlerService {
     store: Arc<dyn Store>,
 }
@@ -37,11 +39,13 @@ impl ServiceFactory<Self> for ChainNotifyHandlerService {
 impl ActorService for ChainNotifyHandlerService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<NewHeadBlock>();
+        ctx.subscribe::<ChainReorgEvent>();
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<NewHeadBlock>();
+        ctx.unsubscribe::<ChainReorgEvent>();
         Ok(())
     }
 }
@@ -58,12 +62,35 @@ impl EventHandler<Self, NewHeadBlock> for ChainNotifyHandlerService {
         self.notify_new_block(block, ctx);
 
         // notify events
-        if let Err(e) = self.notify_events(block, self.store.clone(), ctx) {
+        if let Err(e) = self.notify_events(block, self.store.clone(), false, ctx) {
             error!(target: "pubsub", "fail to notify events to client, err: {}", &e);
         }
     }
 }
 
+impl EventHandler<Self, ChainReorgEvent> for ChainNotifyHandlerService {
+    fn handle_event(
+        &mut self,
+        item: ChainReorgEvent,
+        ctx: &mut ServiceContext<ChainNotifyHandlerService>,
+    ) {
+        // remove events from the latest retracted block.
+        for block in item.retracted.iter().rev() {
+            if let Err(e) = self.notify_events(block, self.store.clone(), true, ctx) {
+                error!(target: "pubsub", "fail to notify removed events to client, err: {}", &e);
+            }
+        }
+        // the events of the new head are notified by `NewHeadBlock`.
+        if let Some((_head, enacted)) = item.enacted.split_last() {
+            for block in enacted {
+                if let Err(e) = self.notify_events(block, self.store.clone(), false, ctx) {
+                    error!(target: "pubsub", "fail to notify events to client, err: {}", &e);
+                }
+            }
+        }
+    }
+}
+
 impl ChainNotifyHandlerService {
     pub fn notify_new_block(&self, block: &Block, ctx: &mut ServiceContext<Self>) {
         let thin_block = ThinBlock::new(
@@ -77,6 +104,7 @@ impl ChainNotifyHandlerService {
         &self,
         block: &Block,
         store: Arc<dyn Store>,
+        removed: bool,
         ctx: &mut ServiceContext<Self>,
     ) -> Result<()> {
         let block_number = block.header().number();
@@ -90,7 +118,7 @@ impl ChainNotifyHandlerService {
             // get events directly by txn_info_id
             let events = store.get_contract_events(txn_info_id)?.unwrap_or_default();
             all_events.extend(events.into_iter().enumerate().map(|(idx, evt)| {
-                Event::new(
+                let mut event = Event::new(
                     block_id,
                     block_number,
                     txn_info.transaction_hash(),
@@ -98,7 +126,9 @@ impl ChainNotifyHandlerService {
                     Some(txn_info.transaction_global_index),
                     Some(idx as u32),
                     evt,
-                )
+                );
+                event.removed = removed;
+                event
             }));
         }
         let events_notification: ContractEventNotification =
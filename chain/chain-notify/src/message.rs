@@ -22,6 +22,8 @@ pub struct Event {
     pub transaction_global_index: Option<u64>,
     pub event_index: Option<u32>,
     pub contract_event: ContractEvent,
+    /// The event is removed from the main chain, because its block is retracted by a chain reorg.
+    pub removed: bool,
 }
 
 impl Event {
@@ -42,6 +44,7 @@ impl Event {
             transaction_global_index,
             event_index,
             contract_event,
+            removed: false,
         }
     }
 }
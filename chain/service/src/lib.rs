@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod chain_service;
+mod state_pruner;
 
 pub use chain_service::ChainReaderService;
 pub use starcoin_chain_api::{ChainAsyncService, ReadableChainService, WriteableChainService};
+pub use state_pruner::StatePrunerService;
@@ -0,0 +1,107 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_config::NodeConfig;
+use starcoin_logger::prelude::*;
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_storage::state_node::StatePruneStore;
+use starcoin_storage::{Storage, Store};
+use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::system_events::NewHeadBlock;
+use std::cmp::min;
+use std::sync::Arc;
+
+/// Max blocks pruned on one new head, to avoid blocking the service too long
+/// when the pruner catches up.
+const MAX_PRUNE_BLOCKS_PER_HEAD: u64 = 32;
+
+/// StatePrunerService watch `NewHeadBlock`, and delete the state nodes which become
+/// unreachable by the main chain blocks older than the prune window.
+pub struct StatePrunerService {
+    storage: Arc<Storage>,
+    prune_window: u64,
+}
+
+impl StatePrunerService {
+    pub fn new(storage: Arc<Storage>, prune_window: u64) -> Self {
+        Self {
+            storage,
+            prune_window,
+        }
+    }
+
+    fn prune(&self, head_number: BlockNumber, head_block_info: &BlockInfo) -> Result<()> {
+        let target = match head_number.checked_sub(self.prune_window) {
+            Some(target) => target,
+            None => return Ok(()),
+        };
+        let from = self
+            .storage
+            .get_state_pruned_block_number()?
+            .map(|block_number| block_number.saturating_add(1))
+            .unwrap_or(0);
+        if from > target {
+            return Ok(());
+        }
+        let to = min(target, from.saturating_add(MAX_PRUNE_BLOCKS_PER_HEAD - 1));
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            head_block_info.get_block_accumulator_info().clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let mut pruned = 0;
+        for block_number in from..=to {
+            let block_id = block_accumulator.get_leaf(block_number)?.ok_or_else(|| {
+                format_err!(
+                    "Can not find main chain block id by number {}",
+                    block_number
+                )
+            })?;
+            pruned += self.storage.prune_state_nodes(block_number, block_id)?;
+        }
+        debug!(
+            "[state-pruner] prune state of blocks [{}, {}], delete {} state nodes",
+            from, to, pruned
+        );
+        Ok(())
+    }
+}
+
+impl ServiceFactory<Self> for StatePrunerService {
+    fn create(ctx: &mut ServiceContext<StatePrunerService>) -> Result<StatePrunerService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let prune_window = config
+            .storage
+            .state_prune_window()
+            .ok_or_else(|| format_err!("State pruning is not enabled by config."))?;
+        Ok(Self::new(storage, prune_window))
+    }
+}
+
+impl ActorService for StatePrunerService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for StatePrunerService {
+    fn handle_event(&mut self, item: NewHeadBlock, _ctx: &mut ServiceContext<StatePrunerService>) {
+        let NewHeadBlock(executed_block) = item;
+        if let Err(e) = self.prune(
+            executed_block.header().number(),
+            executed_block.block_info(),
+        ) {
+            error!("[state-pruner] fail to prune state, err: {:?}", e);
+        }
+    }
+}
@@ -41,7 +41,10 @@ use starcoin_vm_types::time::TimeService;
 use std::cmp::min;
 use std::iter::Extend;
 use std::option::Option::{None, Some};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use storage::Store;
 
 pub struct ChainStatusWithBlock {
@@ -428,9 +431,10 @@ impl BlockChain {
         );
 
         watch(CHAIN_WATCH_NAME, "n23");
-        statedb
-            .flush()
+        let stale_state_nodes = statedb
+            .flush_with_stale_nodes()
             .map_err(BlockExecutorError::BlockChainStateErr)?;
+        storage.save_stale_state_nodes(header.number(), block_id, stale_state_nodes)?;
         // If chain state is matched, and accumulator is matched,
         // then, we save flush states, and save block data.
         watch(CHAIN_WATCH_NAME, "n24");
@@ -469,8 +473,10 @@ impl BlockChain {
             "events' length should be equal to txn infos' length"
         );
         let txn_info_ids: Vec<_> = txn_infos.iter().map(|info| info.id()).collect();
-        for (info_id, events) in txn_info_ids.iter().zip(txn_events.into_iter()) {
-            storage.save_contract_events(*info_id, events)?;
+        let txn_events: Vec<_> = txn_info_ids.iter().cloned().zip(txn_events).collect();
+        storage.save_block_event_index(block_id, block.header().number(), txn_events.as_slice())?;
+        for (info_id, events) in txn_events {
+            storage.save_contract_events(info_id, events)?;
         }
 
         storage.save_transaction_infos(
@@ -834,7 +840,7 @@ impl ChainReader for BlockChain {
         let state_proof = if let Some(access_path) = access_path {
             let statedb = self
                 .statedb
-                .fork_at(transaction_info.txn_info().state_root_hash());
+                .try_fork_at(transaction_info.txn_info().state_root_hash())?;
             Some(statedb.get_with_proof(&access_path)?)
         } else {
             None
@@ -859,6 +865,8 @@ impl BlockChain {
             return Ok(vec![]);
         }
 
+        let event_key_index = self.collect_event_key_index(&filter, max_block_number)?;
+
         let (mut cur_block_number, tail) = if reverse {
             (max_block_number, filter.from_block)
         } else {
@@ -866,16 +874,16 @@ impl BlockChain {
         };
         let mut event_with_infos = vec![];
         'outer: loop {
-            let block = self.get_block_by_number(cur_block_number)?.ok_or_else(|| {
+            let block_id = self.get_hash_by_number(cur_block_number)?.ok_or_else(|| {
                 anyhow::anyhow!(format!(
                     "cannot find block({}) on main chain(head: {})",
                     cur_block_number,
                     chain_header.id()
                 ))
             })?;
-            let block_id = block.id();
-            let block_number = block.header().number();
-            let mut txn_info_ids = self.storage.get_block_txn_info_ids(block_id)?;
+            let block_number = cur_block_number;
+            let mut txn_info_ids =
+                self.txn_info_ids_to_filter(block_id, &filter, event_key_index.as_ref())?;
             if reverse {
                 txn_info_ids.reverse();
             }
@@ -907,7 +915,7 @@ impl BlockChain {
                 let filtered_event_with_info =
                     filtered_events.map(|(idx, evt)| ContractEventInfo {
                         block_hash: block_id,
-                        block_number: block.header().number(),
+                        block_number,
                         transaction_hash: txn_info.transaction_hash(),
                         transaction_index: txn_info.transaction_index,
                         transaction_global_index: txn_info.transaction_global_index,
@@ -949,6 +957,62 @@ impl BlockChain {
         }
         Ok(event_with_infos)
     }
+
+    /// Collect the txn info ids which emit events with the `filter.event_keys` from the event key index,
+    /// grouped by block id.
+    /// Return None if the filter has no event keys, or the index can not be scanned(such as memory storage).
+    fn collect_event_key_index(
+        &self,
+        filter: &Filter,
+        max_block_number: BlockNumber,
+    ) -> Result<Option<HashMap<HashValue, HashSet<HashValue>>>> {
+        if filter.event_keys.is_empty() {
+            return Ok(None);
+        }
+        let mut index: HashMap<HashValue, HashSet<HashValue>> = HashMap::new();
+        for event_key in &filter.event_keys {
+            let entries = match self.storage.get_event_key_index(
+                *event_key,
+                filter.from_block,
+                max_block_number,
+            ) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    debug!("Event key index is unavailable, fallback to bloom: {:?}", e);
+                    return Ok(None);
+                }
+            };
+            for (_block_number, block_id, txn_info_ids) in entries {
+                index.entry(block_id).or_default().extend(txn_info_ids);
+            }
+        }
+        Ok(Some(index))
+    }
+
+    /// Get the txn info ids of block `block_id` whose events may match the `filter`.
+    /// An indexed block is skipped if the event key index or the event bloom shows it can not match,
+    /// a block which is not indexed yet(waiting for backfill) is fully scanned.
+    fn txn_info_ids_to_filter(
+        &self,
+        block_id: HashValue,
+        filter: &Filter,
+        event_key_index: Option<&HashMap<HashValue, HashSet<HashValue>>>,
+    ) -> Result<Vec<HashValue>> {
+        if let Some(indexed_ids) = event_key_index.and_then(|index| index.get(&block_id)) {
+            return Ok(self
+                .storage
+                .get_block_txn_info_ids(block_id)?
+                .into_iter()
+                .filter(|id| indexed_ids.contains(id))
+                .collect());
+        }
+        match self.storage.get_block_event_bloom(block_id)? {
+            // the block is indexed, but no event with the filter's event keys.
+            Some(_) if event_key_index.is_some() => Ok(vec![]),
+            Some(bloom) if !bloom.may_match(filter)? => Ok(vec![]),
+            _ => self.storage.get_block_txn_info_ids(block_id),
+        }
+    }
 }
 
 impl ChainWriter for BlockChain {
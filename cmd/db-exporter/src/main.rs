@@ -212,6 +212,7 @@ enum Cmd {
     GenBlockTransactions(GenBlockTransactionsOptions),
     ExportSnapshot(ExportSnapshotOptions),
     ApplySnapshot(ApplySnapshotOptions),
+    BackfillEventIndex(BackfillEventIndexOptions),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -362,6 +363,20 @@ pub struct ApplySnapshotOptions {
     pub input_path: PathBuf,
 }
 
+#[derive(Debug, Parser)]
+#[clap(
+    name = "backfill-event-index",
+    about = "build event index for blocks committed before event index introduced"
+)]
+pub struct BackfillEventIndexOptions {
+    #[clap(long, short = 'n')]
+    /// Chain Network
+    pub net: BuiltinNetworkID,
+    #[clap(long, short = 'o', parse(from_os_str))]
+    /// starcoin node db path. like ~/.starcoin/main
+    pub to_path: PathBuf,
+}
+
 fn main() -> anyhow::Result<()> {
     let opt = Opt::parse();
     let cmd = match opt.cmd {
@@ -488,6 +503,11 @@ fn main() -> anyhow::Result<()> {
         return result;
     }
 
+    if let Cmd::BackfillEventIndex(option) = cmd {
+        let result = backfill_event_index(option.to_path, option.net);
+        return result;
+    }
+
     Ok(())
 }
 
@@ -705,6 +725,58 @@ pub fn startup_info_back(
     Ok(())
 }
 
+pub fn backfill_event_index(to_dir: PathBuf, network: BuiltinNetworkID) -> anyhow::Result<()> {
+    let net = ChainNetwork::new_builtin(network);
+    let db_stoarge = DBStorage::new(to_dir.join("starcoindb/db"), RocksdbConfig::default(), None)?;
+    let mut instance =
+        StorageInstance::new_cache_and_db_instance(CacheStorage::new(None), db_stoarge);
+    instance.check_upgrade()?;
+    let storage = Arc::new(Storage::new(instance)?);
+    let (chain_info, _) = Genesis::init_and_check_storage(&net, storage.clone(), to_dir.as_ref())?;
+    let chain = BlockChain::new(
+        net.time_service(),
+        chain_info.head().id(),
+        storage.clone(),
+        None,
+    )
+    .expect("create block chain should success.");
+
+    let event_index_start = storage.get_event_index_start()?.unwrap_or(0);
+    if event_index_start == 0 {
+        println!("event index of all blocks is already built");
+        return Ok(());
+    }
+    let start_time = SystemTime::now();
+    let bar = ProgressBar::new(event_index_start);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:100.cyan/blue} {percent}% {msg}"),
+    );
+    // backfill from high to low, so the indexed blocks are always [event_index_start, head].
+    let mut block_number = event_index_start;
+    while block_number > 0 {
+        block_number -= 1;
+        let block_id = chain
+            .get_hash_by_number(block_number)?
+            .ok_or_else(|| format_err!("{} get block error", block_number))?;
+        storage.build_block_event_index(block_id, block_number)?;
+        if block_number % BATCH_SIZE == 0 {
+            storage.save_event_index_start(block_number)?;
+        }
+        bar.set_message(format!("index block {}", block_number).as_str());
+        bar.inc(1);
+    }
+    storage.save_event_index_start(0)?;
+    bar.finish();
+    let use_time = SystemTime::now().duration_since(start_time)?;
+    println!(
+        "backfill event index of {} blocks, use time: {:?}",
+        event_index_start,
+        use_time.as_secs()
+    );
+    Ok(())
+}
+
 pub fn gen_block_transactions(
     to_dir: PathBuf,
     block_num: Option<u64>,
@@ -1,11 +1,14 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::payout::{now_secs, FundLimit, Payout, PayoutStatus, PayoutStore};
 use anyhow::{format_err, Result};
+use serde::Serialize;
 use starcoin_account_api::AccountInfo;
 use starcoin_crypto::HashValue;
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
 use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::TransactionStatusView;
 use starcoin_rpc_client::{RpcClient, StateRootOption};
 use starcoin_state_api::StateReaderExt;
 use starcoin_types::account_address::AccountAddress;
@@ -18,6 +21,19 @@ pub struct Faucet {
     faucet_account: AccountInfo,
     faucet_account_password: String,
     max_amount_pre_request: TokenValue<STCUnit>,
+    limit: FundLimit,
+    store: PayoutStore,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FaucetStatus {
+    pub address: AccountAddress,
+    /// Balance of the faucet account in nanoSTC.
+    pub balance: u128,
+    /// Total amount in nanoSTC of the payouts in the last 24 hours.
+    pub daily_amount: u128,
+    pub daily_cap: Option<u128>,
+    pub recent_payouts: Vec<Payout>,
 }
 
 const DEFAULT_GAS_PRICE: u64 = 1;
@@ -29,21 +45,86 @@ impl Faucet {
         faucet_account: AccountInfo,
         faucet_account_password: String,
         max_amount_pre_request: TokenValue<STCUnit>,
+        limit: FundLimit,
+        store: PayoutStore,
     ) -> Self {
         Faucet {
             client,
             faucet_account,
             faucet_account_password,
             max_amount_pre_request,
+            limit,
+            store,
         }
     }
 
-    pub fn transfer(
-        &self,
+    /// Transfer to the receiver if the request is allowed by the limit, and record the payout.
+    pub fn fund(
+        &mut self,
         amount: Option<TokenValue<STCUnit>>,
         receiver: AccountAddress,
+        ip: Option<String>,
     ) -> Result<HashValue> {
-        let amount = amount
+        let amount = self.fund_amount(amount);
+        let now = now_secs();
+        self.store
+            .check(&self.limit, receiver, ip.as_deref(), amount.scaling(), now)?;
+        let txn_hash = self.transfer(Some(amount), receiver)?;
+        let payout = Payout {
+            address: receiver,
+            ip,
+            amount: amount.scaling(),
+            txn_hash,
+            created_at: now,
+            status: PayoutStatus::Pending,
+        };
+        if let Err(e) = self.store.add(payout) {
+            error!("Save payout of txn {} failed: {:?}", txn_hash, e);
+        }
+        Ok(txn_hash)
+    }
+
+    /// Refresh the status of pending payouts, and report the faucet status.
+    pub fn status(&mut self, recent: usize) -> Result<FaucetStatus> {
+        self.refresh_payouts()?;
+        let address = *self.faucet_account.address();
+        let balance = self
+            .client
+            .state_reader(StateRootOption::Latest)?
+            .get_balance(address)?
+            .unwrap_or_default();
+        Ok(FaucetStatus {
+            address,
+            balance,
+            daily_amount: self.store.daily_amount(now_secs()),
+            daily_cap: self.limit.daily_cap,
+            recent_payouts: self.store.recent(recent),
+        })
+    }
+
+    fn refresh_payouts(&mut self) -> Result<()> {
+        let now = now_secs();
+        let mut changed = false;
+        for payout in self.store.pending_mut() {
+            payout.status = match self.client.chain_get_transaction_info(payout.txn_hash)? {
+                Some(info) if info.status == TransactionStatusView::Executed => {
+                    PayoutStatus::Confirmed
+                }
+                Some(_) => PayoutStatus::Failed,
+                // the txn can not be packaged after it is expired.
+                None if payout.created_at + DEFAULT_EXPIRATION_TIME < now => PayoutStatus::Failed,
+                None => continue,
+            };
+            changed = true;
+        }
+        if changed {
+            self.store.save()?;
+        }
+        Ok(())
+    }
+
+    fn fund_amount(&self, amount: Option<TokenValue<STCUnit>>) -> TokenValue<STCUnit> {
+        amount
             .and_then(|value| {
                 if value.scaling() > self.max_amount_pre_request.scaling() {
                     None
@@ -51,7 +132,15 @@ impl Faucet {
                     Some(value)
                 }
             })
-            .unwrap_or(self.max_amount_pre_request);
+            .unwrap_or(self.max_amount_pre_request)
+    }
+
+    pub fn transfer(
+        &self,
+        amount: Option<TokenValue<STCUnit>>,
+        receiver: AccountAddress,
+    ) -> Result<HashValue> {
+        let amount = self.fund_amount(amount);
 
         let sequence_number = match self
             .client
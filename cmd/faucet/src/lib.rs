@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod faucet;
+pub mod payout;
+pub mod verifier;
 pub mod web;
 
 #[macro_export]
@@ -4,6 +4,8 @@
 use anyhow::{format_err, Result};
 use clap::Parser;
 use futures::executor;
+use starcoin_faucet::payout::{FundLimit, PayoutStore};
+use starcoin_faucet::verifier::{CaptchaCommandVerifier, FundVerifier, PowVerifier};
 use starcoin_faucet::{faucet::Faucet, web};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
@@ -25,6 +27,33 @@ pub struct FaucetOpt {
     pub faucet_account_password: String,
     #[clap(long, short = 'm', default_value = "1 STC")]
     pub max_amount_pre_request: TokenValue<STCUnit>,
+    #[clap(long, parse(from_os_str), default_value = "faucet_payouts.json")]
+    /// The file to persist the payout records.
+    pub payout_store: PathBuf,
+    #[clap(long, default_value = "3600")]
+    /// Seconds an address must wait between two payouts, 0 to disable.
+    pub address_cooldown: u64,
+    #[clap(long, default_value = "60")]
+    /// Seconds an ip must wait between two payouts, 0 to disable.
+    pub ip_cooldown: u64,
+    #[clap(long)]
+    /// Max total amount of the payouts in the last 24 hours.
+    pub daily_cap: Option<TokenValue<STCUnit>>,
+    #[clap(long, conflicts_with = "captcha-verify-cmd")]
+    /// Require a proof of work nonce with the given leading zero bits in the fund request,
+    /// the nonce is computed for a challenge issued by `/api/challenge`.
+    pub pow_difficulty: Option<u32>,
+    #[clap(long, default_value = "300")]
+    /// Seconds a proof of work challenge is valid.
+    pub pow_challenge_ttl: u64,
+    #[clap(long, parse(from_os_str))]
+    /// Verify the captcha token of the fund request by the command,
+    /// the command is called with the token and client ip, and should exit with 0 if the token is valid.
+    pub captcha_verify_cmd: Option<PathBuf>,
+    #[clap(long)]
+    /// Read the client ip from the last entry of the `X-Forwarded-For` header,
+    /// only enable it when the faucet is behind a reverse proxy which appends the header.
+    pub trust_forwarded_for: bool,
 }
 
 fn main() -> Result<()> {
@@ -42,13 +71,30 @@ fn main() -> Result<()> {
     let account = account
         .ok_or_else(|| format_err!("Can not find default account, Please input from account."))?;
     let faucet_address = account.address;
+    let limit = FundLimit {
+        address_cooldown: opts.address_cooldown,
+        ip_cooldown: opts.ip_cooldown,
+        daily_cap: opts.daily_cap.map(|cap| cap.scaling()),
+    };
+    let store = PayoutStore::load(opts.payout_store.as_path())?;
+    let verifier: Option<Box<dyn FundVerifier>> =
+        match (opts.pow_difficulty, opts.captcha_verify_cmd) {
+            (Some(difficulty), _) => Some(Box::new(PowVerifier::new(
+                difficulty,
+                opts.pow_challenge_ttl,
+            ))),
+            (None, Some(command)) => Some(Box::new(CaptchaCommandVerifier::new(command))),
+            (None, None) => None,
+        };
     let faucet = Faucet::new(
         client,
         account,
         opts.faucet_account_password.clone(),
         opts.max_amount_pre_request,
+        limit,
+        store,
     );
-    let fut = web::run(server, faucet);
+    let fut = web::run(server, faucet, verifier, opts.trust_forwarded_for);
     println!(
         "Faucet serve on: {}, with faucet account: {}",
         opts.server_addr, faucet_address
@@ -0,0 +1,219 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ONE_DAY_SECS: u64 = 24 * 60 * 60;
+/// Max count of payouts kept in the store, the oldest payouts are dropped first.
+const MAX_PAYOUT_RECORDS: usize = 10000;
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PayoutStatus {
+    /// The transaction is submitted, but not found on chain yet.
+    Pending,
+    /// The transaction is executed on chain.
+    Confirmed,
+    /// The transaction is failed on chain, or expired.
+    Failed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Payout {
+    pub address: AccountAddress,
+    pub ip: Option<String>,
+    /// The amount in nanoSTC.
+    pub amount: u128,
+    pub txn_hash: HashValue,
+    /// Unix timestamp in seconds when the payout is submitted.
+    pub created_at: u64,
+    pub status: PayoutStatus,
+}
+
+impl Payout {
+    /// Failed payouts are not counted by the limits.
+    fn is_counted(&self) -> bool {
+        self.status != PayoutStatus::Failed
+    }
+}
+
+/// Limits of the fund requests, a limit is disabled if it is 0 or absent.
+#[derive(Clone, Debug, Default)]
+pub struct FundLimit {
+    /// Seconds an address must wait between two payouts.
+    pub address_cooldown: u64,
+    /// Seconds an ip must wait between two payouts.
+    pub ip_cooldown: u64,
+    /// Max total amount in nanoSTC of the payouts in the last 24 hours.
+    pub daily_cap: Option<u128>,
+}
+
+/// Persistent log of the payouts, in json format.
+pub struct PayoutStore {
+    path: PathBuf,
+    payouts: Vec<Payout>,
+}
+
+impl PayoutStore {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let payouts = if path.exists() {
+            serde_json::from_slice(fs::read(&path)?.as_slice())?
+        } else {
+            vec![]
+        };
+        Ok(Self { path, payouts })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&self.payouts)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    pub fn add(&mut self, payout: Payout) -> Result<()> {
+        self.payouts.push(payout);
+        if self.payouts.len() > MAX_PAYOUT_RECORDS {
+            let overflow = self.payouts.len() - MAX_PAYOUT_RECORDS;
+            self.payouts.drain(..overflow);
+        }
+        self.save()
+    }
+
+    /// The latest payouts, the newest first.
+    pub fn recent(&self, limit: usize) -> Vec<Payout> {
+        self.payouts.iter().rev().take(limit).cloned().collect()
+    }
+
+    pub fn pending_mut(&mut self) -> impl Iterator<Item = &mut Payout> {
+        self.payouts
+            .iter_mut()
+            .filter(|payout| payout.status == PayoutStatus::Pending)
+    }
+
+    /// Total amount of the payouts in the last 24 hours.
+    pub fn daily_amount(&self, now: u64) -> u128 {
+        self.payouts
+            .iter()
+            .filter(|payout| {
+                payout.is_counted() && payout.created_at.saturating_add(ONE_DAY_SECS) > now
+            })
+            .fold(0u128, |total, payout| total.saturating_add(payout.amount))
+    }
+
+    /// Check the fund request is allowed by the limit.
+    pub fn check(
+        &self,
+        limit: &FundLimit,
+        address: AccountAddress,
+        ip: Option<&str>,
+        amount: u128,
+        now: u64,
+    ) -> Result<()> {
+        for payout in self
+            .payouts
+            .iter()
+            .rev()
+            .filter(|payout| payout.is_counted())
+        {
+            // the cooldown is configured by the user, so the add may overflow.
+            let address_cooldown_end = payout.created_at.saturating_add(limit.address_cooldown);
+            if payout.address == address && address_cooldown_end > now {
+                bail!(
+                    "Address {} is funded recently, please retry after {} seconds",
+                    address,
+                    address_cooldown_end - now
+                );
+            }
+            let ip_cooldown_end = payout.created_at.saturating_add(limit.ip_cooldown);
+            if ip.is_some() && payout.ip.as_deref() == ip && ip_cooldown_end > now {
+                bail!(
+                    "Too many requests from your ip, please retry after {} seconds",
+                    ip_cooldown_end - now
+                );
+            }
+        }
+        if let Some(daily_cap) = limit.daily_cap {
+            if self.daily_amount(now).saturating_add(amount) > daily_cap {
+                bail!("The faucet reaches the daily cap, please retry tomorrow");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_payout(address: AccountAddress, ip: &str, amount: u128, created_at: u64) -> Payout {
+        Payout {
+            address,
+            ip: Some(ip.to_string()),
+            amount,
+            txn_hash: HashValue::random(),
+            created_at,
+            status: PayoutStatus::Pending,
+        }
+    }
+
+    #[test]
+    fn test_payout_limit() -> Result<()> {
+        let dir = starcoin_config::temp_dir();
+        let path = dir.as_ref().join("payouts.json");
+        let mut store = PayoutStore::load(&path)?;
+        let limit = FundLimit {
+            address_cooldown: 3600,
+            ip_cooldown: 60,
+            daily_cap: Some(300),
+        };
+        let now = now_secs();
+        let address1 = AccountAddress::random();
+        let address2 = AccountAddress::random();
+        store.check(&limit, address1, Some("127.0.0.1"), 100, now)?;
+        store.add(new_payout(address1, "127.0.0.1", 100, now))?;
+        let max_limit = FundLimit {
+            address_cooldown: u64::MAX,
+            ip_cooldown: u64::MAX,
+            daily_cap: Some(u128::MAX),
+        };
+        assert!(store
+            .check(&max_limit, address1, None, u128::MAX, now + 60)
+            .is_err());
+
+        assert!(store
+            .check(&limit, address1, Some("127.0.0.2"), 100, now + 60)
+            .is_err());
+        assert!(store
+            .check(&limit, address2, Some("127.0.0.1"), 100, now + 30)
+            .is_err());
+        store.check(&limit, address2, Some("127.0.0.1"), 100, now + 60)?;
+        store.check(&limit, address1, Some("127.0.0.2"), 100, now + 3600)?;
+        // daily cap
+        assert!(store
+            .check(&limit, address2, Some("127.0.0.2"), 201, now + 60)
+            .is_err());
+
+        // the store is reloaded after restart, failed payouts are not counted.
+        let mut store = PayoutStore::load(&path)?;
+        assert_eq!(store.recent(10).len(), 1);
+        store
+            .pending_mut()
+            .for_each(|payout| payout.status = PayoutStatus::Failed);
+        store.check(&limit, address1, Some("127.0.0.1"), 300, now)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::payout::now_secs;
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Max count of the unexpired proof of work challenges.
+const MAX_POW_CHALLENGES: usize = 10000;
+
+/// The anti-abuse proof sent with the fund request.
+#[derive(Clone, Debug, Default)]
+pub struct FundProof {
+    /// The proof of work challenge issued by the faucet.
+    pub challenge: Option<HashValue>,
+    /// The proof of work nonce.
+    pub nonce: Option<u64>,
+    /// The captcha token issued by the captcha service.
+    pub captcha_token: Option<String>,
+}
+
+/// A proof of work challenge issued by the faucet, it can be used by only one fund request.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PowChallenge {
+    pub challenge: HashValue,
+    /// The leading zero bits the proof of work requires.
+    pub difficulty: u32,
+    /// Unix timestamp in seconds when the challenge expires.
+    pub expire_at: u64,
+}
+
+/// Hook to verify a fund request before the payout.
+pub trait FundVerifier: Send + Sync {
+    fn verify(&self, address: AccountAddress, ip: Option<&str>, proof: &FundProof) -> Result<()>;
+
+    /// Issue a challenge for the next fund request, None if the verifier does not use challenges.
+    fn issue_challenge(&self) -> Result<Option<PowChallenge>> {
+        Ok(None)
+    }
+}
+
+/// Proof of work verifier, the sha3 hash of `challenge ++ address ++ nonce(little endian)`
+/// should have at least `difficulty` leading zero bits. The challenge is issued by the faucet
+/// and expires after `challenge_ttl` seconds, so the proof can not be computed in advance,
+/// and every challenge can be used only once.
+pub struct PowVerifier {
+    difficulty: u32,
+    challenge_ttl: u64,
+    /// The unexpired challenges and their expire time.
+    challenges: Mutex<HashMap<HashValue, u64>>,
+}
+
+impl PowVerifier {
+    pub fn new(difficulty: u32, challenge_ttl: u64) -> Self {
+        Self {
+            difficulty,
+            challenge_ttl,
+            challenges: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn issue(&self, now: u64) -> Result<PowChallenge> {
+        let mut challenges = self.challenges.lock().expect("lock should not be poisoned");
+        challenges.retain(|_, expire_at| *expire_at > now);
+        ensure!(
+            challenges.len() < MAX_POW_CHALLENGES,
+            "Too many proof of work challenges, please retry later"
+        );
+        let challenge = HashValue::random();
+        let expire_at = now.saturating_add(self.challenge_ttl);
+        challenges.insert(challenge, expire_at);
+        Ok(PowChallenge {
+            challenge,
+            difficulty: self.difficulty,
+            expire_at,
+        })
+    }
+
+    /// Verify the proof, the challenge of the proof is consumed even if the nonce is invalid.
+    pub fn verify_proof(&self, address: AccountAddress, proof: &FundProof, now: u64) -> Result<()> {
+        let challenge = proof
+            .challenge
+            .ok_or_else(|| format_err!("Proof of work challenge is required"))?;
+        let nonce = proof
+            .nonce
+            .ok_or_else(|| format_err!("Proof of work nonce is required"))?;
+        let expire_at = self
+            .challenges
+            .lock()
+            .expect("lock should not be poisoned")
+            .remove(&challenge)
+            .ok_or_else(|| format_err!("Unknown or used proof of work challenge"))?;
+        ensure!(expire_at > now, "Proof of work challenge is expired");
+        ensure!(
+            Self::leading_zero_bits(challenge, address, nonce) >= self.difficulty,
+            "Invalid proof of work nonce, difficulty is {}",
+            self.difficulty
+        );
+        Ok(())
+    }
+
+    pub fn leading_zero_bits(challenge: HashValue, address: AccountAddress, nonce: u64) -> u32 {
+        let mut data = challenge.to_vec();
+        data.extend_from_slice(address.to_vec().as_slice());
+        data.extend_from_slice(&nonce.to_le_bytes());
+        let hash = HashValue::sha3_256_of(data.as_slice());
+        let mut bits = 0;
+        for byte in hash.as_ref() {
+            bits += byte.leading_zeros();
+            if *byte != 0 {
+                break;
+            }
+        }
+        bits
+    }
+}
+
+impl FundVerifier for PowVerifier {
+    fn verify(&self, address: AccountAddress, _ip: Option<&str>, proof: &FundProof) -> Result<()> {
+        self.verify_proof(address, proof, now_secs())
+    }
+
+    fn issue_challenge(&self) -> Result<Option<PowChallenge>> {
+        self.issue(now_secs()).map(Some)
+    }
+}
+
+/// Verify the captcha token by an external command, the command is called with
+/// the token and the client ip as arguments, and exit with 0 if the token is valid.
+pub struct CaptchaCommandVerifier {
+    command: PathBuf,
+}
+
+impl CaptchaCommandVerifier {
+    pub fn new(command: PathBuf) -> Self {
+        Self { command }
+    }
+}
+
+impl FundVerifier for CaptchaCommandVerifier {
+    fn verify(&self, _address: AccountAddress, ip: Option<&str>, proof: &FundProof) -> Result<()> {
+        let token = proof
+            .captcha_token
+            .as_ref()
+            .ok_or_else(|| format_err!("Captcha token is required"))?;
+        let status = Command::new(&self.command)
+            .arg(token)
+            .arg(ip.unwrap_or_default())
+            .status()?;
+        ensure!(status.success(), "Invalid captcha token");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(challenge: &PowChallenge, address: AccountAddress) -> u64 {
+        (0..)
+            .find(|nonce| {
+                PowVerifier::leading_zero_bits(challenge.challenge, address, *nonce)
+                    >= challenge.difficulty
+            })
+            .unwrap()
+    }
+
+    fn proof(challenge: &PowChallenge, nonce: u64) -> FundProof {
+        FundProof {
+            challenge: Some(challenge.challenge),
+            nonce: Some(nonce),
+            captcha_token: None,
+        }
+    }
+
+    #[test]
+    fn test_pow_verifier() -> Result<()> {
+        let verifier = PowVerifier::new(8, 60);
+        let now = now_secs();
+        let address = AccountAddress::random();
+
+        let challenge = verifier.issue(now)?;
+        assert_eq!(challenge.expire_at, now + 60);
+        // the proof is bound to the address.
+        let other_address = AccountAddress::random();
+        let nonce = (0..)
+            .find(|nonce| {
+                PowVerifier::leading_zero_bits(challenge.challenge, address, *nonce) >= 8
+                    && PowVerifier::leading_zero_bits(challenge.challenge, other_address, *nonce)
+                        < 8
+            })
+            .unwrap();
+        assert!(verifier
+            .verify_proof(other_address, &proof(&challenge, nonce), now)
+            .is_err());
+
+        let challenge = verifier.issue(now)?;
+        let nonce = solve(&challenge, address);
+        verifier.verify_proof(address, &proof(&challenge, nonce), now + 1)?;
+        // the challenge can be used only once.
+        assert!(verifier
+            .verify_proof(address, &proof(&challenge, nonce), now + 1)
+            .is_err());
+
+        // the challenge expires.
+        let challenge = verifier.issue(now)?;
+        let nonce = solve(&challenge, address);
+        assert!(verifier
+            .verify_proof(address, &proof(&challenge, nonce), now + 60)
+            .is_err());
+
+        // the challenge must be issued by the verifier.
+        let challenge = PowChallenge {
+            challenge: HashValue::random(),
+            difficulty: 8,
+            expire_at: now + 60,
+        };
+        let nonce = solve(&challenge, address);
+        assert!(verifier
+            .verify_proof(address, &proof(&challenge, nonce), now)
+            .is_err());
+        assert!(verifier
+            .verify_proof(address, &FundProof::default(), now)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pow_challenge_limit() -> Result<()> {
+        let verifier = PowVerifier::new(0, 60);
+        let now = now_secs();
+        for _ in 0..MAX_POW_CHALLENGES {
+            verifier.issue(now)?;
+        }
+        assert!(verifier.issue(now).is_err());
+        // the expired challenges are evicted.
+        verifier.issue(now + 60)?;
+        Ok(())
+    }
+}
@@ -2,17 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::faucet::Faucet;
+use crate::verifier::{FundProof, FundVerifier};
 use anyhow::{bail, Error, Result};
 use ascii::AsciiString;
 use once_cell::sync::Lazy;
 use rust_embed::RustEmbed;
 use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_value::TokenValue;
 use starcoin_types::account_config::STCUnit;
 use std::fmt::{Debug, Formatter};
 use std::io::Cursor;
+use std::net::IpAddr;
 use std::str::FromStr;
 use tiny_http::{Header, Method, Request, Response, Server};
 
@@ -54,12 +57,21 @@ fn response(result: Result<serde_json::Value>) -> Response<Cursor<String>> {
     }
 }
 
+const RECENT_PAYOUTS: usize = 20;
+
 static G_CONTENT_TYPE: Lazy<Header> = Lazy::new(|| Header {
     field: "Content-Type".parse().unwrap(),
     value: AsciiString::from_ascii("text/html; charset=utf8").unwrap(),
 });
 
-pub async fn run(server: Server, faucet: Faucet) {
+/// If `trust_forwarded_for` is true, the client ip is read from the `X-Forwarded-For` header,
+/// it should only be enabled when the faucet is behind a reverse proxy which appends the header.
+pub async fn run(
+    server: Server,
+    mut faucet: Faucet,
+    verifier: Option<Box<dyn FundVerifier>>,
+    trust_forwarded_for: bool,
+) {
     for mut request in server.incoming_requests() {
         let pos = request
             .url()
@@ -73,7 +85,29 @@ pub async fn run(server: Server, faucet: Faucet) {
                 let _err = request.respond(response);
             }
             "/api/fund" => {
-                let resp = handle_fund(&faucet, &mut request).await;
+                let resp = handle_fund(
+                    &mut faucet,
+                    verifier.as_deref(),
+                    trust_forwarded_for,
+                    &mut request,
+                )
+                .await;
+                if let Err(err) =
+                    request.respond(response(resp).with_header(G_CONTENT_TYPE.clone()))
+                {
+                    error!("response err: {}", err)
+                }
+            }
+            "/api/challenge" => {
+                let resp = handle_challenge(verifier.as_deref()).await;
+                if let Err(err) =
+                    request.respond(response(resp).with_header(G_CONTENT_TYPE.clone()))
+                {
+                    error!("response err: {}", err)
+                }
+            }
+            "/api/status" => {
+                let resp = handle_status(&mut faucet).await;
                 if let Err(err) =
                     request.respond(response(resp).with_header(G_CONTENT_TYPE.clone()))
                 {
@@ -87,7 +121,41 @@ pub async fn run(server: Server, faucet: Faucet) {
     }
 }
 
-async fn handle_fund(faucet: &Faucet, request: &mut Request) -> Result<serde_json::Value> {
+/// The client ip, the last entry of the `X-Forwarded-For` header is appended by the reverse proxy,
+/// the former entries are sent by the client and can be forged.
+fn client_ip(request: &Request, trust_forwarded_for: bool) -> String {
+    if trust_forwarded_for {
+        let forwarded_ip = request
+            .headers()
+            .iter()
+            .rev()
+            .find(|header| header.field.equiv("X-Forwarded-For"))
+            .and_then(|header| header.value.as_str().rsplit(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+        if let Some(ip) = forwarded_ip {
+            return ip.to_string();
+        }
+    }
+    request.remote_addr().ip().to_string()
+}
+
+async fn handle_challenge(verifier: Option<&dyn FundVerifier>) -> Result<serde_json::Value> {
+    let challenge = match verifier {
+        Some(verifier) => verifier.issue_challenge()?,
+        None => None,
+    };
+    match challenge {
+        Some(challenge) => Ok(serde_json::to_value(challenge)?),
+        None => bail!("The faucet does not require a challenge"),
+    }
+}
+
+async fn handle_fund(
+    faucet: &mut Faucet,
+    verifier: Option<&dyn FundVerifier>,
+    trust_forwarded_for: bool,
+    request: &mut Request,
+) -> Result<serde_json::Value> {
     info!("fund: {}", request.url());
     debug!("request: {:?}", request);
 
@@ -97,23 +165,51 @@ async fn handle_fund(faucet: &Faucet, request: &mut Request) -> Result<serde_jso
     let mut body = String::new();
     request.as_reader().read_to_string(&mut body)?;
     let fund_request = serde_json::from_str::<FundRequest>(body.as_str())?;
+    let ip = client_ip(request, trust_forwarded_for);
+    if let Some(verifier) = verifier {
+        let proof = FundProof {
+            challenge: fund_request.challenge,
+            nonce: fund_request.nonce,
+            captcha_token: fund_request.captcha_token.clone(),
+        };
+        verifier.verify(fund_request.address, Some(ip.as_str()), &proof)?;
+    }
     let amount = fund_request
         .amount
         .and_then(|amount| TokenValue::<STCUnit>::from_str(amount.as_str()).ok());
-    let txn_hash = faucet.transfer(amount, fund_request.address)?;
+    let txn_hash = faucet.fund(amount, fund_request.address, Some(ip))?;
     Ok(serde_json::json!({
        "transaction_id": txn_hash.to_string()
     }))
 }
 
+async fn handle_status(faucet: &mut Faucet) -> Result<serde_json::Value> {
+    let status = faucet.status(RECENT_PAYOUTS)?;
+    Ok(serde_json::to_value(status)?)
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 struct FundRequest {
     address: AccountAddress,
     amount: Option<String>,
+    /// The proof of work challenge issued by `/api/challenge`,
+    /// required if the faucet enables proof of work.
+    #[serde(default)]
+    challenge: Option<HashValue>,
+    /// The proof of work nonce, required if the faucet enables proof of work.
+    #[serde(default)]
+    nonce: Option<u64>,
+    /// The captcha token, required if the faucet enables captcha.
+    #[serde(default)]
+    captcha_token: Option<String>,
 }
 
 impl Debug for FundRequest {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "address: {:?}, amount: {:?}", self.address, self.amount,)
+        write!(
+            f,
+            "address: {:?}, amount: {:?}, challenge: {:?}, nonce: {:?}",
+            self.address, self.amount, self.challenge, self.nonce
+        )
     }
 }
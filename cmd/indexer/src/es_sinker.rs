@@ -1,5 +1,6 @@
-use crate::{BlockData, BlockSimplified, BlockWithMetadata, EventData};
-use anyhow::Result;
+use crate::{BlockData, BlockSimplified, BlockWithMetadata, EventData, LocalTipInfo, Sinker};
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
 use elasticsearch::http::response::Response;
 use elasticsearch::indices::{
     IndicesCreateParts, IndicesExistsParts, IndicesGetMappingParts, IndicesPutMappingParts,
@@ -8,7 +9,6 @@ use elasticsearch::{
     BulkOperation, BulkOperations, BulkParts, DeleteByQueryParts, DeleteParts, Elasticsearch,
     GetParts,
 };
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
@@ -59,12 +59,6 @@ struct SinkState {
     tip: Option<LocalTipInfo>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub struct LocalTipInfo {
-    pub block_hash: HashValue,
-    pub block_number: u64,
-}
-
 impl EsSinker {
     pub fn new(es: Elasticsearch, config: IndexConfig) -> Self {
         Self {
@@ -93,8 +87,47 @@ impl EsSinker {
         }
         Ok(())
     }
+
+    async fn get_remote_tip_header(&self) -> Result<Option<LocalTipInfo>> {
+        let block_index = self.config.block_index.as_str();
+        let resp_data: Value = self
+            .es
+            .indices()
+            .get_mapping(IndicesGetMappingParts::Index(&[block_index]))
+            .send()
+            .await?
+            .error_for_status_code()?
+            .json()
+            .await?;
+        let v = resp_data[block_index]["mappings"]["_meta"]["tip"].clone();
+        let tip_info: Option<LocalTipInfo> = serde_json::from_value(v)?;
+        Ok(tip_info)
+    }
+
+    async fn check_status_code(resp: Response) -> anyhow::Result<()> {
+        // check response
+        if resp.status_code().is_client_error() || resp.status_code().is_server_error() {
+            let exception = resp.exception().await?;
+            if let Some(ex) = exception {
+                anyhow::bail!("{}", serde_json::to_string(&ex)?);
+            }
+        } else {
+            let bulk_response: serde_json::Value = resp.json().await?;
+            if let Some(true) = bulk_response["errors"].as_bool() {
+                anyhow::bail!(
+                    "[es] bulk error: {}",
+                    serde_json::to_string(&bulk_response)?
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Sinker for EsSinker {
     /// init es indices
-    pub async fn init_indices(&self) -> Result<()> {
+    async fn init(&self) -> Result<()> {
         let block_index = self.config.block_index.as_str();
         let uncle_block_index = self.config.uncle_block_index.as_str();
         let txn_info_index = self.config.txn_info_index.as_str();
@@ -116,11 +149,11 @@ impl EsSinker {
         Ok(())
     }
 
-    pub async fn update_remote_tip_header(
+    async fn update_remote_tip_header(
         &self,
         block_hash: HashValue,
         block_number: u64,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<()> {
         let tip_info = LocalTipInfo {
             block_number,
             block_hash,
@@ -139,13 +172,12 @@ impl EsSinker {
             .body(body)
             .send()
             .await?;
-        let resp = resp.error_for_status_code()?;
-        let data = resp.json().await?;
+        resp.error_for_status_code()?;
         // self.state.write().await.tip = Some(tip_info);
-        Ok(data)
+        Ok(())
     }
 
-    pub async fn update_local_tip_header(
+    async fn update_local_tip_header(
         &self,
         block_hash: HashValue,
         block_number: u64,
@@ -158,28 +190,12 @@ impl EsSinker {
         Ok(())
     }
 
-    pub async fn get_local_tip_header(&self) -> Result<Option<LocalTipInfo>> {
+    async fn get_local_tip_header(&self) -> Result<Option<LocalTipInfo>> {
         let tip = self.state.read().await.tip.clone();
         Ok(tip)
     }
 
-    async fn get_remote_tip_header(&self) -> Result<Option<LocalTipInfo>> {
-        let block_index = self.config.block_index.as_str();
-        let resp_data: Value = self
-            .es
-            .indices()
-            .get_mapping(IndicesGetMappingParts::Index(&[block_index]))
-            .send()
-            .await?
-            .error_for_status_code()?
-            .json()
-            .await?;
-        let v = resp_data[block_index]["mappings"]["_meta"]["tip"].clone();
-        let tip_info: Option<LocalTipInfo> = serde_json::from_value(v)?;
-        Ok(tip_info)
-    }
-
-    pub async fn rollback_to_last_block(&self) -> Result<()> {
+    async fn rollback_to_last_block(&self) -> Result<()> {
         let tip_header = self.get_local_tip_header().await?;
         if tip_header.is_none() {
             return Ok(());
@@ -203,7 +219,11 @@ impl EsSinker {
         };
 
         // first, rollback tip header
-        let rollback_to = (parent_hash, tip_header.block_number - 1);
+        let rollback_number = tip_header
+            .block_number
+            .checked_sub(1)
+            .ok_or_else(|| format_err!("cannot rollback the genesis block {}", block_id))?;
+        let rollback_to = (parent_hash, rollback_number);
         self.update_remote_tip_header(rollback_to.0, rollback_to.1)
             .await?;
         self.update_local_tip_header(rollback_to.0, rollback_to.1)
@@ -264,13 +284,8 @@ impl EsSinker {
         Ok(())
     }
 
-    pub async fn repair_block(&self, block: BlockData) -> Result<()> {
-        self.bulk(vec![block]).await?;
-        Ok(())
-    }
-
     // bulk insert data into es.
-    pub async fn bulk(&self, blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    async fn bulk(&self, blocks: Vec<BlockData>) -> anyhow::Result<()> {
         if blocks.is_empty() {
             return Ok(());
         }
@@ -338,7 +353,7 @@ impl EsSinker {
     }
 
     // bulk insert data into es.
-    pub async fn bulk_uncle(&self, uncle_blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    async fn bulk_uncle(&self, uncle_blocks: Vec<BlockData>) -> anyhow::Result<()> {
         if uncle_blocks.is_empty() {
             return Ok(());
         }
@@ -365,31 +380,11 @@ impl EsSinker {
 
         EsSinker::check_status_code(resp).await
     }
-
-    async fn check_status_code(resp: Response) -> anyhow::Result<()> {
-        // check response
-        if resp.status_code().is_client_error() || resp.status_code().is_server_error() {
-            let exception = resp.exception().await?;
-            if let Some(ex) = exception {
-                anyhow::bail!("{}", serde_json::to_string(&ex)?);
-            }
-        } else {
-            let bulk_response: serde_json::Value = resp.json().await?;
-            if let Some(true) = bulk_response["errors"].as_bool() {
-                anyhow::bail!(
-                    "[es] bulk error: {}",
-                    serde_json::to_string(&bulk_response)?
-                );
-            }
-        }
-        Ok(())
-    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::LocalTipInfo;
-    use crate::{EsSinker, IndexConfig};
+    use crate::{EsSinker, IndexConfig, LocalTipInfo, Sinker};
     use elasticsearch::http::transport::SingleNodeConnectionPool;
     use elasticsearch::http::Url;
     use elasticsearch::Elasticsearch;
@@ -420,7 +415,7 @@ mod tests {
             block_number: 1,
         };
 
-        let _ = sinker
+        sinker
             .update_remote_tip_header(tip_info.block_hash, tip_info.block_number)
             .await
             .unwrap();
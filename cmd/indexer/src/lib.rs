@@ -1,7 +1,11 @@
 mod block_client;
 mod es_sinker;
+mod sinker;
+mod sql_sinker;
 pub use block_client::BlockClient;
-pub use es_sinker::{EsSinker, IndexConfig, LocalTipInfo};
+pub use es_sinker::{EsSinker, IndexConfig};
+pub use sinker::{LocalTipInfo, Sinker};
+pub use sql_sinker::SqlSinker;
 
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
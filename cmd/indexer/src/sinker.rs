@@ -0,0 +1,45 @@
+use crate::BlockData;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LocalTipInfo {
+    pub block_hash: HashValue,
+    pub block_number: u64,
+}
+
+/// The storage backend which indexed blocks are written to.
+///
+/// The local tip is the latest block read from the node, and the remote tip is the latest block
+/// persisted by the sinker, they are the same after the read blocks are bulk written.
+#[async_trait]
+pub trait Sinker: Send + Sync {
+    /// init the storage, and load the remote tip as local tip.
+    async fn init(&self) -> Result<()>;
+
+    async fn update_remote_tip_header(
+        &self,
+        block_hash: HashValue,
+        block_number: u64,
+    ) -> Result<()>;
+
+    async fn update_local_tip_header(&self, block_hash: HashValue, block_number: u64)
+        -> Result<()>;
+
+    async fn get_local_tip_header(&self) -> Result<Option<LocalTipInfo>>;
+
+    /// delete the tip block and its related data, and set the tip to its parent block.
+    async fn rollback_to_last_block(&self) -> Result<()>;
+
+    /// bulk write blocks, with their txns, events and uncles.
+    async fn bulk(&self, blocks: Vec<BlockData>) -> Result<()>;
+
+    /// bulk write uncle blocks.
+    async fn bulk_uncle(&self, uncle_blocks: Vec<BlockData>) -> Result<()>;
+
+    async fn repair_block(&self, block: BlockData) -> Result<()> {
+        self.bulk(vec![block]).await
+    }
+}
@@ -1,6 +1,12 @@
 use dyn_clone::DynClone;
 use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use serde::{Deserialize, Serialize};
 use starcoin_types::system_events::{MintBlockEvent, SealEvent};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+pub mod protocol;
 
 pub trait Solver: Send + DynClone {
     fn solve(
@@ -10,3 +16,76 @@ pub trait Solver: Send + DynClone {
         stop_rx: UnboundedReceiver<bool>,
     );
 }
+
+/// A half-open range of nonces assigned to one solver worker.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NonceRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl NonceRange {
+    /// Split the whole nonce space into `total` disjoint ranges and return the `id`th one,
+    /// the last range takes the remainder.
+    pub fn partition(id: u32, total: u32) -> NonceRange {
+        assert!(id < total, "partition id {} out of total {}", id, total);
+        let span = u32::max_value() / total;
+        let start = span * id;
+        let end = if id == total - 1 {
+            u32::max_value()
+        } else {
+            start + span
+        };
+        NonceRange { start, end }
+    }
+
+    pub fn contains(&self, nonce: u32) -> bool {
+        self.start <= nonce && nonce < self.end
+    }
+}
+
+impl From<NonceRange> for Range<u32> {
+    fn from(range: NonceRange) -> Self {
+        range.start..range.end
+    }
+}
+
+/// Counts the hashes computed by solvers, shared by all clones of a solver.
+/// The miner client drains it periodically to report the hash rate to the node.
+#[derive(Clone, Debug, Default)]
+pub struct HashCounter(Arc<AtomicU64>);
+
+impl HashCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, hashes: u64) {
+        self.0.fetch_add(hashes, Ordering::Relaxed);
+    }
+
+    /// Return the hashes counted since the last call and reset the counter.
+    pub fn take(&self) -> u64 {
+        self.0.swap(0, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonceRange;
+
+    #[test]
+    fn test_partition_nonce() {
+        let total = 3;
+        let ranges = (0..total)
+            .map(|id| NonceRange::partition(id, total))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges[0].start, 0);
+        assert_eq!(ranges[total as usize - 1].end, u32::max_value());
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(ranges[1].contains(ranges[1].start));
+        assert!(!ranges[1].contains(ranges[1].end));
+    }
+}
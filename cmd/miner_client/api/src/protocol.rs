@@ -0,0 +1,119 @@
+//! The stdio protocol between the miner client and an external solver process.
+//!
+//! The miner client spawns the solver executable once per solver instance and talks to it with
+//! newline-delimited JSON: requests go to the solver's stdin and responses come back on its
+//! stdout, stderr is left to the solver for logging. The first message in each direction is a
+//! `Hello`, a solver answering with another protocol version is rejected.
+//!
+//! A `Solve` request replaces whatever job the solver is working on, so a `Stop` carrying an
+//! older job id must be ignored.
+
+use crate::NonceRange;
+use anyhow::{format_err, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use starcoin_types::system_events::MintBlockEvent;
+use std::io::{BufRead, Write};
+
+pub const SOLVER_PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SolverRequest {
+    Hello {
+        version: u32,
+        /// Index of this solver instance, starting from 0.
+        instance: u32,
+        instances: u32,
+    },
+    Solve {
+        job_id: u64,
+        event: MintBlockEvent,
+        /// Nonces this instance should search, disjoint from the other instances.
+        nonce_range: NonceRange,
+    },
+    Stop {
+        job_id: u64,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SolverResponse {
+    Hello {
+        version: u32,
+        name: String,
+    },
+    Seal {
+        job_id: u64,
+        nonce: u32,
+    },
+    /// Hashes computed since the previous report, solvers should report at least every few seconds.
+    HashRate {
+        job_id: u64,
+        hashes: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Write one message as a single JSON line and flush it.
+pub fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read the next message, return `None` when the stream is closed.
+pub fn read_message<R: BufRead, T: DeserializeOwned>(reader: &mut R) -> Result<Option<T>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+    serde_json::from_str(line.trim())
+        .map(Some)
+        .map_err(|e| format_err!("Invalid solver message {:?}: {}", line.trim(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_types::genesis_config::ConsensusStrategy;
+    use starcoin_types::U256;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let request = SolverRequest::Solve {
+            job_id: 1,
+            event: MintBlockEvent::new(
+                Default::default(),
+                ConsensusStrategy::Keccak,
+                vec![1u8; 76],
+                U256::from(1024),
+                1,
+                None,
+            ),
+            nonce_range: NonceRange::partition(0, 2),
+        };
+        let mut buf = vec![];
+        write_message(&mut buf, &request).unwrap();
+        write_message(&mut buf, &SolverRequest::Stop { job_id: 1 }).unwrap();
+        let mut reader = Cursor::new(buf);
+        let decoded: Option<SolverRequest> = read_message(&mut reader).unwrap();
+        assert_eq!(decoded, Some(request));
+        let decoded: Option<SolverRequest> = read_message(&mut reader).unwrap();
+        assert_eq!(decoded, Some(SolverRequest::Stop { job_id: 1 }));
+        let decoded: Option<SolverRequest> = read_message(&mut reader).unwrap();
+        assert_eq!(decoded, None);
+    }
+}
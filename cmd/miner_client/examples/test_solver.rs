@@ -0,0 +1,130 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A reference solver speaking the miner client solver stdio protocol, it scans the assigned nonce
+//! range sequentially on one thread. Used to test `ProcessSolver`, and as an example for solver authors.
+
+use anyhow::{bail, Result};
+use consensus::{difficult_to_target, Consensus};
+use parking_lot::Mutex;
+use starcoin_miner_client_api::protocol::{
+    read_message, write_message, SolverRequest, SolverResponse, SOLVER_PROTOCOL_VERSION,
+};
+use starcoin_miner_client_api::NonceRange;
+use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::U256;
+use std::io::{self, Stdout};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const HASH_RATE_REPORT_INTERVAL: Duration = Duration::from_secs(2);
+// Job id 0 is never assigned by the miner client, it marks the solver as idle.
+const IDLE: u64 = 0;
+
+fn send(stdout: &Mutex<Stdout>, response: &SolverResponse) {
+    if let Err(e) = write_message(&mut *stdout.lock(), response) {
+        eprintln!("Failed to write solver response: {}", e);
+    }
+}
+
+fn search(
+    job_id: u64,
+    event: MintBlockEvent,
+    nonce_range: NonceRange,
+    current_job: Arc<AtomicU64>,
+    stdout: Arc<Mutex<Stdout>>,
+) {
+    let extra = event.extra.map(|extra| extra.extra).unwrap_or_default();
+    let target = difficult_to_target(event.difficulty);
+    let mut hashes = 0u64;
+    let mut last_report = Instant::now();
+    for nonce in nonce_range.start..nonce_range.end {
+        if current_job.load(Ordering::SeqCst) != job_id {
+            break;
+        }
+        match event
+            .strategy
+            .calculate_pow_hash(&event.minting_blob, nonce, &extra)
+        {
+            Ok(pow_hash) => {
+                hashes += 1;
+                let pow_hash: U256 = pow_hash.into();
+                if pow_hash <= target {
+                    send(&stdout, &SolverResponse::HashRate { job_id, hashes });
+                    send(&stdout, &SolverResponse::Seal { job_id, nonce });
+                    let _ = current_job.compare_exchange(
+                        job_id,
+                        IDLE,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    );
+                    return;
+                }
+            }
+            Err(e) => {
+                send(
+                    &stdout,
+                    &SolverResponse::Error {
+                        message: e.to_string(),
+                    },
+                );
+                return;
+            }
+        }
+        if last_report.elapsed() >= HASH_RATE_REPORT_INTERVAL {
+            send(&stdout, &SolverResponse::HashRate { job_id, hashes });
+            hashes = 0;
+            last_report = Instant::now();
+        }
+    }
+    send(&stdout, &SolverResponse::HashRate { job_id, hashes });
+}
+
+fn main() -> Result<()> {
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let stdout = Arc::new(Mutex::new(io::stdout()));
+    match read_message(&mut stdin)? {
+        Some(SolverRequest::Hello {
+            version,
+            instance,
+            instances,
+        }) => {
+            send(
+                &stdout,
+                &SolverResponse::Hello {
+                    version: SOLVER_PROTOCOL_VERSION,
+                    name: format!("test-solver-{}/{}", instance, instances),
+                },
+            );
+            if version != SOLVER_PROTOCOL_VERSION {
+                bail!("Unsupported solver protocol version {}", version);
+            }
+        }
+        other => bail!("Expect hello request, got {:?}", other),
+    }
+    let current_job = Arc::new(AtomicU64::new(IDLE));
+    while let Some(request) = read_message(&mut stdin)? {
+        match request {
+            SolverRequest::Solve {
+                job_id,
+                event,
+                nonce_range,
+            } => {
+                current_job.store(job_id, Ordering::SeqCst);
+                let current_job = current_job.clone();
+                let stdout = stdout.clone();
+                thread::spawn(move || search(job_id, event, nonce_range, current_job, stdout));
+            }
+            SolverRequest::Stop { job_id } => {
+                // A stop for a replaced job is stale, keep solving the current one.
+                let _ =
+                    current_job.compare_exchange(job_id, IDLE, Ordering::SeqCst, Ordering::SeqCst);
+            }
+            SolverRequest::Hello { .. } => bail!("Unexpected hello request"),
+        }
+    }
+    Ok(())
+}
@@ -7,44 +7,40 @@ use futures_channel::mpsc::{unbounded, UnboundedSender};
 use logger::prelude::*;
 use rand::Rng;
 use starcoin_config::{MinerClientConfig, TimeService};
-use starcoin_miner_client_api::Solver;
+use starcoin_miner_client_api::{HashCounter, NonceRange, Solver};
 use starcoin_types::system_events::MintBlockEvent;
 use starcoin_types::U256;
 use starcoin_types::{block::BlockHeaderExtra, genesis_config::ConsensusStrategy};
-use std::ops::Range;
 use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
 
+// Flush the local hash count of a worker into the shared counter every this many hashes.
+const HASH_COUNT_BATCH: u64 = 1024;
+
 #[derive(Clone)]
 pub struct CpuSolver {
     config: MinerClientConfig,
     time_service: Arc<dyn TimeService>,
+    hash_counter: HashCounter,
 }
 
 impl CpuSolver {
-    pub fn new(config: MinerClientConfig, time_service: Arc<dyn TimeService>) -> CpuSolver {
+    pub fn new(
+        config: MinerClientConfig,
+        time_service: Arc<dyn TimeService>,
+        hash_counter: HashCounter,
+    ) -> CpuSolver {
         Self {
             config,
             time_service,
+            hash_counter,
         }
     }
 
-    fn nonce_generator(nonce_range: &Range<u32>) -> u32 {
+    fn nonce_generator(nonce_range: &NonceRange) -> u32 {
         let mut rng = rand::thread_rng();
-        let Range { start, end } = nonce_range;
-        rng.gen_range(*start..*end)
-    }
-
-    fn partition_nonce(id: u32, total: u32) -> Range<u32> {
-        let span = u32::max_value() / total;
-        let start = span * id;
-        let end = match id {
-            x if x < total - 1 => start + span,
-            x if x == total - 1 => u32::max_value(),
-            _ => unreachable!(),
-        };
-        Range { start, end }
+        rng.gen_range(nonce_range.start..nonce_range.end)
     }
 }
 
@@ -59,10 +55,11 @@ impl Solver for CpuSolver {
         let worker_txs = (0..thread_num)
             .map(|i| {
                 let worker_name = format!("starcoin-miner-cpu-worker-{}", i);
-                let nonce_range = Self::partition_nonce(i as u32, thread_num as u32);
+                let nonce_range = NonceRange::partition(i as u32, thread_num as u32);
                 let (tx, mut rx) = unbounded::<bool>();
                 let mut nonce_tx = nonce_tx.clone();
                 let time_service = self.time_service.clone();
+                let shared_counter = self.hash_counter.clone();
                 let minting_blob = task.minting_blob.to_owned();
                 let strategy = task.strategy;
                 let diff = task.difficulty;
@@ -76,12 +73,17 @@ impl Solver for CpuSolver {
                     .name(worker_name)
                     .spawn(move || {
                         let mut hash_counter = 0u64;
+                        let mut reported = 0u64;
                         let start = Instant::now();
 
                         loop {
                             if rx.try_next().is_ok() {
                                 break;
                             }
+                            if hash_counter - reported >= HASH_COUNT_BATCH {
+                                shared_counter.add(hash_counter - reported);
+                                reported = hash_counter;
+                            }
                             match strategy {
                                 ConsensusStrategy::Dummy => {
                                     let nonce = strategy.solve_consensus_nonce(
@@ -123,6 +125,7 @@ impl Solver for CpuSolver {
                                 }
                             }
                         }
+                        shared_counter.add(hash_counter - reported);
                     });
                 tx
             })
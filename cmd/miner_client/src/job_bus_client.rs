@@ -3,12 +3,15 @@ use anyhow::Result;
 use async_trait::async_trait;
 use futures::stream::BoxStream;
 use futures::stream::StreamExt;
-use starcoin_miner::{MinerService, SubmitSealRequest};
+use starcoin_miner::{MinerService, SubmitHashRateRequest, SubmitSealRequest};
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
 use starcoin_types::time::TimeService;
 use std::sync::Arc;
 
+/// The worker name of the miner client running inside the node.
+const LOCAL_WORKER: &str = "local";
+
 #[derive(Clone)]
 pub struct JobBusClient {
     bus: ServiceRef<BusService>,
@@ -49,6 +52,15 @@ impl JobClient for JobBusClient {
         Ok(())
     }
 
+    async fn submit_hash_rate(&self, hash_rate: u64) -> Result<()> {
+        self.miner_service
+            .send(SubmitHashRateRequest {
+                worker: LOCAL_WORKER.to_string(),
+                hash_rate,
+            })
+            .await
+    }
+
     fn time_service(&self) -> Arc<dyn TimeService> {
         self.time_service.clone()
     }
@@ -20,6 +20,7 @@ pub struct JobRpcClient {
     rpc_client: Arc<RpcClient>,
     seal_sender: UnboundedSender<(Vec<u8>, u32, BlockHeaderExtra)>,
     time_service: Arc<dyn TimeService>,
+    worker: String,
 }
 
 impl JobRpcClient {
@@ -44,9 +45,16 @@ impl JobRpcClient {
             rpc_client,
             seal_sender,
             time_service: Arc::new(RealTimeService::new()),
+            worker: format!("miner-client-{}", std::process::id()),
         }
     }
 
+    /// Set the worker name used when reporting hash rate to the node.
+    pub fn with_worker(mut self, worker: String) -> Self {
+        self.worker = worker;
+        self
+    }
+
     fn forward_mint_block_stream(&self) -> BoxStream<'static, MintBlockEvent> {
         let (sender, receiver) = unbounded();
         let client = self.rpc_client.clone();
@@ -112,6 +120,17 @@ impl JobClient for JobRpcClient {
         Ok(())
     }
 
+    async fn submit_hash_rate(&self, hash_rate: u64) -> Result<()> {
+        let client = self.rpc_client.clone();
+        let worker = self.worker.clone();
+        Self::spawn(async move {
+            if let Err(e) = client.miner_submit_hashrate(worker, hash_rate) {
+                warn!("Submit hash rate error: {}", e);
+            }
+        });
+        Ok(())
+    }
+
     fn time_service(&self) -> Arc<dyn TimeService> {
         self.time_service.clone()
     }
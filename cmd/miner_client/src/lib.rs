@@ -4,6 +4,7 @@ mod cpu_solver;
 pub mod job_bus_client;
 pub mod job_client;
 pub mod miner;
+pub mod process_solver;
 mod solver;
 pub mod stratum_client;
 pub mod stratum_client_service;
@@ -24,5 +25,7 @@ use std::sync::Arc;
 pub trait JobClient: Send + Unpin + Sync + Clone {
     async fn subscribe(&self) -> Result<BoxStream<'static, MintBlockEvent>>;
     async fn submit_seal(&self, seal: SealEvent) -> Result<()>;
+    /// Report the hash rate of this miner client, in hashes per second.
+    async fn submit_hash_rate(&self, hash_rate: u64) -> Result<()>;
     fn time_service(&self) -> Arc<dyn TimeService>;
 }
@@ -22,8 +22,10 @@ pub struct StarcoinOpt {
     #[clap(long, short = 'u')]
     pub user: String,
     #[clap(long, short = 'n', default_value = "1")]
+    /// CPU solver threads, or solver processes when --plugin-path is set.
     pub thread_num: u16,
     #[clap(long, short = 'p')]
+    /// Path of an external solver executable speaking the solver stdio protocol.
     pub plugin_path: Option<String>,
 }
 
@@ -10,10 +10,13 @@ use futures::SinkExt;
 use logger::prelude::*;
 use parking_lot::Mutex;
 use starcoin_config::MinerClientConfig;
-use starcoin_miner_client_api::Solver;
+use starcoin_miner_client_api::{HashCounter, Solver};
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_types::system_events::MintBlockEvent;
 use std::thread;
+use std::time::{Duration, Instant};
+
+const HASH_RATE_REPORT_INTERVAL: Duration = Duration::from_secs(10);
 
 pub struct MinerClient<C: JobClient> {
     nonce_rx: Option<mpsc::UnboundedReceiver<SealEvent>>,
@@ -21,11 +24,17 @@ pub struct MinerClient<C: JobClient> {
     job_client: C,
     num_seals_found: Mutex<u32>,
     solver: Box<dyn Solver>,
+    hash_counter: HashCounter,
     current_task: Option<mpsc::UnboundedSender<bool>>,
 }
 
 impl<C: JobClient> MinerClient<C> {
-    pub fn new(_config: MinerClientConfig, job_client: C, solver: Box<dyn Solver>) -> Result<Self> {
+    pub fn new(
+        _config: MinerClientConfig,
+        job_client: C,
+        solver: Box<dyn Solver>,
+        hash_counter: HashCounter,
+    ) -> Result<Self> {
         let (nonce_tx, nonce_rx) = mpsc::unbounded();
         Ok(Self {
             nonce_rx: Some(nonce_rx),
@@ -33,6 +42,7 @@ impl<C: JobClient> MinerClient<C> {
             job_client,
             num_seals_found: Mutex::new(0),
             solver,
+            hash_counter,
             current_task: None,
         })
     }
@@ -53,6 +63,19 @@ impl<C: JobClient> ActorService for MinerClientService<C> {
             .take()
             .expect("Inner error for take nonce rx");
         ctx.add_stream(seals);
+        let hash_counter = self.inner.hash_counter.clone();
+        let mut last_report = Instant::now();
+        ctx.run_interval(HASH_RATE_REPORT_INTERVAL, move |ctx| {
+            let hashes = hash_counter.take();
+            let hash_rate = (hashes as f64 / last_report.elapsed().as_secs_f64()) as u64;
+            last_report = Instant::now();
+            let job_client = job_client.clone();
+            ctx.spawn(async move {
+                if let Err(err) = job_client.submit_hash_rate(hash_rate).await {
+                    warn!("Submit hash rate failed: {}", err);
+                }
+            });
+        });
         Ok(())
     }
 }
@@ -61,8 +84,13 @@ impl<C: JobClient> ServiceFactory<Self> for MinerClientService<C> {
     fn create(ctx: &mut ServiceContext<MinerClientService<C>>) -> Result<MinerClientService<C>> {
         let config = ctx.get_shared::<MinerClientConfig>()?;
         let job_client = ctx.get_shared::<C>()?;
-        let solver = create_solver(config.clone(), Some(job_client.time_service()))?;
-        let inner = MinerClient::new(config, job_client, solver)?;
+        let hash_counter = HashCounter::new();
+        let solver = create_solver(
+            config.clone(),
+            Some(job_client.time_service()),
+            hash_counter.clone(),
+        )?;
+        let inner = MinerClient::new(config, job_client, solver, hash_counter)?;
         Ok(Self { inner })
     }
 }
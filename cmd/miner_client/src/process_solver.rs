@@ -0,0 +1,223 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::SealEvent;
+use anyhow::{bail, format_err, Result};
+use futures::executor::block_on;
+use futures::StreamExt;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use logger::prelude::*;
+use parking_lot::Mutex;
+use starcoin_miner_client_api::protocol::{
+    read_message, write_message, SolverRequest, SolverResponse, SOLVER_PROTOCOL_VERSION,
+};
+use starcoin_miner_client_api::{HashCounter, NonceRange, Solver};
+use starcoin_types::system_events::MintBlockEvent;
+use std::io::BufReader;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+struct SolverInstance {
+    child: Child,
+    stdin: ChildStdin,
+}
+
+impl Drop for SolverInstance {
+    fn drop(&mut self) {
+        if let Err(e) = self.child.kill() {
+            debug!("Failed to kill solver process {}: {}", self.child.id(), e);
+        }
+        let _ = self.child.wait();
+    }
+}
+
+struct CurrentJob {
+    job_id: u64,
+    event: MintBlockEvent,
+    nonce_tx: UnboundedSender<SealEvent>,
+}
+
+/// A solver which drives external solver processes over the stdio protocol in
+/// `starcoin_miner_client_api::protocol`, each process searches a disjoint nonce range.
+#[derive(Clone)]
+pub struct ProcessSolver {
+    instances: Arc<Vec<Mutex<SolverInstance>>>,
+    current_job: Arc<Mutex<Option<CurrentJob>>>,
+    next_job_id: Arc<AtomicU64>,
+}
+
+impl ProcessSolver {
+    /// Spawn `instances` processes of the solver executable at `path` and handshake with them.
+    pub fn spawn(path: &str, instances: u32, hash_counter: HashCounter) -> Result<Self> {
+        if instances == 0 {
+            bail!("Solver instances should be greater than 0");
+        }
+        let current_job = Arc::new(Mutex::new(None));
+        let mut solver_instances = vec![];
+        for instance in 0..instances {
+            let mut child = Command::new(path)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .spawn()
+                .map_err(|e| format_err!("Failed to spawn solver {}: {}", path, e))?;
+            let stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| format_err!("Solver stdin is not piped"))?;
+            let stdout = child
+                .stdout
+                .take()
+                .ok_or_else(|| format_err!("Solver stdout is not piped"))?;
+            // Dropping the instance kills the process if the handshake fails.
+            let mut solver_instance = SolverInstance { child, stdin };
+            let mut stdout = BufReader::new(stdout);
+            write_message(
+                &mut solver_instance.stdin,
+                &SolverRequest::Hello {
+                    version: SOLVER_PROTOCOL_VERSION,
+                    instance,
+                    instances,
+                },
+            )?;
+            match read_message(&mut stdout)? {
+                Some(SolverResponse::Hello { version, name }) => {
+                    if version != SOLVER_PROTOCOL_VERSION {
+                        bail!(
+                            "Solver {} uses protocol version {}, expect {}",
+                            name,
+                            version,
+                            SOLVER_PROTOCOL_VERSION
+                        );
+                    }
+                    info!(
+                        "[miner-client-solver] Solver {} instance {}/{} started",
+                        name, instance, instances
+                    );
+                }
+                other => bail!("Unexpected solver handshake response: {:?}", other),
+            }
+            let nonce_range = NonceRange::partition(instance, instances);
+            let current_job = current_job.clone();
+            let hash_counter = hash_counter.clone();
+            thread::Builder::new()
+                .name(format!("starcoin-miner-solver-{}", instance))
+                .spawn(move || {
+                    Self::read_responses(stdout, instance, nonce_range, current_job, hash_counter)
+                })?;
+            solver_instances.push(Mutex::new(solver_instance));
+        }
+        Ok(Self {
+            instances: Arc::new(solver_instances),
+            current_job,
+            next_job_id: Arc::new(AtomicU64::new(1)),
+        })
+    }
+
+    fn read_responses(
+        mut stdout: BufReader<ChildStdout>,
+        instance: u32,
+        nonce_range: NonceRange,
+        current_job: Arc<Mutex<Option<CurrentJob>>>,
+        hash_counter: HashCounter,
+    ) {
+        loop {
+            match read_message(&mut stdout) {
+                Ok(Some(SolverResponse::Seal { job_id, nonce })) => {
+                    if !nonce_range.contains(nonce) {
+                        warn!(
+                            "[miner-client-solver-{}] Seal nonce {} out of range {:?}",
+                            instance, nonce, nonce_range
+                        );
+                        continue;
+                    }
+                    match current_job.lock().as_ref() {
+                        Some(job) if job.job_id == job_id => {
+                            info!(
+                                "[miner-client-solver-{}] New seal found by solver, nonce {:?}",
+                                instance, nonce
+                            );
+                            if let Err(e) = job.nonce_tx.unbounded_send(SealEvent {
+                                minting_blob: job.event.minting_blob.clone(),
+                                nonce,
+                                extra: job.event.extra.clone(),
+                                hash_result: Default::default(),
+                            }) {
+                                error!("[miner-client-solver] Failed to send seal: {:?}", e);
+                            }
+                        }
+                        _ => debug!(
+                            "[miner-client-solver-{}] Ignore seal of stale job {}",
+                            instance, job_id
+                        ),
+                    }
+                }
+                Ok(Some(SolverResponse::HashRate { hashes, .. })) => hash_counter.add(hashes),
+                Ok(Some(SolverResponse::Error { message })) => {
+                    error!(
+                        "[miner-client-solver-{}] Solver error: {}",
+                        instance, message
+                    )
+                }
+                Ok(Some(response)) => warn!(
+                    "[miner-client-solver-{}] Unexpected solver response: {:?}",
+                    instance, response
+                ),
+                Ok(None) => {
+                    error!("[miner-client-solver-{}] Solver process exited", instance);
+                    break;
+                }
+                Err(e) => {
+                    error!("[miner-client-solver-{}] {}", instance, e);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn broadcast(&self, request: impl Fn(u32) -> SolverRequest) {
+        for (instance, solver_instance) in self.instances.iter().enumerate() {
+            if let Err(e) =
+                write_message(&mut solver_instance.lock().stdin, &request(instance as u32))
+            {
+                error!(
+                    "[miner-client-solver-{}] Failed to send request to solver: {}",
+                    instance, e
+                );
+            }
+        }
+    }
+}
+
+impl Solver for ProcessSolver {
+    fn solve(
+        &mut self,
+        event: MintBlockEvent,
+        nonce_tx: UnboundedSender<SealEvent>,
+        mut stop_rx: UnboundedReceiver<bool>,
+    ) {
+        let job_id = self.next_job_id.fetch_add(1, Ordering::SeqCst);
+        let instances = self.instances.len() as u32;
+        *self.current_job.lock() = Some(CurrentJob {
+            job_id,
+            event: event.clone(),
+            nonce_tx,
+        });
+        self.broadcast(|instance| SolverRequest::Solve {
+            job_id,
+            event: event.clone(),
+            nonce_range: NonceRange::partition(instance, instances),
+        });
+        block_on(stop_rx.next());
+        {
+            // A newer job may have replaced this one already.
+            let mut current_job = self.current_job.lock();
+            if current_job.as_ref().map(|job| job.job_id) == Some(job_id) {
+                *current_job = None;
+            }
+        }
+        self.broadcast(|_| SolverRequest::Stop { job_id });
+    }
+}
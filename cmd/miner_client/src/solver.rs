@@ -1,31 +1,26 @@
 use crate::cpu_solver::CpuSolver;
+use crate::process_solver::ProcessSolver;
 use anyhow::Result;
 use starcoin_config::{MinerClientConfig, TimeService};
-use starcoin_miner_client_api::Solver;
+use starcoin_miner_client_api::{HashCounter, Solver};
 use std::sync::Arc;
 
-type CreateSolver = extern "C" fn() -> Box<dyn Solver>;
-
-const SOLVER_CREATER: &[u8] = b"create_solver";
-
+/// Create the built-in CPU solver, or when `plugin_path` is set, spawn `miner_thread` instances of
+/// the external solver executable speaking `starcoin_miner_client_api::protocol`.
 pub fn create_solver(
     config: MinerClientConfig,
     time_service: Option<Arc<dyn TimeService>>,
+    hash_counter: HashCounter,
 ) -> Result<Box<dyn Solver>> {
-    match config.plugin_path {
+    match &config.plugin_path {
         None => {
             let ts = time_service.expect("time service should exist");
-            Ok(Box::new(CpuSolver::new(config, ts)))
+            Ok(Box::new(CpuSolver::new(config, ts, hash_counter)))
         }
-        Some(path) => unsafe {
-            //Since this issue https://github.com/nagisa/rust_libloading/issues/41
-            #[cfg(target_os = "linux")]
-            let lib = libloading::os::unix::Library::open(Some(path), 0x2 | 0x1000)?;
-            #[cfg(not(target_os = "linux"))]
-            let lib = libloading::Library::new(path)?;
-            let call_ref = lib.get::<CreateSolver>(SOLVER_CREATER)?;
-
-            Ok(call_ref())
-        },
+        Some(path) => Ok(Box::new(ProcessSolver::spawn(
+            path,
+            config.miner_thread() as u32,
+            hash_counter,
+        )?)),
     }
 }
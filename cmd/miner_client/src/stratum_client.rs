@@ -6,7 +6,7 @@ use async_trait::async_trait;
 use byteorder::{LittleEndian, WriteBytesExt};
 use futures::future;
 use futures::stream::{BoxStream, StreamExt};
-use logger::prelude::error;
+use logger::prelude::{debug, error};
 use starcoin_service_registry::ServiceRef;
 use starcoin_stratum::rpc::LoginRequest;
 use starcoin_stratum::target_hex_to_difficulty;
@@ -96,6 +96,12 @@ impl JobClient for StratumJobClient {
         Ok(())
     }
 
+    async fn submit_hash_rate(&self, hash_rate: u64) -> Result<()> {
+        // The stratum protocol has no hash rate report, the pool estimates it from the shares.
+        debug!("Miner client hash rate: {}", hash_rate);
+        Ok(())
+    }
+
     fn time_service(&self) -> Arc<dyn TimeService> {
         self.time_service.clone()
     }
@@ -0,0 +1,75 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use consensus::Consensus;
+use futures::executor::block_on;
+use futures::{SinkExt, StreamExt};
+use futures_channel::mpsc::unbounded;
+use starcoin_miner_client::process_solver::ProcessSolver;
+use starcoin_miner_client::{BlockHeaderExtra, ConsensusStrategy, MintBlockEvent, U256};
+use starcoin_miner_client_api::{HashCounter, NonceRange, Solver};
+use std::path::PathBuf;
+use std::thread;
+
+/// The `test_solver` example, cargo builds the examples of the package before running its tests.
+fn test_solver_path() -> PathBuf {
+    let mut dir = std::env::current_exe().unwrap();
+    dir.pop();
+    if dir.ends_with("deps") {
+        dir.pop();
+    }
+    let path = dir
+        .join("examples")
+        .join(format!("test_solver{}", std::env::consts::EXE_SUFFIX));
+    assert!(
+        path.exists(),
+        "{} not found, build it by `cargo build -p starcoin-miner-client --example test_solver`",
+        path.display()
+    );
+    path
+}
+
+#[test]
+fn test_process_solver() {
+    let hash_counter = HashCounter::new();
+    let instances = 2;
+    let solver_path = test_solver_path();
+    let solver = ProcessSolver::spawn(
+        solver_path.to_str().unwrap(),
+        instances,
+        hash_counter.clone(),
+    )
+    .unwrap();
+    let difficulty = U256::from(16);
+    let event = MintBlockEvent::new(
+        Default::default(),
+        ConsensusStrategy::Keccak,
+        vec![1u8; 76],
+        difficulty,
+        1,
+        None,
+    );
+
+    for _ in 0..2 {
+        let (nonce_tx, mut nonce_rx) = unbounded();
+        let (mut stop_tx, stop_rx) = unbounded();
+        let mut task_solver = solver.clone();
+        let task_event = event.clone();
+        let handle = thread::spawn(move || task_solver.solve(task_event, nonce_tx, stop_rx));
+
+        let seal = block_on(nonce_rx.next()).unwrap();
+        assert_eq!(seal.minting_blob, event.minting_blob);
+        assert!((0..instances).any(|id| NonceRange::partition(id, instances).contains(seal.nonce)));
+        ConsensusStrategy::Keccak
+            .verify_blob(
+                seal.minting_blob,
+                seal.nonce,
+                BlockHeaderExtra::default(),
+                difficulty,
+            )
+            .unwrap();
+        block_on(stop_tx.send(true)).unwrap();
+        handle.join().unwrap();
+    }
+    assert!(hash_counter.take() > 0);
+}
@@ -6,7 +6,8 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
-use starcoin_account_api::AccountInfo;
+use serde::{Deserialize, Serialize};
+use starcoin_account_api::{AccountInfo, DEFAULT_MNEMONIC_WORD_COUNT};
 
 /// Create a new account
 #[derive(Debug, Parser, Default)]
@@ -14,6 +15,28 @@ use starcoin_account_api::AccountInfo;
 pub struct CreateOpt {
     #[clap(short = 'p')]
     password: String,
+
+    /// Create the HD wallet with a new mnemonic, and create the first account of the wallet.
+    /// Back up the mnemonic in the output, it is only shown once.
+    #[clap(long = "mnemonic", conflicts_with("derive"))]
+    mnemonic: bool,
+
+    /// The word count of the new mnemonic, one of 12, 15, 18, 21, 24.
+    #[clap(long = "word-count", requires("mnemonic"))]
+    word_count: Option<usize>,
+
+    /// Derive the next account from the HD wallet, the password should be the wallet password.
+    #[clap(long = "derive")]
+    derive: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateAccountView {
+    #[serde(flatten)]
+    pub account: AccountInfo,
+    /// The mnemonic of the new HD wallet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
 }
 
 pub struct CreateCommand;
@@ -22,15 +45,35 @@ impl CommandAction for CreateCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = CreateOpt;
-    type ReturnItem = AccountInfo;
+    type ReturnItem = CreateAccountView;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
-    ) -> Result<AccountInfo> {
+    ) -> Result<CreateAccountView> {
+        let opt = ctx.opt();
         let account_client = ctx.state().account_client();
-        let account = account_client.create_account(ctx.opt().password.clone())?;
-        Ok(account)
+        let view = if opt.mnemonic {
+            let mnemonic_account = account_client.create_mnemonic_account(
+                opt.password.clone(),
+                opt.word_count.unwrap_or(DEFAULT_MNEMONIC_WORD_COUNT),
+            )?;
+            CreateAccountView {
+                account: mnemonic_account.account,
+                mnemonic: Some(mnemonic_account.mnemonic),
+            }
+        } else if opt.derive {
+            CreateAccountView {
+                account: account_client.derive_account(opt.password.clone())?,
+                mnemonic: None,
+            }
+        } else {
+            CreateAccountView {
+                account: account_client.create_account(opt.password.clone())?,
+                mnemonic: None,
+            }
+        };
+        Ok(view)
     }
 
     fn skip_history(&self, _ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>) -> bool {
@@ -36,6 +36,10 @@ pub struct ExecuteScriptFunctionOpt {
     #[clap(long = "function", name = "script-function")]
     /// script function to execute, example: 0x1::TransferScripts::peer_to_peer_v2
     script_function: FunctionIdView,
+
+    #[clap(long = "estimate")]
+    /// estimate mode, estimate the max gas amount of the transaction and dry run with it, do not submit the transaction.
+    estimate: bool,
 }
 
 pub struct ExecuteScriptFunctionCmd;
@@ -54,14 +58,18 @@ impl CommandAction for ExecuteScriptFunctionCmd {
         let type_tags = opt.type_tags.clone().unwrap_or_default();
         let args = opt.args.clone().unwrap_or_default();
         let script_function = opt.script_function.clone().0;
-        ctx.state().build_and_execute_transaction(
-            opt.transaction_opts.clone(),
-            TransactionPayload::ScriptFunction(ScriptFunction::new(
-                script_function.module,
-                script_function.function,
-                type_tags,
-                convert_txn_args(&args),
-            )),
-        )
+        let payload = TransactionPayload::ScriptFunction(ScriptFunction::new(
+            script_function.module,
+            script_function.function,
+            type_tags,
+            convert_txn_args(&args),
+        ));
+        if opt.estimate {
+            ctx.state()
+                .build_and_estimate_transaction(opt.transaction_opts.clone(), payload)
+        } else {
+            ctx.state()
+                .build_and_execute_transaction(opt.transaction_opts.clone(), payload)
+        }
     }
 }
@@ -6,12 +6,13 @@ use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
 use starcoin_account_api::{AccountInfo, AccountPrivateKey};
 use starcoin_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use starcoin_vm_types::account_address::AccountAddress;
 use std::path::PathBuf;
 
-/// Import account by private key to node wallet.
+/// Import account by private key, or recover the HD wallet by mnemonic, to node wallet.
 #[derive(Debug, Parser)]
 #[clap(name = "import")]
 pub struct ImportOpt {
@@ -29,18 +30,37 @@ pub struct ImportOpt {
     )]
     from_file: Option<PathBuf>,
 
+    /// Recover the HD wallet from the mnemonic, the password is used as the wallet password.
+    #[clap(
+        long = "mnemonic",
+        conflicts_with_all(&["input", "from_file", "account_address"])
+    )]
+    mnemonic: Option<String>,
+
+    /// The count of the accounts to restore from the mnemonic.
+    #[clap(long = "count", default_value = "1", requires("mnemonic"))]
+    count: u32,
+
     /// if account_address is absent, generate address by public_key.
     #[clap(name = "account_address")]
     account_address: Option<AccountAddress>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ImportAccountView {
+    Account(AccountInfo),
+    /// The accounts restored from the mnemonic.
+    Accounts(Vec<AccountInfo>),
+}
+
 pub struct ImportCommand;
 
 impl CommandAction for ImportCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = ImportOpt;
-    type ReturnItem = AccountInfo;
+    type ReturnItem = ImportAccountView;
 
     fn run(
         &self,
@@ -48,6 +68,11 @@ impl CommandAction for ImportCommand {
     ) -> Result<Self::ReturnItem> {
         let opt: &ImportOpt = ctx.opt();
         let client = ctx.state().account_client();
+        if let Some(mnemonic) = opt.mnemonic.as_ref() {
+            let accounts =
+                client.import_mnemonic(mnemonic.clone(), opt.password.clone(), opt.count)?;
+            return Ok(ImportAccountView::Accounts(accounts));
+        }
         let private_key = match (opt.from_input.as_ref(), opt.from_file.as_ref()) {
             (Some(p), _) => AccountPrivateKey::from_encoded_string(p)?,
             (None, Some(p)) => {
@@ -55,7 +80,9 @@ impl CommandAction for ImportCommand {
                 AccountPrivateKey::from_encoded_string(data.as_str())?
             }
             (None, None) => {
-                bail!("private key should be specified, use one of <input>, <from-file>")
+                bail!(
+                    "private key should be specified, use one of <input>, <from-file>, <mnemonic>"
+                )
             }
         };
 
@@ -67,7 +94,7 @@ impl CommandAction for ImportCommand {
             private_key.to_bytes().to_vec(),
             opt.password.clone(),
         )?;
-        Ok(account)
+        Ok(ImportAccountView::Account(account))
     }
 
     fn skip_history(&self, _ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>) -> bool {
@@ -0,0 +1,181 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::mutlisig_transaction::{
+    try_submit_multisig_txn, write_multisig_txn, MultisigSubmitOpt, MultisigTransaction,
+    MultisigTransactionView,
+};
+use crate::StarcoinOpt;
+use anyhow::{bail, format_err, Result};
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountPublicKey;
+use starcoin_rpc_api::types::{FunctionIdView, TransactionStatusView};
+use starcoin_rpc_client::StateRootOption;
+use starcoin_state_api::StateReaderExt;
+use starcoin_types::transaction::{
+    parse_transaction_argument, DryRunTransaction, RawUserTransaction, TransactionArgument,
+};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE_STR;
+use starcoin_vm_types::transaction::{ScriptFunction, TransactionPayload};
+use starcoin_vm_types::transaction_argument::convert_txn_args;
+use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
+use std::path::PathBuf;
+
+/// Create a multisig txn container running a script function, co-sign it with the unlocked local
+/// accounts, and output it to a file for other participants to sign.
+/// The multisig account should be imported by `account import-multisig` or `account import-readonly`.
+#[derive(Debug, Parser)]
+#[clap(name = "create")]
+pub struct CreateMultisigTxnOpt {
+    #[clap(short = 's')]
+    /// sender address of this multisig txn.
+    sender: AccountAddress,
+
+    #[clap(long = "function", name = "script-function")]
+    /// script function to execute, example: 0x1::TransferScripts::peer_to_peer_v2
+    script_function: FunctionIdView,
+
+    #[clap(
+    short = 't',
+    long = "type_tag",
+    name = "type-tag",
+    help = "can specify multi type_tag",
+    parse(try_from_str = parse_type_tag)
+    )]
+    type_tags: Option<Vec<TypeTag>>,
+
+    #[clap(long = "arg", name = "transaction-arg",  parse(try_from_str = parse_transaction_argument))]
+    /// transaction arguments
+    args: Option<Vec<TransactionArgument>>,
+
+    #[clap(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+
+    #[clap(
+        short = 'g',
+        long = "max-gas",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+
+    #[clap(
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+
+    #[clap(short = 'o', long = "output")]
+    /// file or dir to write the container. Default to current dir.
+    output: Option<PathBuf>,
+
+    #[clap(long = "json")]
+    /// write the container as json instead of bcs.
+    json: bool,
+
+    #[clap(flatten)]
+    submit: MultisigSubmitOpt,
+}
+
+pub struct CreateMultisigTxnCommand;
+
+impl CommandAction for CreateMultisigTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = CreateMultisigTxnOpt;
+    type ReturnItem = MultisigTransactionView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let rpc_client = ctx.state().client();
+        let account_client = ctx.state().account_client();
+        let sender = opt.sender;
+
+        let account = account_client.get_account(sender)?.ok_or_else(|| {
+            format_err!(
+                "cannot find multisig address {}, import it by `account import-multisig` or `account import-readonly` first",
+                sender
+            )
+        })?;
+        let public_key = match &account.public_key {
+            AccountPublicKey::Single(_) => {
+                bail!("sender {} is not a multisig address", sender);
+            }
+            AccountPublicKey::Multi(m) => m.clone(),
+        };
+
+        let function_id = opt.script_function.clone().0;
+        let script_function = ScriptFunction::new(
+            function_id.module,
+            function_id.function,
+            opt.type_tags.clone().unwrap_or_default(),
+            convert_txn_args(&opt.args.clone().unwrap_or_default()),
+        );
+        let node_info = rpc_client.node_info()?;
+        let chain_state_reader = rpc_client.state_reader(StateRootOption::Latest)?;
+        let account_resource = chain_state_reader
+            .get_account_resource(sender)?
+            .ok_or_else(|| format_err!("address {} not exists on chain", sender))?;
+        let raw_txn = RawUserTransaction::new(
+            sender,
+            account_resource.sequence_number(),
+            TransactionPayload::ScriptFunction(script_function),
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time + node_info.now_seconds,
+            ctx.state().net().chain_id(),
+            STC_TOKEN_CODE_STR.to_string(),
+        );
+
+        let output = rpc_client.dry_run_raw(DryRunTransaction {
+            public_key: AccountPublicKey::Multi(public_key.clone()),
+            raw_txn: raw_txn.clone(),
+        })?;
+        // Use `eprintln` instead of `println`, for keep the cli stdout's format(such as json) is not broken by print.
+        eprintln!(
+            "Transaction dry run execute output: \n {}",
+            serde_json::to_string_pretty(&output)?
+        );
+        match &output.txn_output.status {
+            TransactionStatusView::Executed => {}
+            TransactionStatusView::Discard {
+                status_code,
+                status_code_name,
+            } => {
+                bail!(
+                    "TransactionStatus is discard: {:?}, {}",
+                    status_code,
+                    status_code_name
+                )
+            }
+            s => {
+                bail!("pre-run failed, status: {:?}", s);
+            }
+        }
+
+        let txn =
+            account_client.sign_multisig_txn(MultisigTransaction::new(raw_txn, &public_key))?;
+        eprintln!(
+            "{} signatures added by local accounts",
+            txn.signature_count()
+        );
+        let output = write_multisig_txn(&txn, opt.output.as_deref(), opt.json)?;
+        let mut view = MultisigTransactionView::new(ctx.state(), &txn, Some(output))?;
+        view.submitted = try_submit_multisig_txn(ctx.state(), &txn, &opt.submit)?;
+        Ok(view)
+    }
+}
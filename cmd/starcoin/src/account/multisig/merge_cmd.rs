@@ -0,0 +1,60 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::mutlisig_transaction::{
+    read_multisig_txn, try_submit_multisig_txn, write_multisig_txn, MultisigSubmitOpt,
+    MultisigTransactionView,
+};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use std::path::PathBuf;
+
+/// Merge the signatures of multisig txn containers of the same txn, and submit the txn once the
+/// threshold of signatures is met.
+#[derive(Debug, Parser)]
+#[clap(name = "merge")]
+pub struct MergeMultisigTxnOpt {
+    #[clap(name = "multisig-file", required = true, min_values = 2)]
+    /// the multisig txn container files signed by different participants.
+    files: Vec<PathBuf>,
+
+    #[clap(short = 'o', long = "output")]
+    /// file or dir to write the merged container. Default to current dir.
+    output: Option<PathBuf>,
+
+    #[clap(long = "json")]
+    /// write the container as json instead of bcs.
+    json: bool,
+
+    #[clap(flatten)]
+    submit: MultisigSubmitOpt,
+}
+
+pub struct MergeMultisigTxnCommand;
+
+impl CommandAction for MergeMultisigTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = MergeMultisigTxnOpt;
+    type ReturnItem = MultisigTransactionView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let mut txn = read_multisig_txn(opt.files[0].as_path())?;
+        for file in &opt.files[1..] {
+            let other = read_multisig_txn(file.as_path())?;
+            let added = txn.merge(&other)?;
+            eprintln!("{} signatures merged from {}", added, file.display());
+        }
+        let output = write_multisig_txn(&txn, opt.output.as_deref(), opt.json)?;
+        let mut view = MultisigTransactionView::new(ctx.state(), &txn, Some(output))?;
+        view.submitted = try_submit_multisig_txn(ctx.state(), &txn, &opt.submit)?;
+        Ok(view)
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod create_cmd;
+mod merge_cmd;
+mod show_cmd;
+mod sign_cmd;
+mod submit_cmd;
+
+pub use create_cmd::*;
+pub use merge_cmd::*;
+pub use show_cmd::*;
+pub use sign_cmd::*;
+pub use submit_cmd::*;
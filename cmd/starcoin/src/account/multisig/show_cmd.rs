@@ -0,0 +1,37 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::mutlisig_transaction::{read_multisig_txn, MultisigTransactionView};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use std::path::PathBuf;
+
+/// Show the multisig txn container, which signers have signed and whether the threshold is met.
+#[derive(Debug, Parser)]
+#[clap(name = "show")]
+pub struct ShowMultisigTxnOpt {
+    #[clap(name = "multisig-file")]
+    /// the multisig txn container file.
+    file: PathBuf,
+}
+
+pub struct ShowMultisigTxnCommand;
+
+impl CommandAction for ShowMultisigTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ShowMultisigTxnOpt;
+    type ReturnItem = MultisigTransactionView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let file = ctx.opt().file.clone();
+        let txn = read_multisig_txn(file.as_path())?;
+        MultisigTransactionView::new(ctx.state(), &txn, Some(file))
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::mutlisig_transaction::{
+    read_multisig_txn, try_submit_multisig_txn, write_multisig_txn, MultisigSubmitOpt,
+    MultisigTransactionView,
+};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use std::path::PathBuf;
+
+/// Co-sign the multisig txn container with the unlocked local accounts which hold keys of its
+/// signers, and submit the txn once the threshold of signatures is met.
+#[derive(Debug, Parser)]
+#[clap(name = "sign")]
+pub struct SignMultisigTxnOpt {
+    #[clap(name = "multisig-file")]
+    /// the multisig txn container file.
+    file: PathBuf,
+
+    #[clap(short = 'o', long = "output")]
+    /// file or dir to write the signed container, default to overwrite the input file.
+    output: Option<PathBuf>,
+
+    #[clap(long = "json")]
+    /// write the container as json instead of bcs.
+    json: bool,
+
+    #[clap(flatten)]
+    submit: MultisigSubmitOpt,
+}
+
+pub struct SignMultisigTxnCommand;
+
+impl CommandAction for SignMultisigTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SignMultisigTxnOpt;
+    type ReturnItem = MultisigTransactionView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let txn = read_multisig_txn(opt.file.as_path())?;
+        let signature_count = txn.signature_count();
+        let txn = ctx.state().account_client().sign_multisig_txn(txn)?;
+        eprintln!(
+            "{} signatures added by local accounts",
+            txn.signature_count() - signature_count
+        );
+        let output = write_multisig_txn(
+            &txn,
+            Some(opt.output.as_ref().unwrap_or(&opt.file).as_path()),
+            opt.json,
+        )?;
+        let mut view = MultisigTransactionView::new(ctx.state(), &txn, Some(output))?;
+        view.submitted = try_submit_multisig_txn(ctx.state(), &txn, &opt.submit)?;
+        Ok(view)
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::mutlisig_transaction::{read_multisig_txn, submit_multisig_txn};
+use crate::view::ExecutionOutputView;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use std::path::PathBuf;
+
+/// Submit the multisig txn container, fail if the threshold of signatures is not met.
+#[derive(Debug, Parser)]
+#[clap(name = "submit")]
+pub struct SubmitMultisigTxnOpt {
+    #[clap(name = "multisig-file")]
+    /// the multisig txn container file.
+    file: PathBuf,
+
+    #[clap(
+        short = 'b',
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct SubmitMultisigTxnCommand;
+
+impl CommandAction for SubmitMultisigTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubmitMultisigTxnOpt;
+    type ReturnItem = ExecutionOutputView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let txn = read_multisig_txn(opt.file.as_path())?;
+        submit_multisig_txn(ctx.state(), txn, opt.blocking)
+    }
+}
@@ -35,13 +35,42 @@ impl CommandAction for GetTransactionInfoListCommand {
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
+        let mut txn_infos = vec![];
+        self.run_paged(ctx, &mut |page| {
+            txn_infos.extend(page);
+            Ok(())
+        })?;
+        Ok(txn_infos)
+    }
+
+    /// The node limits the transaction infos of one request, so fetch them page by page.
+    fn run_paged(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+        page: &mut dyn FnMut(Self::ReturnItem) -> Result<()>,
+    ) -> Result<()> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        let txn_infos = client.chain_get_transaction_infos(
-            opt.start_index,
-            opt.reverse.unwrap_or(false),
-            opt.count,
-        )?;
-        Ok(txn_infos)
+        let reverse = opt.reverse.unwrap_or(false);
+        let mut start_index = opt.start_index;
+        let mut remaining = opt.count;
+        while remaining > 0 {
+            let txn_infos = client.chain_get_transaction_infos(start_index, reverse, remaining)?;
+            let last_index = match txn_infos.last() {
+                Some(txn_info) => txn_info.transaction_global_index.0,
+                None => break,
+            };
+            remaining = remaining.saturating_sub(txn_infos.len() as u64);
+            page(txn_infos)?;
+            start_index = if reverse {
+                match last_index.checked_sub(1) {
+                    Some(index) => index,
+                    None => break,
+                }
+            } else {
+                last_index + 1
+            };
+        }
+        Ok(())
     }
 }
@@ -31,10 +31,37 @@ impl CommandAction for ListBlockCommand {
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
+        let mut block_view = vec![];
+        self.run_paged(ctx, &mut |page| {
+            block_view.extend(page);
+            Ok(())
+        })?;
+        Ok(block_view)
+    }
+
+    /// The node limits the blocks of one request, so fetch the blocks page by page.
+    fn run_paged(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+        page: &mut dyn FnMut(Self::ReturnItem) -> Result<()>,
+    ) -> Result<()> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        let blocks = client.chain_get_blocks_by_number(opt.number, opt.count)?;
-        let block_view = blocks.into_iter().map(|block| block.header).collect();
-        Ok(block_view)
+        let mut number = opt.number;
+        let mut remaining = opt.count;
+        while remaining > 0 {
+            let blocks = client.chain_get_blocks_by_number(number, remaining)?;
+            let last_number = match blocks.last() {
+                Some(block) => block.header.number.0,
+                None => break,
+            };
+            remaining = remaining.saturating_sub(blocks.len() as u64);
+            page(blocks.into_iter().map(|block| block.header).collect())?;
+            if last_number == 0 {
+                break;
+            }
+            number = Some(last_number - 1);
+        }
+        Ok(())
     }
 }
@@ -197,6 +197,50 @@ impl CliState {
         self.execute_transaction(raw_txn, txn_opts.dry_run, txn_opts.blocking)
     }
 
+    /// Build the transaction, estimate its max gas amount, then dry run the transaction with
+    /// the estimated max gas amount. The transaction is not submitted.
+    pub fn build_and_estimate_transaction(
+        &self,
+        txn_opts: TransactionOptions,
+        payload: TransactionPayload,
+    ) -> Result<ExecuteResultView> {
+        let (raw_txn, future_transaction) = self.build_transaction(
+            txn_opts.sender,
+            txn_opts.sequence_number,
+            txn_opts.gas_unit_price,
+            txn_opts.max_gas_amount,
+            txn_opts.expiration_time_secs,
+            payload,
+        )?;
+        if future_transaction {
+            bail!("there is transaction from sender({}) in the txpool, please wait it to been executed or use sequence_number({}) to replace it.",raw_txn.sender(), raw_txn.sequence_number()-1);
+        }
+        let sender = self.get_account(raw_txn.sender())?;
+        let gas_estimate = self.client.estimate_gas_raw(DryRunTransaction {
+            public_key: sender.public_key,
+            raw_txn: raw_txn.clone(),
+        })?;
+        let raw_txn = match gas_estimate.max_gas_amount {
+            Some(max_gas_amount) => RawUserTransaction::new(
+                raw_txn.sender(),
+                raw_txn.sequence_number(),
+                raw_txn.payload().clone(),
+                max_gas_amount.0,
+                raw_txn.gas_unit_price(),
+                raw_txn.expiration_timestamp_secs(),
+                raw_txn.chain_id(),
+                raw_txn.gas_token_code(),
+            ),
+            None => {
+                eprintln!("txn gas estimation failed");
+                raw_txn
+            }
+        };
+        let mut execute_result = self.execute_transaction(raw_txn, true, false)?;
+        execute_result.gas_estimate = Some(gas_estimate);
+        Ok(execute_result)
+    }
+
     fn build_transaction(
         &self,
         sender: Option<AccountAddress>,
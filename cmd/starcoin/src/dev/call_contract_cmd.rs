@@ -2,12 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_state::CliState;
+use crate::dev::dev_helper::write_collapsed_stacks;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
 use starcoin_abi_decoder::DecodedMoveValue;
-use starcoin_rpc_api::types::{ContractCall, FunctionIdView, TransactionArgumentView, TypeTagView};
+use starcoin_rpc_api::types::{
+    ContractCall, FunctionIdView, ModuleProfileView, StrView, TransactionArgumentView, TypeTagView,
+};
+use starcoin_vm_runtime::profiler::ProfileWeight;
+use std::path::PathBuf;
 
 /// Call Contract command
 ///  Some examples:
@@ -16,6 +22,8 @@ use starcoin_rpc_api::types::{ContractCall, FunctionIdView, TransactionArgumentV
 ///  dev call --function 0x1::Block::current_block_number
 ///  # 0x1::Account::balance<0x1::STC::STC>(0x726098b70ba8aa2cc172af19af8804)
 ///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804
+///  # profile the call, and write the collapsed stacks of gas for flamegraph tools.
+///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804 --profile --flamegraph balance.folded
 ///  ```
 #[derive(Debug, Parser)]
 #[clap(name = "call")]
@@ -37,6 +45,41 @@ pub struct CallContractOpt {
         help = "can specify multi arg"
     )]
     args: Option<Vec<TransactionArgumentView>>,
+
+    #[clap(long)]
+    /// Profile the gas and time of the call by the debug rpc, and return the summary per module.
+    /// The gas is charged by the on-chain gas schedule. A node built in release mode gives the
+    /// call level profile only, the gas of each function or bytecode offset is not measured.
+    /// The bytecode level profile of a node built with debug assertions is estimated.
+    profile: bool,
+
+    #[clap(long, parse(from_os_str), requires = "profile")]
+    /// Write the profile as collapsed stacks to the file, the input of flamegraph tools.
+    flamegraph: Option<PathBuf>,
+
+    #[clap(long, default_value = "gas", requires = "flamegraph")]
+    /// The weight of the collapsed stacks, gas or time.
+    weight: ProfileWeight,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CallContractResult {
+    Values(Vec<DecodedMoveValue>),
+    Profiled(ProfiledCallResult),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfiledCallResult {
+    pub return_values: Vec<DecodedMoveValue>,
+    /// Whether the profile is at the bytecode level, or only at the call level.
+    /// The gas and time of the modules are estimates at the bytecode level.
+    pub bytecode_level: bool,
+    pub gas: StrView<u64>,
+    /// The wall time in nanoseconds.
+    pub time: StrView<u64>,
+    pub modules: Vec<ModuleProfileView>,
+    pub flamegraph: Option<PathBuf>,
 }
 
 pub struct CallContractCommand;
@@ -45,7 +88,7 @@ impl CommandAction for CallContractCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = CallContractOpt;
-    type ReturnItem = Vec<DecodedMoveValue>;
+    type ReturnItem = CallContractResult;
 
     fn run(
         &self,
@@ -59,7 +102,21 @@ impl CommandAction for CallContractCommand {
             args: opt.args.clone().unwrap_or_default(),
         };
 
-        let result = ctx.state().client().contract_call(call)?;
-        Ok(result)
+        if !opt.profile {
+            let result = ctx.state().client().contract_call(call)?;
+            return Ok(CallContractResult::Values(result));
+        }
+        let result = ctx.state().client().debug_profile_call(call)?;
+        if let Some(flamegraph) = &opt.flamegraph {
+            write_collapsed_stacks(&result.profile, opt.weight, flamegraph.as_path())?;
+        }
+        Ok(CallContractResult::Profiled(ProfiledCallResult {
+            return_values: result.return_values,
+            bytecode_level: result.profile.bytecode_level,
+            gas: result.profile.gas,
+            time: result.profile.time,
+            modules: result.profile.modules,
+            flamegraph: opt.flamegraph.clone(),
+        }))
     }
 }
@@ -3,10 +3,13 @@
 
 use anyhow::{ensure, format_err, Result};
 use starcoin_move_compiler::move_command_line_common::files::MOVE_COMPILED_EXTENSION;
+use starcoin_rpc_api::types::ExecutionProfileView;
+use starcoin_vm_runtime::profiler::{ExecutionProfile, ProfileSample, ProfileWeight};
 use starcoin_vm_types::transaction::{Module, Package};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::time::Duration;
 
 pub fn load_package_from_file(mv_or_package_file: &Path) -> Result<Package> {
     ensure!(
@@ -30,3 +33,28 @@ pub fn load_package_from_file(mv_or_package_file: &Path) -> Result<Package> {
     };
     Ok(package)
 }
+
+/// Write the profile as collapsed stacks to `path`, the input of flamegraph tools.
+pub fn write_collapsed_stacks(
+    profile: &ExecutionProfileView,
+    weight: ProfileWeight,
+    path: &Path,
+) -> Result<()> {
+    let profile = ExecutionProfile {
+        samples: profile
+            .samples
+            .iter()
+            .map(|sample| ProfileSample {
+                stack: sample.stack.clone(),
+                offset: sample.offset,
+                instruction: sample.instruction.clone(),
+                count: sample.count.0,
+                gas: sample.gas.0,
+                time: Duration::from_nanos(sample.time.0),
+            })
+            .collect(),
+        bytecode_level: profile.bytecode_level,
+    };
+    std::fs::write(path, profile.to_collapsed_stacks(weight))?;
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::Connect;
+use starcoin_transactional_test_harness::fork_node::ForkNode;
+use std::io::BufRead;
+use std::path::PathBuf;
+
+/// Fork a remote chain at a block and serve it as a local dev node.
+/// The remote state is pulled lazily, txns submitted to the local node are sealed into a new block at once.
+/// Blocks before the fork block are not kept locally, and txn proof is not supported.
+#[derive(Debug, Parser)]
+#[clap(name = "fork")]
+pub struct ForkOpt {
+    #[clap(long = "rpc")]
+    /// The http or websocket rpc url of the remote node, default is the websocket url connected by the console.
+    rpc: Option<String>,
+
+    #[clap(short = 'b', long = "block-number")]
+    /// The block number to fork at, default is the head block of the remote node.
+    block_number: Option<u64>,
+
+    #[clap(long = "http-port", default_value = "9850")]
+    /// The http rpc port of the fork node.
+    http_port: u16,
+
+    #[clap(long = "websocket-port", default_value = "9870")]
+    /// The websocket rpc port of the fork node.
+    ws_port: u16,
+
+    #[clap(long = "data-dir", parse(from_os_str))]
+    /// The data dir of the fork node, default is a temp dir.
+    data_dir: Option<PathBuf>,
+}
+
+pub struct ForkCommand;
+
+impl CommandAction for ForkCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ForkOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let rpc_url = match (&opt.rpc, &ctx.global_opt().connect) {
+            (Some(rpc), _) => rpc.clone(),
+            (None, Some(Connect::WebSocket(url))) => url.clone(),
+            _ => {
+                return Err(format_err!(
+                    "Please set the rpc url of the remote node by --rpc."
+                ))
+            }
+        };
+        let temp_dir = starcoin_config::temp_dir();
+        let mut node_opt = StarcoinOpt {
+            base_data_dir: Some(
+                opt.data_dir
+                    .clone()
+                    .unwrap_or_else(|| temp_dir.path().to_path_buf()),
+            ),
+            ..Default::default()
+        };
+        node_opt.rpc.http.port = Some(opt.http_port);
+        node_opt.rpc.ws.port = Some(opt.ws_port);
+        node_opt.rpc.tcp.disable = true;
+
+        let node = ForkNode::launch(rpc_url.as_str(), opt.block_number, &node_opt)?;
+        let config = node.config();
+        println!(
+            "Fork node of {} started at block {}, http: {}, websocket: {}, ipc: {}",
+            config.net().id(),
+            node.chain().read().fork_number(),
+            config
+                .rpc
+                .get_http_address()
+                .map(|address| address.to_string())
+                .unwrap_or_default(),
+            config
+                .rpc
+                .get_ws_address()
+                .map(|address| address.to_string())
+                .unwrap_or_default(),
+            config.rpc.get_ipc_file().display(),
+        );
+        println!("Press `q` and Enter to quit");
+        for line in std::io::stdin().lock().lines() {
+            if line?.trim() == "q" {
+                break;
+            }
+        }
+        node.stop();
+        Ok(())
+    }
+}
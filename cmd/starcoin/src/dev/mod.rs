@@ -18,6 +18,7 @@ mod call_contract_cmd;
 mod compile_cmd;
 mod deploy_cmd;
 pub(crate) mod dev_helper;
+pub(crate) mod fork_cmd;
 pub(crate) mod gen_block_cmd;
 mod get_coin_cmd;
 pub(crate) mod log_cmd;
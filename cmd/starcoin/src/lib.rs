@@ -42,6 +42,14 @@ pub fn add_command(
                 .subcommand(account::ExecuteScriptCommand)
                 .subcommand(account::sign_multisig_txn_cmd::GenerateMultisigTxnCommand)
                 .subcommand(account::submit_txn_cmd::SubmitSignedTxnCommand)
+                .subcommand(
+                    CustomCommand::with_name("multisig")
+                        .subcommand(account::multisig::CreateMultisigTxnCommand)
+                        .subcommand(account::multisig::SignMultisigTxnCommand)
+                        .subcommand(account::multisig::MergeMultisigTxnCommand)
+                        .subcommand(account::multisig::ShowMultisigTxnCommand)
+                        .subcommand(account::multisig::SubmitMultisigTxnCommand),
+                )
                 .subcommand(account::SignMessageCmd)
                 .subcommand(account::VerifySignMessageCmd)
                 .subcommand(account::DeriveAddressCommand)
@@ -88,6 +96,10 @@ pub fn add_command(
                     .subcommand(node::network::CallPeerCommand)
                     .subcommand(node::network::SetPeerReputation)
                     .subcommand(node::network::BanPeerCommand)
+                    .subcommand(node::network::ListBansCommand)
+                    .subcommand(node::network::UnbanCommand)
+                    .subcommand(node::network::AddReservedCommand)
+                    .subcommand(node::network::RemoveReservedCommand)
 
             ),
         )
@@ -131,7 +143,8 @@ pub fn add_command(
                         .with_about("Subscribe the chain events")
                         .subcommand(dev::SubscribeBlockCommand)
                         .subcommand(dev::SubscribeEventCommand)
-                        .subcommand(dev::SubscribeNewTxnCommand),
+                        .subcommand(dev::SubscribeNewTxnCommand)
+                        .subcommand(dev::SubscribeChainReorgCommand),
                 )
                 .subcommand(
                     CustomCommand::with_name("log")
@@ -141,7 +154,8 @@ pub fn add_command(
                 )
                 .subcommand(dev::panic_cmd::PanicCommand)
                 .subcommand(dev::sleep_cmd::SleepCommand)
-                .subcommand(dev::gen_block_cmd::GenBlockCommand),
+                .subcommand(dev::gen_block_cmd::GenBlockCommand)
+                .subcommand(dev::fork_cmd::ForkCommand),
         )
         .command(CustomCommand::with_name("contract").subcommand(contract::GetContractDataCommand))
 }
@@ -65,7 +65,10 @@ fn run() -> Result<()> {
                 }
                 Connect::WebSocket(address) => {
                     info!("Try to connect node by websocket: {:?}", address);
-                    let client = RpcClient::connect_websocket(address)?;
+                    let client = match opt.rpc_auth_token.clone() {
+                        Some(token) => RpcClient::connect_websocket_with_auth(address, token)?,
+                        None => RpcClient::connect_websocket(address)?,
+                    };
                     (client, None)
                 }
             };
@@ -1,83 +1,172 @@
-use anyhow::Result;
-use serde::{Deserialize, Serialize};
-use starcoin_crypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
-use starcoin_crypto::multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature};
-use starcoin_vm_types::transaction::{RawUserTransaction, SignedUserTransaction};
-use std::collections::HashMap;
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
-pub struct MultisigTransaction {
-    raw_txn: RawUserTransaction,
-    /// user who need to sign the txn.
-    signers: Vec<Ed25519PublicKey>,
-    /// num of signatures needed to fulfill the txn.
-    threshold: u8,
-    /// collected signatures.
-    signatures: HashMap<Ed25519PublicKey, Ed25519Signature>,
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::ExecutionOutputView;
+use anyhow::{format_err, Result};
+use clap::Parser;
+use serde::Serialize;
+pub use starcoin_account_api::MultisigTransaction;
+use starcoin_crypto::hash::{HashValue, PlainCryptoHash};
+use starcoin_crypto::ValidCryptoMaterialStringExt;
+use starcoin_rpc_api::types::RawUserTransactionView;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::transaction::SignedUserTransaction;
+use std::convert::TryInto;
+use std::path::{Path, PathBuf};
+
+pub const MULTISIG_TXN_FILE_EXTENSION: &str = "multisig";
+
+/// Read a multisig txn container, the signed txn files generated by `account sign-multisig-txn`
+/// are accepted too.
+pub fn read_multisig_txn(path: &Path) -> Result<MultisigTransaction> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format_err!("read file {:?} error:{:?}", path, e))?;
+    match MultisigTransaction::decode(bytes.as_slice()) {
+        Ok(txn) => Ok(txn),
+        Err(e) => match bcs_ext::from_bytes::<SignedUserTransaction>(bytes.as_slice()) {
+            Ok(signed_txn) => MultisigTransaction::from_signed_txn(&signed_txn),
+            Err(_) => Err(format_err!(
+                "file {:?} is not a multisig txn container: {}",
+                path,
+                e
+            )),
+        },
+    }
 }
 
-impl MultisigTransaction {
-    pub fn new(raw_txn: RawUserTransaction, signers: Vec<Ed25519PublicKey>, threshold: u8) -> Self {
-        Self {
-            raw_txn,
-            signers,
-            threshold,
-            signatures: HashMap::new(),
+/// Write the container to `output`. If `output` is a dir or absent (current dir), the file is
+/// named by the hash of the raw txn.
+pub fn write_multisig_txn(
+    txn: &MultisigTransaction,
+    output: Option<&Path>,
+    json: bool,
+) -> Result<PathBuf> {
+    let output = match output {
+        Some(output) if !output.is_dir() => output.to_path_buf(),
+        output => {
+            let mut path = match output {
+                Some(dir) => dir.to_path_buf(),
+                None => std::env::current_dir()?,
+            };
+            path.push(txn.raw_txn().crypto_hash().to_hex());
+            if json {
+                path.set_extension(format!("{}.json", MULTISIG_TXN_FILE_EXTENSION));
+            } else {
+                path.set_extension(MULTISIG_TXN_FILE_EXTENSION);
+            }
+            path
         }
+    };
+    if json {
+        std::fs::write(output.as_path(), txn.to_json()?)?;
+    } else {
+        std::fs::write(output.as_path(), txn.to_bcs()?)?;
     }
+    Ok(output)
+}
 
-    pub fn raw_txn(&self) -> &RawUserTransaction {
-        &self.raw_txn
-    }
+#[derive(Debug, Clone, Default, Parser)]
+pub struct MultisigSubmitOpt {
+    #[clap(long = "no-submit")]
+    /// Do not submit the txn when the threshold of signatures is met.
+    no_submit: bool,
 
-    pub fn multi_public_key(&self) -> MultiEd25519PublicKey {
-        MultiEd25519PublicKey::new(self.signers.clone(), self.threshold).expect("multi public key")
-    }
+    #[clap(
+        short = 'b',
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
 
-    pub fn collected_signatures(&self) -> &HashMap<Ed25519PublicKey, Ed25519Signature> {
-        &self.signatures
+/// Submit the txn if the threshold is met and submission is not disabled.
+pub fn try_submit_multisig_txn(
+    state: &CliState,
+    txn: &MultisigTransaction,
+    opt: &MultisigSubmitOpt,
+) -> Result<Option<ExecutionOutputView>> {
+    if opt.no_submit {
+        return Ok(None);
     }
-
-    pub fn can_signed_by(&self, key: &Ed25519PublicKey) -> bool {
-        self.signer_position(key).is_some()
+    if !txn.is_enough() {
+        eprintln!(
+            "multisig txn(address: {}, threshold: {}): {} signatures collected, still require {} signatures",
+            txn.sender(),
+            txn.threshold(),
+            txn.signature_count(),
+            txn.threshold() as usize - txn.signature_count()
+        );
+        return Ok(None);
     }
+    submit_multisig_txn(state, txn.clone(), opt.blocking).map(Some)
+}
 
-    fn signer_position(&self, signer: &Ed25519PublicKey) -> Option<u8> {
-        let mut found = None;
-        for (i, s) in self.signers.iter().enumerate() {
-            if s.to_bytes() == signer.to_bytes() {
-                found = Some(i as u8);
-                break;
-            }
-        }
-        found
+pub fn submit_multisig_txn(
+    state: &CliState,
+    txn: MultisigTransaction,
+    blocking: bool,
+) -> Result<ExecutionOutputView> {
+    let signed_txn = txn.into_signed_txn()?;
+    let txn_hash = signed_txn.id();
+    state.client().submit_transaction(signed_txn)?;
+    eprintln!("txn {:#x} submitted.", txn_hash);
+    if blocking {
+        state.watch_txn(txn_hash)
+    } else {
+        Ok(ExecutionOutputView::new(txn_hash))
     }
+}
 
-    pub fn collect_signature(
-        &mut self,
-        signer: Ed25519PublicKey,
-        signature: Ed25519Signature,
-    ) -> bool {
-        if !self.can_signed_by(&signer) {
-            return false;
-        }
-        self.signatures.insert(signer, signature);
-        true
-    }
+#[derive(Debug, Clone, Serialize)]
+pub struct MultisigSignerView {
+    pub public_key: String,
+    pub signed: bool,
+}
 
-    pub fn into_signed_txn(self) -> Result<SignedUserTransaction> {
-        let mut sigs = vec![];
-        for (key, signature) in self.signatures.iter() {
-            let pos = self
-                .signer_position(key)
-                .expect("should included in signers");
-            sigs.push((signature.clone(), pos));
-        }
-        let multi_sig = MultiEd25519Signature::new(sigs)?;
-        let multi_key = self.multi_public_key();
-        Ok(SignedUserTransaction::multi_ed25519(
-            self.raw_txn,
-            multi_key,
-            multi_sig,
-        ))
+#[derive(Debug, Clone, Serialize)]
+pub struct MultisigTransactionView {
+    /// The file the container is written to.
+    pub file: Option<PathBuf>,
+    /// The hash of the raw txn, identifies the container.
+    pub raw_txn_hash: HashValue,
+    pub sender: AccountAddress,
+    pub threshold: u8,
+    pub signers: Vec<MultisigSignerView>,
+    pub signature_count: usize,
+    pub is_enough: bool,
+    pub raw_txn: RawUserTransactionView,
+    /// The submitted txn, if the container has been submitted.
+    pub submitted: Option<ExecutionOutputView>,
+}
+
+impl MultisigTransactionView {
+    pub fn new(state: &CliState, txn: &MultisigTransaction, file: Option<PathBuf>) -> Result<Self> {
+        let signed = txn.signed();
+        let mut raw_txn: RawUserTransactionView = txn.raw_txn().clone().try_into()?;
+        raw_txn.decoded_payload = Some(
+            state
+                .decode_txn_payload(txn.raw_txn().payload())?
+                .try_into()?,
+        );
+        Ok(Self {
+            file,
+            raw_txn_hash: txn.raw_txn().crypto_hash(),
+            sender: txn.sender(),
+            threshold: txn.threshold(),
+            signers: txn
+                .signers()
+                .iter()
+                .map(|signer| MultisigSignerView {
+                    public_key: signer.to_encoded_string().unwrap_or_default(),
+                    signed: signed.contains(signer),
+                })
+                .collect(),
+            signature_count: txn.signature_count(),
+            is_enough: txn.is_enough(),
+            raw_txn,
+            submitted: None,
+        })
     }
 }
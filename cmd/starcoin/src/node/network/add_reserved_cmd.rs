@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+
+#[derive(Debug, Parser, Default)]
+#[clap(name = "add_reserved")]
+///Add a reserved peer, reserved peers are always connected
+pub struct AddReservedOpt {
+    #[clap(name = "peer")]
+    /// format: multiaddr/p2p/peer_id
+    peer: String,
+}
+
+pub struct AddReservedCommand;
+
+impl CommandAction for AddReservedCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = AddReservedOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_add_reserved(ctx.opt().peer.clone())
+    }
+}
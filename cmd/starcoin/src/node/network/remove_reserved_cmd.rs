@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+
+#[derive(Debug, Parser, Default)]
+#[clap(name = "remove_reserved")]
+///Remove a reserved peer
+pub struct RemoveReservedOpt {
+    #[clap(name = "peer")]
+    /// the peer_id of the reserved peer
+    peer: String,
+}
+
+pub struct RemoveReservedCommand;
+
+impl CommandAction for RemoveReservedCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RemoveReservedOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_remove_reserved(ctx.opt().peer.clone())
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+
+#[derive(Debug, Parser)]
+#[clap(name = "unban")]
+/// Remove the manual ban of peer
+pub struct UnbanOpt {
+    #[clap(name = "peer")]
+    /// the peer_id of the banned peer
+    peer: String,
+}
+
+pub struct UnbanCommand;
+
+impl CommandAction for UnbanCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UnbanOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.network_unban(opt.peer.clone())
+    }
+}
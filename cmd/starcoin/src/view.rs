@@ -8,7 +8,7 @@ use starcoin_account_api::AccountInfo;
 use starcoin_crypto::HashValue;
 pub use starcoin_rpc_api::types::TransactionOutputView;
 use starcoin_rpc_api::types::{
-    DryRunOutputView, RawUserTransactionView, StrView, TransactionEventResponse,
+    DryRunOutputView, GasEstimateView, RawUserTransactionView, StrView, TransactionEventResponse,
     TransactionEventView, TransactionInfoView, TypeTagView,
 };
 use starcoin_types::account_address::AccountAddress;
@@ -287,6 +287,8 @@ pub struct ExecuteResultView {
     pub raw_txn_hex: String,
     pub dry_run_output: DryRunOutputView,
     pub execute_output: Option<ExecutionOutputView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_estimate: Option<GasEstimateView>,
 }
 
 impl ExecuteResultView {
@@ -300,6 +302,7 @@ impl ExecuteResultView {
             raw_txn_hex,
             dry_run_output,
             execute_output: None,
+            gas_estimate: None,
         }
     }
     pub fn get_transaction_info(&self) -> Option<TransactionInfoView> {
@@ -19,6 +19,8 @@ where
 {
     global_limiter: DirectRateLimiter,
     user_limiter: KeyedRateLimiter<User>,
+    /// limiters of the users who have custom quota.
+    custom_user_limiters: HashMap<User, DirectRateLimiter>,
 }
 
 impl<User> ApiLimiter<User>
@@ -26,9 +28,21 @@ where
     User: Clone + Hash + Eq,
 {
     pub fn new(global_quota: Quota, user_quota: Quota) -> Self {
+        Self::new_with_custom_user_quotas(global_quota, user_quota, &HashMap::new())
+    }
+
+    pub fn new_with_custom_user_quotas(
+        global_quota: Quota,
+        user_quota: Quota,
+        custom_user_quotas: &HashMap<User, Quota>,
+    ) -> Self {
         Self {
             global_limiter: DirectRateLimiter::direct(global_quota),
             user_limiter: KeyedRateLimiter::keyed(user_quota),
+            custom_user_limiters: custom_user_quotas
+                .iter()
+                .map(|(user, quota)| (user.clone(), DirectRateLimiter::direct(*quota)))
+                .collect(),
         }
     }
 
@@ -37,7 +51,10 @@ where
         user: Option<&User>,
     ) -> Result<(), NotUntil<<DefaultClock as Clock>::Instant>> {
         if let Some(u) = user {
-            self.user_limiter.check_key(u)?;
+            match self.custom_user_limiters.get(u) {
+                Some(limiter) => limiter.check()?,
+                None => self.user_limiter.check_key(u)?,
+            }
         }
         self.global_limiter.check()?;
         Ok(())
@@ -58,6 +75,8 @@ where
     default_user_api_quota: Quota,
     /// custom user quota when calling a api.
     custom_user_api_quotas: HashMap<ApiName, Quota>,
+    /// quota of the specific users when calling a api, override the user quota of the api.
+    custom_user_quotas: HashMap<User, Quota>,
 
     limiters: DashMap<ApiName, ApiLimiter<User>>,
 }
@@ -78,10 +97,16 @@ where
             custom_global_api_quotas,
             default_user_api_quota,
             custom_user_api_quotas,
+            custom_user_quotas: HashMap::new(),
             limiters: Default::default(),
         }
     }
 
+    pub fn with_custom_user_quotas(mut self, custom_user_quotas: HashMap<User, Quota>) -> Self {
+        self.custom_user_quotas = custom_user_quotas;
+        self
+    }
+
     pub fn check(&self, api: &ApiName, user: Option<&User>) -> Result<(), anyhow::Error> {
         let elem = match self.limiters.entry(api.clone()) {
             Entry::Occupied(o) => o.into_ref(),
@@ -105,7 +130,7 @@ where
             .get(api)
             .cloned()
             .unwrap_or(self.default_user_api_quota);
-        ApiLimiter::new(global_quota, user_quota)
+        ApiLimiter::new_with_custom_user_quotas(global_quota, user_quota, &self.custom_user_quotas)
     }
 }
 
@@ -135,4 +160,29 @@ mod tests {
         let result = limiter.check(Some(&"abc".to_string()));
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_custom_user_limit() {
+        let global_quota = Quota::per_second(unsafe { NonZeroU32::new_unchecked(100) });
+        let user_quota = Quota::per_second(unsafe { NonZeroU32::new_unchecked(2) });
+        let custom_quotas = vec![(
+            "admin".to_string(),
+            Quota::per_second(unsafe { NonZeroU32::new_unchecked(10) }),
+        )]
+        .into_iter()
+        .collect();
+        let limiter = ApiLimiter::<String>::new_with_custom_user_quotas(
+            global_quota,
+            user_quota,
+            &custom_quotas,
+        );
+        for _i in 0..10 {
+            assert!(limiter.check(Some(&"admin".to_string())).is_ok());
+        }
+        assert!(limiter.check(Some(&"admin".to_string())).is_err());
+        for _i in 0..2 {
+            assert!(limiter.check(Some(&"abc".to_string())).is_ok());
+        }
+        assert!(limiter.check(Some(&"abc".to_string())).is_err());
+    }
 }
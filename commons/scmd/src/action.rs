@@ -20,6 +20,17 @@ pub trait CommandAction {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem>;
 
+    /// Run the command and hand the result to `page` piece by piece, so long lists can be printed
+    /// before the whole result is fetched. Every page is a complete `ReturnItem`.
+    /// The default implementation returns the result of `run` as a single page.
+    fn run_paged(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+        page: &mut dyn FnMut(Self::ReturnItem) -> Result<()>,
+    ) -> Result<()> {
+        page(self.run(ctx)?)
+    }
+
     /// This command should skip record in console history when return true.
     fn skip_history(&self, _ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>) -> bool {
         false
@@ -29,6 +29,15 @@ where
         arg_matches: &ArgMatches,
     ) -> Result<(HistoryOp, Value)>;
 
+    /// Same as `exec`, but hand the result to `page` piece by piece, see `CommandAction::run_paged`.
+    fn exec_paged(
+        &mut self,
+        state: Arc<State>,
+        global_opt: Arc<GlobalOpt>,
+        arg_matches: &ArgMatches,
+        page: &mut dyn FnMut(Value) -> Result<()>,
+    ) -> Result<HistoryOp>;
+
     fn get_command(&mut self) -> &mut Command<'static>;
 }
 
@@ -223,6 +232,25 @@ where
             None => Err(anyhow::Error::msg(self.help_message())),
         }
     }
+
+    fn exec_action_paged(
+        &mut self,
+        ctx: &ExecContext<State, GlobalOpt, Opt>,
+        page: &mut dyn FnMut(Value) -> Result<()>,
+    ) -> Result<HistoryOp> {
+        match &self.action {
+            Some(action) => {
+                let skip_history_op = if action.skip_history(ctx) {
+                    HistoryOp::Skip
+                } else {
+                    HistoryOp::Record
+                };
+                action.run_paged(ctx, &mut |item| page(serde_json::to_value(item)?))?;
+                Ok(skip_history_op)
+            }
+            None => Err(anyhow::Error::msg(self.help_message())),
+        }
+    }
 }
 
 impl<State, GlobalOpt, Opt, ReturnItem, Action> Default
@@ -272,6 +300,30 @@ where
         Ok(value)
     }
 
+    fn exec_paged(
+        &mut self,
+        state: Arc<State>,
+        global_opt: Arc<GlobalOpt>,
+        arg_matches: &ArgMatches,
+        page: &mut dyn FnMut(Value) -> Result<()>,
+    ) -> Result<HistoryOp> {
+        let opt = Arc::new(Opt::from_arg_matches(arg_matches)?);
+        let ctx = ExecContext::new(state, global_opt, opt);
+        if self.has_subcommand() {
+            if let Some((subcmd_name, subcmd_matches)) = arg_matches.subcommand() {
+                return match self.subcommands.get_mut(subcmd_name) {
+                    Some(subcmd) => {
+                        subcmd.exec_paged(ctx.state, ctx.global_opt, subcmd_matches, page)
+                    }
+                    None => {
+                        unreachable!("this should not happen, because sub cmd has check by clip.")
+                    }
+                };
+            }
+        }
+        self.exec_action_paged(&ctx, page)
+    }
+
     fn get_command(&mut self) -> &mut Command<'static> {
         &mut self.app
     }
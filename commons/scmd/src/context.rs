@@ -4,6 +4,7 @@
 use crate::error::CmdError;
 use crate::{
     print_action_result, CommandAction, CommandExec, CustomCommand, HistoryOp, OutputFormat,
+    ResultPrinter,
 };
 use anyhow::Result;
 use clap::Parser;
@@ -105,7 +106,7 @@ where
             .arg(
                 Arg::new(G_OUTPUT_FORMAT_ARG)
                     .short('o')
-                    .help("set output-format, support [json|table]")
+                    .help("set output-format, support [json|table|csv|yaml|ndjson]")
                     .takes_value(true)
                     .default_value("json"),
             );
@@ -178,8 +179,20 @@ where
 
     /// Execute command by parse std::env::args_os() and print result.
     pub fn exec(self) -> Result<()> {
-        let (output_format, result) = self.exec_inner(&mut std::env::args_os())?;
-        print_action_result(output_format, result, false)
+        // The printer is created on the first page, when the output format has been parsed.
+        let mut printer: Option<ResultPrinter> = None;
+        let (output_format, result) = self.exec_inner(
+            &mut std::env::args_os(),
+            Some(&mut |output_format: OutputFormat, page: Value| {
+                printer
+                    .get_or_insert_with(|| ResultPrinter::new(output_format, false))
+                    .print_page(page)
+            }),
+        )?;
+        match printer {
+            Some(printer) => printer.finish(result.map(|_| ())),
+            None => print_action_result(output_format, result, false),
+        }
     }
 
     /// Execute command by args and return Command execute ReturnItem
@@ -187,13 +200,19 @@ where
     where
         ReturnItem: for<'de> serde::Deserialize<'de> + serde::Serialize + 'static,
     {
-        let (_output_format, result) = self.exec_inner(args)?;
+        let (_output_format, result) = self.exec_inner(args, None)?;
         let value = result?;
         serde_json::from_value(value).map_err(|e| e.into())
     }
 
-    /// Execute command by the args.
-    fn exec_inner<I, T>(mut self, iter: I) -> Result<(OutputFormat, Result<Value>)>
+    /// Execute command by the args. If `page` is provided, the command result is handed to it page
+    /// by page and the returned value is null.
+    #[allow(clippy::type_complexity)]
+    fn exec_inner<I, T>(
+        mut self,
+        iter: I,
+        page: Option<&mut dyn FnMut(OutputFormat, Value) -> Result<()>>,
+    ) -> Result<(OutputFormat, Result<Value>)>
     where
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
@@ -233,11 +252,22 @@ where
                 cmd_name => {
                     let cmd = self.commands.get_mut(cmd_name);
                     match (cmd, arg_matches) {
-                        (Some(cmd), arg_matches) => {
-                            let (_, value) =
-                                cmd.exec(Arc::new(state), Arc::new(global_opt), arg_matches)?;
-                            Ok(value)
-                        }
+                        (Some(cmd), arg_matches) => match page {
+                            Some(page) => {
+                                cmd.exec_paged(
+                                    Arc::new(state),
+                                    Arc::new(global_opt),
+                                    arg_matches,
+                                    &mut |value| page(output_format, value),
+                                )?;
+                                Ok(Value::Null)
+                            }
+                            None => {
+                                let (_, value) =
+                                    cmd.exec(Arc::new(state), Arc::new(global_opt), arg_matches)?;
+                                Ok(value)
+                            }
+                        },
                         _ => Err(CmdError::need_help(Self::app_help_message(&mut app)).into()),
                     }
                 }
@@ -272,10 +302,10 @@ where
                     .arg(
                         Arg::new("format")
                             .takes_value(true)
-                            .possible_values(&["json", "table"])
+                            .possible_values(&OutputFormat::VARIANTS)
                             .ignore_case(true)
                             .default_value("json")
-                            .help("Output format should be json or table."),
+                            .help("Output format should be json, table, csv, yaml or ndjson."),
                     )
                     .override_help("Set console output format.")
                     .display_order(996),
@@ -378,7 +408,7 @@ where
                                     OutputFormat::from_str(params[1]).unwrap_or_default();
                                 println!("Set output format to: {}", output_format);
                             } else {
-                                println!("Usage: output [format] 'Output format: JSON|TABLE'");
+                                println!("Usage: output [format] 'Output format: JSON|TABLE|CSV|YAML|NDJSON'");
                             }
                         }
                         "console" => continue,
@@ -390,24 +420,24 @@ where
                                     let app = cmd.get_command();
                                     match app.try_get_matches_from_mut(params) {
                                         Ok(arg_matches) => {
-                                            let cmd_result = cmd.exec(
+                                            let mut printer =
+                                                ResultPrinter::new(output_format, true);
+                                            let cmd_result = cmd.exec_paged(
                                                 state.clone(),
                                                 global_opt.clone(),
                                                 &arg_matches,
+                                                &mut |page| printer.print_page(page),
                                             );
                                             let (skip_history, result) = match cmd_result {
-                                                Ok((history_op, value)) => (
-                                                    matches!(history_op, HistoryOp::Skip),
-                                                    Ok(value),
-                                                ),
+                                                Ok(history_op) => {
+                                                    (matches!(history_op, HistoryOp::Skip), Ok(()))
+                                                }
                                                 Err(err) => (false, Err(err)),
                                             };
                                             if !skip_history {
                                                 rl.add_history_entry(line.as_str());
                                             }
-                                            if let Err(err) =
-                                                print_action_result(output_format, result, true)
-                                            {
+                                            if let Err(err) = printer.finish(result) {
                                                 println!("Print result error: {:?}", err);
                                             }
                                         }
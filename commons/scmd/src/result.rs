@@ -5,14 +5,31 @@ use anyhow::{bail, Result};
 use cli_table::format::CellFormat;
 use cli_table::{Cell, Row, Table};
 use flatten_json::flatten;
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
+use std::io::Write;
 use std::str::FromStr;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum OutputFormat {
     JSON,
     TABLE,
+    CSV,
+    YAML,
+    /// Newline-delimited JSON, one line per list item.
+    NDJSON,
+}
+
+impl OutputFormat {
+    pub const VARIANTS: [&'static str; 5] = ["json", "table", "csv", "yaml", "ndjson"];
+
+    /// Whether the result can be printed page by page, other formats print one document.
+    pub fn is_streaming(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::TABLE | OutputFormat::CSV | OutputFormat::NDJSON
+        )
+    }
 }
 
 impl FromStr for OutputFormat {
@@ -22,6 +39,9 @@ impl FromStr for OutputFormat {
         Ok(match s {
             "json" | "JSON" => OutputFormat::JSON,
             "table" | "TABLE" => OutputFormat::TABLE,
+            "csv" | "CSV" => OutputFormat::CSV,
+            "yaml" | "YAML" => OutputFormat::YAML,
+            "ndjson" | "NDJSON" => OutputFormat::NDJSON,
             _ => OutputFormat::JSON,
         })
     }
@@ -38,6 +58,9 @@ impl std::fmt::Display for OutputFormat {
         let s = match self {
             OutputFormat::TABLE => "TABLE",
             OutputFormat::JSON => "JSON",
+            OutputFormat::CSV => "CSV",
+            OutputFormat::YAML => "YAML",
+            OutputFormat::NDJSON => "NDJSON",
         };
         write!(f, "{}", s)
     }
@@ -68,8 +91,39 @@ pub fn print_action_result(
             };
             print_json(value)
         }
-        OutputFormat::TABLE => {
+        OutputFormat::YAML => {
+            if console_mode && result.is_err() {
+                println!("{}", result.unwrap_err());
+                return Ok(());
+            }
+            let value = match result {
+                Ok(value) => {
+                    if value.is_null() {
+                        value
+                    } else {
+                        json!({ "ok": value })
+                    }
+                }
+                Err(err) => json!({"err": err.to_string()}),
+            };
+            print_yaml(value)
+        }
+        OutputFormat::NDJSON => {
+            if console_mode && result.is_err() {
+                println!("{}", result.unwrap_err());
+                return Ok(());
+            }
+            match result {
+                Ok(value) => print_ndjson(value),
+                Err(err) => print_ndjson(json!({"err": err.to_string()})),
+            }
+        }
+        OutputFormat::TABLE | OutputFormat::CSV => {
             match result {
+                Ok(value) if format == OutputFormat::CSV => {
+                    let mut printer = CsvPrinter::default();
+                    printer.print(&mut std::io::stdout(), &value)?;
+                }
                 Ok(value) => print_table(value)?,
                 // err may contains help message, so directly print err.
                 Err(err) => println!("{}", err),
@@ -79,6 +133,65 @@ pub fn print_action_result(
     }
 }
 
+/// Print the result of a command page by page. Streaming formats print every page as it comes,
+/// the others collect the pages and print them as one document in `finish`.
+pub struct ResultPrinter {
+    format: OutputFormat,
+    console_mode: bool,
+    csv: CsvPrinter,
+    pages: Option<Value>,
+}
+
+impl ResultPrinter {
+    pub fn new(format: OutputFormat, console_mode: bool) -> Self {
+        Self {
+            format,
+            console_mode,
+            csv: CsvPrinter::default(),
+            pages: None,
+        }
+    }
+
+    pub fn print_page(&mut self, page: Value) -> Result<()> {
+        if page.is_null() {
+            return Ok(());
+        }
+        match self.format {
+            OutputFormat::TABLE => print_table(page),
+            OutputFormat::CSV => self.csv.print(&mut std::io::stdout(), &page),
+            OutputFormat::NDJSON => print_ndjson(page),
+            OutputFormat::JSON | OutputFormat::YAML => {
+                self.pages = Some(match (self.pages.take(), page) {
+                    (None, page) => page,
+                    (Some(Value::Array(mut values)), Value::Array(page)) => {
+                        values.extend(page);
+                        Value::Array(values)
+                    }
+                    (Some(Value::Array(mut values)), page) => {
+                        values.push(page);
+                        Value::Array(values)
+                    }
+                    (Some(value), page) => Value::Array(vec![value, page]),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Print the collected pages, or the error if the command failed.
+    pub fn finish(self, result: Result<()>) -> Result<()> {
+        match result {
+            Ok(()) if self.format.is_streaming() => Ok(()),
+            Ok(()) => print_action_result(
+                self.format,
+                Ok(self.pages.unwrap_or(Value::Null)),
+                self.console_mode,
+            ),
+            Err(err) => print_action_result(self.format, Err(err), self.console_mode),
+        }
+    }
+}
+
 pub fn print_json(value: Value) -> Result<()> {
     if value.is_null() {
         return Ok(());
@@ -88,6 +201,101 @@ pub fn print_json(value: Value) -> Result<()> {
     Ok(())
 }
 
+pub fn print_yaml(value: Value) -> Result<()> {
+    if value.is_null() {
+        return Ok(());
+    }
+    let yaml = serde_yaml::to_string(&value)?;
+    print!("{}", yaml);
+    Ok(())
+}
+
+/// Print every item of a list as one JSON line, other values as a single line.
+pub fn print_ndjson(value: Value) -> Result<()> {
+    let stdout = std::io::stdout();
+    write_ndjson(&mut stdout.lock(), value)
+}
+
+fn write_ndjson<W: Write>(out: &mut W, value: Value) -> Result<()> {
+    let values = match value {
+        Value::Null => vec![],
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+    for value in values {
+        serde_json::to_writer(&mut *out, &value)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn flatten_value(value: &Value) -> Result<Map<String, Value>> {
+    let mut flat = json!({});
+    flatten(value, &mut flat, None, true, None)
+        .map_err(|e| anyhow::Error::msg(e.description().to_string()))?;
+    match flat {
+        Value::Object(obj) => Ok(obj),
+        _ => unreachable!("flatten result must be a object"),
+    }
+}
+
+/// Print list items as CSV rows, nested values are flattened into `parent.child` columns.
+/// The header is taken from the first page, so fields first seen in a later page are dropped.
+#[derive(Default)]
+struct CsvPrinter {
+    header: Option<Vec<String>>,
+}
+
+impl CsvPrinter {
+    fn print<W: Write>(&mut self, out: &mut W, value: &Value) -> Result<()> {
+        let values = match value {
+            Value::Null => return Ok(()),
+            Value::Array(values) => values.iter().collect::<Vec<_>>(),
+            value => vec![value],
+        };
+        if values.iter().any(|value| value.is_array()) {
+            bail!("Not support embed array in Action Result.")
+        }
+        let is_simple = |value: &Value| !value.is_object();
+        let rows = values
+            .into_iter()
+            .map(|value| {
+                if is_simple(value) {
+                    let mut row = Map::new();
+                    row.insert("Result".to_string(), value.clone());
+                    Ok(row)
+                } else {
+                    flatten_value(value)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut writer = csv::Writer::from_writer(out);
+        if self.header.is_none() {
+            let mut header: Vec<String> = vec![];
+            for row in &rows {
+                for key in row.keys() {
+                    if !header.contains(key) {
+                        header.push(key.clone());
+                    }
+                }
+            }
+            writer.write_record(&header)?;
+            self.header = Some(header);
+        }
+        let header = self.header.as_ref().expect("header must be set");
+        for row in rows {
+            writer.write_record(
+                header
+                    .iter()
+                    .map(|field| row.get(field).map(value_to_string).unwrap_or_default()),
+            )?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
 fn build_rows(values: &[Value]) -> Result<(Vec<Row>, Box<dyn RowBuilder>)> {
     let bold = CellFormat::builder().bold(true).build();
     let mut rows = vec![];
@@ -223,3 +431,35 @@ impl RowBuilder for ObjectRowBuilder {
         Ok(Row::new(cells))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_pages() {
+        let mut printer = CsvPrinter::default();
+        let mut out = vec![];
+        printer
+            .print(
+                &mut out,
+                &json!([{"name": "a", "address": {"city": "x"}}, {"name": "b,c"}]),
+            )
+            .unwrap();
+        printer
+            .print(&mut out, &json!([{"name": "d", "age": 1}]))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "address.city,name\nx,a\n,\"b,c\"\n,d\n"
+        );
+    }
+
+    #[test]
+    fn test_ndjson() {
+        let mut out = vec![];
+        write_ndjson(&mut out, json!([{"a": 1}, "b"])).unwrap();
+        write_ndjson(&mut out, json!(2)).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"a\":1}\n\"b\"\n2\n");
+    }
+}
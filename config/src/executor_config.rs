@@ -0,0 +1,53 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::Result;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Eq, Serialize, Parser)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutorConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "parallel-execution", long)]
+    /// execute the user transactions of a block in parallel. default to false.
+    parallel_execution: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "execution-concurrency-level", long)]
+    /// the number of threads for parallel execution. default to the number of cpus.
+    concurrency_level: Option<usize>,
+}
+
+impl ExecutorConfig {
+    pub fn set_parallel_execution(&mut self, parallel_execution: bool) {
+        self.parallel_execution = Some(parallel_execution);
+    }
+
+    pub fn parallel_execution(&self) -> bool {
+        self.parallel_execution.unwrap_or(false)
+    }
+
+    /// The concurrency level of block execution, 1 if parallel execution is disabled.
+    pub fn concurrency_level(&self) -> usize {
+        if self.parallel_execution() {
+            self.concurrency_level.unwrap_or_else(num_cpus::get).max(1)
+        } else {
+            1
+        }
+    }
+}
+
+impl ConfigModule for ExecutorConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+        if let Some(m) = opt.executor.parallel_execution.as_ref() {
+            self.parallel_execution = Some(*m);
+        }
+        if let Some(m) = opt.executor.concurrency_level.as_ref() {
+            self.concurrency_level = Some(*m);
+        }
+        Ok(())
+    }
+}
@@ -29,12 +29,14 @@ mod account_vault_config;
 mod api_config;
 mod api_quota;
 mod available_port;
+mod executor_config;
 pub mod genesis_config;
 mod helper;
 mod logger_config;
 mod metrics_config;
 mod miner_config;
 mod network_config;
+mod rpc_auth;
 mod rpc_config;
 mod storage_config;
 mod stratum_config;
@@ -52,6 +54,7 @@ pub use api_quota::{ApiQuotaConfig, QuotaDuration};
 pub use available_port::{
     get_available_port_from, get_random_available_port, get_random_available_ports,
 };
+pub use executor_config::ExecutorConfig;
 pub use genesis_config::{
     BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
     FutureBlockParameterResolver, GenesisBlockParameter, GenesisBlockParameterConfig,
@@ -62,6 +65,7 @@ pub use logger_config::LoggerConfig;
 pub use metrics_config::MetricsConfig;
 pub use miner_config::{MinerClientConfig, MinerConfig};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
+pub use rpc_auth::{RpcApiKey, RpcAuthKeyFile};
 pub use rpc_config::{
     ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
     WsConfiguration,
@@ -69,7 +73,7 @@ pub use rpc_config::{
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_vm_types::time::{MockTimeService, RealTimeService, TimeService};
 pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
-pub use txpool_config::TxPoolConfig;
+pub use txpool_config::{TxPoolConfig, TxPoolOrdering};
 
 pub static G_CRATE_VERSION: &str = clap::crate_version!();
 pub static G_GIT_VERSION: &str = git_version!(
@@ -181,6 +185,11 @@ pub struct StarcoinOpt {
     /// Connect and attach to a node
     pub connect: Option<Connect>,
 
+    #[serde(skip)]
+    #[clap(long = "rpc-auth-token")]
+    /// Api key or jwt token to authenticate the websocket connection to a node.
+    pub rpc_auth_token: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(long = "data-dir", short = 'd', parse(from_os_str))]
     /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
@@ -218,6 +227,8 @@ pub struct StarcoinOpt {
     #[clap(flatten)]
     pub sync: SyncConfig,
     #[clap(flatten)]
+    pub executor: ExecutorConfig,
+    #[clap(flatten)]
     pub vault: AccountVaultConfig,
     #[serde(default)]
     #[clap(flatten)]
@@ -444,6 +455,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub sync: SyncConfig,
     #[serde(default)]
+    pub executor: ExecutorConfig,
+    #[serde(default)]
     pub vault: AccountVaultConfig,
     #[serde(default)]
     pub metrics: MetricsConfig,
@@ -517,6 +530,7 @@ impl NodeConfig {
         self.storage.merge_with_opt(opt, base.clone())?;
         self.tx_pool.merge_with_opt(opt, base.clone())?;
         self.sync.merge_with_opt(opt, base.clone())?;
+        self.executor.merge_with_opt(opt, base.clone())?;
         self.vault.merge_with_opt(opt, base.clone())?;
         self.metrics.merge_with_opt(opt, base.clone())?;
         self.logger.merge_with_opt(opt, base.clone())?;
@@ -65,7 +65,10 @@ impl MinerConfig {
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MinerClientConfig {
     pub server: Option<String>,
+    /// Path of an external solver executable speaking the miner client solver stdio protocol,
+    /// the built-in CPU solver is used if not set.
     pub plugin_path: Option<String>,
+    /// CPU solver threads, or external solver processes if `plugin_path` is set.
     pub miner_thread: u16,
     pub enable_stderr: bool,
 }
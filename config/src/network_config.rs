@@ -31,6 +31,7 @@ use std::sync::Arc;
 
 pub static G_DEFAULT_NETWORK_PORT: u16 = 9840;
 static G_NETWORK_KEY_FILE: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("network_key"));
+static G_PEER_STORE_FILE: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("peers.json"));
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, Parser)]
 pub struct NetworkRpcQuotaConfiguration {
@@ -192,6 +193,18 @@ pub struct NetworkConfig {
     #[clap(long = "discover-local")]
     pub discover_local: Option<bool>,
 
+    #[serde(skip_serializing_if = "Seeds::is_empty")]
+    #[serde(default)]
+    #[clap(long = "reserved-peer", default_value = "")]
+    /// Reserved peers are always connected, multi peer should use ',' as delimiter.
+    pub reserved_peers: Seeds,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "reserved-only")]
+    /// Only connect to the reserved peers, all non-reserved inbound and outbound connections are rejected.
+    /// By default this option is `false`.
+    pub reserved_only: Option<bool>,
+
     #[serde(skip)]
     #[clap(long = "disable-seed")]
     /// Do not connect to seed node, include builtin and config seed.
@@ -242,6 +255,16 @@ pub struct NetworkConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(name = "unsupported-protocols", long, use_value_delimiter = true)]
     pub unsupported_protocols: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "peer-store-file", parse(from_os_str))]
+    /// File to persist the known peer addresses, bans and reputations, default is peers.json under the data dir.
+    pub peer_store_file: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "peer-ban-duration")]
+    /// Duration in seconds of a manual peer ban, the ban is permanent if absent or 0.
+    pub peer_ban_duration: Option<u64>,
 }
 
 impl NetworkConfig {
@@ -279,6 +302,20 @@ impl NetworkConfig {
         seeds
     }
 
+    pub fn reserved_peers(&self) -> Vec<MultiaddrWithPeerId> {
+        let self_peer_id = self.self_peer_id();
+        self.reserved_peers
+            .clone()
+            .into_vec()
+            .into_iter()
+            .filter(|node| &node.peer_id != self_peer_id.origin())
+            .collect()
+    }
+
+    pub fn reserved_only(&self) -> bool {
+        self.reserved_only.unwrap_or(false)
+    }
+
     pub fn network_keypair(&self) -> &(Ed25519PrivateKey, Ed25519PublicKey) {
         self.network_keypair.as_ref().expect("Config should init.")
     }
@@ -335,6 +372,19 @@ impl NetworkConfig {
         }
     }
 
+    pub fn peer_store_file(&self) -> PathBuf {
+        let path = self.peer_store_file.as_ref().unwrap_or(&G_PEER_STORE_FILE);
+        if path.is_absolute() {
+            path.clone()
+        } else {
+            self.base().data_dir().join(path.as_path())
+        }
+    }
+
+    pub fn peer_ban_duration(&self) -> Option<u64> {
+        self.peer_ban_duration.filter(|duration| *duration > 0)
+    }
+
     /// node key loader step:
     /// 1. if node_key is Some, directly decode the key.
     /// 2. try load node key from node_key_file
@@ -407,6 +457,11 @@ impl ConfigModule for NetworkConfig {
             self.disable_seed = opt.network.disable_seed;
         }
 
+        self.reserved_peers.merge(&opt.network.reserved_peers);
+        if opt.network.reserved_only.is_some() {
+            self.reserved_only = opt.network.reserved_only;
+        }
+
         self.network_rpc_quotas
             .merge(&opt.network.network_rpc_quotas)?;
 
@@ -442,6 +497,13 @@ impl ConfigModule for NetworkConfig {
             self.max_outgoing_peers = opt.network.max_outgoing_peers;
         }
 
+        if opt.network.peer_store_file.is_some() {
+            self.peer_store_file = opt.network.peer_store_file.clone();
+        }
+        if opt.network.peer_ban_duration.is_some() {
+            self.peer_ban_duration = opt.network.peer_ban_duration;
+        }
+
         if opt.network.unsupported_protocols.is_some() {
             let mut protocols: HashSet<String> = self
                 .unsupported_protocols
@@ -466,6 +528,9 @@ impl ConfigModule for NetworkConfig {
 
         self.load_or_generate_keypair()?;
         self.generate_listen_address();
+        if self.reserved_only() && self.reserved_peers().is_empty() {
+            warn!("Network is in reserved only mode, but no reserved peer is configured.");
+        }
         Ok(())
     }
 }
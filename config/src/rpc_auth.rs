@@ -0,0 +1,163 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::helper::load_config;
+use crate::{Api, ApiQuotaConfig, ApiSet};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use subtle::ConstantTimeEq;
+
+const PUBSUB_METHOD_PREFIX: &str = "starcoin_";
+
+/// A key in the rpc auth keyfile.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcApiKey {
+    /// Name of the key, it is the user of the api quota, and the `sub` of the jwt token.
+    pub name: String,
+    /// The api key sent by the client, if absent, the key can only be used by jwt token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Apis allowed to call, eg: "chain,state,node" or "all".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apis: Option<ApiSet>,
+    /// Methods allowed to call besides the `apis`, a `*` at the end matches any suffix,
+    /// eg: "account.get", "txpool.*".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+    /// Quota of the key for every api, override the default user api quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ApiQuotaConfig>,
+}
+
+impl RpcApiKey {
+    pub fn is_allowed(&self, method: &str) -> bool {
+        self.apis
+            .as_ref()
+            .map(|apis| check_api(apis, method))
+            .unwrap_or(false)
+            || self
+                .methods
+                .iter()
+                .any(|pattern| match_method(pattern, method))
+    }
+}
+
+/// The pubsub methods are named as `starcoin_subscribe`, other methods are named as `{api}.{method}`.
+fn check_api(apis: &ApiSet, method: &str) -> bool {
+    if method.starts_with(PUBSUB_METHOD_PREFIX) {
+        apis.list_apis().contains(&Api::PubSub)
+    } else {
+        apis.check_rpc_method(method)
+    }
+}
+
+fn match_method(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+/// The rpc auth keyfile, in toml format, eg:
+///
+/// ```toml
+/// jwt_secret = "a random secret"
+/// anonymous_apis = "chain,node"
+///
+/// [[keys]]
+/// name = "admin"
+/// key = "a random key"
+/// apis = "all"
+///
+/// [[keys]]
+/// name = "wallet"
+/// key = "another random key"
+/// apis = "chain,state,txpool"
+/// methods = ["account.get*"]
+/// quota = "100/s"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcAuthKeyFile {
+    /// Secret to verify the HS256 jwt tokens, jwt token is not accepted if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt_secret: Option<String>,
+    /// Apis can be called without credential, all requests without credential are rejected if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anonymous_apis: Option<ApiSet>,
+    #[serde(default)]
+    pub keys: Vec<RpcApiKey>,
+}
+
+impl RpcAuthKeyFile {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let keyfile: Self = load_config(path)?;
+        keyfile.check()?;
+        Ok(keyfile)
+    }
+
+    fn check(&self) -> Result<()> {
+        if let Some(jwt_secret) = self.jwt_secret.as_ref() {
+            ensure!(
+                !jwt_secret.is_empty(),
+                "Rpc auth jwt_secret can not be empty"
+            );
+        }
+        let mut names = HashSet::new();
+        let mut keys = HashSet::new();
+        for api_key in &self.keys {
+            ensure!(
+                !api_key.name.is_empty(),
+                "Rpc auth key name can not be empty"
+            );
+            ensure!(
+                names.insert(api_key.name.as_str()),
+                "Duplicate rpc auth key name: {}",
+                api_key.name
+            );
+            if let Some(key) = api_key.key.as_ref() {
+                ensure!(
+                    !key.is_empty(),
+                    "Rpc auth key of {} can not be empty",
+                    api_key.name
+                );
+                ensure!(
+                    keys.insert(key.as_str()),
+                    "Duplicate rpc auth key of {}",
+                    api_key.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_key_by_name(&self, name: &str) -> Option<&RpcApiKey> {
+        self.keys.iter().find(|api_key| api_key.name == name)
+    }
+
+    /// Find the key by constant time comparison, all the keys are compared to avoid timing attack.
+    pub fn get_key(&self, key: &str) -> Option<&RpcApiKey> {
+        self.keys.iter().fold(None, |found, api_key| {
+            let matched = api_key
+                .key
+                .as_ref()
+                .map(|k| bool::from(k.as_bytes().ct_eq(key.as_bytes())))
+                .unwrap_or(false);
+            if matched {
+                Some(api_key)
+            } else {
+                found
+            }
+        })
+    }
+
+    pub fn is_anonymous_allowed(&self, method: &str) -> bool {
+        self.anonymous_apis
+            .as_ref()
+            .map(|apis| check_api(apis, method))
+            .unwrap_or(false)
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     get_available_port_from, get_random_available_ports, parse_key_val, ApiQuotaConfig, ApiSet,
-    BaseConfig, ConfigModule, QuotaDuration, StarcoinOpt,
+    BaseConfig, ConfigModule, QuotaDuration, RpcAuthKeyFile, StarcoinOpt,
 };
 use anyhow::Result;
 use clap::Parser;
@@ -382,6 +382,16 @@ pub struct RpcConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(long = "query-max-txn-info-range")]
     pub txn_info_query_max_range: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "rpc-auth-keyfile", parse(from_os_str))]
+    /// Path of the rpc auth keyfile, relative path is resolved from the data dir.
+    /// If set, the http, tcp and websocket rpc require an api key or jwt token.
+    pub auth_keyfile: Option<PathBuf>,
+
+    #[serde(skip)]
+    #[clap(skip)]
+    auth_keys: Option<Arc<RpcAuthKeyFile>>,
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -452,6 +462,21 @@ impl RpcConfig {
             .unwrap_or(DEFAULT_TXN_INFO_QUEYR_MAX_RANGE)
     }
 
+    /// The loaded rpc auth keyfile, None means the rpc does not require authentication.
+    pub fn auth_keys(&self) -> Option<Arc<RpcAuthKeyFile>> {
+        self.auth_keys.clone()
+    }
+
+    fn auth_keyfile(&self) -> Option<PathBuf> {
+        self.auth_keyfile.as_ref().map(|path| {
+            if path.is_absolute() {
+                path.clone()
+            } else {
+                self.base().data_dir().join(path)
+            }
+        })
+    }
+
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
     }
@@ -527,6 +552,9 @@ impl ConfigModule for RpcConfig {
         if opt.rpc.txn_info_query_max_range.is_some() {
             self.txn_info_query_max_range = opt.rpc.txn_info_query_max_range;
         }
+        if opt.rpc.auth_keyfile.is_some() {
+            self.auth_keyfile = opt.rpc.auth_keyfile.clone();
+        }
         self.http.merge(&opt.rpc.http)?;
         self.tcp.merge(&opt.rpc.tcp)?;
         self.ws.merge(&opt.rpc.ws)?;
@@ -534,6 +562,13 @@ impl ConfigModule for RpcConfig {
         self.api_quotas.merge(&opt.rpc.api_quotas)?;
 
         self.generate_address();
+        self.auth_keys = match self.auth_keyfile() {
+            Some(keyfile) => {
+                info!("Rpc auth keyfile: {:?}", keyfile);
+                Some(Arc::new(RpcAuthKeyFile::load(keyfile)?))
+            }
+            None => None,
+        };
 
         info!("Http rpc address: {:?}", self.get_http_address());
         info!("TCP rpc address: {:?}", self.get_tcp_address());
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -67,6 +67,10 @@ impl Default for RocksdbConfig {
 
 static G_DEFAULT_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("starcoindb/db"));
 pub const DEFAULT_CACHE_SIZE: usize = 20000;
+/// The state of at least this many recent blocks is kept when state pruning is enabled,
+/// a block which forks from the main chain below the pruned block number is rejected,
+/// because the state of the fork point may be pruned.
+pub const MIN_STATE_PRUNE_WINDOW: u64 = 256;
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
@@ -102,6 +106,14 @@ pub struct StorageConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(name = "rocksdb-bytes-per-sync", long, help = "rocksdb bytes per sync")]
     pub bytes_per_sync: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "state-prune-window",
+        long,
+        help = "keep the state of the latest N blocks and prune the older state, the node keeps all state if not set. Once enabled, the state db can not go back to archive mode."
+    )]
+    pub state_prune_window: Option<u64>,
 }
 
 impl StorageConfig {
@@ -129,6 +141,11 @@ impl StorageConfig {
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+
+    /// The count of recent blocks whose state is kept, `None` means archive mode.
+    pub fn state_prune_window(&self) -> Option<u64> {
+        self.state_prune_window
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -149,6 +166,17 @@ impl ConfigModule for StorageConfig {
         if opt.storage.wal_bytes_per_sync.is_some() {
             self.wal_bytes_per_sync = opt.storage.wal_bytes_per_sync;
         }
+        if opt.storage.state_prune_window.is_some() {
+            self.state_prune_window = opt.storage.state_prune_window;
+        }
+        if let Some(state_prune_window) = self.state_prune_window {
+            ensure!(
+                state_prune_window >= MIN_STATE_PRUNE_WINDOW,
+                "state prune window should be at least {}, got {}",
+                MIN_STATE_PRUNE_WINDOW,
+                state_prune_window
+            );
+        }
         Ok(())
     }
 }
@@ -7,8 +7,11 @@ use serde::{Deserialize, Serialize};
 use starcoin_logger::prelude::*;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 
 const DEFAULT_STRATUM_PORT: u16 = 9880;
+const DEFAULT_SHARE_TIME_SECS: u64 = 10;
+const DEFAULT_MIN_SHARE_DIFFICULTY: u64 = 1024;
 // UNSPECIFIED is 0.0.0.0
 const DEFAULT_STRATUM_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 
@@ -28,6 +31,17 @@ pub struct StratumConfig {
     /// Stratum address, default is 0.0.0.0
     pub address: Option<IpAddr>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "stratum-share-time")]
+    /// Expected seconds between two shares of a worker, the share difficulty of each worker is adjusted to it.
+    /// Default is 10, 0 disables the variable share difficulty and workers get the block difficulty.
+    pub share_time: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "stratum-min-share-difficulty")]
+    /// Initial and minimum share difficulty of a worker, default is 1024.
+    pub min_share_difficulty: Option<u64>,
+
     #[clap(skip)]
     #[serde(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -54,6 +68,16 @@ impl StratumConfig {
         });
         format!("{}:{}", address, port).parse::<SocketAddr>().ok()
     }
+
+    pub fn share_time(&self) -> Duration {
+        Duration::from_secs(self.share_time.unwrap_or(DEFAULT_SHARE_TIME_SECS))
+    }
+
+    pub fn min_share_difficulty(&self) -> u64 {
+        self.min_share_difficulty
+            .unwrap_or(DEFAULT_MIN_SHARE_DIFFICULTY)
+            .max(1)
+    }
 }
 
 impl ConfigModule for StratumConfig {
@@ -68,6 +92,12 @@ impl ConfigModule for StratumConfig {
         if opt.stratum.port.is_some() {
             self.port = opt.stratum.port;
         }
+        if opt.stratum.share_time.is_some() {
+            self.share_time = opt.stratum.share_time;
+        }
+        if opt.stratum.min_share_difficulty.is_some() {
+            self.min_share_difficulty = opt.stratum.min_share_difficulty;
+        }
         info!(
             "Stratum listen address: {:?}, port:{:?}",
             self.address, self.port
@@ -28,6 +28,24 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// download the state of a recent pivot block from peers instead of executing all history blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fast-sync",
+        long,
+        help = "fast sync the state at a recent pivot block from peers when the node is far behind, default false."
+    )]
+    fast_sync: Option<bool>,
+
+    /// run as a light node, only sync headers and read the state from peers with proof
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "light",
+        long,
+        help = "run as a light node which only syncs block headers and reads the state from full peers with proof, default false."
+    )]
+    light: Option<bool>,
 }
 
 impl SyncConfig {
@@ -38,6 +56,18 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn fast_sync(&self) -> bool {
+        self.fast_sync.unwrap_or(false)
+    }
+
+    pub fn light(&self) -> bool {
+        self.light.unwrap_or(false)
+    }
+
+    pub fn set_light(&mut self, light: bool) {
+        self.light = Some(light);
+    }
 }
 
 impl ConfigModule for SyncConfig {
@@ -50,6 +80,14 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.fast_sync.is_some() {
+            self.fast_sync = opt.sync.fast_sync;
+        }
+
+        if opt.sync.light.is_some() {
+            self.light = opt.sync.light;
+        }
+
         Ok(())
     }
 }
@@ -134,6 +134,10 @@ fn test_example_config_compact() -> Result<()> {
             "8090",
             "--stratum-address",
             "127.0.0.1",
+            "--stratum-share-time",
+            "5",
+            "--stratum-min-share-difficulty",
+            "4096",
         ];
         let opt = StarcoinOpt::try_parse_from(args)?;
         let config = NodeConfig::load_with_opt(&opt)?;
@@ -184,3 +188,62 @@ fn test_check_method_in_api_sets() {
     assert!(!ApiSet::UnsafeContext.check_rpc_method("unknown"));
     assert!(!ApiSet::UnsafeContext.check_rpc_method(""));
 }
+
+#[test]
+fn test_rpc_auth_keyfile() -> Result<()> {
+    let temp_path = temp_dir();
+    let keyfile = temp_path.path().join("rpc_auth.toml");
+    std::fs::write(
+        &keyfile,
+        r#"
+jwt_secret = "secret"
+anonymous_apis = "node,chain"
+
+[[keys]]
+name = "admin"
+key = "admin_key"
+apis = "all"
+
+[[keys]]
+name = "wallet"
+key = "wallet_key"
+apis = "state"
+methods = ["account.get*", "txpool.submit_hex_transaction"]
+quota = "10/s"
+"#,
+    )?;
+    let keys = RpcAuthKeyFile::load(&keyfile)?;
+    assert!(keys.is_anonymous_allowed("chain.info"));
+    assert!(!keys.is_anonymous_allowed("account.unlock"));
+    assert!(!keys.is_anonymous_allowed("starcoin_subscribe"));
+
+    let admin = keys.get_key("admin_key").expect("admin key should exist");
+    assert!(admin.is_allowed("account.unlock"));
+    assert!(admin.is_allowed("starcoin_subscribe"));
+
+    let wallet = keys
+        .get_key_by_name("wallet")
+        .expect("wallet key should exist");
+    assert!(wallet.is_allowed("state.get_resource"));
+    assert!(wallet.is_allowed("account.get_default_account"));
+    assert!(wallet.is_allowed("txpool.submit_hex_transaction"));
+    assert!(!wallet.is_allowed("txpool.submit_transaction"));
+    assert!(!wallet.is_allowed("account.unlock"));
+    assert_eq!(wallet.quota, Some("10/s".parse()?));
+    assert!(keys.get_key("unknown").is_none());
+
+    std::fs::write(
+        &keyfile,
+        r#"
+[[keys]]
+name = "admin"
+key = "key1"
+
+[[keys]]
+name = "admin"
+key = "key2"
+"#,
+    )?;
+    assert!(RpcAuthKeyFile::load(&keyfile).is_err());
+    Ok(())
+}
@@ -1,16 +1,64 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use crate::{parse_key_val, BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{bail, Result};
 use clap::Parser;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use starcoin_system::get_free_mem_size;
+use starcoin_types::account_address::AccountAddress;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
 
-#[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Parser)]
+static G_DEFAULT_JOURNAL_PATH: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("txpool.journal"));
+
+/// How the txpool orders the pending txns, and chooses the txn to evict when the pool is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxPoolOrdering {
+    /// Order by gas price, local and retracted txns are boosted.
+    GasPrice,
+    /// Order by the time txns enter the pool.
+    Fifo,
+    /// Txns of local or priority senders first, then order by gas price.
+    Priority,
+}
+
+impl Default for TxPoolOrdering {
+    fn default() -> Self {
+        TxPoolOrdering::GasPrice
+    }
+}
+
+impl fmt::Display for TxPoolOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxPoolOrdering::GasPrice => write!(f, "gas_price"),
+            TxPoolOrdering::Fifo => write!(f, "fifo"),
+            TxPoolOrdering::Priority => write!(f, "priority"),
+        }
+    }
+}
+
+impl FromStr for TxPoolOrdering {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().replace('-', "_").as_str() {
+            "gas_price" => TxPoolOrdering::GasPrice,
+            "fifo" => TxPoolOrdering::Fifo,
+            "priority" => TxPoolOrdering::Priority,
+            _ => bail!("Unknown txpool ordering: {}", s),
+        })
+    }
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
 pub struct TxPoolConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -37,6 +85,61 @@ pub struct TxPoolConfig {
     #[clap(name = "txpool-min-gas-price", long)]
     /// reject transaction whose gas_price is less than the min_gas_price. default to 1.
     min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-journal", long)]
+    /// persist locally submitted transactions to a journal, and reload them after restart. default to false.
+    journal: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-journal-path", long, parse(from_os_str))]
+    /// the journal file of txpool.
+    /// Default: txpool.journal in data_dir
+    journal_path: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-ordering", long)]
+    /// ordering policy of the pool: gas_price, fifo or priority. default to gas_price.
+    ordering: Option<TxPoolOrdering>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-priority-sender", long, number_of_values = 1)]
+    /// senders whose txns go first under the priority ordering, besides the locally submitted txns.
+    priority_senders: Option<Vec<AccountAddress>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-price-bump-percent", long)]
+    /// percentage a txn must raise the gas price by to replace a pooled txn with the same sequence number. default to 0.
+    price_bump_percent: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "txpool-sender-quota",
+        long,
+        number_of_values = 1,
+        parse(try_from_str = parse_key_val)
+    )]
+    /// maximal number of transactions of a sender, override txpool-max-per-sender, eg: 0x1=1024
+    sender_quotas: Option<Vec<(AccountAddress, u64)>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-txn-expiry", long)]
+    /// seconds a transaction can stay in the pool before evicted. default to no limit.
+    txn_expiry: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "txpool-sender-expiry",
+        long,
+        number_of_values = 1,
+        parse(try_from_str = parse_key_val)
+    )]
+    /// seconds the transactions of a sender can stay in the pool, override txpool-txn-expiry, eg: 0x1=600
+    sender_expiry: Option<Vec<(AccountAddress, u64)>>,
+
+    #[serde(skip)]
+    #[clap(skip)]
+    base: Option<Arc<BaseConfig>>,
 }
 
 impl TxPoolConfig {
@@ -68,10 +171,61 @@ impl TxPoolConfig {
     pub fn min_gas_price(&self) -> u64 {
         self.min_gas_price.unwrap_or(1)
     }
+    pub fn set_journal(&mut self, journal: bool) {
+        self.journal = Some(journal);
+    }
+    pub fn journal(&self) -> bool {
+        self.journal.unwrap_or(false)
+    }
+    pub fn journal_path(&self) -> PathBuf {
+        let path = self
+            .journal_path
+            .as_ref()
+            .unwrap_or(&G_DEFAULT_JOURNAL_PATH);
+        if path.is_absolute() {
+            path.clone()
+        } else {
+            self.base().data_dir().join(path)
+        }
+    }
+    pub fn set_ordering(&mut self, ordering: TxPoolOrdering) {
+        self.ordering = Some(ordering);
+    }
+    pub fn ordering(&self) -> TxPoolOrdering {
+        self.ordering.unwrap_or_default()
+    }
+    pub fn priority_senders(&self) -> Vec<AccountAddress> {
+        self.priority_senders.clone().unwrap_or_default()
+    }
+    pub fn set_price_bump_percent(&mut self, price_bump_percent: u64) {
+        self.price_bump_percent = Some(price_bump_percent);
+    }
+    pub fn price_bump_percent(&self) -> u64 {
+        self.price_bump_percent.unwrap_or(0)
+    }
+    pub fn set_sender_quotas(&mut self, sender_quotas: Vec<(AccountAddress, u64)>) {
+        self.sender_quotas = Some(sender_quotas);
+    }
+    pub fn sender_quotas(&self) -> Vec<(AccountAddress, u64)> {
+        self.sender_quotas.clone().unwrap_or_default()
+    }
+    pub fn set_txn_expiry(&mut self, txn_expiry: u64) {
+        self.txn_expiry = Some(txn_expiry);
+    }
+    pub fn txn_expiry(&self) -> Option<u64> {
+        self.txn_expiry
+    }
+    pub fn sender_expiry(&self) -> Vec<(AccountAddress, u64)> {
+        self.sender_expiry.clone().unwrap_or_default()
+    }
+    fn base(&self) -> &BaseConfig {
+        self.base.as_ref().expect("Config should init.")
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
         let txpool_opt = &opt.txpool;
         if let Some(m) = txpool_opt.max_mem_usage.as_ref() {
             self.max_mem_usage = Some(*m);
@@ -88,6 +242,30 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.min_gas_price.as_ref() {
             self.min_gas_price = Some(*m);
         }
+        if let Some(m) = txpool_opt.journal.as_ref() {
+            self.journal = Some(*m);
+        }
+        if txpool_opt.journal_path.is_some() {
+            self.journal_path = txpool_opt.journal_path.clone();
+        }
+        if let Some(m) = txpool_opt.ordering.as_ref() {
+            self.ordering = Some(*m);
+        }
+        if txpool_opt.priority_senders.is_some() {
+            self.priority_senders = txpool_opt.priority_senders.clone();
+        }
+        if let Some(m) = txpool_opt.price_bump_percent.as_ref() {
+            self.price_bump_percent = Some(*m);
+        }
+        if txpool_opt.sender_quotas.is_some() {
+            self.sender_quotas = txpool_opt.sender_quotas.clone();
+        }
+        if let Some(m) = txpool_opt.txn_expiry.as_ref() {
+            self.txn_expiry = Some(*m);
+        }
+        if txpool_opt.sender_expiry.is_some() {
+            self.sender_expiry = txpool_opt.sender_expiry.clone();
+        }
         Ok(())
     }
 }
@@ -4,15 +4,29 @@ use crate::difficult_to_target;
 use anyhow::{bail, format_err, Result};
 use starcoin_chain_api::ChainReader;
 use starcoin_logger::prelude::*;
-use starcoin_types::block::BlockHeader;
+use starcoin_types::block::{BlockHeader, BlockNumber};
 use starcoin_types::{U256, U512};
+use starcoin_vm_types::on_chain_resource::Epoch;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 
 /// Get the target of next pow work
 pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
-    let epoch = chain.epoch();
-    let current_header = chain.current_header();
+    get_next_work_required_by(chain.epoch(), &chain.current_header(), |n| {
+        chain.get_header_by_number(n)
+    })
+}
+
+/// Get the target of the pow work after `current_header`,
+/// the headers in the difficulty window are read by `get_header_by_number`.
+pub fn get_next_work_required_by<F>(
+    epoch: &Epoch,
+    current_header: &BlockHeader,
+    get_header_by_number: F,
+) -> Result<U256>
+where
+    F: Fn(BlockNumber) -> Result<Option<BlockHeader>>,
+{
     if current_header.number() <= 1 {
         return Ok(difficult_to_target(current_header.difficulty()));
     }
@@ -32,8 +46,7 @@ pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
             .ok_or_else(|| format_err!("block number overflow"))?)
         .rev()
         .map(|n| {
-            chain
-                .get_header_by_number(n)?
+            get_header_by_number(n)?
                 .ok_or_else(|| format_err!("Can not find header by number {}", n))
                 .map(|header| header.into())
         })
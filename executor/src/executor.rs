@@ -46,6 +46,12 @@ fn do_execute_block_transactions(
     Ok(result)
 }
 
+/// Set the concurrency level of block transaction execution, level 1 means sequential execution.
+/// Only the first call takes effect.
+pub fn set_execution_concurrency_level(concurrency_level: usize) {
+    StarcoinVM::set_concurrency_level_once(concurrency_level)
+}
+
 pub fn validate_transaction(
     chain_state: &dyn StateView,
     txn: SignedUserTransaction,
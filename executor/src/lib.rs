@@ -14,7 +14,9 @@ pub use starcoin_transaction_builder::{
     encode_transfer_script_function, peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME,
     DEFAULT_MAX_GAS_AMOUNT,
 };
+pub use vm_runtime::execution_trace;
 pub use vm_runtime::metrics::VMMetrics;
+pub use vm_runtime::profiler;
 
 pub mod account;
 mod block_executor;
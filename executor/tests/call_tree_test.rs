@@ -0,0 +1,144 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_dev::playground::trace_dry_run;
+use starcoin_executor::account::create_account_txn_sent_as_association;
+use starcoin_executor::execution_trace::CallKind;
+use starcoin_executor::profiler::MOVE_VM_TRACE_ENV;
+use starcoin_executor::Account;
+use starcoin_transaction_builder::{DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT};
+use starcoin_types::account_config;
+use starcoin_types::transaction::{
+    DryRunTransaction, RawUserTransaction, Script, Transaction, TransactionPayload,
+};
+use starcoin_vm_types::token::stc::STCUnit;
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use test_helper::executor::{
+    compile_script, execute_and_apply, get_sequence_number, prepare_genesis,
+};
+
+// The Move VM reads the `MOVE_VM_TRACE` env once, so this is the only test of the process.
+#[stest::test]
+fn test_trace_nested_calls() -> Result<()> {
+    // the vm built without debug assertions never writes the instruction trace.
+    if !cfg!(debug_assertions) {
+        return Ok(());
+    }
+    let trace_dir = tempfile::tempdir()?;
+    std::env::set_var(MOVE_VM_TRACE_ENV, trace_dir.path().join("move_vm_trace"));
+
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let sender = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &sender,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        STCUnit::STC.value_of(1).scaling(),
+        expiration,
+        &net,
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let code = compile_script(
+        r#"
+        script {
+            use 0x1::Account;
+            use 0x1::STC::STC;
+
+            fun main(account: signer, amount: u128) {
+                let token = Account::withdraw<STC>(&account, amount);
+                Account::deposit_to_self<STC>(&account, token);
+            }
+        }
+        "#,
+    );
+    let withdraw = |amount: u128| -> Result<_> {
+        trace_dry_run(
+            &chain_state,
+            DryRunTransaction {
+                raw_txn: RawUserTransaction::new_with_default_gas_token(
+                    *sender.address(),
+                    0,
+                    TransactionPayload::Script(Script::new(
+                        code.clone(),
+                        vec![],
+                        vec![bcs_ext::to_bytes(&amount)?],
+                    )),
+                    DEFAULT_MAX_GAS_AMOUNT,
+                    1,
+                    expiration,
+                    net.chain_id(),
+                ),
+                public_key: sender.public_key(),
+            },
+            None,
+        )
+    };
+
+    let (status, _, trace) = withdraw(1000)?;
+    assert!(matches!(status, VMStatus::Executed));
+    let script = trace
+        .calls
+        .iter()
+        .find(|call| call.kind == CallKind::Script)
+        .unwrap();
+    assert_eq!(script.return_values, Some(vec![]));
+    let functions: Vec<_> = script
+        .children
+        .iter()
+        .map(|call| {
+            assert_eq!(call.kind, CallKind::Call);
+            assert!(call.error.is_none());
+            call.function.as_ref().unwrap().as_str()
+        })
+        .collect();
+    assert_eq!(functions, vec!["withdraw", "deposit_to_self"]);
+    assert!(script.children.iter().all(|call| !call.children.is_empty()));
+    // the sender's balance is mutated by the callees of the script.
+    assert!(script
+        .writes
+        .iter()
+        .any(|(access_path, _)| access_path.address == *sender.address()));
+    assert!(
+        script
+            .children
+            .iter()
+            .map(|call| call.gas_used)
+            .sum::<u64>()
+            <= script.gas_used
+    );
+
+    // withdraw more than the balance, the nested call is aborted.
+    let (status, _, trace) = withdraw(STCUnit::STC.value_of(2).scaling())?;
+    assert!(matches!(status, VMStatus::MoveAbort(_, _)));
+    let script = trace
+        .calls
+        .iter()
+        .find(|call| call.kind == CallKind::Script)
+        .unwrap();
+    assert!(matches!(script.error, Some(VMStatus::MoveAbort(_, _))));
+    assert!(script.return_values.is_none());
+    assert_eq!(script.children.len(), 1);
+    let withdraw_call = &script.children[0];
+    assert_eq!(withdraw_call.kind, CallKind::Call);
+    assert_eq!(
+        withdraw_call.module.as_ref().unwrap().name().as_str(),
+        "Account"
+    );
+    assert_eq!(
+        withdraw_call.function.as_ref().unwrap().as_str(),
+        "withdraw"
+    );
+    assert!(matches!(
+        withdraw_call.error,
+        Some(VMStatus::MoveAbort(_, _))
+    ));
+    assert!(!withdraw_call.children.is_empty());
+    assert!(withdraw_call
+        .children
+        .iter()
+        .any(|call| call.error.is_some()));
+    Ok(())
+}
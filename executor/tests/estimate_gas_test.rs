@@ -0,0 +1,149 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_dev::playground::{dry_run, estimate_gas};
+use starcoin_executor::account::create_account_txn_sent_as_association;
+use starcoin_executor::Account;
+use starcoin_transaction_builder::{
+    raw_peer_to_peer_txn, DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_types::account_config;
+use starcoin_types::account_config::G_STC_TOKEN_CODE;
+use starcoin_types::transaction::{DryRunTransaction, RawUserTransaction, Transaction};
+use starcoin_vm_types::token::stc::STCUnit;
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use test_helper::executor::{execute_and_apply, get_sequence_number, prepare_genesis};
+
+fn with_max_gas_amount(raw_txn: &RawUserTransaction, max_gas_amount: u64) -> RawUserTransaction {
+    RawUserTransaction::new(
+        raw_txn.sender(),
+        raw_txn.sequence_number(),
+        raw_txn.payload().clone(),
+        max_gas_amount,
+        raw_txn.gas_unit_price(),
+        raw_txn.expiration_timestamp_secs(),
+        raw_txn.chain_id(),
+        raw_txn.gas_token_code(),
+    )
+}
+
+#[stest::test]
+fn test_estimate_gas_of_transfer() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let sender = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &sender,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        STCUnit::STC.value_of(100).scaling(),
+        expiration,
+        &net,
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    // transfer to a non-exists account, the receiver account is created by the txn.
+    let receiver = Account::new();
+    let raw_txn = raw_peer_to_peer_txn(
+        *sender.address(),
+        *receiver.address(),
+        STCUnit::STC.value_of(1).scaling(),
+        0,
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        G_STC_TOKEN_CODE.clone(),
+        expiration,
+        net.chain_id(),
+    );
+    let (max_gas_amount, status, output) = estimate_gas(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn: raw_txn.clone(),
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(matches!(status, VMStatus::Executed));
+    assert!(output.gas_used() <= max_gas_amount);
+
+    // the estimated max gas amount is the smallest one the txn can be executed with.
+    let (status, _) = dry_run(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn: with_max_gas_amount(&raw_txn, max_gas_amount - 1),
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(!matches!(status, VMStatus::Executed));
+
+    let txn = Transaction::UserTransaction(
+        sender.sign_txn(with_max_gas_amount(&raw_txn, max_gas_amount)),
+    );
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    // transfer to the existing account costs less gas than creating the account.
+    let raw_txn = raw_peer_to_peer_txn(
+        *sender.address(),
+        *receiver.address(),
+        STCUnit::STC.value_of(1).scaling(),
+        1,
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        G_STC_TOKEN_CODE.clone(),
+        expiration,
+        net.chain_id(),
+    );
+    let (max_gas_amount_of_existing_account, status, _) = estimate_gas(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn,
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(matches!(status, VMStatus::Executed));
+    assert!(max_gas_amount_of_existing_account < max_gas_amount);
+    Ok(())
+}
+
+#[stest::test]
+fn test_estimate_gas_of_aborted_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let sender = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &sender,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        STCUnit::STC.value_of(1).scaling(),
+        expiration,
+        &net,
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    // transfer more than the balance, the txn is aborted with any max gas amount.
+    let raw_txn = raw_peer_to_peer_txn(
+        *sender.address(),
+        *Account::new().address(),
+        STCUnit::STC.value_of(2).scaling(),
+        0,
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        G_STC_TOKEN_CODE.clone(),
+        expiration,
+        net.chain_id(),
+    );
+    let (_, status, _) = estimate_gas(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn,
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(matches!(status, VMStatus::MoveAbort(_, _)));
+    Ok(())
+}
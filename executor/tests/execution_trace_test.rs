@@ -0,0 +1,220 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_dev::playground::{
+    profile_call_contract, profile_dry_run, replay_and_trace, trace_dry_run,
+};
+use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use starcoin_executor::execution_trace::CallKind;
+use starcoin_executor::profiler::ProfileWeight;
+use starcoin_executor::Account;
+use starcoin_transaction_builder::{
+    raw_peer_to_peer_txn, DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_types::account_config;
+use starcoin_types::account_config::G_STC_TOKEN_CODE;
+use starcoin_types::block_metadata::BlockMetadata;
+use starcoin_types::transaction::{DryRunTransaction, Transaction};
+use starcoin_vm_types::account_config::core_code_address;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::token::stc::stc_type_tag;
+use starcoin_vm_types::token::stc::STCUnit;
+use starcoin_vm_types::transaction_argument::TransactionArgument;
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use test_helper::executor::{
+    current_block_number, execute_and_apply, get_sequence_number, prepare_genesis,
+};
+use vm_runtime::starcoin_vm::StarcoinVM;
+
+#[stest::test]
+fn test_trace_dry_run() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let sender = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &sender,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        STCUnit::STC.value_of(1).scaling(),
+        expiration,
+        &net,
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let transfer = |amount: u128| {
+        raw_peer_to_peer_txn(
+            *sender.address(),
+            *Account::new().address(),
+            amount,
+            0,
+            1,
+            DEFAULT_MAX_GAS_AMOUNT,
+            G_STC_TOKEN_CODE.clone(),
+            expiration,
+            net.chain_id(),
+        )
+    };
+    let (status, output, trace) = trace_dry_run(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn: transfer(STCUnit::STC.value_of(1).scaling() / 2),
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(matches!(status, VMStatus::Executed));
+    let kinds: Vec<_> = trace.calls.iter().map(|call| call.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            CallKind::Prologue,
+            CallKind::ScriptFunction,
+            CallKind::Epilogue
+        ]
+    );
+    assert!(trace.calls.iter().all(|call| call.error.is_none()));
+    assert!(trace.calls.iter().map(|call| call.gas_used).sum::<u64>() <= output.gas_used());
+    // the sender's resources are read by the prologue.
+    assert!(trace.calls[0]
+        .reads
+        .iter()
+        .any(|read| read.access_path.address == *sender.address()));
+
+    // transfer more than the balance, the script function is aborted.
+    let (status, _, trace) = trace_dry_run(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn: transfer(STCUnit::STC.value_of(2).scaling()),
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(matches!(status, VMStatus::MoveAbort(_, _)));
+    let script_function = trace
+        .calls
+        .iter()
+        .find(|call| call.kind == CallKind::ScriptFunction)
+        .unwrap();
+    assert!(matches!(
+        script_function.error,
+        Some(VMStatus::MoveAbort(_, _))
+    ));
+    Ok(())
+}
+
+#[stest::test]
+fn test_replay_and_trace() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let sender = Account::new();
+    let receiver = Account::new();
+    net.time_service().sleep(1000);
+    let miner = Account::new();
+    let txns = vec![
+        Transaction::BlockMetadata(BlockMetadata::new(
+            starcoin_crypto::HashValue::random(),
+            net.time_service().now_millis(),
+            *miner.address(),
+            Some(miner.auth_key()),
+            0,
+            current_block_number(&chain_state) + 1,
+            net.chain_id(),
+            0,
+        )),
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            &sender,
+            get_sequence_number(account_config::association_address(), &chain_state),
+            STCUnit::STC.value_of(1).scaling(),
+            expiration,
+            &net,
+        )),
+        Transaction::UserTransaction(peer_to_peer_txn(
+            &sender,
+            &receiver,
+            0,
+            1000,
+            expiration,
+            net.chain_id(),
+        )),
+    ];
+    let outputs =
+        StarcoinVM::new(None).execute_block_transactions(&chain_state, txns.clone(), None)?;
+
+    // the transfer depends on the account created by the previous txn in the block.
+    let (status, output, trace) = replay_and_trace(&chain_state, txns.clone(), 2, None)?;
+    assert!(matches!(status, VMStatus::Executed));
+    assert_eq!(&outputs[2].1, &output);
+    assert_eq!(trace.calls.len(), 3);
+
+    // the block metadata can not be traced.
+    assert!(replay_and_trace(&chain_state, txns, 0, None).is_err());
+    Ok(())
+}
+
+#[stest::test]
+fn test_profile_dry_run_and_call() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let sender = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &sender,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        STCUnit::STC.value_of(1).scaling(),
+        expiration,
+        &net,
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let (status, output, profile) = profile_dry_run(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn: raw_peer_to_peer_txn(
+                *sender.address(),
+                *Account::new().address(),
+                1000,
+                0,
+                1,
+                DEFAULT_MAX_GAS_AMOUNT,
+                G_STC_TOKEN_CODE.clone(),
+                expiration,
+                net.chain_id(),
+            ),
+            public_key: sender.public_key(),
+        },
+        None,
+    )?;
+    assert!(matches!(status, VMStatus::Executed));
+    assert!(profile.gas() > 0);
+    assert!(profile.gas() <= output.gas_used());
+    assert!(profile
+        .samples
+        .iter()
+        .all(|sample| ["prologue", "script_function", "epilogue"]
+            .contains(&sample.stack[0].as_str())));
+    assert!(profile
+        .to_collapsed_stacks(ProfileWeight::Time)
+        .lines()
+        .any(|line| line.starts_with("script_function;")));
+    let summary = profile.module_summary();
+    assert_eq!(summary.iter().map(|m| m.gas).sum::<u64>(), profile.gas());
+
+    // the readonly function is charged by the on-chain gas schedule when profiling.
+    let (rets, profile) = profile_call_contract(
+        &chain_state,
+        ModuleId::new(core_code_address(), Identifier::new("Account")?),
+        "balance",
+        vec![stc_type_tag()],
+        vec![TransactionArgument::Address(*sender.address())],
+        None,
+    )?;
+    assert_eq!(rets.len(), 1);
+    assert!(profile.gas() > 0);
+    assert!(profile
+        .samples
+        .iter()
+        .all(|sample| sample.stack[0] == "function"));
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::ChainNetwork;
+use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use starcoin_executor::Account;
+use starcoin_state_api::ChainStateWriter;
+use starcoin_transaction_builder::DEFAULT_EXPIRATION_TIME;
+use starcoin_types::account_config;
+use starcoin_types::block_metadata::BlockMetadata;
+use starcoin_types::transaction::{Transaction, TransactionStatus};
+use statedb::ChainStateDB;
+use test_helper::executor::{current_block_number, get_sequence_number, prepare_genesis};
+use vm_runtime::starcoin_vm::StarcoinVM;
+
+const CONCURRENCY_LEVEL: usize = 4;
+
+fn block_metadata(chain_state: &ChainStateDB, net: &ChainNetwork) -> Transaction {
+    net.time_service().sleep(1000);
+    let miner = Account::new();
+    Transaction::BlockMetadata(BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        net.time_service().now_millis(),
+        *miner.address(),
+        Some(miner.auth_key()),
+        0,
+        current_block_number(chain_state) + 1,
+        net.chain_id(),
+        0,
+    ))
+}
+
+/// Execute the block both sequentially and in parallel, check the outputs are the same,
+/// then apply the outputs to the state.
+fn execute_and_compare(
+    chain_state: &ChainStateDB,
+    txns: Vec<Transaction>,
+    block_gas_limit: Option<u64>,
+) -> Result<Vec<TransactionStatus>> {
+    let sequential_outputs = StarcoinVM::new(None).execute_block_transactions(
+        chain_state,
+        txns.clone(),
+        block_gas_limit,
+    )?;
+    let mut vm = StarcoinVM::new(None);
+    vm.set_concurrency_level(CONCURRENCY_LEVEL);
+    let parallel_outputs = vm.execute_block_transactions(chain_state, txns, block_gas_limit)?;
+    assert_eq!(sequential_outputs, parallel_outputs);
+
+    let mut status = vec![];
+    for (_, output) in sequential_outputs {
+        if let TransactionStatus::Keep(_) = output.status() {
+            chain_state.apply_write_set(output.write_set().clone())?;
+            chain_state.commit()?;
+        }
+        status.push(output.status().clone());
+    }
+    Ok(status)
+}
+
+#[stest::test]
+fn test_parallel_execution_same_as_sequential() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+
+    // create accounts in one block, all the txns are sent by association.
+    let accounts: Vec<Account> = (0..8).map(|_| Account::new()).collect();
+    let association_seq = get_sequence_number(account_config::association_address(), &chain_state);
+    let mut txns = vec![block_metadata(&chain_state, &net)];
+    txns.extend(accounts.iter().enumerate().map(|(i, account)| {
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            account,
+            association_seq + i as u64,
+            100_000_000,
+            expiration,
+            &net,
+        ))
+    }));
+    let status = execute_and_compare(&chain_state, txns, None)?;
+    assert!(status
+        .iter()
+        .all(|status| matches!(status, TransactionStatus::Keep(_))));
+
+    let mut seq_numbers = vec![0u64; accounts.len()];
+    for block in 0..5usize {
+        let mut txns = vec![block_metadata(&chain_state, &net)];
+        for i in 0..40usize {
+            // few senders to make conflicts on the sender's account, and random receivers.
+            let sender_idx = (i + block) % 3;
+            let receiver_idx = (i * 7 + block * 3 + 1) % accounts.len();
+            let receiver = if i % 5 == 0 {
+                Account::new()
+            } else {
+                accounts[receiver_idx].clone()
+            };
+            let (seq_number, amount) = match i % 11 {
+                // too new sequence number, discarded.
+                3 => (seq_numbers[sender_idx] + 100, 1_000),
+                // insufficient balance, aborted but kept.
+                7 => (seq_numbers[sender_idx], u64::MAX as u128),
+                _ => (seq_numbers[sender_idx], (1_000 * (i + 1)) as u128),
+            };
+            if i % 11 != 3 {
+                seq_numbers[sender_idx] += 1;
+            }
+            txns.push(Transaction::UserTransaction(peer_to_peer_txn(
+                &accounts[sender_idx],
+                &receiver,
+                seq_number,
+                amount,
+                expiration,
+                net.chain_id(),
+            )));
+        }
+        let status = execute_and_compare(&chain_state, txns, None)?;
+        assert!(status
+            .iter()
+            .any(|status| matches!(status, TransactionStatus::Discard(_))));
+    }
+    Ok(())
+}
+
+#[stest::test]
+fn test_parallel_execution_with_block_gas_limit() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let association_seq = get_sequence_number(account_config::association_address(), &chain_state);
+    let mut txns = vec![block_metadata(&chain_state, &net)];
+    txns.extend((0..20u64).map(|i| {
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            &Account::new(),
+            association_seq + i,
+            10_000,
+            expiration,
+            &net,
+        ))
+    }));
+    let total_txns = txns.len();
+    let status = execute_and_compare(&chain_state, txns, Some(10_000))?;
+    assert!(status.len() < total_txns);
+    Ok(())
+}
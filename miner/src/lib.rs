@@ -12,8 +12,9 @@ use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRef,
     ServiceRequest,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 mod create_block_template;
 pub mod generate_block_event_pacemaker;
@@ -44,11 +45,39 @@ impl ServiceRequest for UpdateSubscriberNumRequest {
     type Response = Option<MintBlockEvent>;
 }
 
+/// Report the hash rate of a miner client worker, in hashes per second.
+#[derive(Clone, Debug)]
+pub struct SubmitHashRateRequest {
+    pub worker: String,
+    pub hash_rate: u64,
+}
+
+impl ServiceRequest for SubmitHashRateRequest {
+    type Response = ();
+}
+
+/// Get the hash rates reported by miner client workers that are still active.
+#[derive(Clone, Debug)]
+pub struct HashRateRequest;
+
+impl ServiceRequest for HashRateRequest {
+    type Response = Vec<WorkerHashRate>;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkerHashRate {
+    pub worker: String,
+    pub hash_rate: u64,
+    /// Time elapsed since the worker last reported.
+    pub last_report: Duration,
+}
+
 pub struct MinerService {
     config: Arc<NodeConfig>,
     current_task: Option<MintTask>,
     create_block_template_service: ServiceRef<BlockBuilderService>,
     client_subscribers_num: u32,
+    hash_rates: HashMap<String, (u64, Instant)>,
     metrics: Option<MinerMetrics>,
 }
 
@@ -118,6 +147,7 @@ impl ServiceFactory<MinerService> for MinerService {
             current_task: None,
             create_block_template_service,
             client_subscribers_num: 0,
+            hash_rates: HashMap::new(),
             metrics,
         })
     }
@@ -149,8 +179,44 @@ impl ServiceHandler<Self, SubmitSealRequest> for MinerService {
     }
 }
 
+impl ServiceHandler<Self, SubmitHashRateRequest> for MinerService {
+    fn handle(&mut self, req: SubmitHashRateRequest, _ctx: &mut ServiceContext<MinerService>) {
+        debug!(target: "miner", "Worker {} report hash rate: {}", req.worker, req.hash_rate);
+        self.hash_rates.insert(
+            req.worker,
+            (req.hash_rate.min(MAX_WORKER_HASH_RATE), Instant::now()),
+        );
+        self.expire_hash_rates();
+    }
+}
+
+impl ServiceHandler<Self, HashRateRequest> for MinerService {
+    fn handle(
+        &mut self,
+        _req: HashRateRequest,
+        _ctx: &mut ServiceContext<MinerService>,
+    ) -> Vec<WorkerHashRate> {
+        self.expire_hash_rates();
+        let mut hash_rates = self
+            .hash_rates
+            .iter()
+            .map(|(worker, (hash_rate, reported_at))| WorkerHashRate {
+                worker: worker.clone(),
+                hash_rate: *hash_rate,
+                last_report: reported_at.elapsed(),
+            })
+            .collect::<Vec<_>>();
+        hash_rates.sort_by(|a, b| a.worker.cmp(&b.worker));
+        hash_rates
+    }
+}
+
 // one hour
 const MAX_BLOCK_TIME_GAP: u64 = 3600 * 1000;
+// miner clients report every few seconds, so a worker silent for two minutes is gone.
+const HASH_RATE_EXPIRE: Duration = Duration::from_secs(120);
+// the hash rate is reported by the workers, cap it far above any real device, about 1 PH/s.
+const MAX_WORKER_HASH_RATE: u64 = 1 << 50;
 
 impl MinerService {
     pub fn dispatch_task(&mut self, ctx: &mut ServiceContext<MinerService>) -> Result<()> {
@@ -243,6 +309,20 @@ impl MinerService {
     pub fn is_minting(&self) -> bool {
         self.current_task.is_some()
     }
+
+    /// Drop the workers which have not reported within `HASH_RATE_EXPIRE`, and refresh the total hash rate metric.
+    fn expire_hash_rates(&mut self) {
+        self.hash_rates
+            .retain(|_, (_, reported_at)| reported_at.elapsed() < HASH_RATE_EXPIRE);
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.miner_hash_rate.set(
+                self.hash_rates
+                    .values()
+                    .map(|(hash_rate, _)| *hash_rate)
+                    .fold(0u64, u64::saturating_add),
+            );
+        }
+    }
 }
 
 impl EventHandler<Self, GenerateBlockEvent> for MinerService {
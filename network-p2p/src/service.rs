@@ -42,7 +42,7 @@ use crate::network_state::{
 };
 use crate::protocol::event::Event;
 use crate::protocol::generic_proto::{NotificationsSink, Ready};
-use crate::protocol::{Protocol, HARD_CORE_PROTOCOL_ID};
+use crate::protocol::Protocol;
 use crate::request_responses::{InboundFailure, OutboundFailure, RequestFailure, ResponseFailure};
 use crate::{
     behaviour::{Behaviour, BehaviourOut},
@@ -119,6 +119,8 @@ pub struct NetworkService {
     /// Field extracted from the [`Metrics`] struct and necessary to report the
     /// notifications-related metrics.
     notifications_sizes_metric: Option<HistogramVec>,
+    /// Number of the peer sets, one set per notification protocol.
+    num_peer_sets: usize,
 }
 
 impl NetworkWorker {
@@ -168,6 +170,13 @@ impl NetworkWorker {
             known_addresses.push((bootnode.peer_id, bootnode.multiaddr.clone()));
         }
 
+        // The addresses of reserved nodes should be known, otherwise they can not be connected.
+        for reserved in params.network_config.reserved_nodes.iter() {
+            if !known_addresses.contains(&(reserved.peer_id, reserved.multiaddr.clone())) {
+                known_addresses.push((reserved.peer_id, reserved.multiaddr.clone()));
+            }
+        }
+
         let boot_node_ids = Arc::new(boot_node_ids);
 
         // Check for duplicate bootnodes.
@@ -195,6 +204,7 @@ impl NetworkWorker {
         let is_major_syncing = Arc::new(AtomicBool::new(false));
 
         let notif_protocols = params.network_config.notifications_protocols.clone();
+        let num_peer_sets = notif_protocols.len();
         let mut sets_conf = Vec::with_capacity(notif_protocols.len());
         let s: Vec<PeerId> = params
             .network_config
@@ -329,6 +339,7 @@ impl NetworkWorker {
             notifications_sizes_metric: metrics
                 .as_ref()
                 .map(|metrics| metrics.notifications_sizes.clone()),
+            num_peer_sets,
         });
 
         Ok(NetworkWorker {
@@ -826,34 +837,52 @@ impl NetworkService {
             .unbounded_send(ServiceToWorkerMsg::PutValue(key, value));
     }
 
+    /// All the peer sets, the reserved peers should be same in every set.
+    fn peer_sets(&self) -> impl Iterator<Item = sc_peerset::SetId> {
+        (0..self.num_peer_sets).map(sc_peerset::SetId::from)
+    }
+
     /// Connect to unreserved peers and allow unreserved peers to connect.
     pub fn accept_unreserved_peers(&self) {
-        self.peerset.set_reserved_only(HARD_CORE_PROTOCOL_ID, false);
+        for set_id in self.peer_sets() {
+            self.peerset.set_reserved_only(set_id, false);
+        }
     }
 
     /// Disconnect from unreserved peers and deny new unreserved peers to connect.
     pub fn deny_unreserved_peers(&self) {
-        self.peerset.set_reserved_only(HARD_CORE_PROTOCOL_ID, true);
+        for set_id in self.peer_sets() {
+            self.peerset.set_reserved_only(set_id, true);
+        }
     }
 
     /// Removes a `PeerId` from the list of reserved peers.
     pub fn remove_reserved_peer(&self, peer: PeerId) {
-        self.peerset
-            .remove_reserved_peer(HARD_CORE_PROTOCOL_ID, peer);
+        for set_id in self.peer_sets() {
+            self.peerset.remove_reserved_peer(set_id, peer);
+        }
     }
 
     /// Adds a `PeerId` and its address as reserved. The string should encode the address
     /// and peer ID of the remote node.
     pub fn add_reserved_peer(&self, peer: String) -> Result<(), String> {
         let (peer_id, addr) = parse_str_addr(&peer).map_err(|e| format!("{:?}", e))?;
-        self.peerset
-            .add_reserved_peer(HARD_CORE_PROTOCOL_ID, peer_id);
+        for set_id in self.peer_sets() {
+            self.peerset.add_reserved_peer(set_id, peer_id);
+        }
         let _ = self
             .to_worker
             .unbounded_send(ServiceToWorkerMsg::AddKnownAddress(peer_id, addr));
         Ok(())
     }
 
+    /// Adds an address for a node, the node may be connected later by the discovery.
+    pub fn add_known_address(&self, peer_id: PeerId, addr: Multiaddr) {
+        let _ = self
+            .to_worker
+            .unbounded_send(ServiceToWorkerMsg::AddKnownAddress(peer_id, addr));
+    }
+
     /// Returns the number of peers we're connected to.
     pub fn num_connected(&self) -> usize {
         self.num_connected.load(Ordering::Relaxed)
@@ -3,8 +3,8 @@
 #![deny(clippy::integer_arithmetic)]
 
 use crate::messages::{
-    BanPeer, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerMessage,
-    PeerReputations, ReportReputation,
+    BanPeer, GetPeerById, GetPeerSet, GetSelfPeer, ListBannedPeers, NotificationMessage,
+    PeerMessage, PeerReputations, ReportReputation,
 };
 use anyhow::*;
 use futures::future::BoxFuture;
@@ -48,6 +48,7 @@ pub trait NetworkActor:
     + ServiceHandler<Self, PeerReputations>
     + ServiceHandler<Self, GetSelfPeer>
     + ServiceHandler<Self, GetPeerById>
+    + ServiceHandler<Self, ListBannedPeers>
 {
 }
 
@@ -5,6 +5,7 @@ use crate::ReputationChange;
 use anyhow::*;
 use bcs_ext::{BCSCodec, Sample};
 use futures::channel::oneshot::Receiver;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
@@ -327,6 +328,23 @@ pub struct BanPeer {
     pub ban: bool,
 }
 
+/// A manual ban of peer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BannedPeerInfo {
+    pub peer_id: PeerId,
+    /// Unix timestamp in seconds when the peer is banned.
+    pub banned_at: u64,
+    /// Unix timestamp in seconds when the ban expires, the ban is permanent if absent.
+    pub expire_at: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ListBannedPeers;
+
+impl ServiceRequest for ListBannedPeers {
+    type Response = Vec<BannedPeerInfo>;
+}
+
 #[derive(Clone, Debug)]
 pub struct PeerReputations {
     pub threshold: i32,
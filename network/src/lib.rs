@@ -5,6 +5,7 @@
 
 pub mod helper;
 mod network_metrics;
+pub mod peer_store;
 mod service;
 pub mod service_ref;
 pub mod worker;
@@ -0,0 +1,343 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use network_api::messages::BannedPeerInfo;
+use network_p2p_types::Multiaddr;
+use serde::{Deserialize, Serialize};
+use starcoin_types::peer_info::PeerId;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Persisted reputations are ignored after this duration in seconds.
+pub const PEER_REPUTATION_EXPIRE_SECS: u64 = 86400;
+/// Max count of peers kept in the address book, the least recently seen peers are dropped first.
+const MAX_PEER_RECORDS: usize = 1024;
+/// Max count of addresses kept for a peer.
+const MAX_ADDRESSES_PER_PEER: usize = 8;
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
+
+/// Address book entry of a peer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerAddressRecord {
+    pub peer_id: PeerId,
+    pub addresses: Vec<Multiaddr>,
+    /// Unix timestamp in seconds when the peer is connected last time.
+    pub last_seen: u64,
+    /// Count of the connections to the peer.
+    pub success_count: u64,
+    /// Count of the bad behaviours reported for the peer.
+    pub failure_count: u64,
+}
+
+impl PeerAddressRecord {
+    fn new(peer_id: PeerId) -> Self {
+        Self {
+            peer_id,
+            addresses: vec![],
+            last_seen: 0,
+            success_count: 0,
+            failure_count: 0,
+        }
+    }
+
+    pub fn success_rate(&self) -> f64 {
+        let total = self.success_count.saturating_add(self.failure_count);
+        if total == 0 {
+            0f64
+        } else {
+            self.success_count as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerReputationRecord {
+    pub peer_id: PeerId,
+    pub reputation: i32,
+    /// Unix timestamp in seconds when the reputation is changed last time.
+    pub updated_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PeerStoreData {
+    #[serde(default)]
+    peers: Vec<PeerAddressRecord>,
+    #[serde(default)]
+    bans: Vec<BannedPeerInfo>,
+    #[serde(default)]
+    reputations: Vec<PeerReputationRecord>,
+}
+
+/// Persistent peer store, keep the known addresses, manual bans and reputations of peers
+/// in a json file, so they survive the node restart.
+pub struct PeerStore {
+    path: PathBuf,
+    peers: HashMap<PeerId, PeerAddressRecord>,
+    bans: HashMap<PeerId, BannedPeerInfo>,
+    reputations: HashMap<PeerId, PeerReputationRecord>,
+}
+
+impl PeerStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            peers: HashMap::new(),
+            bans: HashMap::new(),
+            reputations: HashMap::new(),
+        }
+    }
+
+    /// Load the store from the file, an empty store is returned if the file does not exist.
+    /// Expired bans and reputations are dropped.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            return Ok(Self::new(path));
+        }
+        let data: PeerStoreData = serde_json::from_slice(fs::read(&path)?.as_slice())?;
+        let now = now_secs();
+        Ok(Self {
+            path,
+            peers: data
+                .peers
+                .into_iter()
+                .map(|record| (record.peer_id.clone(), record))
+                .collect(),
+            bans: data
+                .bans
+                .into_iter()
+                .filter(|ban| !is_expired(ban, now))
+                .map(|ban| (ban.peer_id.clone(), ban))
+                .collect(),
+            reputations: data
+                .reputations
+                .into_iter()
+                .filter(|record| !is_reputation_expired(record, now))
+                .map(|record| (record.peer_id.clone(), record))
+                .collect(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Write the store to the file, a temp file is written first for avoid a broken store file.
+    pub fn save(&self) -> Result<()> {
+        let mut peers: Vec<_> = self.peers.values().cloned().collect();
+        peers.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+        peers.truncate(MAX_PEER_RECORDS);
+        let data = PeerStoreData {
+            peers,
+            bans: self.bans(),
+            reputations: self.reputations.values().cloned().collect(),
+        };
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&data)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    pub fn peers(&self) -> impl Iterator<Item = &PeerAddressRecord> {
+        self.peers.values()
+    }
+
+    pub fn get_peer(&self, peer_id: &PeerId) -> Option<&PeerAddressRecord> {
+        self.peers.get(peer_id)
+    }
+
+    /// Merge the addresses of the peer, the latest addresses are kept first.
+    pub fn add_addresses<I>(&mut self, peer_id: PeerId, addresses: I)
+    where
+        I: IntoIterator<Item = Multiaddr>,
+    {
+        let record = self
+            .peers
+            .entry(peer_id.clone())
+            .or_insert_with(|| PeerAddressRecord::new(peer_id));
+        for address in addresses {
+            record.addresses.retain(|addr| addr != &address);
+            record.addresses.insert(0, address);
+        }
+        record.addresses.truncate(MAX_ADDRESSES_PER_PEER);
+    }
+
+    pub fn on_connected(&mut self, peer_id: PeerId, now: u64) {
+        let record = self
+            .peers
+            .entry(peer_id.clone())
+            .or_insert_with(|| PeerAddressRecord::new(peer_id));
+        record.last_seen = now;
+        record.success_count = record.success_count.saturating_add(1);
+    }
+
+    pub fn on_failure(&mut self, peer_id: PeerId) {
+        let record = self
+            .peers
+            .entry(peer_id.clone())
+            .or_insert_with(|| PeerAddressRecord::new(peer_id));
+        record.failure_count = record.failure_count.saturating_add(1);
+    }
+
+    /// Ban the peer for `duration` seconds, or permanent if `duration` is None.
+    pub fn ban(&mut self, peer_id: PeerId, now: u64, duration: Option<u64>) {
+        self.bans.insert(
+            peer_id.clone(),
+            BannedPeerInfo {
+                peer_id,
+                banned_at: now,
+                expire_at: duration.map(|duration| now.saturating_add(duration)),
+            },
+        );
+    }
+
+    pub fn unban(&mut self, peer_id: &PeerId) -> bool {
+        self.bans.remove(peer_id).is_some()
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.bans.contains_key(peer_id)
+    }
+
+    /// All bans, order by the ban time.
+    pub fn bans(&self) -> Vec<BannedPeerInfo> {
+        let mut bans: Vec<_> = self.bans.values().cloned().collect();
+        bans.sort_by(|a, b| a.banned_at.cmp(&b.banned_at));
+        bans
+    }
+
+    /// Remove the expired bans and return the peers of them.
+    pub fn take_expired_bans(&mut self, now: u64) -> Vec<PeerId> {
+        let expired: Vec<PeerId> = self
+            .bans
+            .values()
+            .filter(|ban| is_expired(ban, now))
+            .map(|ban| ban.peer_id.clone())
+            .collect();
+        for peer_id in &expired {
+            self.bans.remove(peer_id);
+        }
+        expired
+    }
+
+    /// Replace the reputations by the current reputations of the peerset,
+    /// the update time of a reputation is kept if the reputation is not changed.
+    pub fn update_reputations(&mut self, reputations: Vec<(PeerId, i32)>, now: u64) {
+        self.reputations = reputations
+            .into_iter()
+            .filter(|(_, reputation)| *reputation != 0)
+            .map(|(peer_id, reputation)| {
+                let updated_at = match self.reputations.get(&peer_id) {
+                    Some(record) if record.reputation == reputation => record.updated_at,
+                    _ => now,
+                };
+                (
+                    peer_id.clone(),
+                    PeerReputationRecord {
+                        peer_id,
+                        reputation,
+                        updated_at,
+                    },
+                )
+            })
+            .collect();
+    }
+
+    /// The reputations which are not expired.
+    pub fn reputations(&self, now: u64) -> Vec<(PeerId, i32)> {
+        self.reputations
+            .values()
+            .filter(|record| !is_reputation_expired(record, now))
+            .map(|record| (record.peer_id.clone(), record.reputation))
+            .collect()
+    }
+}
+
+fn is_expired(ban: &BannedPeerInfo, now: u64) -> bool {
+    ban.expire_at
+        .map(|expire_at| expire_at <= now)
+        .unwrap_or(false)
+}
+
+fn is_reputation_expired(record: &PeerReputationRecord, now: u64) -> bool {
+    record
+        .updated_at
+        .saturating_add(PEER_REPUTATION_EXPIRE_SECS)
+        <= now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_store_save_and_load() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("peers.json");
+        let now = now_secs();
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        let peer3 = PeerId::random();
+
+        let mut store = PeerStore::load(&path)?;
+        assert_eq!(store.peers().count(), 0);
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/9840".parse()?;
+        store.add_addresses(peer1.clone(), vec![address.clone(), address.clone()]);
+        store.on_connected(peer1.clone(), now);
+        store.on_connected(peer1.clone(), now);
+        store.on_failure(peer1.clone());
+        store.ban(peer2.clone(), now, None);
+        store.ban(peer3.clone(), now, Some(0));
+        store.update_reputations(vec![(peer1.clone(), -100), (peer3.clone(), 0)], now);
+        store.save()?;
+
+        let store = PeerStore::load(&path)?;
+        let record = store.get_peer(&peer1).unwrap();
+        assert_eq!(record.addresses, vec![address]);
+        assert_eq!(record.last_seen, now);
+        assert_eq!(record.success_count, 2);
+        assert!((record.success_rate() - 2f64 / 3f64).abs() < 1e-9);
+        // the ban of peer3 is expired.
+        assert!(store.is_banned(&peer2));
+        assert!(!store.is_banned(&peer3));
+        assert_eq!(store.reputations(now), vec![(peer1, -100)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_peer_store_expire() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut store = PeerStore::load(dir.path().join("peers.json"))?;
+        let now = now_secs();
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        store.ban(peer1.clone(), now, Some(10));
+        store.ban(peer2.clone(), now, None);
+        assert!(store.take_expired_bans(now).is_empty());
+        assert_eq!(
+            store.take_expired_bans(now.saturating_add(10)),
+            vec![peer1.clone()]
+        );
+        assert!(!store.is_banned(&peer1));
+        assert!(store.unban(&peer2));
+        assert!(store.bans().is_empty());
+
+        store.update_reputations(vec![(peer1.clone(), -10)], now);
+        // the update time is kept if the reputation is not changed.
+        store.update_reputations(vec![(peer1.clone(), -10)], now.saturating_add(1));
+        let expire_at = now.saturating_add(PEER_REPUTATION_EXPIRE_SECS);
+        assert!(store.reputations(expire_at).is_empty());
+        store.update_reputations(vec![(peer1.clone(), -20)], now.saturating_add(1));
+        assert_eq!(store.reputations(expire_at), vec![(peer1, -20)]);
+        Ok(())
+    }
+}
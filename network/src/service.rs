@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::network_metrics::NetworkMetrics;
+use crate::peer_store::{now_secs, PeerStore};
 use crate::{build_network_worker, Announcement};
 use anyhow::{format_err, Result};
 use bytes::Bytes;
@@ -12,11 +13,13 @@ use futures::{
 };
 use lru::LruCache;
 use network_api::messages::{
-    AnnouncementType, BanPeer, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage,
-    PeerEvent, PeerMessage, PeerReputations, ReportReputation, TransactionsMessage,
+    AnnouncementType, BanPeer, GetPeerById, GetPeerSet, GetSelfPeer, ListBannedPeers,
+    NotificationMessage, PeerEvent, PeerMessage, PeerReputations, ReportReputation,
+    TransactionsMessage,
 };
-use network_api::{BroadcastProtocolFilter, NetworkActor, PeerMessageHandler};
+use network_api::{BroadcastProtocolFilter, NetworkActor, PeerMessageHandler, ReputationChange};
 use network_p2p::{Event, NetworkWorker};
+use network_p2p_types::Multiaddr;
 use rand::prelude::SliceRandom;
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
@@ -33,15 +36,30 @@ use starcoin_types::system_events::SyncStatusChangeEvent;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+
+const PEER_STORE_SYNC_INTERVAL: Duration = Duration::from_secs(60);
 
 pub struct NetworkActorService {
     worker: Option<NetworkWorker>,
     inner: Inner,
+    peer_store: PeerStore,
 
     network_worker_handle: Option<AbortHandle>,
 }
 
+/// Collect the addresses and reputations of peers from the network worker, and persist them.
+#[derive(Debug)]
+pub(crate) struct SyncPeerStoreEvent;
+
+#[derive(Debug)]
+pub(crate) struct PeerStoreSnapshot {
+    addresses: Vec<(PeerId, Vec<Multiaddr>)>,
+    reputations: Vec<(PeerId, i32)>,
+}
+
 impl NetworkActor for NetworkActorService {}
 
 impl NetworkActorService {
@@ -63,10 +81,19 @@ impl NetworkActorService {
         )?;
         let service = worker.service().clone();
         //let self_info = PeerInfo::new(config.network.self_peer_id(), chain_info);
+        let peer_store_file = config.network.peer_store_file();
+        let peer_store = PeerStore::load(&peer_store_file).unwrap_or_else(|e| {
+            warn!(
+                "[network] Load peer store from {:?} failed, ignore it: {:?}",
+                peer_store_file, e
+            );
+            PeerStore::new(peer_store_file)
+        });
         let inner = Inner::new(config, self_info, service, peer_message_handler)?;
         Ok(Self {
             worker: Some(worker),
             inner,
+            peer_store,
             network_worker_handle: None,
         })
     }
@@ -74,6 +101,47 @@ impl NetworkActorService {
     pub fn network_service(&self) -> Arc<network_p2p::NetworkService> {
         self.inner.network_service.clone()
     }
+
+    /// Apply the persisted bans, addresses and reputations to the network worker.
+    fn restore_peer_store(&self) {
+        let network_service = &self.inner.network_service;
+        let bans = self.peer_store.bans();
+        for ban in &bans {
+            network_service.ban_peer(ban.peer_id.clone().into(), true);
+        }
+        let mut peers = 0usize;
+        for record in self.peer_store.peers() {
+            if self.peer_store.is_banned(&record.peer_id) {
+                continue;
+            }
+            for address in &record.addresses {
+                network_service.add_known_address(record.peer_id.clone().into(), address.clone());
+            }
+            peers = peers.saturating_add(1);
+        }
+        for (peer_id, reputation) in self.peer_store.reputations(now_secs()) {
+            network_service.report_peer(
+                peer_id.into(),
+                ReputationChange::new(reputation, "Restore reputation"),
+            );
+        }
+        info!(
+            "[network] Restore {} peers and {} bans from peer store {:?}",
+            peers,
+            bans.len(),
+            self.peer_store.path()
+        );
+    }
+
+    fn save_peer_store(&self) {
+        if let Err(e) = self.peer_store.save() {
+            warn!(
+                "[network] Save peer store to {:?} failed: {:?}",
+                self.peer_store.path(),
+                e
+            );
+        }
+    }
 }
 
 impl ActorService for NetworkActorService {
@@ -95,6 +163,10 @@ impl ActorService for NetworkActorService {
                 Ok(Ok(_)) => {}
             }
         }));
+        self.restore_peer_store();
+        ctx.run_interval(PEER_STORE_SYNC_INTERVAL, |ctx| {
+            ctx.notify(SyncPeerStoreEvent)
+        });
         Ok(())
     }
 
@@ -104,10 +176,71 @@ impl ActorService for NetworkActorService {
         if let Some(abort_handle) = self.network_worker_handle.take() {
             abort_handle.abort();
         }
+        self.save_peer_store();
         Ok(())
     }
 }
 
+impl EventHandler<Self, SyncPeerStoreEvent> for NetworkActorService {
+    fn handle_event(&mut self, _msg: SyncPeerStoreEvent, ctx: &mut ServiceContext<Self>) {
+        let network_service = self.network_service();
+        let self_ref = ctx.self_ref();
+        ctx.spawn(async move {
+            let addresses: Vec<(PeerId, Vec<Multiaddr>)> =
+                match network_service.network_state().await {
+                    Ok(state) => state
+                        .connected_peers
+                        .into_iter()
+                        .filter_map(|(peer_id, peer)| {
+                            PeerId::from_str(peer_id.as_str()).ok().map(|peer_id| {
+                                (peer_id, peer.known_addresses.into_iter().collect())
+                            })
+                        })
+                        .collect(),
+                    Err(e) => {
+                        debug!("[network] Get network state failed: {:?}", e);
+                        return;
+                    }
+                };
+            let reputations: Vec<(PeerId, i32)> = match network_service.reputations(i32::MIN).await
+            {
+                Ok(reputations) => reputations
+                    .into_iter()
+                    .map(|(peer_id, reputation)| (peer_id.into(), reputation))
+                    .collect(),
+                Err(e) => {
+                    debug!("[network] Get peer reputations failed: {:?}", e);
+                    return;
+                }
+            };
+            if let Err(e) = self_ref.notify(PeerStoreSnapshot {
+                addresses,
+                reputations,
+            }) {
+                debug!("[network] Notify peer store snapshot failed: {:?}", e);
+            }
+        });
+    }
+}
+
+impl EventHandler<Self, PeerStoreSnapshot> for NetworkActorService {
+    fn handle_event(&mut self, msg: PeerStoreSnapshot, _ctx: &mut ServiceContext<Self>) {
+        let now = now_secs();
+        for (peer_id, addresses) in msg.addresses {
+            self.peer_store.add_addresses(peer_id, addresses);
+        }
+        self.peer_store.update_reputations(msg.reputations, now);
+        for peer_id in self.peer_store.take_expired_bans(now) {
+            info!(
+                "[network] The ban of peer {} is expired, unban it.",
+                peer_id
+            );
+            self.inner.network_service.ban_peer(peer_id.into(), false);
+        }
+        self.save_peer_store();
+    }
+}
+
 impl EventHandler<Self, SyncStatusChangeEvent> for NetworkActorService {
     fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
         self.inner.update_chain_status(msg.0);
@@ -134,6 +267,10 @@ impl EventHandler<Self, Event> for NetworkActorService {
                     remote, protocol, notif_protocols, rpc_protocols
                 );
                 let peer_event = PeerEvent::Open(remote.into(), info.clone());
+                let peer_id: PeerId = remote.into();
+                if !self.inner.peers.contains_key(&peer_id) {
+                    self.peer_store.on_connected(peer_id, now_secs());
+                }
                 self.inner
                     .on_peer_connected(remote.into(), *info, notif_protocols, rpc_protocols);
                 ctx.broadcast(peer_event);
@@ -167,6 +304,9 @@ impl EventHandler<Self, ReportReputation> for NetworkActorService {
         msg: ReportReputation,
         _ctx: &mut ServiceContext<NetworkActorService>,
     ) {
+        if msg.change.value < 0 {
+            self.peer_store.on_failure(msg.peer_id.clone());
+        }
         self.inner
             .network_service
             .report_peer(msg.peer_id.into(), msg.change);
@@ -175,6 +315,16 @@ impl EventHandler<Self, ReportReputation> for NetworkActorService {
 
 impl EventHandler<Self, BanPeer> for NetworkActorService {
     fn handle_event(&mut self, msg: BanPeer, _ctx: &mut ServiceContext<NetworkActorService>) {
+        if msg.ban {
+            self.peer_store.ban(
+                msg.peer_id.clone(),
+                now_secs(),
+                self.inner.config.network.peer_ban_duration(),
+            );
+        } else {
+            self.peer_store.unban(&msg.peer_id);
+        }
+        self.save_peer_store();
         self.inner
             .network_service
             .ban_peer(msg.peer_id.into(), msg.ban);
@@ -321,6 +471,16 @@ impl ServiceHandler<Self, GetPeerById> for NetworkActorService {
     }
 }
 
+impl ServiceHandler<Self, ListBannedPeers> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: ListBannedPeers,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <ListBannedPeers as ServiceRequest>::Response {
+        self.peer_store.bans()
+    }
+}
+
 impl ServiceHandler<Self, GetSelfPeer> for NetworkActorService {
     fn handle(
         &mut self,
@@ -9,7 +9,7 @@ use futures::channel::oneshot::Receiver;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use log::warn;
-use network_api::messages::NotificationMessage;
+use network_api::messages::{BannedPeerInfo, ListBannedPeers, NotificationMessage};
 use network_api::{NetworkService, PeerProvider, ReputationChange, SupportedRpcProtocol};
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::{IfDisconnected, Multiaddr, RequestFailure};
@@ -132,6 +132,17 @@ impl NetworkServiceRef {
             .map_err(|e| format_err!("{:?}", e))
     }
 
+    /// Add a reserved peer, the peer format is multiaddr/p2p/peer_id.
+    pub fn add_reserved_peer(&self, peer: String) -> Result<()> {
+        self.network_service
+            .add_reserved_peer(peer)
+            .map_err(|e| format_err!("{:?}", e))
+    }
+
+    pub fn remove_reserved_peer(&self, peer_id: PeerId) {
+        self.network_service.remove_reserved_peer(peer_id.into())
+    }
+
     pub async fn network_state(&self) -> Result<NetworkState> {
         self.network_service
             .network_state()
@@ -155,4 +166,9 @@ impl NetworkServiceRef {
     pub async fn is_connected(&self, peer_id: PeerId) -> bool {
         self.network_service.is_connected(peer_id.into()).await
     }
+
+    /// List the manual bans kept in the peer store.
+    pub async fn list_bans(&self) -> Result<Vec<BannedPeerInfo>> {
+        self.service_ref.send(ListBannedPeers).await
+    }
 }
@@ -7,7 +7,7 @@ use futures::channel::mpsc::channel;
 use futures::prelude::*;
 use log::{debug, error, info};
 use network_api::PeerInfo;
-use network_p2p::config::{RequestResponseConfig, TransportConfig};
+use network_p2p::config::{NonReservedPeerMode, RequestResponseConfig, TransportConfig};
 use network_p2p::{
     identity, NetworkConfiguration, NetworkWorker, NodeKeyConfig, Params, ProtocolId, Secret,
 };
@@ -70,24 +70,49 @@ pub fn build_network_worker(
                 }
             })
             .collect::<Vec<_>>(),
-        None => vec![],
+        // without the rpc service, the rpc protocols are only used to send requests to peers.
+        None => starcoin_network_rpc_api::G_RPC_INFO
+            .clone()
+            .into_protocols()
+            .into_iter()
+            .map(|rpc_protocol| RequestResponseConfig {
+                name: rpc_protocol,
+                max_request_size: MAX_REQUEST_SIZE,
+                max_response_size: MAX_RESPONSE_SIZE,
+                request_timeout: Duration::from_secs(30),
+                inbound_queue: None,
+            })
+            .collect::<Vec<_>>(),
     };
     let allow_non_globals_in_dht = discover_local;
     let boot_nodes = network_config.seeds();
+    let reserved_nodes = network_config.reserved_peers();
+    let non_reserved_mode = if network_config.reserved_only() {
+        NonReservedPeerMode::Deny
+    } else {
+        NonReservedPeerMode::Accept
+    };
 
     info!("Final bootstrap seeds: {:?}", boot_nodes);
+    info!(
+        "Reserved peers: {:?}, non-reserved peer mode: {:?}",
+        reserved_nodes, non_reserved_mode
+    );
     let self_info = PeerInfo::new(
         network_config.self_peer_id(),
         chain_info.clone(),
         protocols.to_vec(),
         rpc_protocols
             .iter()
+            .filter(|config| config.inbound_queue.is_some())
             .map(|config| config.name.clone())
             .collect(),
     );
     let config = NetworkConfiguration {
         listen_addresses: vec![network_config.listen()],
         boot_nodes,
+        reserved_nodes,
+        non_reserved_mode,
         node_key: {
             let secret = identity::ed25519::SecretKey::from_bytes(
                 &mut network_config.network_keypair().0.to_bytes(),
@@ -368,3 +368,71 @@ async fn test_filter_protocol() {
         msg_3.notification.protocol_name()
     );
 }
+
+#[stest::test]
+async fn test_reserved_only_network() {
+    let mut node_config_1 = NodeConfig::random_for_test();
+    let mut node_config_2 = NodeConfig::random_for_test();
+    node_config_1.network.reserved_peers = vec![node_config_2.network.self_address()].into();
+    node_config_1.network.reserved_only = Some(true);
+    node_config_2.network.reserved_peers = vec![node_config_1.network.self_address()].into();
+    node_config_2.network.reserved_only = Some(true);
+    let node_config_1 = Arc::new(node_config_1);
+    let node_config_2 = Arc::new(node_config_2);
+    let service1 = build_network_with_config(node_config_1.clone(), None)
+        .await
+        .unwrap();
+    let service2 = build_network_with_config(node_config_2.clone(), None)
+        .await
+        .unwrap();
+
+    // node3 is not a reserved peer of node1 and node2.
+    let mut node_config_3 = NodeConfig::random_for_test();
+    node_config_3.network.seeds = vec![
+        node_config_1.network.self_address(),
+        node_config_2.network.self_address(),
+    ]
+    .into();
+    let service3 = build_network_with_config(Arc::new(node_config_3), None)
+        .await
+        .unwrap();
+    Delay::new(Duration::from_secs(2)).await;
+    assert!(service1.service_ref.is_connected(service2.peer_id()).await);
+    assert!(service2.service_ref.is_connected(service1.peer_id()).await);
+    assert!(!service1.service_ref.is_connected(service3.peer_id()).await);
+    assert!(!service2.service_ref.is_connected(service3.peer_id()).await);
+    assert!(!service3.service_ref.is_connected(service1.peer_id()).await);
+}
+
+#[stest::test]
+async fn test_add_and_remove_reserved_peer() {
+    let mut node_config_1 = NodeConfig::random_for_test();
+    node_config_1.network.reserved_only = Some(true);
+    let node_config_1 = Arc::new(node_config_1);
+    let service1 = build_network_with_config(node_config_1.clone(), None)
+        .await
+        .unwrap();
+
+    let mut node_config_2 = NodeConfig::random_for_test();
+    node_config_2.network.seeds = vec![node_config_1.network.self_address()].into();
+    let node_config_2 = Arc::new(node_config_2);
+    let service2 = build_network_with_config(node_config_2.clone(), None)
+        .await
+        .unwrap();
+    Delay::new(Duration::from_secs(2)).await;
+    assert!(!service1.service_ref.is_connected(service2.peer_id()).await);
+
+    service1
+        .service_ref
+        .add_reserved_peer(node_config_2.network.self_address().to_string())
+        .unwrap();
+    Delay::new(Duration::from_secs(2)).await;
+    assert!(service1.service_ref.is_connected(service2.peer_id()).await);
+    assert!(service2.service_ref.is_connected(service1.peer_id()).await);
+
+    service1
+        .service_ref
+        .remove_reserved_peer(service2.peer_id());
+    Delay::new(Duration::from_secs(2)).await;
+    assert!(!service1.service_ref.is_connected(service2.peer_id()).await);
+}
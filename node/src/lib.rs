@@ -28,6 +28,7 @@ use tokio::runtime::Runtime;
 
 pub mod crash_handler;
 mod genesis_parameter_resolve;
+pub mod light_rpc;
 mod metrics;
 pub mod network_service_factory;
 pub mod node;
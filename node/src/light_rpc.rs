@@ -0,0 +1,419 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The chain and state rpc of the light node, backed by the `LightClient`.
+//! The data not covered by the verified headers, such as txns indexed by hash and events,
+//! can not be verified by the light node, these methods return an error.
+
+use anyhow::format_err;
+use futures::future::{FutureExt, TryFutureExt};
+use jsonrpc_core::ErrorCode;
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::chain::{ChainApi, GetBlockOption, GetEventOption, GetTransactionOption};
+use starcoin_rpc_api::state::{
+    GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption, StateApi,
+};
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::{
+    AccountStateSetView, BlockHeaderView, BlockInfoView, BlockNumberOrHash, BlockView, ChainId,
+    ChainInfoView, CodeView, ListCodeView, ListResourceView, ResourceView, StateWithProofView,
+    StrView, TransactionEventResponse, TransactionInfoView, TransactionInfoWithProofView,
+    TransactionView,
+};
+use starcoin_rpc_api::FutureResult;
+use starcoin_rpc_server::module::map_err;
+use starcoin_sync::light::LightClient;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::language_storage::{ModuleId, StructTag};
+use starcoin_types::startup_info::ChainInfo;
+use std::convert::TryInto;
+use std::sync::Arc;
+
+fn unsupported<T: Send + 'static>(method: &str) -> FutureResult<T> {
+    let err = jsonrpc_core::Error {
+        code: ErrorCode::MethodNotFound,
+        message: format!("{} is not supported by the light node", method),
+        data: None,
+    };
+    Box::pin(futures::future::err(err))
+}
+
+/// Resolve the state root by the verified headers, same as the full node.
+fn resolve_state_root(
+    client: &LightClient,
+    state_root: Option<HashValue>,
+    block: Option<BlockNumberOrHash>,
+) -> anyhow::Result<HashValue> {
+    let header = match (state_root, block) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("state_root and block can not be set at the same time")
+        }
+        (Some(state_root), None) => return Ok(state_root),
+        (None, None) => client.current_header(),
+        (None, Some(BlockNumberOrHash::Number(number))) => client
+            .get_header_by_number(number)?
+            .ok_or_else(|| format_err!("cannot find block by number {}", number))?,
+        (None, Some(BlockNumberOrHash::Hash(hash))) => client
+            .get_header(hash)?
+            .ok_or_else(|| format_err!("cannot find block by hash {}", hash))?,
+    };
+    Ok(header.state_root())
+}
+
+pub struct LightChainRpcImpl {
+    config: Arc<NodeConfig>,
+    genesis_hash: HashValue,
+    client: LightClient,
+}
+
+impl LightChainRpcImpl {
+    pub fn new(config: Arc<NodeConfig>, genesis_hash: HashValue, client: LightClient) -> Self {
+        Self {
+            config,
+            genesis_hash,
+            client,
+        }
+    }
+}
+
+impl ChainApi for LightChainRpcImpl {
+    fn id(&self) -> jsonrpc_core::Result<ChainId> {
+        Ok(self.config.net().id().into())
+    }
+
+    fn info(&self) -> FutureResult<ChainInfoView> {
+        let info = ChainInfo::new(
+            self.config.net().chain_id(),
+            self.genesis_hash,
+            self.client.status(),
+        );
+        Box::pin(futures::future::ok(info.into()))
+    }
+
+    fn get_block_by_hash(
+        &self,
+        hash: HashValue,
+        option: Option<GetBlockOption>,
+    ) -> FutureResult<Option<BlockView>> {
+        if option.unwrap_or_default().decode {
+            return unsupported("chain.get_block_by_hash with decode");
+        }
+        let client = self.client.clone();
+        let fut = async move {
+            let result = client.get_block(hash).await?;
+            let block: Option<BlockView> = result.map(|b| b.try_into()).transpose()?;
+            Ok(block)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_block_by_number(
+        &self,
+        number: u64,
+        option: Option<GetBlockOption>,
+    ) -> FutureResult<Option<BlockView>> {
+        if option.unwrap_or_default().decode {
+            return unsupported("chain.get_block_by_number with decode");
+        }
+        let client = self.client.clone();
+        let fut = async move {
+            let result = match client.get_header_by_number(number)? {
+                Some(header) => client.get_block(header.id()).await?,
+                None => None,
+            };
+            let block: Option<BlockView> = result.map(|b| b.try_into()).transpose()?;
+            Ok(block)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_blocks_by_number(
+        &self,
+        number: Option<BlockNumber>,
+        count: u64,
+    ) -> FutureResult<Vec<BlockView>> {
+        let client = self.client.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let end_block_number = match number {
+                Some(num) => num.min(client.current_header().number()),
+                None => client.current_header().number(),
+            };
+            let max_return_num = count
+                .min(end_block_number + 1)
+                .min(config.rpc.block_query_max_range());
+            let mut blocks = vec![];
+            for number in (end_block_number + 1 - max_return_num..=end_block_number).rev() {
+                let header = client
+                    .get_header_by_number(number)?
+                    .ok_or_else(|| format_err!("cannot find block by number {}", number))?;
+                let block = client
+                    .get_block(header.id())
+                    .await?
+                    .ok_or_else(|| format_err!("cannot find block by number {}", number))?;
+                blocks.push(BlockView::try_from_block(block, true)?);
+            }
+            Ok(blocks)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_block_info_by_number(&self, number: u64) -> FutureResult<Option<BlockInfoView>> {
+        let result = self
+            .client
+            .get_block_info_by_number(number)
+            .map(|info| info.map(Into::into))
+            .map_err(map_err);
+        Box::pin(futures::future::ready(result))
+    }
+
+    fn get_transaction(
+        &self,
+        _transaction_hash: HashValue,
+        _option: Option<GetTransactionOption>,
+    ) -> FutureResult<Option<TransactionView>> {
+        unsupported("chain.get_transaction")
+    }
+
+    fn get_transaction_info(
+        &self,
+        _transaction_hash: HashValue,
+    ) -> FutureResult<Option<TransactionInfoView>> {
+        unsupported("chain.get_transaction_info")
+    }
+
+    fn get_block_txn_infos(&self, block_hash: HashValue) -> FutureResult<Vec<TransactionInfoView>> {
+        let client = self.client.clone();
+        let fut = async move {
+            Ok(client
+                .get_block_txn_infos(block_hash)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<_>>())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_txn_info_by_block_and_index(
+        &self,
+        block_hash: HashValue,
+        idx: u64,
+    ) -> FutureResult<Option<TransactionInfoView>> {
+        let client = self.client.clone();
+        let fut = async move {
+            Ok(client
+                .get_block_txn_infos(block_hash)
+                .await?
+                .into_iter()
+                .nth(idx as usize)
+                .map(Into::into))
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_events_by_txn_hash(
+        &self,
+        _txn_hash: HashValue,
+        _option: Option<GetEventOption>,
+    ) -> FutureResult<Vec<TransactionEventResponse>> {
+        unsupported("chain.get_events_by_txn_hash")
+    }
+
+    fn get_events(
+        &self,
+        _filter: EventFilter,
+        _option: Option<GetEventOption>,
+    ) -> FutureResult<Vec<TransactionEventResponse>> {
+        unsupported("chain.get_events")
+    }
+
+    fn get_headers(&self, block_hashes: Vec<HashValue>) -> FutureResult<Vec<BlockHeaderView>> {
+        let result = block_hashes
+            .into_iter()
+            .filter_map(|id| self.client.get_header(id).transpose())
+            .map(|header| header.map(Into::into))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(map_err);
+        Box::pin(futures::future::ready(result))
+    }
+
+    fn get_transaction_infos(
+        &self,
+        _start_global_index: u64,
+        _reverse: bool,
+        _max_size: u64,
+    ) -> FutureResult<Vec<TransactionInfoView>> {
+        unsupported("chain.get_transaction_infos")
+    }
+
+    fn get_transaction_proof(
+        &self,
+        _block_hash: HashValue,
+        _transaction_global_index: u64,
+        _event_index: Option<u64>,
+        _access_path: Option<StrView<AccessPath>>,
+    ) -> FutureResult<Option<TransactionInfoWithProofView>> {
+        unsupported("chain.get_transaction_proof")
+    }
+}
+
+pub struct LightStateRpcImpl {
+    client: LightClient,
+}
+
+impl LightStateRpcImpl {
+    pub fn new(client: LightClient) -> Self {
+        Self { client }
+    }
+}
+
+impl StateApi for LightStateRpcImpl {
+    fn get(
+        &self,
+        access_path: AccessPath,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<Vec<u8>>> {
+        let client = self.client.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(&client, None, block)?;
+            client.get_state(state_root, access_path).await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<StateWithProofView> {
+        let client = self.client.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(&client, None, block)?;
+            let proof = client.get_state_with_proof(state_root, access_path).await?;
+            Ok(proof.into())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_account_state(
+        &self,
+        address: AccountAddress,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<AccountState>> {
+        let client = self.client.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(&client, None, block)?;
+            client.get_account_state(state_root, address).await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_account_state_set(
+        &self,
+        _address: AccountAddress,
+        _state_root: Option<HashValue>,
+        _block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<AccountStateSetView>> {
+        unsupported("state.get_account_state_set")
+    }
+
+    fn get_state_root(&self, block: Option<BlockNumberOrHash>) -> FutureResult<HashValue> {
+        let result = resolve_state_root(&self.client, None, block).map_err(map_err);
+        Box::pin(futures::future::ready(result))
+    }
+
+    fn get_with_proof_by_root(
+        &self,
+        access_path: AccessPath,
+        state_root: HashValue,
+    ) -> FutureResult<StateWithProofView> {
+        let client = self.client.clone();
+        let fut = async move {
+            let proof = client.get_state_with_proof(state_root, access_path).await?;
+            Ok(proof.into())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_code(
+        &self,
+        module_id: StrView<ModuleId>,
+        option: Option<GetCodeOption>,
+    ) -> FutureResult<Option<CodeView>> {
+        let option = option.unwrap_or_default();
+        if option.resolve {
+            return unsupported("state.get_code with resolve");
+        }
+        let client = self.client.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(&client, option.state_root, option.block)?;
+            let code = client
+                .get_state(state_root, AccessPath::from(&module_id.0))
+                .await?;
+            Ok(code.map(|c| CodeView {
+                code: StrView(c),
+                abi: None,
+            }))
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_resource(
+        &self,
+        addr: AccountAddress,
+        resource_type: StrView<StructTag>,
+        option: Option<GetResourceOption>,
+    ) -> FutureResult<Option<ResourceView>> {
+        let option = option.unwrap_or_default();
+        if option.decode {
+            return unsupported("state.get_resource with decode");
+        }
+        let client = self.client.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(&client, option.state_root, option.block)?;
+            let data = client
+                .get_state(
+                    state_root,
+                    AccessPath::resource_access_path(addr, resource_type.0),
+                )
+                .await?;
+            Ok(data.map(|d| ResourceView {
+                raw: StrView(d),
+                json: None,
+            }))
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn list_resource(
+        &self,
+        _addr: AccountAddress,
+        _option: Option<ListResourceOption>,
+    ) -> FutureResult<ListResourceView> {
+        unsupported("state.list_resource")
+    }
+
+    fn list_code(
+        &self,
+        _addr: AccountAddress,
+        _option: Option<ListCodeOption>,
+    ) -> FutureResult<ListCodeView> {
+        unsupported("state.list_code")
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::peer_message_handler::NodePeerMessageHandler;
+use crate::peer_message_handler::{LightPeerMessageHandler, NodePeerMessageHandler};
 use anyhow::{format_err, Result};
 use starcoin_block_relayer::BlockRelayer;
 use starcoin_config::NodeConfig;
@@ -42,3 +42,22 @@ impl ServiceFactory<NetworkActorService> for NetworkServiceFactory {
         Ok(actor_service)
     }
 }
+
+/// The light node does not serve the network rpc, and ignores the txns and blocks from peers.
+pub struct LightNetworkServiceFactory;
+
+impl ServiceFactory<NetworkActorService> for LightNetworkServiceFactory {
+    fn create(ctx: &mut ServiceContext<NetworkActorService>) -> Result<NetworkActorService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let chain_info = storage
+            .get_chain_info()?
+            .ok_or_else(|| format_err!("Can not get chain info."))?;
+        let actor_service =
+            NetworkActorService::new(config, chain_info, None, LightPeerMessageHandler)?;
+        let network_service = actor_service.network_service();
+        let network_async_service = NetworkServiceRef::new(network_service, ctx.self_ref());
+        ctx.put_shared(network_async_service)?;
+        Ok(actor_service)
+    }
+}
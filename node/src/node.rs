@@ -2,9 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::metrics::{MetricsPushActorService, MetricsServerActorService};
-use crate::network_service_factory::NetworkServiceFactory;
+use crate::network_service_factory::{LightNetworkServiceFactory, NetworkServiceFactory};
 use crate::peer_message_handler::NodePeerMessageHandler;
-use crate::rpc_service_factory::RpcServiceFactory;
+use crate::rpc_service_factory::{LightRpcServiceFactory, RpcServiceFactory};
 use crate::NodeHandle;
 use actix::prelude::*;
 use anyhow::{format_err, Result};
@@ -15,7 +15,7 @@ use network_api::{PeerProvider, PeerSelector, PeerStrategy};
 use starcoin_account_service::{AccountEventService, AccountService, AccountStorage};
 use starcoin_block_relayer::BlockRelayer;
 use starcoin_chain_notify::ChainNotifyHandlerService;
-use starcoin_chain_service::ChainReaderService;
+use starcoin_chain_service::{ChainReaderService, StatePrunerService};
 use starcoin_config::NodeConfig;
 use starcoin_executor::VMMetrics;
 use starcoin_genesis::{Genesis, GenesisError};
@@ -43,12 +43,14 @@ use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::errors::StorageInitError;
 use starcoin_storage::metrics::StorageMetrics;
+use starcoin_storage::state_node::StatePruneStore;
 use starcoin_storage::storage::StorageInstance;
 use starcoin_storage::{BlockStore, Storage};
 use starcoin_stratum::service::{StratumService, StratumServiceFactory};
 use starcoin_stratum::stratum::{Stratum, StratumFactory};
 use starcoin_sync::announcement::AnnouncementService;
 use starcoin_sync::block_connector::{BlockConnectorService, ExecuteRequest, ResetRequest};
+use starcoin_sync::light::LightSyncService;
 use starcoin_sync::sync::SyncService;
 use starcoin_sync::txn_sync::TxnSyncService;
 use starcoin_sync::verified_rpc_client::VerifiedRpcClient;
@@ -275,6 +277,14 @@ impl NodeService {
         if let Some(vm_metrics) = vm_metrics {
             registry.put_shared(vm_metrics).await?;
         }
+        let concurrency_level = config.executor.concurrency_level();
+        if concurrency_level > 1 {
+            info!(
+                "Enable parallel execution with concurrency level {}",
+                concurrency_level
+            );
+        }
+        starcoin_executor::set_execution_concurrency_level(concurrency_level);
         let bus = registry.service_ref::<BusService>().await?;
         let storage_metrics = config
             .metrics
@@ -297,6 +307,13 @@ impl NodeService {
         storage_instance.check_upgrade()?;
         let upgrade_time = SystemTime::now().duration_since(start_time)?;
         let storage = Arc::new(Storage::new(storage_instance)?);
+        if let Some(state_prune_window) = config.storage.state_prune_window() {
+            info!(
+                "Enable state pruning, keep the state of latest {} blocks",
+                state_prune_window
+            );
+            storage.enable_state_prune()?;
+        }
         registry.put_shared(storage.clone()).await?;
         let (chain_info, genesis) =
             Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
@@ -312,6 +329,11 @@ impl NodeService {
 
         let node_service = registry.register::<NodeService>().await?;
 
+        if config.sync.light() {
+            Self::init_light_system(&registry, config).await?;
+            return Ok((registry, node_service));
+        }
+
         registry.register::<ChainStateService>().await?;
 
         let vault_config = &config.vault;
@@ -334,6 +356,10 @@ impl NodeService {
 
         registry.register::<ChainNotifyHandlerService>().await?;
 
+        if config.storage.state_prune_window().is_some() {
+            registry.register::<StatePrunerService>().await?;
+        }
+
         registry.register::<BlockConnectorService>().await?;
         registry.register::<SyncService>().await?;
 
@@ -404,4 +430,29 @@ impl NodeService {
 
         Ok((registry, node_service))
     }
+
+    /// The light node only syncs the verified headers, and reads the state from peers,
+    /// so the txpool, chain, miner and related services are not started.
+    async fn init_light_system(
+        registry: &ServiceRef<RegistryService>,
+        config: Arc<NodeConfig>,
+    ) -> Result<()> {
+        info!("Start node in light mode.");
+        registry
+            .register_by_factory::<NetworkActorService, LightNetworkServiceFactory>()
+            .await?;
+        //wait Network service init
+        Delay::new(Duration::from_millis(200)).await;
+
+        registry.register::<LightSyncService>().await?;
+        registry
+            .register_by_factory::<RpcService, LightRpcServiceFactory>()
+            .await?;
+
+        // start metrics server
+        if !config.metrics.disable_metrics() {
+            registry.register::<MetricsServerActorService>().await?;
+        }
+        Ok(())
+    }
 }
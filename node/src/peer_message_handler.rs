@@ -84,3 +84,17 @@ impl PeerMessageHandler for NodePeerMessageHandler {
         }
     }
 }
+
+/// The light node does not keep a txpool or full blocks, the txns and blocks from peers are ignored.
+#[derive(Default)]
+pub struct LightPeerMessageHandler;
+
+impl PeerMessageHandler for LightPeerMessageHandler {
+    fn handle_message(&self, peer_message: PeerMessage) {
+        debug!(
+            "Light node ignore message from peer {}: {:?}",
+            peer_message.peer_id,
+            peer_message.notification.protocol_name()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::light_rpc::{LightChainRpcImpl, LightStateRpcImpl};
 use crate::node::NodeService;
 use anyhow::Result;
 use starcoin_account_service::AccountService;
@@ -18,9 +19,11 @@ use starcoin_rpc_server::module::{
     StateRpcImpl, SyncManagerRpcImpl, TxPoolRpcImpl,
 };
 use starcoin_rpc_server::service::RpcService;
-use starcoin_service_registry::{ServiceContext, ServiceFactory};
+use starcoin_service_registry::{ServiceContext, ServiceFactory, ServiceRef};
 use starcoin_state_service::ChainStateService;
 use starcoin_storage::Storage;
+use starcoin_stratum::stratum::Stratum;
+use starcoin_sync::light::LightClient;
 use starcoin_sync::sync::SyncService;
 use starcoin_txpool::TxPoolService;
 use std::sync::Arc;
@@ -56,9 +59,12 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         let txpool_service = ctx.get_shared::<TxPoolService>()?;
         let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
 
+        let chain_service = ctx.service_ref::<ChainReaderService>()?.clone();
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
-            .map(|service_ref| StateRpcImpl::new(service_ref.clone(), storage.clone()));
+            .map(|service_ref| {
+                StateRpcImpl::new(service_ref.clone(), chain_service.clone(), storage.clone())
+            });
         let chain_state_service = ctx.service_ref::<ChainStateService>()?.clone();
         let account_service = ctx.service_ref_opt::<AccountService>()?.cloned();
         let account_api = account_service.clone().map(|service_ref| {
@@ -71,23 +77,28 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         });
         let pubsub_service = ctx.service_ref::<PubSubService>()?.clone();
         let pubsub_api = Some(PubSubImpl::new(pubsub_service));
+        let vm_metrics = ctx.get_shared_opt::<VMMetrics>()?;
         let debug_api = Some(DebugRpcImpl::new(
             config.clone(),
             log_handler,
             ctx.bus_ref().clone(),
+            chain_service.clone(),
+            storage.clone(),
+            vm_metrics.clone(),
         ));
+        let stratum = ctx.service_ref_opt::<Stratum>()?.cloned();
         let miner_api = ctx
             .service_ref_opt::<MinerService>()?
-            .map(|service_ref| MinerRpcImpl::new(service_ref.clone()));
+            .map(|service_ref| MinerRpcImpl::new(service_ref.clone(), stratum));
 
         let contract_api = {
-            let vm_metrics = ctx.get_shared_opt::<VMMetrics>()?;
             let dev_playground = PlaygroudService::new(storage.clone(), vm_metrics);
             ContractRpcImpl::new(
                 config.clone(),
                 account_service,
                 txpool_service,
                 chain_state_service,
+                chain_service,
                 dev_playground,
                 storage,
             )
@@ -110,3 +121,53 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         ))
     }
 }
+
+/// The rpc service of the light node, only serves the node, network, chain and state api,
+/// the chain and state are read by the `LightClient`.
+pub struct LightRpcServiceFactory;
+
+impl ServiceFactory<RpcService> for LightRpcServiceFactory {
+    fn create(ctx: &mut ServiceContext<RpcService>) -> Result<RpcService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let genesis = ctx.get_shared::<Genesis>()?;
+        let client = ctx.get_shared::<LightClient>()?;
+        let network_service = ctx.get_shared::<NetworkServiceRef>()?;
+        let node_api = NodeRpcImpl::new(config.clone(), Some(network_service.clone()));
+        let node_manager_api = ctx
+            .service_ref_opt::<NodeService>()?
+            .map(|service_ref| NodeManagerRpcImpl::new(service_ref.clone()));
+        let network_manager_api = NetworkManagerRpcImpl::new(network_service);
+        let chain_api =
+            LightChainRpcImpl::new(config.clone(), genesis.block().id(), client.clone());
+        let state_api = LightStateRpcImpl::new(client);
+
+        Ok(RpcService::new_with_api(
+            config,
+            node_api,
+            node_manager_api,
+            None::<SyncManagerRpcImpl<ServiceRef<SyncService>>>,
+            Some(network_manager_api),
+            Some(chain_api),
+            None::<TxPoolRpcImpl<TxPoolService>>,
+            None::<
+                AccountRpcImpl<
+                    ServiceRef<AccountService>,
+                    TxPoolService,
+                    ServiceRef<ChainStateService>,
+                >,
+            >,
+            Some(state_api),
+            None::<PubSubImpl>,
+            None::<DebugRpcImpl<ServiceRef<ChainReaderService>>>,
+            None::<MinerRpcImpl>,
+            None::<
+                ContractRpcImpl<
+                    ServiceRef<AccountService>,
+                    TxPoolService,
+                    ServiceRef<ChainStateService>,
+                    ServiceRef<ChainReaderService>,
+                >,
+            >,
+        ))
+    }
+}
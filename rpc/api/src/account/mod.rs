@@ -6,7 +6,7 @@ use jsonrpc_derive::rpc;
 pub use self::gen_client::Client as AccountClient;
 use crate::types::{SignedMessageView, StrView, TransactionRequest};
 use crate::FutureResult;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, MnemonicAccountInfo};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
@@ -20,6 +20,26 @@ pub trait AccountApi {
     fn set_default_account(&self, addr: AccountAddress) -> FutureResult<AccountInfo>;
     #[rpc(name = "account.create")]
     fn create(&self, password: String) -> FutureResult<AccountInfo>;
+    /// Create the HD wallet with a new mnemonic, and the first account of the wallet.
+    /// The word count of the mnemonic is 24 by default.
+    #[rpc(name = "account.create_mnemonic")]
+    fn create_mnemonic(
+        &self,
+        password: String,
+        word_count: Option<u32>,
+    ) -> FutureResult<MnemonicAccountInfo>;
+    /// Recover the HD wallet from the mnemonic, and restore the first `account_count` accounts
+    /// of the wallet, `account_count` is 1 by default.
+    #[rpc(name = "account.import_mnemonic")]
+    fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: Option<u32>,
+    ) -> FutureResult<Vec<AccountInfo>>;
+    /// Derive the next account of the HD wallet, the password is the password of the wallet.
+    #[rpc(name = "account.derive")]
+    fn derive(&self, password: String) -> FutureResult<AccountInfo>;
     #[rpc(name = "account.list")]
     fn list(&self) -> FutureResult<Vec<AccountInfo>>;
     #[rpc(name = "account.get")]
@@ -42,6 +62,11 @@ pub trait AccountApi {
         signer: AccountAddress,
     ) -> FutureResult<SignedUserTransaction>;
 
+    /// Co-sign a multisig txn container with the unlocked accounts which hold keys of its signers,
+    /// the container is bcs or json bytes of `MultisigTransaction`, return the bcs bytes of the signed container.
+    #[rpc(name = "account.sign_multisig_txn")]
+    fn sign_multisig_txn(&self, txn: StrView<Vec<u8>>) -> FutureResult<StrView<Vec<u8>>>;
+
     /// unlock account for duration in seconds, default to u32::max.
     #[rpc(name = "account.unlock")]
     fn unlock(
@@ -1,7 +1,8 @@
 pub use self::gen_client::Client as ContractClient;
 use crate::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
+    AnnotatedMoveStructView, AnnotatedMoveValueView, BlockNumberOrHash, ContractCall,
+    DryRunOutputView, DryRunTransactionRequest, FunctionIdView, GasEstimateView, ModuleIdView,
+    StrView, StructTagView,
 };
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
@@ -11,11 +12,16 @@ use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
 
+/// All the methods run on the state of the optional `block`, default is the latest block.
 #[rpc(client, server, schema)]
 pub trait ContractApi {
     /// get code of module
     #[rpc(name = "contract.get_code")]
-    fn get_code(&self, module_id: StrView<ModuleId>) -> FutureResult<Option<StrView<Vec<u8>>>>;
+    fn get_code(
+        &self,
+        module_id: StrView<ModuleId>,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<StrView<Vec<u8>>>>;
 
     /// get resource data of `addr`
     #[rpc(name = "contract.get_resource")]
@@ -23,18 +29,34 @@ pub trait ContractApi {
         &self,
         addr: AccountAddress,
         resource_type: StrView<StructTag>,
+        block: Option<BlockNumberOrHash>,
     ) -> FutureResult<Option<AnnotatedMoveStructView>>;
 
     /// Call a move contract, return returned move values.
     #[rpc(name = "contract.call")]
-    fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>>;
+    fn call(
+        &self,
+        call: ContractCall,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Vec<AnnotatedMoveValueView>>;
 
     /// Call a move contract, return move values.
     #[rpc(name = "contract.call_v2")]
-    fn call_v2(&self, call: ContractCall) -> FutureResult<Vec<DecodedMoveValue>>;
+    fn call_v2(
+        &self,
+        call: ContractCall,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Vec<DecodedMoveValue>>;
 
+    /// Dry run the transaction, return the execution trace too if `trace` is true.
+    /// The nested calls are only traced by a node built with debug assertions, see `ExecutionTraceView`.
     #[rpc(name = "contract.dry_run")]
-    fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<DryRunOutputView>;
+    fn dry_run(
+        &self,
+        txn: DryRunTransactionRequest,
+        block: Option<BlockNumberOrHash>,
+        trace: Option<bool>,
+    ) -> FutureResult<DryRunOutputView>;
 
     /// Dry run RawUserTransaction, the raw_txn parameter is RawUserTransaction's hex
     #[rpc(name = "contract.dry_run_raw")]
@@ -42,14 +64,47 @@ pub trait ContractApi {
         &self,
         raw_txn: String,
         sender_public_key: StrView<AccountPublicKey>,
+        block: Option<BlockNumberOrHash>,
+        trace: Option<bool>,
     ) -> FutureResult<DryRunOutputView>;
 
+    /// Estimate the smallest max gas amount of the transaction by dry run,
+    /// the gas unit price suggested by the txpool is used if the request does not set it.
+    #[rpc(name = "contract.estimate_gas")]
+    fn estimate_gas(
+        &self,
+        txn: DryRunTransactionRequest,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<GasEstimateView>;
+
+    /// Estimate gas of RawUserTransaction, the raw_txn parameter is RawUserTransaction's hex,
+    /// the estimation uses the raw txn's own gas unit price.
+    #[rpc(name = "contract.estimate_gas_raw")]
+    fn estimate_gas_raw(
+        &self,
+        raw_txn: String,
+        sender_public_key: StrView<AccountPublicKey>,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<GasEstimateView>;
+
     #[rpc(name = "contract.resolve_function")]
-    fn resolve_function(&self, function_id: FunctionIdView) -> FutureResult<FunctionABI>;
+    fn resolve_function(
+        &self,
+        function_id: FunctionIdView,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<FunctionABI>;
     #[rpc(name = "contract.resolve_struct")]
-    fn resolve_struct(&self, struct_tag: StructTagView) -> FutureResult<StructInstantiation>;
+    fn resolve_struct(
+        &self,
+        struct_tag: StructTagView,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<StructInstantiation>;
     #[rpc(name = "contract.resolve_module")]
-    fn resolve_module(&self, module_id: ModuleIdView) -> FutureResult<ModuleABI>;
+    fn resolve_module(
+        &self,
+        module_id: ModuleIdView,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<ModuleABI>;
 }
 #[test]
 fn test() {
@@ -3,10 +3,16 @@
 
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use starcoin_crypto::HashValue;
 use starcoin_logger::LogPattern;
 
 pub use self::gen_client::Client as DebugClient;
-use crate::types::FactoryAction;
+use crate::types::{
+    BlockNumberOrHash, ContractCall, ContractCallProfileView, FactoryAction, StrView,
+    TransactionProfileView, TransactionTraceView,
+};
+use crate::FutureResult;
+use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
 #[rpc(client, server, schema)]
 pub trait DebugApi {
     /// Update log level, if logger_name is none, update global log level.
@@ -28,6 +34,40 @@ pub trait DebugApi {
     /// Get and set txn factory status.
     #[rpc(name = "txfactory.status")]
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool>;
+
+    /// Replay the committed user transaction on the parent state of its block,
+    /// and return the execution trace. The nested calls are only traced by a node built with debug
+    /// assertions, see `ExecutionTraceView`.
+    #[rpc(name = "debug.trace_transaction")]
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionTraceView>;
+
+    /// Replay the committed user transaction like `debug.trace_transaction`,
+    /// and return the gas and time profile.
+    /// A node built in release mode only returns the call level profile, the gas of each call into Move.
+    #[rpc(name = "debug.profile_transaction")]
+    fn profile_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionProfileView>;
+
+    /// Dry run RawUserTransaction on the state of the optional `block`, default is the latest block,
+    /// and return the gas and time profile. The raw_txn parameter is RawUserTransaction's hex.
+    /// A node built in release mode only returns the call level profile, the gas of each call into Move.
+    #[rpc(name = "debug.profile_dry_run_raw")]
+    fn profile_dry_run_raw(
+        &self,
+        raw_txn: String,
+        sender_public_key: StrView<AccountPublicKey>,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<TransactionProfileView>;
+
+    /// Call a move function on the state of the optional `block`, default is the latest block,
+    /// and return the gas and time profile.
+    /// Different from `contract.call_v2`, the gas is charged by the on-chain gas schedule.
+    /// A node built in release mode only returns the call level profile, the gas of each call into Move.
+    #[rpc(name = "debug.profile_call")]
+    fn profile_call(
+        &self,
+        call: ContractCall,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<ContractCallProfileView>;
 }
 #[test]
 fn test() {
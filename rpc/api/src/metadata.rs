@@ -2,8 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use jsonrpc_pubsub::{PubSubMetadata, Session};
+use parking_lot::RwLock;
 use std::sync::Arc;
 
+/// The method to authenticate the session by an api key or jwt token, the params is `[credential]`.
+pub const RPC_AUTHENTICATE_METHOD: &str = "rpc.authenticate";
+
 /// RPC methods metadata.
 #[derive(Clone, Default, Debug)]
 pub struct Metadata {
@@ -12,6 +16,9 @@ pub struct Metadata {
     /// Request PubSub Session
     pub session: Option<Arc<Session>>,
     pub user: Option<String>,
+    /// The api key or jwt token of the request, read from the http `Authorization` header,
+    /// or set by the `rpc.authenticate` call, it is shared by all the requests of the session.
+    pub credential: Arc<RwLock<Option<String>>>,
 }
 
 impl Metadata {
@@ -19,6 +26,7 @@ impl Metadata {
         Self {
             session: Some(session),
             user: None,
+            credential: Default::default(),
         }
     }
 }
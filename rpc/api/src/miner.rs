@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as MinerClient;
-use crate::types::MintedBlockView;
+use crate::types::{MinerHashRateView, MintedBlockView, StratumWorkerView};
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
 use starcoin_types::system_events::MintBlockEvent;
@@ -20,6 +20,15 @@ pub trait MinerApi {
     /// get current mining job
     #[rpc(name = "mining.get_job")]
     fn get_job(&self) -> FutureResult<Option<MintBlockEvent>>;
+    /// report the hash rate of a miner client worker, in hashes per second
+    #[rpc(name = "mining.submit_hashrate")]
+    fn submit_hashrate(&self, worker: String, hash_rate: u64) -> FutureResult<()>;
+    /// get the hash rates reported by active miner client workers
+    #[rpc(name = "mining.get_hashrate")]
+    fn get_hashrate(&self) -> FutureResult<MinerHashRateView>;
+    /// get the share statistics of stratum workers
+    #[rpc(name = "mining.get_stratum_workers")]
+    fn get_stratum_workers(&self) -> FutureResult<Vec<StratumWorkerView>>;
 }
 
 #[test]
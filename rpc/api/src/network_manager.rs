@@ -6,6 +6,7 @@ use crate::types::StrView;
 use crate::FutureResult;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use network_api::messages::BannedPeerInfo;
 use network_p2p_types::network_state::NetworkState;
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use std::borrow::Cow;
@@ -40,6 +41,22 @@ pub trait NetworkManagerApi {
     /// ban peer
     #[rpc(name = "network_manager.ban_peer")]
     fn ban_peer(&self, peer_id: String, ban: bool) -> Result<()>;
+
+    /// List the manual bans, the bans are persisted and kept after restart.
+    #[rpc(name = "network_manager.list_bans")]
+    fn list_bans(&self) -> FutureResult<Vec<BannedPeerInfo>>;
+
+    /// Remove the manual ban of peer.
+    #[rpc(name = "network_manager.unban")]
+    fn unban(&self, peer_id: String) -> Result<()>;
+
+    /// Add a reserved peer, reserved peers are always connected. The peer format is multiaddr/p2p/peer_id.
+    #[rpc(name = "network_manager.add_reserved")]
+    fn add_reserved(&self, peer: String) -> Result<()>;
+
+    /// Remove a reserved peer.
+    #[rpc(name = "network_manager.remove_reserved")]
+    fn remove_reserved(&self, peer_id: String) -> Result<()>;
 }
 
 #[test]
@@ -3,7 +3,7 @@
 
 pub use self::gen_client::Client as StateClient;
 use crate::types::{
-    AccountStateSetView, CodeView, ListCodeView, ListResourceView, ResourceView,
+    AccountStateSetView, BlockNumberOrHash, CodeView, ListCodeView, ListResourceView, ResourceView,
     StateWithProofView, StrView,
 };
 use crate::FutureResult;
@@ -18,24 +18,40 @@ use starcoin_types::{
 };
 #[rpc(client, server, schema)]
 pub trait StateApi {
+    /// get state at `access_path`, on the state of `block`, default is the latest block.
     #[rpc(name = "state.get")]
-    fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>>;
+    fn get(
+        &self,
+        access_path: AccessPath,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<Vec<u8>>>;
 
     #[rpc(name = "state.get_with_proof")]
-    fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProofView>;
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<StateWithProofView>;
 
     #[rpc(name = "state.get_account_state")]
-    fn get_account_state(&self, address: AccountAddress) -> FutureResult<Option<AccountState>>;
+    fn get_account_state(
+        &self,
+        address: AccountAddress,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<AccountState>>;
 
+    /// get account state set on `state_root` or the state of `block`, only one of them can be set.
     #[rpc(name = "state.get_account_state_set")]
     fn get_account_state_set(
         &self,
         address: AccountAddress,
         state_root: Option<HashValue>,
+        block: Option<BlockNumberOrHash>,
     ) -> FutureResult<Option<AccountStateSetView>>;
 
+    /// get the state root of `block`, default is the latest block.
     #[rpc(name = "state.get_state_root")]
-    fn get_state_root(&self) -> FutureResult<HashValue>;
+    fn get_state_root(&self, block: Option<BlockNumberOrHash>) -> FutureResult<HashValue>;
 
     #[rpc(name = "state.get_with_proof_by_root")]
     fn get_with_proof_by_root(
@@ -83,6 +99,8 @@ pub trait StateApi {
 pub struct GetResourceOption {
     pub decode: bool,
     pub state_root: Option<HashValue>,
+    /// Get the resource on the state of the block, can not be used with state_root.
+    pub block: Option<BlockNumberOrHash>,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
@@ -90,6 +108,8 @@ pub struct GetResourceOption {
 pub struct GetCodeOption {
     pub resolve: bool,
     pub state_root: Option<HashValue>,
+    /// Get the code on the state of the block, can not be used with state_root.
+    pub block: Option<BlockNumberOrHash>,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
@@ -98,6 +118,8 @@ pub struct ListResourceOption {
     pub decode: bool,
     /// The state tree root, default is the latest block state root
     pub state_root: Option<HashValue>,
+    /// List the resources on the state of the block, can not be used with state_root.
+    pub block: Option<BlockNumberOrHash>,
     //TODO support filter by type and pagination
 }
 
@@ -107,6 +129,8 @@ pub struct ListCodeOption {
     pub resolve: bool,
     /// The state tree root, default is the latest block state root
     pub state_root: Option<HashValue>,
+    /// List the codes on the state of the block, can not be used with state_root.
+    pub block: Option<BlockNumberOrHash>,
     //TODO support filter by type and pagination
 }
 #[test]
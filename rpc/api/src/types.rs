@@ -61,6 +61,43 @@ pub struct MintedBlockView {
     pub block_hash: HashValue,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct MinerHashRateView {
+    /// Sum of the hash rates of all active workers, in hashes per second.
+    pub total: StrView<u64>,
+    pub workers: Vec<WorkerHashRateView>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct WorkerHashRateView {
+    pub worker: String,
+    pub hash_rate: StrView<u64>,
+    /// Seconds elapsed since the worker last reported.
+    pub last_report_secs: StrView<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct StratumWorkerView {
+    pub login: String,
+    #[schemars(with = "String")]
+    pub share_difficulty: U256,
+    pub valid_shares: StrView<u64>,
+    pub stale_shares: StrView<u64>,
+    pub invalid_shares: StrView<u64>,
+    /// Hash rate estimated from the valid shares, in hashes per second.
+    pub hash_rate: StrView<u64>,
+    /// Seconds elapsed since the last share of the worker.
+    pub last_share_secs: Option<StrView<u64>>,
+}
+
+/// Identify a block by the block number of the main chain, or by the block hash.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum BlockNumberOrHash {
+    Number(BlockNumber),
+    Hash(HashValue),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ResourceView {
     pub raw: StrView<Vec<u8>>,
@@ -449,6 +486,27 @@ impl From<BlockHeader> for BlockHeaderView {
     }
 }
 
+impl From<BlockHeaderView> for BlockHeader {
+    fn from(header_view: BlockHeaderView) -> Self {
+        BlockHeader::new_with_auth_key(
+            header_view.parent_hash,
+            header_view.timestamp.0,
+            header_view.number.0,
+            header_view.author,
+            header_view.author_auth_key,
+            header_view.txn_accumulator_root,
+            header_view.block_accumulator_root,
+            header_view.state_root,
+            header_view.gas_used.0,
+            header_view.difficulty,
+            header_view.body_hash,
+            genesis_config::ChainId::new(header_view.chain_id),
+            header_view.nonce,
+            header_view.extra,
+        )
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct RawUserTransactionView {
     /// Sender's address.
@@ -965,6 +1023,9 @@ pub struct TransactionEventResponse {
     pub decode_event_data: Option<DecodedMoveValue>,
     #[serde(flatten)]
     pub event: TransactionEventView,
+    /// The event is removed from the main chain by a chain reorg, only set by the events subscription.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
@@ -1060,6 +1121,158 @@ pub struct DryRunOutputView {
     pub explained_status: VmStatusExplainView,
     #[serde(flatten)]
     pub txn_output: TransactionOutputView,
+    /// The execution trace, only returned when dry run with trace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<ExecutionTraceView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionTraceView {
+    pub transaction_hash: HashValue,
+    pub block_hash: HashValue,
+    pub explained_status: VmStatusExplainView,
+    #[serde(flatten)]
+    pub txn_output: TransactionOutputView,
+    pub trace: ExecutionTraceView,
+}
+
+/// The execution trace of a user transaction.
+/// The top level calls the vm makes for the transaction are traced in execution order. They are the
+/// prologue, then the script, script function or init script, then the epilogue. If the prologue
+/// fails, there is no other call; if the script fails, the epilogue still runs to charge the gas.
+/// The Move functions called inside a top level call are its children, they are only traced if the
+/// node is built with debug assertions and writes the Move VM instruction trace. A node built in
+/// release mode returns the top level calls without children.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExecutionTraceView {
+    /// The top level calls in execution order.
+    pub calls: Vec<CallTraceView>,
+    /// The state read outside the calls, such as the on-chain configs loaded by the vm.
+    pub reads: Vec<StateReadView>,
+    /// The state written by the transaction.
+    pub writes: Vec<StateWriteView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CallTraceView {
+    /// One of prologue, script, script_function, init_script and epilogue for the top level
+    /// calls, call for the nested calls.
+    pub kind: String,
+    /// None for script.
+    pub function: Option<FunctionIdView>,
+    /// The type arguments, empty for the nested calls.
+    pub ty_args: Vec<TypeTagView>,
+    /// The arguments decoded by the function abi, or the hex of the bcs bytes if can not decode.
+    /// Empty for the nested calls.
+    pub args: Vec<DecodedMoveValue>,
+    /// The return values decoded like the arguments, none if the call failed or is a nested call.
+    pub return_values: Option<Vec<DecodedMoveValue>>,
+    /// Gas used by the call, including the functions it calls.
+    /// The gas of a nested call is apportioned from the top level call by the instruction costs.
+    pub gas_used: StrView<u64>,
+    /// The state first read by the call or the functions it calls,
+    /// the state read by previous calls is not included. Empty for the nested calls.
+    pub reads: Vec<StateReadView>,
+    /// The state written by the transaction for the resources the call or the functions it calls
+    /// move or mutably borrow, empty if the nested calls are not traced.
+    pub writes: Vec<StateWriteView>,
+    /// The error if the call failed, a nested call fails with the error of the top level call.
+    pub error: Option<VmStatusExplainView>,
+    /// The Move functions called by the call in execution order.
+    pub children: Vec<CallTraceView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StateReadView {
+    pub access_path: AccessPath,
+    pub exists: bool,
+    /// The annotated resource, none for code or the resource can not be annotated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<AnnotatedMoveStructView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StateWriteView {
+    pub access_path: AccessPath,
+    pub action: WriteOpView,
+    /// The annotated resource, none for code, deletion or the resource can not be annotated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<AnnotatedMoveStructView>,
+}
+
+/// The gas and time profile of a transaction or a contract call.
+/// Only the gas and the wall time of each call the vm makes into Move are measured, the Move VM
+/// has no hook to meter the gas of each function or bytecode offset. A node built in release mode
+/// returns the call level profile only. If the node is built with debug assertions and writes the
+/// Move VM instruction trace, the measured gas and time are apportioned to the bytecode offsets by
+/// the instruction costs, as estimates.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExecutionProfileView {
+    /// Whether the samples are at the bytecode level, or only at the call level.
+    /// The gas and the time of the bytecode level samples and of the modules are estimates.
+    pub bytecode_level: bool,
+    pub gas: StrView<u64>,
+    /// The wall time in nanoseconds.
+    pub time: StrView<u64>,
+    pub samples: Vec<ProfileSampleView>,
+    /// The gas and time spent in each module, sorted by the gas descending.
+    pub modules: Vec<ModuleProfileView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProfileSampleView {
+    /// The call stack, outermost first. The first frame is the kind of the call the vm makes,
+    /// one of prologue, script, script_function, init_script, epilogue and function.
+    pub stack: Vec<String>,
+    /// The bytecode offset in the innermost function, none for the call level samples.
+    pub offset: Option<u16>,
+    pub instruction: Option<String>,
+    /// The times the offset is executed.
+    pub count: StrView<u64>,
+    /// The gas, estimated if the sample is at the bytecode level.
+    pub gas: StrView<u64>,
+    /// The wall time in nanoseconds, estimated if the sample is at the bytecode level.
+    pub time: StrView<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ModuleProfileView {
+    pub module: String,
+    /// The number of distinct functions executed.
+    pub functions: u64,
+    /// The instructions executed, zero if the profile is at the call level.
+    pub instructions: StrView<u64>,
+    pub gas: StrView<u64>,
+    /// The wall time in nanoseconds.
+    pub time: StrView<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionProfileView {
+    pub explained_status: VmStatusExplainView,
+    #[serde(flatten)]
+    pub txn_output: TransactionOutputView,
+    pub profile: ExecutionProfileView,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ContractCallProfileView {
+    pub return_values: Vec<DecodedMoveValue>,
+    pub profile: ExecutionProfileView,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GasEstimateView {
+    /// The smallest max gas amount the transaction can be executed with,
+    /// none if the transaction can not be executed.
+    pub max_gas_amount: Option<StrView<u64>>,
+    /// Gas used by the transaction when executed with the estimated max gas amount.
+    pub gas_used: StrView<u64>,
+    /// Gas unit price the transaction is estimated with.
+    pub gas_unit_price: StrView<u64>,
+    /// Gas unit price suggested by the txpool, a percentile of the pending txns' gas prices.
+    pub suggested_gas_unit_price: StrView<u64>,
+    pub explained_status: VmStatusExplainView,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -1632,6 +1845,17 @@ impl From<AccumulatorInfo> for AccumulatorInfoView {
     }
 }
 
+impl From<AccumulatorInfoView> for AccumulatorInfo {
+    fn from(info_view: AccumulatorInfoView) -> Self {
+        AccumulatorInfo::new(
+            info_view.accumulator_root,
+            info_view.frozen_subtree_roots,
+            info_view.num_leaves.0,
+            info_view.num_nodes.0,
+        )
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct BlockInfoView {
     /// Block hash
@@ -1656,6 +1880,17 @@ impl From<BlockInfo> for BlockInfoView {
     }
 }
 
+impl From<BlockInfoView> for BlockInfo {
+    fn from(block_info_view: BlockInfoView) -> Self {
+        BlockInfo::new(
+            block_info_view.block_hash,
+            block_info_view.total_difficulty,
+            block_info_view.txn_accumulator_info.into(),
+            block_info_view.block_accumulator_info.into(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::{ByteCodeOrScriptFunction, FunctionId};
@@ -30,6 +30,8 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Chain reorganization subscription.
+    ChainReorg,
 }
 
 /// Subscription result.
@@ -41,6 +43,7 @@ pub enum Result {
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventResponse>),
     MintBlock(Box<MintBlockEvent>),
+    ChainReorg(Box<ChainReorg>),
 }
 
 impl Serialize for Result {
@@ -53,10 +56,25 @@ impl Serialize for Result {
             Result::Event(ref evt) => evt.serialize(serializer),
             Result::TransactionHash(ref hash) => hash.serialize(serializer),
             Result::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Result::ChainReorg(ref reorg) => reorg.serialize(serializer),
         }
     }
 }
 
+/// The main chain switched to another branch.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainReorg {
+    /// The common ancestor of the old and the new main chain.
+    pub common_ancestor: HashValue,
+    /// Ids of the blocks removed from the main chain, in ascending order of block number.
+    pub retracted: Vec<HashValue>,
+    /// Ids of the blocks added to the main chain, in ascending order of block number.
+    pub enacted: Vec<HashValue>,
+    /// Whether `retracted` or `enacted` only contains the blocks near the heads.
+    pub truncated: bool,
+}
+
 /// Subscription kind.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
 pub enum Params {
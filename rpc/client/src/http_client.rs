@@ -0,0 +1,125 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+//! The http transport of jsonrpc-client-transports can not set the request headers,
+//! so the http connection which sends the credential by the header
+//! `Authorization: Bearer {api key or jwt token}` is implemented here.
+
+use futures::channel::mpsc;
+use futures::StreamExt;
+use hyper::client::HttpConnector;
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
+use hyper::{Body, Client, Request, Uri};
+use hyper_tls::HttpsConnector;
+use jsonrpc_client_transports::{RpcChannel, RpcError, RpcMessage};
+use jsonrpc_core::{Call, Id, MethodCall, Notification, Output, Response, Version};
+use serde_json::Value;
+use starcoin_logger::prelude::*;
+
+/// Max parallel requests of a http connection.
+const MAX_PARALLEL: usize = 8;
+
+type HttpClient = Client<HttpsConnector<HttpConnector>>;
+
+/// Connect to the http `url`, must be called in the tokio runtime.
+pub(crate) fn connect(url: &str, credential: Option<String>) -> Result<RpcChannel, RpcError> {
+    let uri: Uri = url.parse().map_err(|e| RpcError::Other(Box::new(e)))?;
+    let client: HttpClient = Client::builder().build(HttpsConnector::new());
+    let (sender, receiver) = mpsc::unbounded::<RpcMessage>();
+    let mut next_id = 0u64;
+    let worker = receiver.for_each_concurrent(MAX_PARALLEL, move |msg| {
+        next_id += 1;
+        handle_message(
+            client.clone(),
+            uri.clone(),
+            credential.clone(),
+            msg,
+            next_id,
+        )
+    });
+    tokio::spawn(worker);
+    Ok(sender.into())
+}
+
+async fn handle_message(
+    client: HttpClient,
+    uri: Uri,
+    credential: Option<String>,
+    msg: RpcMessage,
+    id: u64,
+) {
+    match msg {
+        RpcMessage::Call(call) => {
+            let request = Call::MethodCall(MethodCall {
+                jsonrpc: Some(Version::V2),
+                method: call.method,
+                params: call.params,
+                id: Id::Num(id),
+            });
+            let result = match post(&client, uri, credential.as_deref(), &request).await {
+                Ok(body) => parse_output(body.as_slice()),
+                Err(e) => Err(e),
+            };
+            // the caller may be dropped.
+            let _ = call.sender.send(result);
+        }
+        RpcMessage::Notify(notify) => {
+            let request = Call::Notification(Notification {
+                jsonrpc: Some(Version::V2),
+                method: notify.method,
+                params: notify.params,
+            });
+            if let Err(e) = post(&client, uri, credential.as_deref(), &request).await {
+                debug!("Send notification by http failed: {}", e);
+            }
+        }
+        RpcMessage::Subscribe(subscribe) => {
+            let _ = subscribe.sender.unbounded_send(Err(RpcError::Client(
+                "Subscription is not supported by the http connection".to_string(),
+            )));
+        }
+    }
+}
+
+async fn post(
+    client: &HttpClient,
+    uri: Uri,
+    credential: Option<&str>,
+    request: &Call,
+) -> Result<Vec<u8>, RpcError> {
+    let body = serde_json::to_vec(request).map_err(|e| RpcError::Other(Box::new(e)))?;
+    let mut builder = Request::post(uri).header(CONTENT_TYPE, "application/json");
+    if let Some(credential) = credential {
+        builder = builder.header(AUTHORIZATION, format!("Bearer {}", credential));
+    }
+    let request = builder
+        .body(Body::from(body))
+        .map_err(|e| RpcError::Other(Box::new(e)))?;
+    let response = client
+        .request(request)
+        .await
+        .map_err(|e| RpcError::Other(Box::new(e)))?;
+    if !response.status().is_success() {
+        return Err(RpcError::Client(format!(
+            "Unexpected response status code: {}",
+            response.status()
+        )));
+    }
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|e| RpcError::Other(Box::new(e)))?;
+    Ok(body.to_vec())
+}
+
+fn parse_output(body: &[u8]) -> Result<Value, RpcError> {
+    let response: Response = serde_json::from_slice(body).map_err(|e| {
+        RpcError::ParseError(String::from_utf8_lossy(body).into_owned(), Box::new(e))
+    })?;
+    match response {
+        Response::Single(Output::Success(success)) => Ok(success.result),
+        Response::Single(Output::Failure(failure)) => Err(RpcError::JsonRpcError(failure.error)),
+        Response::Batch(_) => Err(RpcError::Client(
+            "Unexpected batch response of a single request".to_string(),
+        )),
+    }
+}
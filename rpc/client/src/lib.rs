@@ -12,35 +12,38 @@ use futures::{TryStream, TryStreamExt};
 use jsonrpc_client_transports::RawClient;
 pub use jsonrpc_core::Params;
 use jsonrpc_core_client::{transports::ipc, transports::ws, RpcChannel};
+use network_api::messages::BannedPeerInfo;
 use network_api::PeerStrategy;
 use network_p2p_types::network_state::NetworkState;
 use parking_lot::Mutex;
 use serde_json::Value;
 use starcoin_abi_types::{FunctionABI, ModuleABI, StructInstantiation};
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, MnemonicAccountInfo, MultisigTransaction};
 use starcoin_crypto::HashValue;
 use starcoin_logger::{prelude::*, LogPattern};
 use starcoin_rpc_api::chain::{GetBlockOption, GetEventOption, GetTransactionOption};
+use starcoin_rpc_api::metadata::RPC_AUTHENTICATE_METHOD;
 use starcoin_rpc_api::node::NodeInfo;
 use starcoin_rpc_api::service::RpcAsyncService;
 use starcoin_rpc_api::state::{
     GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
 };
-use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::pubsub::{ChainReorg, EventFilter};
 use starcoin_rpc_api::types::{
     AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockInfoView, BlockView,
-    ChainId, ChainInfoView, CodeView, ContractCall, DecodedMoveValue, DryRunOutputView,
-    DryRunTransactionRequest, FactoryAction, FunctionIdView, ListCodeView, ListResourceView,
-    MintedBlockView, ModuleIdView, PeerInfoView, ResourceView, SignedMessageView,
-    SignedUserTransactionView, StateWithProofView, StrView, StructTagView,
-    TransactionEventResponse, TransactionInfoView, TransactionInfoWithProofView,
-    TransactionRequest, TransactionView,
+    ChainId, ChainInfoView, CodeView, ContractCall, ContractCallProfileView, DecodedMoveValue,
+    DryRunOutputView, DryRunTransactionRequest, FactoryAction, FunctionIdView, GasEstimateView,
+    ListCodeView, ListResourceView, MinerHashRateView, MintedBlockView, ModuleIdView, PeerInfoView,
+    ResourceView, SignedMessageView, SignedUserTransactionView, StateWithProofView, StrView,
+    StratumWorkerView, StructTagView, TransactionEventResponse, TransactionInfoView,
+    TransactionInfoWithProofView, TransactionProfileView, TransactionRequest, TransactionTraceView,
+    TransactionView,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
     miner::MinerClient, network_manager::NetworkManagerClient, node::NodeClient,
     node_manager::NodeManagerClient, state::StateClient, sync_manager::SyncManagerClient,
-    txpool::TxPoolClient, types::TransactionEventView,
+    txpool::TxPoolClient,
 };
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
 use starcoin_sync_api::{PeerScoreResponse, SyncProgressReport};
@@ -64,13 +67,17 @@ use std::time::Duration;
 use tokio::runtime::Runtime;
 
 pub mod chain_watcher;
+mod http_client;
 mod pubsub_client;
 mod remote_state_reader;
 
 #[derive(Clone)]
 enum ConnSource {
     Ipc(PathBuf),
-    WebSocket(String),
+    /// Websocket url and the optional api key or jwt token to authenticate the connection.
+    WebSocket(String, Option<String>),
+    /// Http url and the optional api key or jwt token sent by the header `Authorization: Bearer`.
+    Http(String, Option<String>),
     Local(Box<RpcChannel>),
 }
 
@@ -78,7 +85,8 @@ impl std::fmt::Debug for ConnSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConnSource::Ipc(path) => write!(f, "Ipc({})", path.as_path().to_string_lossy()),
-            ConnSource::WebSocket(url) => write!(f, "WebSocket({})", url),
+            ConnSource::WebSocket(url, _) => write!(f, "WebSocket({})", url),
+            ConnSource::Http(url, _) => write!(f, "Http({})", url),
             ConnSource::Local(_) => write!(f, "Local"),
         }
     }
@@ -114,6 +122,10 @@ impl ConnectionProvider {
         self.runtime.lock().block_on(future)
     }
 
+    fn is_subscription_supported(&self) -> bool {
+        !matches!(self.conn_source, ConnSource::Http(..))
+    }
+
     fn get_rpc_channel(&self) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
         self.block_on(async { self.get_rpc_channel_async().await })
     }
@@ -123,7 +135,19 @@ impl ConnectionProvider {
     ) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
         match self.conn_source.clone() {
             ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
-            ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
+            ConnSource::WebSocket(url, credential) => {
+                let channel: RpcChannel = ws::try_connect(url.as_str())?.await?;
+                if let Some(credential) = credential {
+                    RawClient::from(channel.clone())
+                        .call_method(
+                            RPC_AUTHENTICATE_METHOD,
+                            Params::Array(vec![Value::String(credential)]),
+                        )
+                        .await?;
+                }
+                Ok(channel)
+            }
+            ConnSource::Http(url, credential) => http_client::connect(url.as_str(), credential),
             ConnSource::Local(channel) => Ok(*channel),
         }
     }
@@ -156,9 +180,11 @@ impl RpcClient {
             });
         });
         let watcher = futures::executor::block_on(rx).expect("Init chain watcher fail.");
-        watcher.do_send(StartSubscribe {
-            client: pubsub_client,
-        });
+        if provider.is_subscription_supported() {
+            watcher.do_send(StartSubscribe {
+                client: pubsub_client,
+            });
+        }
         Ok(Self {
             inner: Mutex::new(Some(inner)),
             provider,
@@ -169,7 +195,24 @@ impl RpcClient {
     }
 
     pub fn connect_websocket(url: &str) -> anyhow::Result<Self> {
-        Self::new(ConnSource::WebSocket(url.to_string()))
+        Self::new(ConnSource::WebSocket(url.to_string(), None))
+    }
+
+    /// Connect by websocket, and authenticate the connection by the api key or jwt token.
+    pub fn connect_websocket_with_auth(url: &str, credential: String) -> anyhow::Result<Self> {
+        Self::new(ConnSource::WebSocket(url.to_string(), Some(credential)))
+    }
+
+    /// Connect by http, the subscription is not supported, so the `watch_txn` and `watch_block`
+    /// are not available.
+    pub fn connect_http(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(url.to_string(), None))
+    }
+
+    /// Connect by http, and authenticate every request by the api key or jwt token,
+    /// which is sent by the header `Authorization: Bearer`.
+    pub fn connect_http_with_auth(url: &str, credential: String) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(url.to_string(), Some(credential)))
     }
 
     pub fn connect_local<S>(rpc_service: S) -> anyhow::Result<Self>
@@ -185,11 +228,21 @@ impl RpcClient {
         Self::new(ConnSource::Ipc(path))
     }
 
+    fn ensure_subscription_supported(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.provider.is_subscription_supported(),
+            "Subscription is not supported by {:?}",
+            self.provider.conn_source
+        );
+        Ok(())
+    }
+
     pub fn watch_txn(
         &self,
         txn_hash: HashValue,
         timeout: Option<Duration>,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        self.ensure_subscription_supported()?;
         let chain_watcher = self.chain_watcher.clone();
         let f = async move {
             let r = chain_watcher.send(WatchTxn { txn_hash }).await?;
@@ -205,6 +258,7 @@ impl RpcClient {
         &self,
         block_number: BlockNumber,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        self.ensure_subscription_supported()?;
         let chain_watcher = self.chain_watcher.clone();
         let f = async move {
             let r = chain_watcher.send(WatchBlock(block_number)).await?;
@@ -336,6 +390,38 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_create_mnemonic(
+        &self,
+        password: String,
+        word_count: usize,
+    ) -> anyhow::Result<MnemonicAccountInfo> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .create_mnemonic(password, Some(word_count as u32))
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: u32,
+    ) -> anyhow::Result<Vec<AccountInfo>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .import_mnemonic(mnemonic, password, Some(account_count))
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_derive(&self, password: String) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.derive(password))
+            .map_err(map_err)
+    }
+
     pub fn account_list(&self) -> anyhow::Result<Vec<AccountInfo>> {
         self.call_rpc_blocking(|inner| inner.account_client.list())
             .map_err(map_err)
@@ -378,6 +464,17 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_sign_multisig_txn(
+        &self,
+        txn: MultisigTransaction,
+    ) -> anyhow::Result<MultisigTransaction> {
+        let bytes = StrView(txn.to_bcs()?);
+        let signed = self
+            .call_rpc_blocking(|inner| inner.account_client.sign_multisig_txn(bytes))
+            .map_err(map_err)?;
+        MultisigTransaction::decode(signed.0.as_slice())
+    }
+
     pub fn account_sign_message(
         &self,
         signer: AccountAddress,
@@ -471,7 +568,7 @@ impl RpcClient {
 
     pub fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
         let result: Option<StrView<Vec<u8>>> = self
-            .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
+            .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id), None))
             .map_err(map_err)?;
         Ok(result.map(|s| s.to_string()))
     }
@@ -484,7 +581,7 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .contract_client
-                .get_resource(addr, StrView(resource_type))
+                .get_resource(addr, StrView(resource_type), None)
         })
         .map_err(map_err)
     }
@@ -497,7 +594,7 @@ impl RpcClient {
     }
 
     pub fn state_get(&self, access_path: AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get(access_path))
+        self.call_rpc_blocking(|inner| inner.state_client.get(access_path, None))
             .map_err(map_err)
     }
 
@@ -505,7 +602,7 @@ impl RpcClient {
         &self,
         access_path: AccessPath,
     ) -> anyhow::Result<StateWithProofView> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_with_proof(access_path))
+        self.call_rpc_blocking(|inner| inner.state_client.get_with_proof(access_path, None))
             .map_err(map_err)
     }
 
@@ -523,7 +620,7 @@ impl RpcClient {
     }
 
     pub fn state_get_state_root(&self) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_state_root())
+        self.call_rpc_blocking(|inner| inner.state_client.get_state_root(None))
             .map_err(map_err)
     }
 
@@ -531,7 +628,7 @@ impl RpcClient {
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<AccountState>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_account_state(address))
+        self.call_rpc_blocking(|inner| inner.state_client.get_account_state(address, None))
             .map_err(map_err)
     }
 
@@ -543,7 +640,7 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .state_client
-                .get_account_state_set(address, state_root)
+                .get_account_state_set(address, state_root, None)
         })
         .map_err(map_err)
     }
@@ -559,7 +656,11 @@ impl RpcClient {
             inner.state_client.get_resource(
                 address,
                 StrView(resource_type),
-                Some(GetResourceOption { decode, state_root }),
+                Some(GetResourceOption {
+                    decode,
+                    state_root,
+                    block: None,
+                }),
             )
         })
         .map_err(map_err)
@@ -572,9 +673,14 @@ impl RpcClient {
         state_root: Option<HashValue>,
     ) -> anyhow::Result<ListResourceView> {
         self.call_rpc_blocking(|inner| {
-            inner
-                .state_client
-                .list_resource(address, Some(ListResourceOption { decode, state_root }))
+            inner.state_client.list_resource(
+                address,
+                Some(ListResourceOption {
+                    decode,
+                    state_root,
+                    block: None,
+                }),
+            )
         })
         .map_err(map_err)
     }
@@ -591,6 +697,7 @@ impl RpcClient {
                 Some(GetCodeOption {
                     resolve,
                     state_root,
+                    block: None,
                 }),
             )
         })
@@ -609,6 +716,7 @@ impl RpcClient {
                 Some(ListCodeOption {
                     resolve,
                     state_root,
+                    block: None,
                 }),
             )
         })
@@ -616,7 +724,7 @@ impl RpcClient {
     }
 
     pub fn contract_call(&self, call: ContractCall) -> anyhow::Result<Vec<DecodedMoveValue>> {
-        self.call_rpc_blocking(|inner| inner.contract_client.call_v2(call))
+        self.call_rpc_blocking(|inner| inner.contract_client.call_v2(call, None))
             .map_err(map_err)
     }
 
@@ -624,7 +732,7 @@ impl RpcClient {
         &self,
         function_id: FunctionIdView,
     ) -> anyhow::Result<FunctionABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_function(function_id))
+        self.call_rpc_blocking(|inner| inner.contract_client.resolve_function(function_id, None))
             .map_err(map_err)
     }
 
@@ -632,12 +740,12 @@ impl RpcClient {
         &self,
         struct_tag: StructTagView,
     ) -> anyhow::Result<StructInstantiation> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_struct(struct_tag))
+        self.call_rpc_blocking(|inner| inner.contract_client.resolve_struct(struct_tag, None))
             .map_err(map_err)
     }
 
     pub fn contract_resolve_module(&self, module_id: ModuleIdView) -> anyhow::Result<ModuleABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_module(module_id))
+        self.call_rpc_blocking(|inner| inner.contract_client.resolve_module(module_id, None))
             .map_err(map_err)
     }
 
@@ -669,6 +777,47 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn debug_trace_transaction(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<TransactionTraceView> {
+        self.call_rpc_blocking(|inner| inner.debug_client.trace_transaction(txn_hash))
+            .map_err(map_err)
+    }
+
+    pub fn debug_profile_transaction(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<TransactionProfileView> {
+        self.call_rpc_blocking(|inner| inner.debug_client.profile_transaction(txn_hash))
+            .map_err(map_err)
+    }
+
+    pub fn debug_profile_dry_run_raw(
+        &self,
+        txn: DryRunTransaction,
+    ) -> anyhow::Result<TransactionProfileView> {
+        let DryRunTransaction {
+            raw_txn,
+            public_key,
+        } = txn;
+        let raw_txn = hex::encode(raw_txn.encode()?);
+        self.call_rpc_blocking(|inner| {
+            inner
+                .debug_client
+                .profile_dry_run_raw(raw_txn, StrView(public_key), None)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn debug_profile_call(
+        &self,
+        call: ContractCall,
+    ) -> anyhow::Result<ContractCallProfileView> {
+        self.call_rpc_blocking(|inner| inner.debug_client.profile_call(call, None))
+            .map_err(map_err)
+    }
+
     pub fn sleep(&self, time: u64) -> anyhow::Result<()> {
         self.call_rpc_blocking(|inner| inner.debug_client.sleep(time))
             .map_err(map_err)
@@ -807,7 +956,7 @@ impl RpcClient {
     }
 
     pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<DryRunOutputView> {
-        self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
+        self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn, None, None))
             .map_err(map_err)
     }
     pub fn dry_run_raw(&self, txn: DryRunTransaction) -> anyhow::Result<DryRunOutputView> {
@@ -819,7 +968,24 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .contract_client
-                .dry_run_raw(raw_txn, StrView(public_key))
+                .dry_run_raw(raw_txn, StrView(public_key), None, None)
+        })
+        .map_err(map_err)
+    }
+    pub fn estimate_gas(&self, txn: DryRunTransactionRequest) -> anyhow::Result<GasEstimateView> {
+        self.call_rpc_blocking(|inner| inner.contract_client.estimate_gas(txn, None))
+            .map_err(map_err)
+    }
+    pub fn estimate_gas_raw(&self, txn: DryRunTransaction) -> anyhow::Result<GasEstimateView> {
+        let DryRunTransaction {
+            raw_txn,
+            public_key,
+        } = txn;
+        let raw_txn = hex::encode(raw_txn.encode()?);
+        self.call_rpc_blocking(|inner| {
+            inner
+                .contract_client
+                .estimate_gas_raw(raw_txn, StrView(public_key), None)
         })
         .map_err(map_err)
     }
@@ -843,6 +1009,21 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn miner_submit_hashrate(&self, worker: String, hash_rate: u64) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.miner_client.submit_hashrate(worker, hash_rate))
+            .map_err(map_err)
+    }
+
+    pub fn miner_get_hashrate(&self) -> anyhow::Result<MinerHashRateView> {
+        self.call_rpc_blocking(|inner| inner.miner_client.get_hashrate())
+            .map_err(map_err)
+    }
+
+    pub fn miner_get_stratum_workers(&self) -> anyhow::Result<Vec<StratumWorkerView>> {
+        self.call_rpc_blocking(|inner| inner.miner_client.get_stratum_workers())
+            .map_err(map_err)
+    }
+
     pub fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
         self.call_rpc_blocking(|inner| inner.txpool_client.state())
             .map_err(map_err)
@@ -852,7 +1033,7 @@ impl RpcClient {
         &self,
         filter: EventFilter,
         decode: bool,
-    ) -> anyhow::Result<impl TryStream<Ok = TransactionEventView, Error = anyhow::Error>> {
+    ) -> anyhow::Result<impl TryStream<Ok = TransactionEventResponse, Error = anyhow::Error>> {
         self.call_rpc_blocking(|inner| async move {
             let res = inner.pubsub_client.subscribe_events(filter, decode).await;
             res.map(|s| s.map_err(map_err))
@@ -878,6 +1059,16 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn subscribe_chain_reorg(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = ChainReorg, Error = anyhow::Error>> {
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_chain_reorg().await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
     pub fn subscribe_new_mint_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlockEvent, Error = anyhow::Error>> {
@@ -1027,6 +1218,22 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| inner.network_client.ban_peer(peer_id, ban))
             .map_err(map_err)
     }
+    pub fn network_list_bans(&self) -> anyhow::Result<Vec<BannedPeerInfo>> {
+        self.call_rpc_blocking(|inner| inner.network_client.list_bans())
+            .map_err(map_err)
+    }
+    pub fn network_unban(&self, peer_id: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.unban(peer_id))
+            .map_err(map_err)
+    }
+    pub fn network_add_reserved(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.add_reserved(peer))
+            .map_err(map_err)
+    }
+    pub fn network_remove_reserved(&self, peer_id: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.remove_reserved(peer_id))
+            .map_err(map_err)
+    }
     pub fn close(self) {
         if let Err(e) = self.chain_watcher.try_send(chain_watcher::StopWatcher) {
             debug!("Try to stop chain watcher error: {:?}", e);
@@ -3,8 +3,10 @@
 
 use jsonrpc_core_client::*;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::pubsub::EventParams;
-use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventView};
+use starcoin_rpc_api::types::pubsub::{ChainReorg, EventParams};
+use starcoin_rpc_api::types::{
+    pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventResponse,
+};
 use starcoin_types::system_events::MintBlockEvent;
 
 const STARCOIN_SUBSCRIPTION: &str = "starcoin_subscription";
@@ -34,7 +36,7 @@ impl PubSubClient {
         &self,
         filter: EventFilter,
         decode: bool,
-    ) -> Result<TypedSubscriptionStream<TransactionEventView>, RpcError> {
+    ) -> Result<TypedSubscriptionStream<TransactionEventResponse>, RpcError> {
         self.client.subscribe(
             STARCOIN_SUBSCRIBE,
             (Kind::Events, EventParams { filter, decode }),
@@ -76,4 +78,15 @@ impl PubSubClient {
             "MintBlockEvent",
         )
     }
+    pub async fn subscribe_chain_reorg(
+        &self,
+    ) -> Result<TypedSubscriptionStream<ChainReorg>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::ChainReorg],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "ChainReorg",
+        )
+    }
 }
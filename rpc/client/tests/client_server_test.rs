@@ -3,9 +3,12 @@
 
 use anyhow::Result;
 use futures::{StreamExt, TryStreamExt};
-use starcoin_config::NodeConfig;
+use serde_json::{json, Value};
+use starcoin_config::{BuiltinNetworkID, NodeConfig, StarcoinOpt};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
-use starcoin_rpc_client::RpcClient;
+use starcoin_rpc_client::{Params, RpcClient};
+use starcoin_types::account_config::genesis_address;
 use starcoin_types::system_events::MintBlockEvent;
 use std::sync::Arc;
 use std::time::Duration;
@@ -139,3 +142,153 @@ fn test_client_reconnect_subscribe() -> Result<()> {
     assert_ne!(events2.len(), 0);
     Ok(())
 }
+
+#[stest::test(timeout = 120)]
+fn test_client_auth() -> Result<()> {
+    let temp_path = starcoin_config::temp_dir();
+    let keyfile = temp_path.path().join("rpc_auth.toml");
+    std::fs::write(
+        &keyfile,
+        r#"
+anonymous_apis = "node"
+
+[[keys]]
+name = "reader"
+key = "reader_key"
+apis = "node,chain"
+"#,
+    )?;
+    let mut opt = StarcoinOpt {
+        net: Some(BuiltinNetworkID::Test.into()),
+        base_data_dir: Some(temp_path.path().to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    opt.rpc.auth_keyfile = Some(keyfile);
+    let config = Arc::new(NodeConfig::load_with_opt(&opt)?);
+    let url = config.rpc.get_ws_address().unwrap();
+    let http_url = config.rpc.get_http_address().unwrap();
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let anonymous_client =
+        RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail.");
+    assert!(anonymous_client.node_info().is_ok());
+    assert!(anonymous_client.chain_info().is_err());
+
+    assert!(RpcClient::connect_websocket_with_auth(
+        url.to_string().as_str(),
+        "invalid_key".to_string()
+    )
+    .is_err());
+
+    let reader_client =
+        RpcClient::connect_websocket_with_auth(url.to_string().as_str(), "reader_key".to_string())
+            .expect("connect websocket fail.");
+    assert!(reader_client.node_info().is_ok());
+    assert!(reader_client.chain_info().is_ok());
+    assert!(reader_client.txpool_status().is_err());
+
+    let invalid_http_client =
+        RpcClient::connect_http_with_auth(http_url.to_string().as_str(), "invalid_key".to_string())
+            .expect("connect http fail.");
+    assert!(invalid_http_client.node_info().is_err());
+
+    let reader_http_client =
+        RpcClient::connect_http_with_auth(http_url.to_string().as_str(), "reader_key".to_string())
+            .expect("connect http fail.");
+    assert!(reader_http_client.chain_info().is_ok());
+    assert!(reader_http_client.txpool_status().is_err());
+
+    anonymous_client.close();
+    reader_client.close();
+    invalid_http_client.close();
+    reader_http_client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_state_at_block() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let url = config.rpc.get_ws_address().unwrap();
+    let node_handle = test_helper::run_node_by_config(config)?;
+    node_handle.generate_block()?;
+    node_handle.generate_block()?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let client =
+        RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail.");
+    let header1 = client
+        .chain_get_block_by_number(1, None)?
+        .expect("block 1 should exist")
+        .header;
+    assert_ne!(header1.state_root, client.state_get_state_root()?);
+
+    let call =
+        |method: &str, params: Vec<Value>| client.call_raw_api(method, Params::Array(params));
+    let get_state_root = |block: Value| -> Result<HashValue> {
+        Ok(serde_json::from_value(call(
+            "state.get_state_root",
+            vec![block],
+        )?)?)
+    };
+    // block by number and block by hash.
+    assert_eq!(get_state_root(json!(1))?, header1.state_root);
+    assert_eq!(
+        get_state_root(json!(header1.block_hash))?,
+        header1.state_root
+    );
+    // unknown block.
+    assert!(get_state_root(json!(100)).is_err());
+    assert!(get_state_root(json!(HashValue::random())).is_err());
+
+    let block_number_call = json!({
+        "function_id": "0x1::Block::get_current_block_number",
+        "type_args": [],
+        "args": [],
+    });
+    let by_number = call(
+        "contract.call_v2",
+        vec![block_number_call.clone(), json!(1)],
+    )?;
+    let by_hash = call(
+        "contract.call_v2",
+        vec![block_number_call.clone(), json!(header1.block_hash)],
+    )?;
+    let latest = call("contract.call_v2", vec![block_number_call, Value::Null])?;
+    assert_eq!(by_number, by_hash);
+    assert_ne!(by_number, latest);
+
+    // state_root and block can not be set at the same time.
+    assert!(call(
+        "state.get_account_state_set",
+        vec![
+            json!(genesis_address()),
+            json!(header1.state_root),
+            json!(1)
+        ],
+    )
+    .is_err());
+    assert!(call(
+        "state.get_resource",
+        vec![
+            json!(genesis_address()),
+            json!("0x1::Block::BlockMetadata"),
+            json!({"state_root": header1.state_root, "block": 1}),
+        ],
+    )
+    .is_err());
+    assert!(call(
+        "state.get_resource",
+        vec![
+            json!(genesis_address()),
+            json!("0x1::Block::BlockMetadata"),
+            json!({ "block": 1 }),
+        ],
+    )?
+    .is_object());
+
+    client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
@@ -1,18 +1,25 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::auth_middleware::{JsonApiAuthMiddleware, RpcAuthenticator};
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
 use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
 use starcoin_config::{Api, ApiQuotaConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_middleware::{MetricMiddleware, RpcMetrics};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    MetricMiddleware,
+    JsonApiAuthMiddleware,
+    JsonApiRateLimitMiddleware,
+);
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
     quotas: ApiQuotaConfiguration,
+    authenticator: Option<Arc<RpcAuthenticator>>,
     metrics: Option<RpcMetrics>,
 }
 
@@ -21,45 +28,84 @@ impl ApiRegistry {
         Self {
             apis: Default::default(),
             quotas: api_quotas,
+            authenticator: None,
             metrics,
         }
     }
 
+    /// Require the requests to be authenticated by the `authenticator`, except the ipc requests.
+    pub fn set_authenticator(&mut self, authenticator: Arc<RpcAuthenticator>) {
+        self.authenticator = Some(authenticator);
+    }
+
+    fn new_io_handler(&self, authenticate: bool) -> MetaIoHandler<Metadata, Middlewares> {
+        let authenticator = if authenticate {
+            self.authenticator.clone()
+        } else {
+            None
+        };
+        let user_quotas = authenticator
+            .as_ref()
+            .map(|authenticator| {
+                authenticator
+                    .keys()
+                    .keys
+                    .iter()
+                    .filter_map(|api_key| {
+                        api_key
+                            .quota
+                            .clone()
+                            .map(|quota| (api_key.name.clone(), quota))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let rate_limit = JsonApiRateLimitMiddleware::from_config(self.quotas.clone(), user_quotas);
+        MetaIoHandler::<Metadata, Middlewares>::with_middleware((
+            MetricMiddleware::new(self.metrics.clone()),
+            JsonApiAuthMiddleware::new(authenticator, rate_limit.clone()),
+            rate_limit,
+        ))
+    }
+
     pub fn register<F>(&mut self, api_type: Api, apis: F)
     where
         F: IntoIterator<Item = (String, RemoteProcedure<Metadata>)>,
     {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
-        let metrics = self.metrics.clone();
-        let io_handler = self.apis.entry(api_type).or_insert_with(|| {
-            MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                MetricMiddleware::new(metrics),
-                rate_limit_middleware,
-            ))
-        });
+        let new_io_handler = self.new_io_handler(false);
+        let io_handler = self.apis.entry(api_type).or_insert_with(|| new_io_handler);
         io_handler.extend_with(apis);
     }
 
+    /// Get the apis, the requests are authenticated if the authenticator is set.
     pub fn get_apis(
         &self,
         api_types: impl IntoIterator<Item = Api>,
     ) -> MetaIoHandler<Metadata, Middlewares> {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
-        let metrics = self.metrics.clone();
+        self.get_apis_with_auth(api_types, true)
+    }
+
+    /// Get the apis for the trusted transports, such as ipc, the requests are not authenticated.
+    pub fn get_apis_without_auth(
+        &self,
+        api_types: impl IntoIterator<Item = Api>,
+    ) -> MetaIoHandler<Metadata, Middlewares> {
+        self.get_apis_with_auth(api_types, false)
+    }
+
+    fn get_apis_with_auth(
+        &self,
+        api_types: impl IntoIterator<Item = Api>,
+        authenticate: bool,
+    ) -> MetaIoHandler<Metadata, Middlewares> {
         api_types
             .into_iter()
             .map(|api_type| self.apis.get(&api_type))
-            .fold(
-                MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                    MetricMiddleware::new(metrics),
-                    rate_limit_middleware,
-                )),
-                |mut init, apis| {
-                    if let Some(apis) = apis {
-                        init.extend_with(apis.iter().map(|(k, v)| (k.clone(), v.clone())));
-                    }
-                    init
-                },
-            )
+            .fold(self.new_io_handler(authenticate), |mut init, apis| {
+                if let Some(apis) = apis {
+                    init.extend_with(apis.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+                init
+            })
     }
 }
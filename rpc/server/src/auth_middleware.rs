@@ -0,0 +1,280 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
+use anyhow::{bail, ensure, format_err, Result};
+use hmac::{Hmac, Mac};
+use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{
+    Call, Error, ErrorCode, Failure, Id, Middleware, Output, Params, Success, Value, Version,
+};
+use serde::Deserialize;
+use sha2::Sha256;
+use starcoin_config::{RpcApiKey, RpcAuthKeyFile};
+use starcoin_rpc_api::metadata::{Metadata, RPC_AUTHENTICATE_METHOD};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const UNAUTHORIZED_ERROR: i64 = -10001;
+const PERMISSION_DENIED_ERROR: i64 = -10002;
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    sub: String,
+    #[serde(default)]
+    exp: Option<u64>,
+    #[serde(default)]
+    nbf: Option<u64>,
+}
+
+/// Resolve the api key of a credential, the credential is an api key,
+/// or a HS256 jwt token whose `sub` is the name of an api key.
+#[derive(Debug)]
+pub struct RpcAuthenticator {
+    keys: Arc<RpcAuthKeyFile>,
+}
+
+impl RpcAuthenticator {
+    pub fn new(keys: Arc<RpcAuthKeyFile>) -> Self {
+        Self { keys }
+    }
+
+    pub fn keys(&self) -> &RpcAuthKeyFile {
+        self.keys.as_ref()
+    }
+
+    pub fn authenticate(&self, credential: &str) -> Result<&RpcApiKey> {
+        if let Some(api_key) = self.keys.get_key(credential) {
+            return Ok(api_key);
+        }
+        if credential.split('.').count() == 3 && self.keys.jwt_secret.is_some() {
+            let name = self.verify_jwt(credential)?;
+            return self
+                .keys
+                .get_key_by_name(name.as_str())
+                .ok_or_else(|| format_err!("Unknown jwt token subject: {}", name));
+        }
+        bail!("Invalid api key or jwt token")
+    }
+
+    fn verify_jwt(&self, token: &str) -> Result<String> {
+        let secret = self
+            .keys
+            .jwt_secret
+            .as_ref()
+            .ok_or_else(|| format_err!("Jwt token is not supported"))?;
+        let parts: Vec<&str> = token.split('.').collect();
+        ensure!(parts.len() == 3, "Invalid jwt token format");
+        let header: JwtHeader = serde_json::from_slice(&decode_base64(parts[0])?)?;
+        ensure!(
+            header.alg == "HS256",
+            "Unsupported jwt algorithm: {}",
+            header.alg
+        );
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| format_err!("Invalid jwt secret: {}", e))?;
+        mac.update(parts[0].as_bytes());
+        mac.update(b".");
+        mac.update(parts[1].as_bytes());
+        mac.verify_slice(&decode_base64(parts[2])?)
+            .map_err(|_| format_err!("Invalid jwt token signature"))?;
+
+        let claims: JwtClaims = serde_json::from_slice(&decode_base64(parts[1])?)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if let Some(exp) = claims.exp {
+            ensure!(now < exp, "Jwt token is expired");
+        }
+        if let Some(nbf) = claims.nbf {
+            ensure!(now >= nbf, "Jwt token is not active yet");
+        }
+        Ok(claims.sub)
+    }
+}
+
+fn decode_base64(input: &str) -> Result<Vec<u8>> {
+    Ok(base64::decode_config(input, base64::URL_SAFE_NO_PAD)?)
+}
+
+/// Authenticate the requests of http, tcp and websocket rpc by the api keys.
+/// The authenticated key name becomes the user of the request, so the rate limit of the user
+/// follows the key's quota.
+#[derive(Debug)]
+pub struct JsonApiAuthMiddleware {
+    authenticator: Option<Arc<RpcAuthenticator>>,
+    /// The `rpc.authenticate` call is answered here, before the rate limit middleware,
+    /// so it is rate limited by the client ip here.
+    rate_limit: JsonApiRateLimitMiddleware,
+}
+
+impl JsonApiAuthMiddleware {
+    pub fn new(
+        authenticator: Option<Arc<RpcAuthenticator>>,
+        rate_limit: JsonApiRateLimitMiddleware,
+    ) -> Self {
+        Self {
+            authenticator,
+            rate_limit,
+        }
+    }
+
+    fn authenticate_session(
+        &self,
+        params: Params,
+        meta: &Metadata,
+    ) -> std::result::Result<Value, Error> {
+        let authenticator = match self.authenticator.as_ref() {
+            Some(authenticator) => authenticator,
+            // authentication is not required.
+            None => return Ok(Value::Null),
+        };
+        let (credential,): (String,) = params.parse()?;
+        let api_key = authenticator
+            .authenticate(credential.as_str())
+            .map_err(|e| new_error(UNAUTHORIZED_ERROR, e.to_string()))?;
+        *meta.credential.write() = Some(credential);
+        Ok(Value::String(api_key.name.clone()))
+    }
+
+    fn check(&self, method: &str, meta: &mut Metadata) -> std::result::Result<(), Error> {
+        let authenticator = match self.authenticator.as_ref() {
+            Some(authenticator) => authenticator,
+            None => return Ok(()),
+        };
+        let credential = meta.credential.read().clone();
+        match credential {
+            Some(credential) => {
+                let api_key = authenticator
+                    .authenticate(credential.as_str())
+                    .map_err(|e| new_error(UNAUTHORIZED_ERROR, e.to_string()))?;
+                if !api_key.is_allowed(method) {
+                    return Err(new_error(
+                        PERMISSION_DENIED_ERROR,
+                        format!("Api key {} is not allowed to call {}", api_key.name, method),
+                    ));
+                }
+                meta.user = Some(api_key.name.clone());
+                Ok(())
+            }
+            None if authenticator.keys().is_anonymous_allowed(method) => Ok(()),
+            None => Err(new_error(
+                UNAUTHORIZED_ERROR,
+                format!("Api key or jwt token is required to call {}", method),
+            )),
+        }
+    }
+}
+
+fn new_error(code: i64, message: String) -> Error {
+    Error {
+        code: ErrorCode::ServerError(code),
+        message,
+        data: None,
+    }
+}
+
+fn output(jsonrpc: Option<Version>, id: Id, result: std::result::Result<Value, Error>) -> Output {
+    match result {
+        Ok(result) => Output::Success(Success {
+            jsonrpc,
+            result,
+            id,
+        }),
+        Err(error) => Output::Failure(Failure { jsonrpc, error, id }),
+    }
+}
+
+impl Middleware<Metadata> for JsonApiAuthMiddleware {
+    type Future = jsonrpc_core::FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: Metadata, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, Metadata) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let (method, params, json_version, id) = match &call {
+            Call::MethodCall(m) => (m.method.clone(), m.params.clone(), m.jsonrpc, m.id.clone()),
+            Call::Notification(n) => (n.method.clone(), n.params.clone(), n.jsonrpc, Id::Null),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        if method == RPC_AUTHENTICATE_METHOD {
+            // the user of the request is the client ip before the authentication.
+            let result = self
+                .rate_limit
+                .check(method.as_str(), meta.user.as_ref())
+                .and_then(|_| self.authenticate_session(params, &meta));
+            let output = output(json_version, id, result);
+            return Either::Left(Box::pin(futures::future::ready(Some(output))));
+        }
+        let mut meta = meta;
+        match self.check(method.as_str(), &mut meta) {
+            Ok(()) => Either::Right(next(call, meta)),
+            Err(error) => {
+                let output = output(json_version, id, Err(error));
+                Either::Left(Box::pin(futures::future::ready(Some(output))))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn sign_jwt(secret: &str, claims: &str) -> String {
+        let header =
+            base64::encode_config(r#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+        let claims = base64::encode_config(claims, base64::URL_SAFE_NO_PAD);
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.{}", header, claims).as_bytes());
+        let signature = base64::encode_config(mac.finalize().into_bytes(), base64::URL_SAFE_NO_PAD);
+        format!("{}.{}.{}", header, claims, signature)
+    }
+
+    fn new_authenticator() -> RpcAuthenticator {
+        RpcAuthenticator::new(Arc::new(RpcAuthKeyFile {
+            jwt_secret: Some("secret".to_string()),
+            anonymous_apis: None,
+            keys: vec![RpcApiKey {
+                name: "admin".to_string(),
+                key: Some("admin_key".to_string()),
+                apis: Some(starcoin_config::ApiSet::from_str("all").unwrap()),
+                methods: vec![],
+                quota: None,
+            }],
+        }))
+    }
+
+    #[test]
+    fn test_authenticate_api_key() {
+        let authenticator = new_authenticator();
+        assert_eq!(
+            authenticator.authenticate("admin_key").unwrap().name,
+            "admin"
+        );
+        assert!(authenticator.authenticate("unknown_key").is_err());
+    }
+
+    #[test]
+    fn test_authenticate_jwt() {
+        let authenticator = new_authenticator();
+        let token = sign_jwt("secret", r#"{"sub":"admin"}"#);
+        assert_eq!(authenticator.authenticate(&token).unwrap().name, "admin");
+
+        let token = sign_jwt("other_secret", r#"{"sub":"admin"}"#);
+        assert!(authenticator.authenticate(&token).is_err());
+        let token = sign_jwt("secret", r#"{"sub":"unknown"}"#);
+        assert!(authenticator.authenticate(&token).is_err());
+        let token = sign_jwt("secret", r#"{"sub":"admin","exp":1}"#);
+        assert!(authenticator.authenticate(&token).is_err());
+    }
+}
@@ -3,6 +3,7 @@
 
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::Session;
+use parking_lot::RwLock;
 use starcoin_rpc_api::metadata::Metadata;
 use std::net::IpAddr;
 use std::sync::Arc;
@@ -27,9 +28,18 @@ impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
             }
         }
 
+        // the credential is sent by the header `Authorization: Bearer {api key or jwt token}`.
+        let credential = _req
+            .headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(|v| v.trim().to_string());
+
         Metadata {
             session: None,
             user: client_ip.map(|ip| ip.to_string()),
+            credential: Arc::new(RwLock::new(credential)),
         }
     }
 }
@@ -38,6 +48,7 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
             user: None,
+            credential: Default::default(),
         }
     }
 }
@@ -46,6 +57,7 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            credential: Default::default(),
         }
     }
 }
@@ -57,6 +69,7 @@ impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
         Metadata {
             session,
             user: None,
+            credential: Default::default(),
         }
     }
 }
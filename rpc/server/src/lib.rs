@@ -1,7 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-mod api_registry;
+pub mod api_registry;
+pub mod auth_middleware;
 mod extractors;
 pub mod module;
 mod rate_limit_middleware;
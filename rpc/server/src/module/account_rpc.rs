@@ -5,7 +5,10 @@ use crate::module::helpers::TransactionRequestFiller;
 use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
-use starcoin_account_api::{AccountAsyncService, AccountInfo};
+use starcoin_account_api::{
+    AccountAsyncService, AccountInfo, MnemonicAccountInfo, MultisigTransaction,
+    DEFAULT_MNEMONIC_WORD_COUNT,
+};
 
 use starcoin_config::NodeConfig;
 use starcoin_rpc_api::types::{SignedMessageView, StrView, TransactionRequest};
@@ -97,6 +100,52 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn create_mnemonic(
+        &self,
+        password: String,
+        word_count: Option<u32>,
+    ) -> FutureResult<MnemonicAccountInfo> {
+        let service = self.account.clone();
+        let word_count = word_count
+            .map(|count| count as usize)
+            .unwrap_or(DEFAULT_MNEMONIC_WORD_COUNT);
+        let fut = async move {
+            let result = service
+                .create_mnemonic_account(password, word_count)
+                .await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+        account_count: Option<u32>,
+    ) -> FutureResult<Vec<AccountInfo>> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service
+                .import_mnemonic(mnemonic, password, account_count.unwrap_or(1))
+                .await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn derive(&self, password: String) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.derive_account(password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn list(&self) -> FutureResult<Vec<AccountInfo>> {
         let service = self.account.clone();
         let fut = async move {
@@ -161,6 +210,17 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn sign_multisig_txn(&self, txn: StrView<Vec<u8>>) -> FutureResult<StrView<Vec<u8>>> {
+        let service = self.account.clone();
+        let fut = async move {
+            let txn = MultisigTransaction::decode(txn.0.as_slice())?;
+            let result = service.sign_multisig_txn(txn).await?;
+            Ok(StrView(result.to_bcs()?))
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn unlock(
         &self,
         address: AccountAddress,
@@ -18,8 +18,8 @@ use starcoin_rpc_api::types::{
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::StateView;
+use starcoin_state_tree::StateNodeStore;
 use starcoin_statedb::ChainStateDB;
-use starcoin_storage::Storage;
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::block::BlockNumber;
 use starcoin_types::filter::Filter;
@@ -33,7 +33,7 @@ where
 {
     config: Arc<NodeConfig>,
     genesis_hash: HashValue,
-    storage: Arc<Storage>,
+    storage: Arc<dyn StateNodeStore>,
     service: S,
 }
 
@@ -44,7 +44,7 @@ where
     pub fn new(
         config: Arc<NodeConfig>,
         genesis_hash: HashValue,
-        storage: Arc<Storage>,
+        storage: Arc<dyn StateNodeStore>,
         service: S,
     ) -> Self {
         Self {
@@ -287,6 +287,7 @@ where
                 .map(|e| TransactionEventResponse {
                     event: e.into(),
                     decode_event_data: None,
+                    removed: false,
                 })
                 .collect();
 
@@ -353,6 +354,7 @@ where
                 .map(|e| TransactionEventResponse {
                     event: e.into(),
                     decode_event_data: None,
+                    removed: false,
                 })
                 .collect();
             if let Some(state_root) = state_root {
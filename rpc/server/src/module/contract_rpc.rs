@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::helpers::TransactionRequestFiller;
+use crate::module::helpers::{resolve_state_root, TransactionRequestFiller};
 use crate::module::map_err;
 use anyhow::format_err;
 use futures::future::TryFutureExt;
@@ -10,59 +10,71 @@ use starcoin_abi_decoder::{decode_move_value, DecodedMoveValue};
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_abi_types::{FunctionABI, ModuleABI, StructInstantiation, TypeInstantiation};
 use starcoin_account_api::AccountAsyncService;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_dev::playground::{call_contract, PlaygroudService};
+use starcoin_executor::execution_trace::{CallTrace, ExecutionTrace, StateRead};
 use starcoin_executor::VMMetrics;
 use starcoin_resource_viewer::module_cache::ModuleCache;
 use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::contract_api::ContractApi;
 use starcoin_rpc_api::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
-    TransactionOutputView, WriteOpValueView,
+    AnnotatedMoveStructView, AnnotatedMoveValueView, BlockNumberOrHash, CallTraceView,
+    ContractCall, DryRunOutputView, DryRunTransactionRequest, ExecutionTraceView, FunctionIdView,
+    GasEstimateView, ModuleIdView, StateReadView, StateWriteView, StrView, StructTagView,
+    TransactionOutputView, WriteOpValueView, WriteOpView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::ChainStateAsyncService;
+use starcoin_state_tree::StateNodeStore;
 use starcoin_statedb::ChainStateDB;
-use starcoin_storage::Storage;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::language_storage::{ModuleId, StructTag};
-use starcoin_types::transaction::{DryRunTransaction, RawUserTransaction, TransactionPayload};
+use starcoin_types::transaction::{
+    DryRunTransaction, RawUserTransaction, TransactionOutput, TransactionPayload,
+};
 use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::language_storage::FunctionId;
 use starcoin_vm_types::state_view::StateView;
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
+use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::write_set::WriteOp;
 use std::str::FromStr;
 use std::sync::Arc;
 
-pub struct ContractRpcImpl<Account, Pool, State> {
+pub struct ContractRpcImpl<Account, Pool, State, Chain> {
     pub(crate) account: Option<Account>,
     pub(crate) pool: Pool,
     pub(crate) chain_state: State,
+    pub(crate) chain: Chain,
     pub(crate) node_config: Arc<NodeConfig>,
     playground: PlaygroudService,
-    storage: Arc<Storage>,
+    storage: Arc<dyn StateNodeStore>,
 }
 
-impl<Account, Pool, State> ContractRpcImpl<Account, Pool, State>
+impl<Account, Pool, State, Chain> ContractRpcImpl<Account, Pool, State, Chain>
 where
     Account: AccountAsyncService + 'static,
     Pool: TxPoolSyncService + 'static,
     State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
 {
     pub fn new(
         node_config: Arc<NodeConfig>,
         account: Option<Account>,
         pool: Pool,
         chain_state: State,
+        chain: Chain,
         playground: PlaygroudService,
-        storage: Arc<Storage>,
+        storage: Arc<dyn StateNodeStore>,
     ) -> Self {
         Self {
             account,
             pool,
             chain_state,
+            chain,
             node_config,
             playground,
             storage,
@@ -78,16 +90,25 @@ where
     }
 }
 
-impl<Account, Pool, State> ContractApi for ContractRpcImpl<Account, Pool, State>
+impl<Account, Pool, State, Chain> ContractApi for ContractRpcImpl<Account, Pool, State, Chain>
 where
     Account: AccountAsyncService + 'static,
     Pool: TxPoolSyncService + 'static,
     State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
 {
-    fn get_code(&self, module_id: StrView<ModuleId>) -> FutureResult<Option<StrView<Vec<u8>>>> {
+    fn get_code(
+        &self,
+        module_id: StrView<ModuleId>,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<StrView<Vec<u8>>>> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
         let f = async move {
-            let code = service.get(AccessPath::from(&module_id.0)).await?;
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let state = ChainStateDB::try_new(storage, state_root)?;
+            let code = state.get(&AccessPath::from(&module_id.0))?;
             Ok(code.map(StrView))
         };
         Box::pin(f.map_err(map_err).boxed())
@@ -97,17 +118,19 @@ where
         &self,
         addr: AccountAddress,
         resource_type: StrView<StructTag>,
+        block: Option<BlockNumberOrHash>,
     ) -> FutureResult<Option<AnnotatedMoveStructView>> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
         let playground = self.playground.clone();
         let f = async move {
-            let state_root = service.clone().state_root().await?;
-            let data = service
-                .get(AccessPath::resource_access_path(
-                    addr,
-                    resource_type.0.clone(),
-                ))
-                .await?;
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let state = ChainStateDB::try_new(storage, state_root)?;
+            let data = state.get(&AccessPath::resource_access_path(
+                addr,
+                resource_type.0.clone(),
+            ))?;
             match data {
                 None => Ok(None),
                 Some(d) => {
@@ -119,8 +142,13 @@ where
         };
         Box::pin(f.map_err(map_err).boxed())
     }
-    fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>> {
+    fn call(
+        &self,
+        call: ContractCall,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Vec<AnnotatedMoveValueView>> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let playground = self.playground.clone();
         let ContractCall {
             function_id,
@@ -128,7 +156,7 @@ where
             args,
         } = call;
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = resolve_state_root(service, chain, None, block).await?;
             let output = playground.call_contract(
                 state_root,
                 function_id.0.module,
@@ -142,8 +170,13 @@ where
         Box::pin(f.boxed())
     }
 
-    fn call_v2(&self, call: ContractCall) -> FutureResult<Vec<DecodedMoveValue>> {
+    fn call_v2(
+        &self,
+        call: ContractCall,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Vec<DecodedMoveValue>> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let ContractCall {
             function_id,
@@ -152,8 +185,8 @@ where
         } = call;
         let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
-            let state = ChainStateDB::new(storage, Some(state_root));
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let state = ChainStateDB::try_new(storage, state_root)?;
             let output = call_contract(
                 &state,
                 function_id.0.module,
@@ -172,26 +205,33 @@ where
         Box::pin(f.boxed())
     }
 
-    fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<DryRunOutputView> {
+    fn dry_run(
+        &self,
+        txn: DryRunTransactionRequest,
+        block: Option<BlockNumberOrHash>,
+        trace: Option<bool>,
+    ) -> FutureResult<DryRunOutputView> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let txn_builder = self.txn_request_filler();
         let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = resolve_state_root(service, chain, None, block).await?;
             let DryRunTransactionRequest {
                 transaction,
                 sender_public_key,
             } = txn;
 
             let txn = txn_builder.fill_transaction(transaction).await?;
-            let state_view = ChainStateDB::new(storage, Some(state_root));
+            let state_view = ChainStateDB::try_new(storage, state_root)?;
             dry_run(
                 &state_view,
                 DryRunTransaction {
                     raw_txn: txn,
                     public_key: sender_public_key.0,
                 },
+                trace.unwrap_or_default(),
                 metrics,
             )
         }
@@ -203,20 +243,90 @@ where
         &self,
         raw_txn: String,
         sender_public_key: StrView<AccountPublicKey>,
+        block: Option<BlockNumberOrHash>,
+        trace: Option<bool>,
     ) -> FutureResult<DryRunOutputView> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = resolve_state_root(service, chain, None, block).await?;
             let raw_txn = RawUserTransaction::from_str(raw_txn.as_str())?;
-            let state_view = ChainStateDB::new(storage, Some(state_root));
+            let state_view = ChainStateDB::try_new(storage, state_root)?;
             dry_run(
                 &state_view,
                 DryRunTransaction {
                     raw_txn,
                     public_key: sender_public_key.0,
                 },
+                trace.unwrap_or_default(),
+                metrics,
+            )
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn estimate_gas(
+        &self,
+        txn: DryRunTransactionRequest,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<GasEstimateView> {
+        let service = self.chain_state.clone();
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
+        let txn_builder = self.txn_request_filler();
+        let suggested_gas_unit_price = self.pool.suggested_gas_price(None);
+        let metrics = self.playground.metrics.clone();
+        let f = async move {
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let DryRunTransactionRequest {
+                mut transaction,
+                sender_public_key,
+            } = txn;
+            transaction.gas_unit_price = transaction
+                .gas_unit_price
+                .or(Some(suggested_gas_unit_price));
+
+            let txn = txn_builder.fill_transaction(transaction).await?;
+            let state_view = ChainStateDB::try_new(storage, state_root)?;
+            estimate_gas(
+                &state_view,
+                DryRunTransaction {
+                    raw_txn: txn,
+                    public_key: sender_public_key.0,
+                },
+                suggested_gas_unit_price,
+                metrics,
+            )
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn estimate_gas_raw(
+        &self,
+        raw_txn: String,
+        sender_public_key: StrView<AccountPublicKey>,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<GasEstimateView> {
+        let service = self.chain_state.clone();
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
+        let suggested_gas_unit_price = self.pool.suggested_gas_price(None);
+        let metrics = self.playground.metrics.clone();
+        let f = async move {
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let raw_txn = RawUserTransaction::from_str(raw_txn.as_str())?;
+            let state_view = ChainStateDB::try_new(storage, state_root)?;
+            estimate_gas(
+                &state_view,
+                DryRunTransaction {
+                    raw_txn,
+                    public_key: sender_public_key.0,
+                },
+                suggested_gas_unit_price,
                 metrics,
             )
         }
@@ -224,11 +334,17 @@ where
         Box::pin(f.boxed())
     }
 
-    fn resolve_function(&self, function_id: FunctionIdView) -> FutureResult<FunctionABI> {
+    fn resolve_function(
+        &self,
+        function_id: FunctionIdView,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<FunctionABI> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let fut = async move {
-            let state = ChainStateDB::new(storage, Some(service.state_root().await?));
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let state = ChainStateDB::try_new(storage, state_root)?;
             ABIResolver::new(&state)
                 .resolve_function(&function_id.0.module, function_id.0.function.as_ident_str())
         }
@@ -236,22 +352,34 @@ where
         Box::pin(fut.boxed())
     }
 
-    fn resolve_struct(&self, struct_tag: StructTagView) -> FutureResult<StructInstantiation> {
+    fn resolve_struct(
+        &self,
+        struct_tag: StructTagView,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<StructInstantiation> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let fut = async move {
-            let state = ChainStateDB::new(storage, Some(service.state_root().await?));
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let state = ChainStateDB::try_new(storage, state_root)?;
             ABIResolver::new(&state).resolve_struct_tag(&struct_tag.0)
         }
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
 
-    fn resolve_module(&self, module_id: ModuleIdView) -> FutureResult<ModuleABI> {
+    fn resolve_module(
+        &self,
+        module_id: ModuleIdView,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<ModuleABI> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let fut = async move {
-            let state = ChainStateDB::new(storage, Some(service.state_root().await?));
+            let state_root = resolve_state_root(service, chain, None, block).await?;
+            let state = ChainStateDB::try_new(storage, state_root)?;
             ABIResolver::new(&state).resolve_module(&module_id.0)
         }
         .map_err(map_err);
@@ -262,16 +390,38 @@ where
 pub fn dry_run(
     state_view: &dyn StateView,
     txn: DryRunTransaction,
+    trace: bool,
     metrics: Option<VMMetrics>,
 ) -> anyhow::Result<DryRunOutputView> {
-    let (vm_status, output) = starcoin_dev::playground::dry_run(state_view, txn.clone(), metrics)?;
+    let payload = txn.raw_txn.payload().clone();
+    let (vm_status, output, trace) = if trace {
+        let (vm_status, output, trace) =
+            starcoin_dev::playground::trace_dry_run(state_view, txn, metrics)?;
+        (vm_status, output, Some(trace))
+    } else {
+        let (vm_status, output) = starcoin_dev::playground::dry_run(state_view, txn, metrics)?;
+        (vm_status, output, None)
+    };
+    explain_txn_output(state_view, &payload, vm_status, output, trace)
+}
+
+/// Explain the status and decode the output of a txn executed on `state_view`,
+/// and annotate the execution trace if present.
+pub(crate) fn explain_txn_output(
+    state_view: &dyn StateView,
+    payload: &TransactionPayload,
+    vm_status: VMStatus,
+    output: TransactionOutput,
+    trace: Option<ExecutionTrace>,
+) -> anyhow::Result<DryRunOutputView> {
+    let trace = trace.map(|trace| execution_trace_view(state_view, trace, &output));
     let vm_status_explain = vm_status_translator::explain_vm_status(state_view, vm_status)?;
     let mut txn_output: TransactionOutputView = output.into();
 
     let resolver = {
         let module_cache = ModuleCache::new();
         // If the txn is package txn, we need to use modules in the package to resolve transaction output.
-        if let TransactionPayload::Package(p) = txn.raw_txn.into_payload() {
+        if let TransactionPayload::Package(p) = payload {
             let modules = p
                 .modules()
                 .iter()
@@ -306,5 +456,190 @@ pub fn dry_run(
     Ok(DryRunOutputView {
         explained_status: vm_status_explain,
         txn_output,
+        trace,
+    })
+}
+
+/// Annotate the trace by the `state_view` the txn executed on,
+/// the state can not be annotated, such as the resources of the modules published by the txn,
+/// is returned without annotation.
+fn execution_trace_view(
+    state_view: &dyn StateView,
+    trace: ExecutionTrace,
+    output: &TransactionOutput,
+) -> ExecutionTraceView {
+    let annotator = TraceAnnotator::new(state_view);
+    ExecutionTraceView {
+        calls: trace
+            .calls
+            .into_iter()
+            .map(|call| annotator.view_call(call))
+            .collect(),
+        reads: trace
+            .reads
+            .into_iter()
+            .map(|read| annotator.view_read(read))
+            .collect(),
+        writes: output
+            .write_set()
+            .iter()
+            .map(|(access_path, op)| annotator.view_write(access_path, op))
+            .collect(),
+    }
+}
+
+struct TraceAnnotator<'a> {
+    state_view: &'a dyn StateView,
+    annotator: MoveValueAnnotator<'a>,
+    resolver: ABIResolver<'a>,
+}
+
+impl<'a> TraceAnnotator<'a> {
+    fn new(state_view: &'a dyn StateView) -> Self {
+        Self {
+            state_view,
+            annotator: MoveValueAnnotator::new(state_view),
+            resolver: ABIResolver::new(state_view),
+        }
+    }
+
+    fn view_resource(
+        &self,
+        access_path: &AccessPath,
+        value: &[u8],
+    ) -> Option<AnnotatedMoveStructView> {
+        let struct_tag = access_path.path.as_struct_tag()?;
+        self.annotator
+            .view_struct(struct_tag.clone(), value)
+            .ok()
+            .map(AnnotatedMoveStructView::from)
+    }
+
+    fn view_read(&self, read: StateRead) -> StateReadView {
+        StateReadView {
+            exists: read.value.is_some(),
+            resource: read
+                .value
+                .as_ref()
+                .and_then(|value| self.view_resource(&read.access_path, value)),
+            access_path: read.access_path,
+        }
+    }
+
+    fn view_write(&self, access_path: &AccessPath, op: &WriteOp) -> StateWriteView {
+        match op {
+            WriteOp::Deletion => StateWriteView {
+                access_path: access_path.clone(),
+                action: WriteOpView::Deletion,
+                resource: None,
+            },
+            WriteOp::Value(value) => StateWriteView {
+                access_path: access_path.clone(),
+                action: WriteOpView::Value,
+                resource: self.view_resource(access_path, value),
+            },
+        }
+    }
+
+    fn view_call(&self, call: CallTrace) -> CallTraceView {
+        let function = match (call.module, call.function) {
+            (Some(module), Some(function)) => Some(FunctionId { module, function }),
+            _ => None,
+        };
+        let func_abi = function.as_ref().and_then(|f| {
+            self.resolver
+                .resolve_function_instantiation(&f.module, &f.function, &call.ty_args)
+                .ok()
+        });
+        // the signers of script function are not in the args.
+        let args = decode_values(
+            func_abi.as_ref().map(|abi| {
+                let arg_abis = abi.args();
+                arg_abis[arg_abis.len().saturating_sub(call.args.len())..]
+                    .iter()
+                    .map(|arg| arg.type_abi())
+                    .collect()
+            }),
+            &call.args,
+        );
+        let return_values = call.return_values.as_ref().map(|values| {
+            decode_values(
+                func_abi.as_ref().map(|abi| abi.returns().iter().collect()),
+                values,
+            )
+        });
+        CallTraceView {
+            kind: call.kind.to_string(),
+            function: function.map(StrView),
+            ty_args: call.ty_args.into_iter().map(StrView).collect(),
+            args,
+            return_values,
+            gas_used: StrView(call.gas_used),
+            reads: call
+                .reads
+                .into_iter()
+                .map(|read| self.view_read(read))
+                .collect(),
+            writes: call
+                .writes
+                .iter()
+                .map(|(access_path, op)| self.view_write(access_path, op))
+                .collect(),
+            error: call
+                .error
+                .and_then(|e| vm_status_translator::explain_vm_status(self.state_view, e).ok()),
+            children: call
+                .children
+                .into_iter()
+                .map(|child| self.view_call(child))
+                .collect(),
+        }
+    }
+}
+
+/// Decode the bcs `values` by the type abis, or return the hex of the bcs bytes if can not decode.
+fn decode_values(
+    type_abis: Option<Vec<&TypeInstantiation>>,
+    values: &[Vec<u8>],
+) -> Vec<DecodedMoveValue> {
+    type_abis
+        .filter(|type_abis| type_abis.len() == values.len())
+        .and_then(|type_abis| {
+            type_abis
+                .into_iter()
+                .zip(values.iter())
+                .map(|(abi, value)| decode_move_value(abi, value).ok())
+                .collect::<Option<Vec<_>>>()
+        })
+        .unwrap_or_else(|| {
+            values
+                .iter()
+                .map(|value| {
+                    DecodedMoveValue(serde_json::Value::String(format!(
+                        "0x{}",
+                        hex::encode(value)
+                    )))
+                })
+                .collect()
+        })
+}
+
+pub fn estimate_gas(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+    suggested_gas_unit_price: u64,
+    metrics: Option<VMMetrics>,
+) -> anyhow::Result<GasEstimateView> {
+    let gas_unit_price = txn.raw_txn.gas_unit_price();
+    let (max_gas_amount, vm_status, output) =
+        starcoin_dev::playground::estimate_gas(state_view, txn, metrics)?;
+    let executed = matches!(vm_status, VMStatus::Executed);
+    let explained_status = vm_status_translator::explain_vm_status(state_view, vm_status)?;
+    Ok(GasEstimateView {
+        max_gas_amount: executed.then(|| StrView(max_gas_amount)),
+        gas_used: StrView(output.gas_used()),
+        gas_unit_price: StrView(gas_unit_price),
+        suggested_gas_unit_price: StrView(suggested_gas_unit_price),
+        explained_status,
     })
 }
@@ -1,42 +1,77 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::to_invalid_param_err;
+use crate::module::contract_rpc::explain_txn_output;
 use crate::module::txfactory_rpc::TxFactoryStatusHandle;
+use crate::module::{map_err, to_invalid_param_err};
+use anyhow::format_err;
+use futures::future::TryFutureExt;
+use futures::FutureExt;
 use jsonrpc_core::Result;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_executor::profiler::ExecutionProfile;
+use starcoin_executor::VMMetrics;
 use starcoin_logger::prelude::LevelFilter;
 use starcoin_logger::structured_log::set_slog_level;
 use starcoin_logger::{LogPattern, LoggerHandle};
+use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::debug::DebugApi;
-use starcoin_rpc_api::types::FactoryAction;
+use starcoin_rpc_api::types::{
+    BlockNumberOrHash, ContractCall, ContractCallProfileView, ExecutionProfileView, FactoryAction,
+    ModuleProfileView, ProfileSampleView, StrView, TransactionProfileView, TransactionTraceView,
+};
+use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::Storage;
 use starcoin_types::system_events::GenerateBlockEvent;
+use starcoin_types::transaction::{
+    DryRunTransaction, RawUserTransaction, RichTransactionInfo, Transaction, TransactionOutput,
+    TransactionPayload,
+};
+use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
 use std::str::FromStr;
 use std::sync::Arc;
 
-pub struct DebugRpcImpl {
+pub struct DebugRpcImpl<C> {
     config: Arc<NodeConfig>,
     log_handle: Arc<LoggerHandle>,
     bus: ServiceRef<BusService>,
+    chain_service: C,
+    storage: Arc<Storage>,
+    vm_metrics: Option<VMMetrics>,
 }
 
-impl DebugRpcImpl {
+impl<C> DebugRpcImpl<C>
+where
+    C: ChainAsyncService + 'static,
+{
     pub fn new(
         config: Arc<NodeConfig>,
         log_handle: Arc<LoggerHandle>,
         bus: ServiceRef<BusService>,
+        chain_service: C,
+        storage: Arc<Storage>,
+        vm_metrics: Option<VMMetrics>,
     ) -> Self {
         Self {
             config,
             log_handle,
             bus,
+            chain_service,
+            storage,
+            vm_metrics,
         }
     }
 }
 
-impl DebugApi for DebugRpcImpl {
+impl<C> DebugApi for DebugRpcImpl<C>
+where
+    C: ChainAsyncService + 'static,
+{
     fn set_log_level(&self, logger_name: Option<String>, level: String) -> Result<()> {
         let logger_name = logger_name.and_then(|s| {
             let s = s.trim();
@@ -82,4 +117,254 @@ impl DebugApi for DebugRpcImpl {
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool> {
         Ok(TxFactoryStatusHandle::handle_action(action))
     }
+
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionTraceView> {
+        let service = self.chain_service.clone();
+        let storage = self.storage.clone();
+        let metrics = self.vm_metrics.clone();
+        let f = async move {
+            let committed = CommittedTransaction::load(&service, txn_hash).await?;
+            let state = ChainStateDB::new(storage, Some(committed.parent_state_root));
+            let (vm_status, output, trace) = starcoin_dev::playground::replay_and_trace(
+                &state,
+                committed.txns.clone(),
+                committed.txn_index,
+                metrics,
+            )?;
+            committed.check_replayed_output(&output)?;
+            let output_view =
+                explain_txn_output(&state, &committed.payload, vm_status, output, Some(trace))?;
+            Ok(TransactionTraceView {
+                transaction_hash: txn_hash,
+                block_hash: committed.txn_info.block_id(),
+                explained_status: output_view.explained_status,
+                txn_output: output_view.txn_output,
+                trace: output_view
+                    .trace
+                    .ok_or_else(|| format_err!("The trace of {} is missing", txn_hash))?,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn profile_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionProfileView> {
+        let service = self.chain_service.clone();
+        let storage = self.storage.clone();
+        let metrics = self.vm_metrics.clone();
+        let f = async move {
+            let committed = CommittedTransaction::load(&service, txn_hash).await?;
+            let state = ChainStateDB::new(storage, Some(committed.parent_state_root));
+            let (vm_status, output, profile) = starcoin_dev::playground::replay_and_profile(
+                &state,
+                committed.txns.clone(),
+                committed.txn_index,
+                metrics,
+            )?;
+            committed.check_replayed_output(&output)?;
+            let output_view =
+                explain_txn_output(&state, &committed.payload, vm_status, output, None)?;
+            Ok(TransactionProfileView {
+                explained_status: output_view.explained_status,
+                txn_output: output_view.txn_output,
+                profile: execution_profile_view(profile),
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn profile_dry_run_raw(
+        &self,
+        raw_txn: String,
+        sender_public_key: StrView<AccountPublicKey>,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<TransactionProfileView> {
+        let service = self.chain_service.clone();
+        let storage = self.storage.clone();
+        let metrics = self.vm_metrics.clone();
+        let f = async move {
+            let state_root = state_root_of_block(&service, block).await?;
+            let raw_txn = RawUserTransaction::from_str(raw_txn.as_str())?;
+            let payload = raw_txn.payload().clone();
+            let state = ChainStateDB::new(storage, Some(state_root));
+            let (vm_status, output, profile) = starcoin_dev::playground::profile_dry_run(
+                &state,
+                DryRunTransaction {
+                    raw_txn,
+                    public_key: sender_public_key.0,
+                },
+                metrics,
+            )?;
+            let output_view = explain_txn_output(&state, &payload, vm_status, output, None)?;
+            Ok(TransactionProfileView {
+                explained_status: output_view.explained_status,
+                txn_output: output_view.txn_output,
+                profile: execution_profile_view(profile),
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn profile_call(
+        &self,
+        call: ContractCall,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<ContractCallProfileView> {
+        let service = self.chain_service.clone();
+        let storage = self.storage.clone();
+        let metrics = self.vm_metrics.clone();
+        let ContractCall {
+            function_id,
+            type_args,
+            args,
+        } = call;
+        let f = async move {
+            let state_root = state_root_of_block(&service, block).await?;
+            let state = ChainStateDB::new(storage, Some(state_root));
+            let (output, profile) = starcoin_dev::playground::profile_call_contract(
+                &state,
+                function_id.0.module,
+                function_id.0.function.as_str(),
+                type_args.into_iter().map(|v| v.0).collect(),
+                args.into_iter().map(|v| v.0).collect(),
+                metrics,
+            )?;
+            let annotator = MoveValueAnnotator::new(&state);
+            let return_values = output
+                .into_iter()
+                .map(|(ty, v)| annotator.view_value(&ty, &v).map(Into::into))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Ok(ContractCallProfileView {
+                return_values,
+                profile: execution_profile_view(profile),
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+}
+
+/// A committed user transaction with the transactions of its block.
+struct CommittedTransaction {
+    txn_hash: HashValue,
+    txn_info: RichTransactionInfo,
+    parent_state_root: HashValue,
+    /// The transactions of the block, the block metadata is the first one.
+    txns: Vec<Transaction>,
+    txn_index: usize,
+    payload: TransactionPayload,
+}
+
+impl CommittedTransaction {
+    async fn load<C>(service: &C, txn_hash: HashValue) -> anyhow::Result<Self>
+    where
+        C: ChainAsyncService + 'static,
+    {
+        let txn_info = service
+            .get_transaction_info(txn_hash)
+            .await?
+            .ok_or_else(|| format_err!("Can not find transaction info of {}", txn_hash))?;
+        let block_hash = txn_info.block_id();
+        let block = service
+            .get_block_by_hash(block_hash)
+            .await?
+            .ok_or_else(|| format_err!("Can not find block {}", block_hash))?;
+        let parent_header = service
+            .get_header_by_hash(&block.header().parent_hash())
+            .await?
+            .ok_or_else(|| format_err!("Can not find parent block of block {}", block_hash))?;
+        // same as the block execution of the chain, the block metadata is the first txn.
+        let mut txns = vec![Transaction::BlockMetadata(
+            block.to_metadata(parent_header.gas_used()),
+        )];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        let txn_index = txn_info.transaction_index as usize;
+        let payload = match txns.get(txn_index) {
+            Some(Transaction::UserTransaction(txn)) if txn.id() == txn_hash => {
+                txn.payload().clone()
+            }
+            _ => anyhow::bail!("Transaction {} is not a user transaction", txn_hash),
+        };
+        Ok(Self {
+            txn_hash,
+            txn_info,
+            parent_state_root: parent_header.state_root(),
+            txns,
+            txn_index,
+            payload,
+        })
+    }
+
+    fn check_replayed_output(&self, output: &TransactionOutput) -> anyhow::Result<()> {
+        if output.status().status().ok().as_ref() != Some(self.txn_info.status()) {
+            return Err(format_err!(
+                "The replayed status {:?} of transaction {} is different from the committed status {:?}",
+                output.status(),
+                self.txn_hash,
+                self.txn_info.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+async fn state_root_of_block<C>(
+    service: &C,
+    block: Option<BlockNumberOrHash>,
+) -> anyhow::Result<HashValue>
+where
+    C: ChainAsyncService + 'static,
+{
+    let header = match block {
+        None => service.main_head_header().await?,
+        Some(BlockNumberOrHash::Number(number)) => service
+            .main_block_header_by_number(number)
+            .await?
+            .ok_or_else(|| format_err!("cannot find block by number {}", number))?,
+        Some(BlockNumberOrHash::Hash(hash)) => service
+            .get_header_by_hash(&hash)
+            .await?
+            .ok_or_else(|| format_err!("cannot find block by hash {}", hash))?,
+    };
+    Ok(header.state_root())
+}
+
+fn execution_profile_view(profile: ExecutionProfile) -> ExecutionProfileView {
+    let modules = profile
+        .module_summary()
+        .into_iter()
+        .map(|module| ModuleProfileView {
+            module: module.module,
+            functions: module.functions as u64,
+            instructions: StrView(module.instructions),
+            gas: StrView(module.gas),
+            time: StrView(module.time.as_nanos() as u64),
+        })
+        .collect();
+    ExecutionProfileView {
+        bytecode_level: profile.bytecode_level,
+        gas: StrView(profile.gas()),
+        time: StrView(profile.time().as_nanos() as u64),
+        samples: profile
+            .samples
+            .into_iter()
+            .map(|sample| ProfileSampleView {
+                stack: sample.stack,
+                offset: sample.offset,
+                instruction: sample.instruction,
+                count: StrView(sample.count),
+                gas: StrView(sample.gas),
+                time: StrView(sample.time.as_nanos() as u64),
+            })
+            .collect(),
+        modules,
+    }
 }
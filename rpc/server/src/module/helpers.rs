@@ -1,6 +1,9 @@
+use anyhow::format_err;
 use starcoin_account_api::AccountAsyncService;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
-use starcoin_rpc_api::types::TransactionRequest;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::{BlockNumberOrHash, TransactionRequest};
 use starcoin_state_api::ChainStateAsyncService;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_config::AccountResource;
@@ -103,3 +106,33 @@ where
         Ok(raw_txn)
     }
 }
+
+/// Resolve the state root to run on, the state root of `block` is used if `block` is set,
+/// otherwise `state_root`, default is the state root of the latest block.
+pub(crate) async fn resolve_state_root<State, Chain>(
+    state_service: State,
+    chain_service: Chain,
+    state_root: Option<HashValue>,
+    block: Option<BlockNumberOrHash>,
+) -> anyhow::Result<HashValue>
+where
+    State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
+{
+    let header = match (state_root, block) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("state_root and block can not be set at the same time")
+        }
+        (Some(state_root), None) => return Ok(state_root),
+        (None, None) => return state_service.state_root().await,
+        (None, Some(BlockNumberOrHash::Number(number))) => chain_service
+            .main_block_header_by_number(number)
+            .await?
+            .ok_or_else(|| format_err!("cannot find block by number {}", number))?,
+        (None, Some(BlockNumberOrHash::Hash(hash))) => chain_service
+            .get_header_by_hash(&hash)
+            .await?
+            .ok_or_else(|| format_err!("cannot find block by hash {}", hash))?,
+    };
+    Ok(header.state_root())
+}
@@ -3,22 +3,35 @@
 
 use crate::module::map_err;
 use futures::{FutureExt, TryFutureExt};
-use starcoin_miner::{MinerService, SubmitSealRequest, UpdateSubscriberNumRequest};
+use starcoin_miner::{
+    HashRateRequest, MinerService, SubmitHashRateRequest, SubmitSealRequest,
+    UpdateSubscriberNumRequest,
+};
 use starcoin_rpc_api::miner::MinerApi;
-use starcoin_rpc_api::types::MintedBlockView;
+use starcoin_rpc_api::types::{
+    MinerHashRateView, MintedBlockView, StrView, StratumWorkerView, WorkerHashRateView,
+};
 use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::ServiceRef;
+use starcoin_stratum::stratum::{Stratum, WorkerStatsRequest};
 use starcoin_types::block::BlockHeaderExtra;
 use starcoin_types::system_events::MintBlockEvent;
 use std::convert::TryInto;
 
 pub struct MinerRpcImpl {
     miner_service: ServiceRef<MinerService>,
+    stratum: Option<ServiceRef<Stratum>>,
 }
 
 impl MinerRpcImpl {
-    pub fn new(miner_service: ServiceRef<MinerService>) -> Self {
-        Self { miner_service }
+    pub fn new(
+        miner_service: ServiceRef<MinerService>,
+        stratum: Option<ServiceRef<Stratum>>,
+    ) -> Self {
+        Self {
+            miner_service,
+            stratum,
+        }
     }
 }
 
@@ -61,4 +74,58 @@ impl MinerApi for MinerRpcImpl {
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn submit_hashrate(&self, worker: String, hash_rate: u64) -> FutureResult<()> {
+        let miner_service = self.miner_service.clone();
+        let fut = async move {
+            miner_service
+                .send(SubmitHashRateRequest { worker, hash_rate })
+                .await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_hashrate(&self) -> FutureResult<MinerHashRateView> {
+        let miner_service = self.miner_service.clone();
+        let fut = async move {
+            let workers = miner_service.send(HashRateRequest).await?;
+            let total = workers.iter().map(|worker| worker.hash_rate).sum();
+            Ok(MinerHashRateView {
+                total: StrView(total),
+                workers: workers
+                    .into_iter()
+                    .map(|worker| WorkerHashRateView {
+                        worker: worker.worker,
+                        hash_rate: StrView(worker.hash_rate),
+                        last_report_secs: StrView(worker.last_report.as_secs()),
+                    })
+                    .collect(),
+            })
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_stratum_workers(&self) -> FutureResult<Vec<StratumWorkerView>> {
+        let stratum = self.stratum.clone();
+        let fut = async move {
+            let stratum = stratum.ok_or_else(|| anyhow::anyhow!("Stratum is not enabled"))?;
+            let workers = stratum.send(WorkerStatsRequest).await?;
+            Ok(workers
+                .into_iter()
+                .map(|worker| StratumWorkerView {
+                    login: worker.login,
+                    share_difficulty: worker.share_difficulty,
+                    valid_shares: StrView(worker.valid_shares),
+                    stale_shares: StrView(worker.stale_shares),
+                    invalid_shares: StrView(worker.invalid_shares),
+                    hash_rate: StrView(worker.hash_rate),
+                    last_share_secs: worker.last_share.map(|elapsed| StrView(elapsed.as_secs())),
+                })
+                .collect())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
@@ -36,6 +36,7 @@ use hex::FromHexError;
 use jsonrpc_core::ErrorCode;
 use starcoin_account_api::error::AccountError;
 use starcoin_rpc_api::types::TransactionStatusView;
+use starcoin_state_api::StatePrunedError;
 use starcoin_vm_types::transaction::{CallError, TransactionError, TransactionStatus};
 use starcoin_vm_types::vm_status::VMStatus;
 
@@ -55,6 +56,8 @@ pub fn map_err(err: anyhow::Error) -> jsonrpc_core::Error {
         err.downcast::<MailboxError>().unwrap().into()
     } else if err.is::<VMStatus>() {
         err.downcast::<VMStatus>().unwrap().into()
+    } else if err.is::<StatePrunedError>() {
+        err.downcast::<StatePrunedError>().unwrap().into()
     } else {
         err.into()
     };
@@ -91,6 +94,7 @@ impl From<anyhow::Error> for RpcError {
 
 const TXN_ERROR_BASE: i64 = -50000;
 const ACCOUNT_ERROR_BASE: i64 = -60000;
+const STATE_ERROR_BASE: i64 = -70000;
 
 impl From<AccountError> for RpcError {
     fn from(err: AccountError) -> Self {
@@ -183,6 +187,16 @@ impl From<MailboxError> for RpcError {
     }
 }
 
+impl From<StatePrunedError> for RpcError {
+    fn from(err: StatePrunedError) -> Self {
+        RpcError(jsonrpc_core::Error {
+            code: ErrorCode::ServerError(STATE_ERROR_BASE),
+            message: err.to_string(),
+            data: None,
+        })
+    }
+}
+
 impl From<VMStatus> for RpcError {
     fn from(vm_status: VMStatus) -> Self {
         RpcError(jsonrpc_core::Error {
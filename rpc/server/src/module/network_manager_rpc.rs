@@ -5,6 +5,7 @@ use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use jsonrpc_core::Result;
+use network_api::messages::BannedPeerInfo;
 use network_api::{PeerProvider, ReputationChange, BANNED_THRESHOLD};
 use network_p2p_types::network_state::NetworkState;
 use network_rpc_core::RawRpcClient;
@@ -110,4 +111,27 @@ impl NetworkManagerApi for NetworkManagerRpcImpl {
         service.ban_peer(peer_id, ban);
         Ok(())
     }
+
+    fn list_bans(&self) -> FutureResult<Vec<BannedPeerInfo>> {
+        let service = self.service.clone();
+        let fut = async move { service.list_bans().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn unban(&self, peer_id: String) -> Result<()> {
+        let service = self.service.clone();
+        let peer_id = PeerId::from_str(peer_id.as_str()).map_err(map_err)?;
+        service.ban_peer(peer_id, false);
+        Ok(())
+    }
+
+    fn add_reserved(&self, peer: String) -> Result<()> {
+        self.service.add_reserved_peer(peer).map_err(map_err)
+    }
+
+    fn remove_reserved(&self, peer_id: String) -> Result<()> {
+        let peer_id = PeerId::from_str(peer_id.as_str()).map_err(map_err)?;
+        self.service.remove_reserved_peer(peer_id);
+        Ok(())
+    }
 }
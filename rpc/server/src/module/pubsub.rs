@@ -27,7 +27,7 @@ use starcoin_storage::Storage;
 use starcoin_txpool::TxPoolService;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
@@ -131,6 +131,23 @@ impl PubSubImpl {
                 subscriber,
                 errors::invalid_params("events", "Expected a filter object."),
             )),
+            (pubsub::Kind::ChainReorg, None) => self
+                .service
+                .try_send(SubscribeChainReorg(subscriber))
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.0,
+                            TrySendError::Full(t) => t.0,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::ChainReorg, _) => Err((
+                subscriber,
+                errors::invalid_params("chainReorg", "Expected no parameters."),
+            )),
             (pubsub::Kind::NewMintBlock, _) => self
                 .service
                 .try_send(SubscribeMintBlock(subscriber))
@@ -206,6 +223,7 @@ pub struct PubSubService {
     new_event_subscribers:
         HashMap<SubscriptionId, mpsc::UnboundedSender<ContractEventNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
+    chain_reorg_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<ChainReorgEvent>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
 }
 
@@ -224,6 +242,7 @@ impl PubSubService {
             new_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
+            chain_reorg_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
         }
     }
@@ -242,6 +261,7 @@ impl ActorService for PubSubService {
         ctx.subscribe::<NewHeadNotification>();
         ctx.subscribe::<ContractEventNotification>();
         ctx.subscribe::<MintBlockEvent>();
+        ctx.subscribe::<ChainReorgEvent>();
 
         Ok(())
     }
@@ -269,6 +289,12 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
     }
 }
 
+impl ActorEventHandler<Self, ChainReorgEvent> for PubSubService {
+    fn handle_event(&mut self, msg: ChainReorgEvent, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(&mut self.chain_reorg_subscribers, msg);
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeNewHeads(Subscriber<pubsub::Result>);
 
@@ -292,6 +318,29 @@ impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeChainReorg(Subscriber<pubsub::Result>);
+
+impl ServiceRequest for SubscribeChainReorg {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeChainReorg> for PubSubService {
+    fn handle(&mut self, msg: SubscribeChainReorg, ctx: &mut ServiceContext<Self>) {
+        let SubscribeChainReorg(sink) = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.chain_reorg_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            sink,
+            ChainReorgHandler,
+        ));
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeMintBlock(Subscriber<pubsub::Result>);
 
@@ -418,6 +467,7 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         self.new_header_subscribers.remove(&msg.0);
         self.new_event_subscribers.remove(&msg.0);
         self.mint_block_subscribers.remove(&msg.0);
+        self.chain_reorg_subscribers.remove(&msg.0);
         self.miner_service.do_send(UpdateSubscriberNumRequest {
             number: Some(self.mint_block_subscribers.len() as u32),
         });
@@ -512,6 +562,22 @@ impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct ChainReorgHandler;
+
+impl EventHandler<ChainReorgEvent> for ChainReorgHandler {
+    fn handle(&self, msg: ChainReorgEvent) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        vec![Ok(pubsub::Result::ChainReorg(Box::new(
+            pubsub::ChainReorg {
+                common_ancestor: msg.common_ancestor,
+                retracted: msg.retracted.iter().map(|b| b.id()).collect(),
+                enacted: msg.enacted.iter().map(|b| b.id()).collect(),
+                truncated: msg.truncated,
+            },
+        )))]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ContractEventHandler {
     filter: Filter,
@@ -562,6 +628,7 @@ impl EventHandler<ContractEventNotification> for ContractEventHandler {
                         &e.contract_event,
                     ),
                     decode_event_data: decoded_data,
+                    removed: e.removed,
                 })
             })
             .map(|e| {
@@ -17,13 +17,14 @@ use starcoin_executor::DEFAULT_EXPIRATION_TIME;
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_api::pubsub::StarcoinPubSub;
+use starcoin_rpc_api::types::pubsub;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::RegistryAsyncService;
 use starcoin_state_api::StateReaderExt;
 use starcoin_storage::BlockStore;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::system_events::MintBlockEvent;
-use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::system_events::{ChainReorgEvent, NewHeadBlock};
 use starcoin_types::{account_address, U256};
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use std::sync::Arc;
@@ -130,6 +131,124 @@ pub async fn test_subscribe_to_events() -> Result<()> {
     Ok(())
 }
 
+#[actix_rt::test]
+pub async fn test_subscribe_to_chain_reorg() -> Result<()> {
+    starcoin_logger::init_for_test();
+    let (_txpool_service, storage, config, _, registry) =
+        test_helper::start_txpool_with_miner(1000, true).await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let net = config.net();
+    let miner_account = AccountInfo::random();
+
+    // a block with a transfer txn, which will be retracted.
+    let mut retracted_chain =
+        BlockChain::new(net.time_service(), startup_info.main, storage.clone(), None)?;
+    let txn = {
+        let txn = starcoin_executor::build_transfer_from_association(
+            *AccountInfo::random().address(),
+            0,
+            10000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net,
+        );
+        txn.as_signed_user_txn()?.clone()
+    };
+    let (block_template, _) = retracted_chain.create_block_template(
+        *miner_account.address(),
+        None,
+        vec![txn],
+        vec![],
+        None,
+    )?;
+    let retracted_block = retracted_chain
+        .consensus()
+        .create_block(block_template, net.time_service().as_ref())?;
+    retracted_chain.apply(retracted_block.clone())?;
+
+    // an empty block with the same parent, which will be enacted.
+    net.time_service().sleep(1000);
+    let mut enacted_chain = BlockChain::new(net.time_service(), startup_info.main, storage, None)?;
+    let (block_template, _) = enacted_chain.create_block_template(
+        *miner_account.address(),
+        None,
+        vec![],
+        vec![],
+        None,
+    )?;
+    let enacted_block = enacted_chain
+        .consensus()
+        .create_block(block_template, net.time_service().as_ref())?;
+    enacted_chain.apply(enacted_block.clone())?;
+
+    let bus = registry.service_ref::<BusService>().await?;
+    let _notify_service = registry
+        .register::<ChainNotifyHandlerService>()
+        .await
+        .unwrap();
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    // Fail if params are provided
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"chainReorg"}, {}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: chainReorg","data":"\"Expected no parameters.\""},"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"chainReorg"}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"events"}, {}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":1,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    bus.broadcast(ChainReorgEvent {
+        common_ancestor: startup_info.main,
+        retracted: vec![retracted_block.clone()].into(),
+        enacted: vec![enacted_block.clone()].into(),
+        truncated: false,
+    })?;
+
+    let mut reorg_notified = false;
+    let mut removed_events = 0;
+    while let Ok(Some(res)) = timeout(Duration::from_secs(2), receiver.next()).await {
+        let r: Value = serde_json::from_str(&res).unwrap();
+        let result = &r["params"]["result"];
+        match r["params"]["subscription"].as_u64() {
+            Some(0) => {
+                let reorg: pubsub::ChainReorg = serde_json::from_value(result.clone()).unwrap();
+                assert_eq!(reorg.common_ancestor, startup_info.main);
+                assert_eq!(reorg.retracted, vec![retracted_block.id()]);
+                assert_eq!(reorg.enacted, vec![enacted_block.id()]);
+                assert!(!reorg.truncated);
+                reorg_notified = true;
+            }
+            Some(1) => {
+                let block_hash: HashValue =
+                    serde_json::from_value(result["block_hash"].clone()).unwrap();
+                assert_eq!(block_hash, retracted_block.id());
+                assert_eq!(result["removed"], Value::Bool(true));
+                removed_events += 1;
+            }
+            s => panic!("unexpected subscription: {:?}", s),
+        }
+    }
+    assert!(reorg_notified);
+    assert!(removed_events > 0);
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     // given
@@ -1,11 +1,13 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::helpers::resolve_state_root;
 use crate::module::map_err;
 use bcs_ext::BCSCodec;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use starcoin_abi_resolver::ABIResolver;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_crypto::HashValue;
 use starcoin_dev::playground::view_resource;
 use starcoin_resource_viewer::MoveValueAnnotator;
@@ -13,8 +15,8 @@ use starcoin_rpc_api::state::{
     GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption, StateApi,
 };
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, CodeView, ListCodeView, ListResourceView,
-    ResourceView, StateWithProofView, StrView, StructTagView,
+    AccountStateSetView, AnnotatedMoveStructView, BlockNumberOrHash, CodeView, ListCodeView,
+    ListResourceView, ResourceView, StateWithProofView, StrView, StructTagView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::{ChainStateAsyncService, StateView};
@@ -29,64 +31,115 @@ use starcoin_vm_types::language_storage::StructTag;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-pub struct StateRpcImpl<S>
+pub struct StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService + 'static,
+    C: ChainAsyncService + 'static,
 {
     service: S,
+    chain_service: C,
     state_store: Arc<dyn StateNodeStore>,
 }
 
-impl<S> StateRpcImpl<S>
+impl<S, C> StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    pub fn new(service: S, state_store: Arc<dyn StateNodeStore>) -> Self {
+    pub fn new(service: S, chain_service: C, state_store: Arc<dyn StateNodeStore>) -> Self {
         Self {
             service,
+            chain_service,
             state_store,
         }
     }
 }
 
-impl<S> StateApi for StateRpcImpl<S>
+impl<S, C> StateApi for StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>> {
-        let fut = self.service.clone().get(access_path).map_err(map_err);
-        Box::pin(fut)
+    fn get(
+        &self,
+        access_path: AccessPath,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<Vec<u8>>> {
+        let service = self.service.clone();
+        let fut = match block {
+            None => service.get(access_path).boxed(),
+            Some(block) => {
+                let chain_service = self.chain_service.clone();
+                let db = self.state_store.clone();
+                async move {
+                    let state_root =
+                        resolve_state_root(service, chain_service, None, Some(block)).await?;
+                    ChainStateDB::try_new(db, state_root)?.get(&access_path)
+                }
+                .boxed()
+            }
+        };
+        Box::pin(fut.map_err(map_err))
     }
 
-    fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProofView> {
-        let fut = self
-            .service
-            .clone()
-            .get_with_proof(access_path)
-            .map_ok(|p| p.into())
-            .map_err(map_err);
-        Box::pin(fut)
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<StateWithProofView> {
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            let proof = match block {
+                None => service.get_with_proof(access_path).await?,
+                Some(block) => {
+                    let state_root =
+                        resolve_state_root(service.clone(), chain_service, None, Some(block))
+                            .await?;
+                    service
+                        .get_with_proof_by_root(access_path, state_root)
+                        .await?
+                }
+            };
+            Ok(proof.into())
+        };
+        Box::pin(fut.map_err(map_err).boxed())
     }
 
-    fn get_account_state(&self, address: AccountAddress) -> FutureResult<Option<AccountState>> {
-        let fut = self
-            .service
-            .clone()
-            .get_account_state(address)
-            .map_err(map_err);
-        Box::pin(fut)
+    fn get_account_state(
+        &self,
+        address: AccountAddress,
+        block: Option<BlockNumberOrHash>,
+    ) -> FutureResult<Option<AccountState>> {
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            match block {
+                None => service.get_account_state(address).await,
+                Some(block) => {
+                    let state_root =
+                        resolve_state_root(service.clone(), chain_service, None, Some(block))
+                            .await?;
+                    service.get_account_state_by_root(address, state_root).await
+                }
+            }
+        };
+        Box::pin(fut.map_err(map_err).boxed())
     }
 
     fn get_account_state_set(
         &self,
         address: AccountAddress,
         state_root: Option<HashValue>,
+        block: Option<BlockNumberOrHash>,
     ) -> FutureResult<Option<AccountStateSetView>> {
         let state_service = self.service.clone();
+        let chain_service = self.chain_service.clone();
         let db = self.state_store.clone();
         let fut = async move {
-            let state_root = state_root.unwrap_or(state_service.state_root().await?);
-            let statedb = ChainStateDB::new(db, Some(state_root));
+            let state_root =
+                resolve_state_root(state_service, chain_service, state_root, block).await?;
+            let statedb = ChainStateDB::try_new(db, state_root)?;
             let state = statedb.get_account_state_set(&address)?;
             let annotator = MoveValueAnnotator::new(&statedb);
             match state {
@@ -127,9 +180,15 @@ where
         Box::pin(fut.map_err(map_err).boxed())
     }
 
-    fn get_state_root(&self) -> FutureResult<HashValue> {
-        let fut = self.service.clone().state_root().map_err(map_err);
-        Box::pin(fut)
+    fn get_state_root(&self, block: Option<BlockNumberOrHash>) -> FutureResult<HashValue> {
+        let fut = resolve_state_root(
+            self.service.clone(),
+            self.chain_service.clone(),
+            None,
+            block,
+        )
+        .map_err(map_err);
+        Box::pin(fut.boxed())
     }
 
     fn get_with_proof_by_root(
@@ -152,13 +211,13 @@ where
         option: Option<GetCodeOption>,
     ) -> FutureResult<Option<CodeView>> {
         let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
         let state_store = self.state_store.clone();
         let option = option.unwrap_or_default();
         let f = async move {
-            let state_root = option
-                .state_root
-                .unwrap_or(service.clone().state_root().await?);
-            let chain_state = ChainStateDB::new(state_store, Some(state_root));
+            let state_root =
+                resolve_state_root(service, chain_service, option.state_root, option.block).await?;
+            let chain_state = ChainStateDB::try_new(state_store, state_root)?;
             let code = chain_state.get(&AccessPath::from(&module_id.0))?;
             Ok(match code {
                 None => None,
@@ -186,13 +245,13 @@ where
         option: Option<GetResourceOption>,
     ) -> FutureResult<Option<ResourceView>> {
         let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
         let state_store = self.state_store.clone();
         let option = option.unwrap_or_default();
         let f = async move {
-            let state_root = option
-                .state_root
-                .unwrap_or(service.clone().state_root().await?);
-            let chain_state = ChainStateDB::new(state_store, Some(state_root));
+            let state_root =
+                resolve_state_root(service, chain_service, option.state_root, option.block).await?;
+            let chain_state = ChainStateDB::try_new(state_store, state_root)?;
             let data = chain_state.get(&AccessPath::resource_access_path(
                 addr,
                 resource_type.0.clone(),
@@ -223,13 +282,18 @@ where
         option: Option<ListResourceOption>,
     ) -> FutureResult<ListResourceView> {
         let state_service = self.service.clone();
+        let chain_service = self.chain_service.clone();
         let db = self.state_store.clone();
         let option = option.unwrap_or_default();
         let fut = async move {
-            let state_root = option
-                .state_root
-                .unwrap_or(state_service.state_root().await?);
-            let statedb = ChainStateDB::new(db, Some(state_root));
+            let state_root = resolve_state_root(
+                state_service,
+                chain_service,
+                option.state_root,
+                option.block,
+            )
+            .await?;
+            let statedb = ChainStateDB::try_new(db, state_root)?;
             //TODO implement list state by iter, and pagination
             let state = statedb.get_account_state_set(&addr)?;
             match state {
@@ -275,13 +339,18 @@ where
         option: Option<ListCodeOption>,
     ) -> FutureResult<ListCodeView> {
         let state_service = self.service.clone();
+        let chain_service = self.chain_service.clone();
         let db = self.state_store.clone();
         let option = option.unwrap_or_default();
         let fut = async move {
-            let state_root = option
-                .state_root
-                .unwrap_or(state_service.state_root().await?);
-            let statedb = ChainStateDB::new(db, Some(state_root));
+            let state_root = resolve_state_root(
+                state_service,
+                chain_service,
+                option.state_root,
+                option.block,
+            )
+            .await?;
+            let statedb = ChainStateDB::try_new(db, state_root)?;
             //TODO implement list state by iter, and pagination
             let state = statedb.get_account_state_set(&addr)?;
             match state {
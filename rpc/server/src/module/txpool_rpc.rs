@@ -40,7 +40,7 @@ where
         let txn_hash = txn.id();
         let result: Result<(), jsonrpc_core::Error> = self
             .service
-            .add_txns(vec![txn])
+            .add_local_txns(vec![txn])
             .pop()
             .expect("txpool should return result")
             .map_err(convert_to_rpc_error);
@@ -56,7 +56,7 @@ where
             .and_then(|txn| {
                 let txn_hash = txn.id();
                 self.service
-                    .add_txns(vec![txn])
+                    .add_local_txns(vec![txn])
                     .pop()
                     .expect("txpool should return result")
                     .map(|_| txn_hash)
@@ -9,6 +9,8 @@ pub use api_limiter::Quota;
 use jsonrpc_core::middleware::NoopCallFuture;
 use starcoin_config::{ApiQuotaConfig, ApiQuotaConfiguration, QuotaDuration};
 use starcoin_rpc_api::metadata::Metadata;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 struct QuotaWrapper(Quota);
 
@@ -23,13 +25,17 @@ impl From<ApiQuotaConfig> for QuotaWrapper {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct JsonApiRateLimitMiddleware {
-    limiters: ApiLimiters<MethodName, String>,
+    limiters: Arc<ApiLimiters<MethodName, String>>,
 }
 
 impl JsonApiRateLimitMiddleware {
-    pub fn from_config(quotas: ApiQuotaConfiguration) -> Self {
+    /// `user_quotas` is the quota of the authenticated users, override the default user api quota.
+    pub fn from_config(
+        quotas: ApiQuotaConfiguration,
+        user_quotas: HashMap<String, ApiQuotaConfig>,
+    ) -> Self {
         let limiters = ApiLimiters::new(
             Into::<QuotaWrapper>::into(quotas.default_global_api_quota()).0,
             quotas
@@ -43,8 +49,27 @@ impl JsonApiRateLimitMiddleware {
                 .into_iter()
                 .map(|(k, v)| (k, Into::<QuotaWrapper>::into(v).0))
                 .collect(),
+        )
+        .with_custom_user_quotas(
+            user_quotas
+                .into_iter()
+                .map(|(user, quota)| (user, Into::<QuotaWrapper>::into(quota).0))
+                .collect(),
         );
-        Self { limiters }
+        Self {
+            limiters: Arc::new(limiters),
+        }
+    }
+
+    /// Check the quota of the `method` called by the `user`.
+    pub fn check(&self, method: &str, user: Option<&String>) -> Result<(), Error> {
+        self.limiters
+            .check(&method.to_string(), user)
+            .map_err(|e| Error {
+                code: ErrorCode::ServerError(-10000),
+                message: e.to_string(),
+                data: None,
+            })
     }
 }
 
@@ -64,16 +89,12 @@ impl Middleware<Metadata> for JsonApiRateLimitMiddleware {
             Call::Invalid { .. } => None,
         };
         if let Some((m, json_version, id)) = method {
-            match self.limiters.check(&m, meta.user.as_ref()) {
+            match self.check(&m, meta.user.as_ref()) {
                 Ok(_) => Either::Right(next(call, meta)),
-                Err(e) => {
+                Err(error) => {
                     let output = Output::Failure(Failure {
                         jsonrpc: json_version,
-                        error: Error {
-                            code: ErrorCode::ServerError(-10000),
-                            message: e.to_string(),
-                            data: None,
-                        },
+                        error,
                         id,
                     });
                     Either::Left(Box::pin(futures::future::ready(Some(output))))
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::api_registry::ApiRegistry;
+use crate::auth_middleware::RpcAuthenticator;
 use crate::extractors::{RpcExtractor, WsExtractor};
 use anyhow::Result;
 use futures::stream::*;
@@ -44,11 +45,7 @@ pub struct RpcService {
 
 impl ActorService for RpcService {
     fn started(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
-        self.ipc = self.start_ipc()?;
-        self.http = self.start_http()?;
-        self.tcp = self.start_tcp()?;
-        self.ws = self.start_ws()?;
-        Ok(())
+        self.start()
     }
 
     fn stopped(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
@@ -58,7 +55,11 @@ impl ActorService for RpcService {
 }
 
 impl RpcService {
-    pub fn new(config: Arc<NodeConfig>, api_registry: ApiRegistry) -> Self {
+    pub fn new(config: Arc<NodeConfig>, mut api_registry: ApiRegistry) -> Self {
+        if let Some(auth_keys) = config.rpc.auth_keys() {
+            info!("Rpc: authentication is enabled for http, tcp and websocket rpc.");
+            api_registry.set_authenticator(Arc::new(RpcAuthenticator::new(auth_keys)));
+        }
         Self {
             config,
             api_registry,
@@ -152,13 +153,24 @@ impl RpcService {
         Self::new(config, api_registry)
     }
 
+    /// Start all the rpc servers enabled by the config,
+    /// it is called when the service started, or by the user who runs the service without registry.
+    pub fn start(&mut self) -> Result<()> {
+        self.ipc = self.start_ipc()?;
+        self.http = self.start_http()?;
+        self.tcp = self.start_tcp()?;
+        self.ws = self.start_ws()?;
+        Ok(())
+    }
+
     fn start_ipc(&self) -> Result<Option<jsonrpc_ipc_server::Server>> {
         Ok(if self.config.rpc.ipc.disable {
             None
         } else {
             let ipc_file = self.config.rpc.get_ipc_file();
             let apis: HashSet<Api> = self.config.rpc.ipc.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            // the ipc file is only accessible by the local user, so it is not authenticated.
+            let io_handler = self.api_registry.get_apis_without_auth(apis);
 
             info!("Ipc rpc server start at :{:?}", ipc_file);
             Some(
@@ -14,7 +14,7 @@ pub use chain_state::{
     StateWithProof,
 };
 use serde::de::DeserializeOwned;
-pub use starcoin_state_tree::StateNodeStore;
+pub use starcoin_state_tree::{StateNodeStore, StatePrunedError};
 use starcoin_types::state_set::AccountStateSet;
 use starcoin_vm_types::move_resource::MoveResource;
 pub use starcoin_vm_types::state_view::StateView;
@@ -144,7 +144,7 @@ impl Inner {
     ) -> Result<Option<AccountStateSet>> {
         match state_root {
             Some(root) => {
-                let reader = self.state_db.fork_at(root);
+                let reader = self.state_db.try_fork_at(root)?;
                 reader.get_account_state_set(&address)
             }
             None => self.get_account_state_set(&address),
@@ -156,7 +156,7 @@ impl Inner {
         access_path: AccessPath,
         state_root: HashValue,
     ) -> Result<StateWithProof> {
-        let reader = self.state_db.fork_at(state_root);
+        let reader = self.state_db.try_fork_at(state_root)?;
         reader.get_with_proof(&access_path)
     }
 
@@ -165,7 +165,7 @@ impl Inner {
         account: AccountAddress,
         state_root: HashValue,
     ) -> Result<Option<AccountState>> {
-        let reader = self.state_db.fork_at(state_root);
+        let reader = self.state_db.try_fork_at(state_root)?;
         reader.get_account_state(&account)
     }
 
@@ -5,6 +5,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use starcoin_crypto::hash::HashValue;
 use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
+use thiserror::Error;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StateNode(pub Vec<u8>);
@@ -50,8 +51,40 @@ impl Serialize for StateNode {
     }
 }
 
+/// The state of `state_root` is removed by state pruning.
+#[derive(Error, Debug)]
+#[error("the state of root `{0}` has been pruned, the node only keeps the state of recent blocks")]
+pub struct StatePrunedError(pub HashValue);
+
 pub trait StateNodeStore: std::marker::Send + std::marker::Sync {
     fn get(&self, hash: &HashValue) -> Result<Option<StateNode>>;
     fn put(&self, key: HashValue, node: StateNode) -> Result<()>;
     fn write_nodes(&self, nodes: BTreeMap<HashValue, StateNode>) -> Result<()>;
+
+    /// Write nodes of a state tree flush.
+    /// `created` contains the key of every node created by the flush, one item for each occurrence,
+    /// the store which supports state pruning uses it to count node references.
+    fn write_nodes_with_created(
+        &self,
+        nodes: BTreeMap<HashValue, StateNode>,
+        _created: Vec<HashValue>,
+    ) -> Result<()> {
+        self.write_nodes(nodes)
+    }
+
+    /// Check whether the state of `state_root` has been removed by state pruning.
+    /// A state root which never exists is not pruned.
+    /// The store which does not support state pruning always returns false.
+    fn is_state_pruned(&self, _state_root: &HashValue) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Get the encoded account state of `account_address` when the account is missing in the state tree.
+    /// A store which overlays the state of another chain, such as the forked chain of a dev node,
+    /// loads the account from it, writes the nodes of the account storage trees, and returns the account state,
+    /// so the account can be read and updated locally, but its account proof does not verify against the local state root.
+    /// Other stores always return None.
+    fn get_missing_account_state(&self, _account_address: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
 }
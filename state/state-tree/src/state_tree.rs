@@ -203,6 +203,12 @@ where
 
     /// commit the state change into underline storage.
     pub fn flush(&self) -> Result<()> {
+        self.flush_with_stale_nodes().map(|_| ())
+    }
+
+    /// commit the state change into underline storage,
+    /// and return the keys of the nodes which become stale by the changes, used by state pruning.
+    pub fn flush_with_stale_nodes(&self) -> Result<Vec<HashValue>> {
         let change_set_list = {
             let mut cache_guard = self.cache.lock();
             cache_guard.split_off_idx = Some(cache_guard.change_set_list.len());
@@ -213,21 +219,32 @@ where
         // when self::commit call self::updates(&self, updates: Vec<(K, Option<Blob>)>)
         // the param updates is empty cause this situation
         if change_set_list.is_empty() {
-            return Ok(());
+            return Ok(vec![]);
         }
         let mut root_hash = HashValue::default();
         let mut node_map = BTreeMap::new();
+        // a node may be created, become stale and be created again in different change sets,
+        // so count every occurrence.
+        let mut created = vec![];
+        let mut stale_nodes = vec![];
         for (hash, change_sets) in change_set_list.into_iter() {
             for (nk, n) in change_sets.node_batch.into_iter() {
+                created.push(nk);
                 node_map.insert(nk, n.try_into()?);
             }
+            stale_nodes.extend(
+                change_sets
+                    .stale_node_index_batch
+                    .into_iter()
+                    .map(|index| index.node_key),
+            );
             root_hash = hash;
         }
-        self.storage.write_nodes(node_map)?;
+        self.storage.write_nodes_with_created(node_map, created)?;
         // and then advance the storage root hash
         *self.storage_root_hash.write() = root_hash;
         self.cache.lock().reset(root_hash);
-        Ok(())
+        Ok(stale_nodes)
     }
 
     /// Dump tree to state set.
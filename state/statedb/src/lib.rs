@@ -15,7 +15,7 @@ pub use starcoin_state_api::{
 };
 use starcoin_state_tree::mock::MockStateNodeStore;
 use starcoin_state_tree::AccountStateSetIterator;
-use starcoin_state_tree::{StateNodeStore, StateTree};
+use starcoin_state_tree::{StateNodeStore, StatePrunedError, StateTree};
 use starcoin_types::write_set::{WriteOp, WriteSet, WriteSetMut};
 use starcoin_types::{
     access_path::{AccessPath, DataType},
@@ -180,13 +180,13 @@ impl AccountStateObject {
         Ok(self.to_state())
     }
 
-    pub fn flush(&self) -> Result<()> {
-        self.resource_tree.lock().flush()?;
+    pub fn flush(&self) -> Result<Vec<HashValue>> {
+        let mut stale_nodes = self.resource_tree.lock().flush_with_stale_nodes()?;
         if let Some(code_tree) = self.code_tree.lock().as_ref() {
-            code_tree.flush()?;
+            stale_nodes.extend(code_tree.flush_with_stale_nodes()?);
         }
 
-        Ok(())
+        Ok(stale_nodes)
     }
 
     fn to_state_set(&self) -> Result<AccountStateSet> {
@@ -246,6 +246,37 @@ impl ChainStateDB {
         }
     }
 
+    /// Open the state at `state_root`, return `StatePrunedError` if the state has been pruned.
+    pub fn try_new(store: Arc<dyn StateNodeStore>, state_root: HashValue) -> Result<Self> {
+        if store.is_state_pruned(&state_root)? {
+            return Err(StatePrunedError(state_root).into());
+        }
+        Ok(Self::new(store, Some(state_root)))
+    }
+
+    /// Fork a new statedb at `state_root`, return `StatePrunedError` if the state has been pruned.
+    pub fn try_fork_at(&self, state_root: HashValue) -> Result<Self> {
+        if self.store.is_state_pruned(&state_root)? {
+            return Err(StatePrunedError(state_root).into());
+        }
+        Ok(self.fork_at(state_root))
+    }
+
+    /// Flush data to db, and return the keys of the state nodes which become stale.
+    pub fn flush_with_stale_nodes(&self) -> Result<Vec<HashValue>> {
+        //cache flush
+        let mut stale_nodes = vec![];
+        let mut locks = self.updates.write();
+        for address in locks.iter() {
+            let account_state_object = self.get_account_state_object(address, false)?;
+            stale_nodes.extend(account_state_object.flush()?);
+        }
+        locks.clear();
+        // self tree flush
+        stale_nodes.extend(self.state_tree.flush_with_stale_nodes()?);
+        Ok(stale_nodes)
+    }
+
     fn new_state_tree<K: RawKey>(&self, root_hash: HashValue) -> StateTree<K> {
         StateTree::new(self.store.clone(), Some(root_hash))
     }
@@ -284,11 +315,13 @@ impl ChainStateDB {
         let object = match item {
             Some(item) => item.as_object(),
             None => {
-                let object = self
-                    .get_account_state(account_address)?
-                    .map(|account_state| {
-                        Arc::new(AccountStateObject::new(account_state, self.store.clone()))
-                    });
+                let account_state = match self.get_account_state(account_address)? {
+                    Some(account_state) => Some(account_state),
+                    None => self.get_missing_account_state(account_address)?,
+                };
+                let object = account_state.map(|account_state| {
+                    Arc::new(AccountStateObject::new(account_state, self.store.clone()))
+                });
                 let cache_item = match &object {
                     Some(object) => CacheItem::new(object.clone()),
                     None => CacheItem::AccountNotExist(),
@@ -308,6 +341,17 @@ impl ChainStateDB {
                 None => Ok(None),
             })
     }
+
+    /// Load the account which is missing in the state tree from the store, see `StateNodeStore::get_missing_account_state`.
+    fn get_missing_account_state(
+        &self,
+        account_address: &AccountAddress,
+    ) -> Result<Option<AccountState>> {
+        self.store
+            .get_missing_account_state(account_address.as_ref())?
+            .map(|v| AccountState::decode(v.as_slice()))
+            .transpose()
+    }
 }
 
 impl ChainState for ChainStateDB {}
@@ -341,9 +385,11 @@ impl ChainStateReader for ChainStateDB {
         let account_address = &access_path.address;
         let data_path = &access_path.path;
         let (account_state, account_proof) = self.state_tree.get_with_proof(account_address)?;
-        let account_state = account_state
-            .map(|v| AccountState::decode(v.as_slice()))
-            .transpose()?;
+        let account_state = match account_state {
+            Some(v) => Some(AccountState::decode(v.as_slice())?),
+            // the account proof of a missing account loaded by the store can not be verified.
+            None => self.get_missing_account_state(account_address)?,
+        };
         let state_with_proof = match account_state {
             None => StateWithProof::new(
                 None,
@@ -540,15 +586,7 @@ impl ChainStateWriter for ChainStateDB {
 
     /// flush data to db.
     fn flush(&self) -> Result<()> {
-        //cache flush
-        let mut locks = self.updates.write();
-        for address in locks.iter() {
-            let account_state_object = self.get_account_state_object(address, false)?;
-            account_state_object.flush()?;
-        }
-        locks.clear();
-        // self tree flush
-        self.state_tree.flush()
+        self.flush_with_stale_nodes().map(|_| ())
     }
 }
 
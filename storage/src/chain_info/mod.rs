@@ -4,8 +4,10 @@
 use crate::storage::{ColumnFamily, InnerStorage, KVStore};
 use crate::{StorageVersion, CHAIN_INFO_PREFIX_NAME};
 use anyhow::Result;
+use bcs_ext::BCSCodec;
 use crypto::HashValue;
-use starcoin_types::startup_info::{SnapshotRange, StartupInfo};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::startup_info::{FastSyncProgress, SnapshotRange, StartupInfo};
 use std::convert::{TryFrom, TryInto};
 
 #[derive(Clone)]
@@ -27,6 +29,10 @@ impl ChainInfoStorage {
     const GENESIS_KEY: &'static str = "genesis";
     const STORAGE_VERSION_KEY: &'static str = "storage_version";
     const SNAPSHOT_RANGE_KEY: &'static str = "snapshot_height";
+    const EVENT_INDEX_START_KEY: &'static str = "event_index_start";
+    const STATE_PRUNE_ENABLED_KEY: &'static str = "state_prune_enabled";
+    const STATE_PRUNED_BLOCK_NUMBER_KEY: &'static str = "state_pruned_block_number";
+    const FAST_SYNC_PROGRESS_KEY: &'static str = "fast_sync_progress";
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -95,4 +101,63 @@ impl ChainInfoStorage {
             snapshot_range.try_into()?,
         )
     }
+
+    pub fn get_event_index_start(&self) -> Result<Option<BlockNumber>> {
+        self.get(Self::EVENT_INDEX_START_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(BlockNumber::decode(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_event_index_start(&self, block_number: BlockNumber) -> Result<()> {
+        self.put_sync(
+            Self::EVENT_INDEX_START_KEY.as_bytes().to_vec(),
+            block_number.encode()?,
+        )
+    }
+
+    pub fn get_state_prune_enabled(&self) -> Result<bool> {
+        Ok(self
+            .get(Self::STATE_PRUNE_ENABLED_KEY.as_bytes())?
+            .is_some())
+    }
+
+    pub fn save_state_prune_enabled(&self) -> Result<()> {
+        self.put_sync(Self::STATE_PRUNE_ENABLED_KEY.as_bytes().to_vec(), vec![1])
+    }
+
+    pub fn get_state_pruned_block_number(&self) -> Result<Option<BlockNumber>> {
+        self.get(Self::STATE_PRUNED_BLOCK_NUMBER_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(BlockNumber::decode(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_state_pruned_block_number(&self, block_number: BlockNumber) -> Result<()> {
+        self.put_sync(
+            Self::STATE_PRUNED_BLOCK_NUMBER_KEY.as_bytes().to_vec(),
+            block_number.encode()?,
+        )
+    }
+
+    pub fn get_fast_sync_progress(&self) -> Result<Option<FastSyncProgress>> {
+        self.get(Self::FAST_SYNC_PROGRESS_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bytes.try_into()?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_fast_sync_progress(&self, progress: FastSyncProgress) -> Result<()> {
+        self.put_sync(
+            Self::FAST_SYNC_PROGRESS_KEY.as_bytes().to_vec(),
+            progress.try_into()?,
+        )
+    }
+
+    pub fn remove_fast_sync_progress(&self) -> Result<()> {
+        self.remove(Self::FAST_SYNC_PROGRESS_KEY.as_bytes().to_vec())
+    }
 }
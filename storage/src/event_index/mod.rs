@@ -0,0 +1,272 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::define_storage;
+use crate::storage::{CodecKVStore, KeyCodec, ValueCodec};
+use crate::{BLOCK_EVENT_BLOOM_PREFIX_NAME, EVENT_KEY_INDEX_PREFIX_NAME};
+use anyhow::{ensure, Result};
+use bcs_ext::BCSCodec;
+use byteorder::{BigEndian, ReadBytesExt};
+use crypto::HashValue;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event::EventKey;
+use starcoin_types::filter::Filter;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+/// Size of the event bloom in bytes, 2048 bits.
+pub const EVENT_BLOOM_BYTES: usize = 256;
+const EVENT_BLOOM_BITS: usize = EVENT_BLOOM_BYTES * 8;
+/// How many bits an input sets in the bloom.
+const EVENT_BLOOM_HASH_COUNT: usize = 3;
+
+/// A per-block bloom filter over the event keys, creator addresses and type tags
+/// of all events emitted in the block.
+/// A bloom never gives false negatives, so a block whose bloom does not match a `Filter`
+/// can be skipped without loading its events.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EventBloom(Vec<u8>);
+
+impl Default for EventBloom {
+    fn default() -> Self {
+        Self(vec![0u8; EVENT_BLOOM_BYTES])
+    }
+}
+
+impl EventBloom {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_events<'a, I>(events: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = &'a ContractEvent>,
+    {
+        let mut bloom = Self::new();
+        for event in events {
+            bloom.accrue_event(event)?;
+        }
+        Ok(bloom)
+    }
+
+    pub fn accrue_event(&mut self, event: &ContractEvent) -> Result<()> {
+        self.accrue(event.key().as_bytes());
+        self.accrue(event.key().get_creator_address().as_ref());
+        self.accrue(event.type_tag().encode()?.as_slice());
+        Ok(())
+    }
+
+    pub fn accrue(&mut self, input: &[u8]) {
+        for (byte_index, mask) in Self::bit_positions(input) {
+            self.0[byte_index] |= mask;
+        }
+    }
+
+    pub fn contains_input(&self, input: &[u8]) -> bool {
+        Self::bit_positions(input)
+            .into_iter()
+            .all(|(byte_index, mask)| self.0[byte_index] & mask == mask)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|b| *b == 0)
+    }
+
+    /// Check whether a block with this bloom may contain events matching the `filter`.
+    /// Return false only if it is certain that no event matches.
+    pub fn may_match(&self, filter: &Filter) -> Result<bool> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+        let key_match = filter.event_keys.is_empty()
+            || filter
+                .event_keys
+                .iter()
+                .any(|key| self.contains_input(key.as_bytes()));
+        let addr_match = filter.addrs.is_empty()
+            || filter
+                .addrs
+                .iter()
+                .any(|addr| self.contains_input(addr.as_ref()));
+        let mut type_tag_match = filter.type_tags.is_empty();
+        for type_tag in &filter.type_tags {
+            if self.contains_input(type_tag.encode()?.as_slice()) {
+                type_tag_match = true;
+                break;
+            }
+        }
+        Ok(key_match && addr_match && type_tag_match)
+    }
+
+    fn bit_positions(input: &[u8]) -> [(usize, u8); EVENT_BLOOM_HASH_COUNT] {
+        let hash = HashValue::sha3_256_of(input).to_vec();
+        let mut positions = [(0usize, 0u8); EVENT_BLOOM_HASH_COUNT];
+        for (i, position) in positions.iter_mut().enumerate() {
+            let bit = (((hash[i * 2] as usize) << 8) | hash[i * 2 + 1] as usize) % EVENT_BLOOM_BITS;
+            *position = (EVENT_BLOOM_BYTES - 1 - bit / 8, 1u8 << (bit % 8));
+        }
+        positions
+    }
+}
+
+impl ValueCodec for EventBloom {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.0.clone())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == EVENT_BLOOM_BYTES,
+            "invalid event bloom length: {}",
+            data.len()
+        );
+        Ok(Self(data.to_vec()))
+    }
+}
+
+/// Key of the secondary event index, ordered by event key, then block number.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EventIndexKey {
+    pub event_key: EventKey,
+    pub block_number: BlockNumber,
+    pub block_id: HashValue,
+}
+
+impl EventIndexKey {
+    pub fn new(event_key: EventKey, block_number: BlockNumber, block_id: HashValue) -> Self {
+        Self {
+            event_key,
+            block_number,
+            block_id,
+        }
+    }
+
+    /// The smallest encoded key of `event_key` at `block_number`, used to seek.
+    fn seek_key(event_key: &EventKey, block_number: BlockNumber) -> Vec<u8> {
+        let mut key = event_key.to_vec();
+        key.extend_from_slice(&block_number.to_be_bytes());
+        key
+    }
+}
+
+impl KeyCodec for EventIndexKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut key = Self::seek_key(&self.event_key, self.block_number);
+        key.extend(self.block_id.to_vec());
+        Ok(key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == EventKey::LENGTH + 8 + HashValue::LENGTH,
+            "invalid event index key length: {}",
+            data.len()
+        );
+        let (event_key, rest) = data.split_at(EventKey::LENGTH);
+        let (mut block_number, block_id) = rest.split_at(8);
+        Ok(Self {
+            event_key: EventKey::new(event_key.try_into()?),
+            block_number: block_number.read_u64::<BigEndian>()?,
+            block_id: HashValue::from_slice(block_id)?,
+        })
+    }
+}
+
+define_storage!(
+    BlockEventBloomStorage,
+    HashValue,
+    EventBloom,
+    BLOCK_EVENT_BLOOM_PREFIX_NAME
+);
+
+// value is the txn info ids in the block which emit events with the event key.
+define_storage!(
+    EventKeyIndexStorage,
+    EventIndexKey,
+    Vec<HashValue>,
+    EVENT_KEY_INDEX_PREFIX_NAME
+);
+
+pub trait EventIndexStore {
+    /// Build and save the event bloom and the event key index of block `block_id`.
+    /// `txn_events` are the events of every transaction in the block, keyed by txn info id.
+    fn save_block_event_index(
+        &self,
+        block_id: HashValue,
+        block_number: BlockNumber,
+        txn_events: &[(HashValue, Vec<ContractEvent>)],
+    ) -> Result<()>;
+
+    /// Get the event bloom of block `block_id`.
+    /// Return `None` if the block is not indexed yet.
+    fn get_block_event_bloom(&self, block_id: HashValue) -> Result<Option<EventBloom>>;
+
+    /// Get the `(block_number, block_id, txn_info_ids)` entries which emit events with `event_key`
+    /// in block range [from_block, to_block], ordered by block number.
+    /// Blocks of every branch are returned, caller should filter out the blocks not on its chain.
+    fn get_event_key_index(
+        &self,
+        event_key: EventKey,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<(BlockNumber, HashValue, Vec<HashValue>)>>;
+}
+
+impl BlockEventBloomStorage {
+    pub fn save_event_bloom(&self, block_id: HashValue, bloom: EventBloom) -> Result<()> {
+        self.put(block_id, bloom)
+    }
+}
+
+impl EventKeyIndexStorage {
+    pub fn save_event_key_index(
+        &self,
+        block_id: HashValue,
+        block_number: BlockNumber,
+        txn_events: &[(HashValue, Vec<ContractEvent>)],
+    ) -> Result<()> {
+        let mut index: BTreeMap<EventKey, Vec<HashValue>> = BTreeMap::new();
+        for (txn_info_id, events) in txn_events {
+            for event in events {
+                let txn_info_ids = index.entry(*event.key()).or_default();
+                if txn_info_ids.last() != Some(txn_info_id) {
+                    txn_info_ids.push(*txn_info_id);
+                }
+            }
+        }
+        if index.is_empty() {
+            return Ok(());
+        }
+        self.put_all(
+            index
+                .into_iter()
+                .map(|(event_key, txn_info_ids)| {
+                    (
+                        EventIndexKey::new(event_key, block_number, block_id),
+                        txn_info_ids,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    pub fn get_event_key_index(
+        &self,
+        event_key: EventKey,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<(BlockNumber, HashValue, Vec<HashValue>)>> {
+        let mut iter = self.iter()?;
+        iter.seek(EventIndexKey::seek_key(&event_key, from_block))?;
+        let mut result = vec![];
+        for item in iter {
+            let (key, txn_info_ids) = item?;
+            if key.event_key != event_key || key.block_number > to_block {
+                break;
+            }
+            result.push((key.block_number, key.block_id, txn_info_ids));
+        }
+        Ok(result)
+    }
+}
@@ -8,27 +8,37 @@ use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStorage, BlockInfoStore};
 use crate::chain_info::ChainInfoStorage;
 use crate::contract_event::ContractEventStorage;
-use crate::state_node::StateStorage;
+use crate::event_index::{
+    BlockEventBloomStorage, EventBloom, EventIndexStore, EventKeyIndexStorage,
+};
+use crate::state_node::{
+    PrunedStateRootStorage, StaleStateNodeKey, StaleStateNodeStorage, StateNodeRefCountStorage,
+    StatePruneStore, StateStorage, PINNED_REF_COUNT,
+};
 use crate::storage::{CodecKVStore, CodecWriteBatch, ColumnFamilyName, StorageInstance};
 use crate::transaction::TransactionStorage;
 use crate::transaction_info::{TransactionInfoHashStorage, TransactionInfoStorage};
 use anyhow::{bail, format_err, Error, Result};
+use crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
 use crypto::HashValue;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorTreeStore;
 use starcoin_state_store_api::{StateNode, StateNodeStore};
 use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event::EventKey;
 use starcoin_types::peer_info::PeerId;
-use starcoin_types::startup_info::{ChainInfo, ChainStatus, SnapshotRange};
+use starcoin_types::startup_info::{ChainInfo, ChainStatus, FastSyncProgress, SnapshotRange};
 use starcoin_types::transaction::{RichTransactionInfo, Transaction};
 use starcoin_types::{
-    block::{Block, BlockBody, BlockHeader, BlockInfo},
+    block::{Block, BlockBody, BlockHeader, BlockInfo, BlockNumber},
     startup_info::StartupInfo,
 };
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 pub mod accumulator;
@@ -40,6 +50,7 @@ pub mod chain_info;
 pub mod contract_event;
 pub mod db_storage;
 pub mod errors;
+pub mod event_index;
 pub mod metrics;
 pub mod state_node;
 pub mod storage;
@@ -68,6 +79,11 @@ pub const TRANSACTION_INFO_PREFIX_NAME_V2: ColumnFamilyName = "transaction_info_
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
 pub const FAILED_BLOCK_PREFIX_NAME: ColumnFamilyName = "failed_block";
+pub const BLOCK_EVENT_BLOOM_PREFIX_NAME: ColumnFamilyName = "block_event_bloom";
+pub const EVENT_KEY_INDEX_PREFIX_NAME: ColumnFamilyName = "event_key_index";
+pub const STATE_NODE_REF_COUNT_PREFIX_NAME: ColumnFamilyName = "state_node_ref_count";
+pub const STALE_STATE_NODE_PREFIX_NAME: ColumnFamilyName = "stale_state_node";
+pub const PRUNED_STATE_ROOT_PREFIX_NAME: ColumnFamilyName = "pruned_state_root";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -132,17 +148,68 @@ static VEC_PREFIX_NAME_V3: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         FAILED_BLOCK_PREFIX_NAME,
     ]
 });
+
+static VEC_PREFIX_NAME_V4: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
+    vec![
+        BLOCK_ACCUMULATOR_NODE_PREFIX_NAME,
+        TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME,
+        BLOCK_PREFIX_NAME,
+        BLOCK_HEADER_PREFIX_NAME,
+        BLOCK_BODY_PREFIX_NAME, // unused column
+        BLOCK_INFO_PREFIX_NAME,
+        BLOCK_TRANSACTIONS_PREFIX_NAME,
+        BLOCK_TRANSACTION_INFOS_PREFIX_NAME,
+        STATE_NODE_PREFIX_NAME,
+        CHAIN_INFO_PREFIX_NAME,
+        TRANSACTION_PREFIX_NAME,
+        TRANSACTION_INFO_PREFIX_NAME, // unused column
+        TRANSACTION_INFO_PREFIX_NAME_V2,
+        TRANSACTION_INFO_HASH_PREFIX_NAME,
+        CONTRACT_EVENT_PREFIX_NAME,
+        FAILED_BLOCK_PREFIX_NAME,
+        BLOCK_EVENT_BLOOM_PREFIX_NAME,
+        EVENT_KEY_INDEX_PREFIX_NAME,
+    ]
+});
+
+static VEC_PREFIX_NAME_V5: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
+    vec![
+        BLOCK_ACCUMULATOR_NODE_PREFIX_NAME,
+        TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME,
+        BLOCK_PREFIX_NAME,
+        BLOCK_HEADER_PREFIX_NAME,
+        BLOCK_BODY_PREFIX_NAME, // unused column
+        BLOCK_INFO_PREFIX_NAME,
+        BLOCK_TRANSACTIONS_PREFIX_NAME,
+        BLOCK_TRANSACTION_INFOS_PREFIX_NAME,
+        STATE_NODE_PREFIX_NAME,
+        CHAIN_INFO_PREFIX_NAME,
+        TRANSACTION_PREFIX_NAME,
+        TRANSACTION_INFO_PREFIX_NAME, // unused column
+        TRANSACTION_INFO_PREFIX_NAME_V2,
+        TRANSACTION_INFO_HASH_PREFIX_NAME,
+        CONTRACT_EVENT_PREFIX_NAME,
+        FAILED_BLOCK_PREFIX_NAME,
+        BLOCK_EVENT_BLOOM_PREFIX_NAME,
+        EVENT_KEY_INDEX_PREFIX_NAME,
+        STATE_NODE_REF_COUNT_PREFIX_NAME,
+        STALE_STATE_NODE_PREFIX_NAME,
+        PRUNED_STATE_ROOT_PREFIX_NAME,
+    ]
+});
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
 pub enum StorageVersion {
     V1 = 1,
     V2 = 2,
     V3 = 3,
+    V4 = 4,
+    V5 = 5,
 }
 
 impl StorageVersion {
     pub fn current_version() -> StorageVersion {
-        StorageVersion::V3
+        StorageVersion::V5
     }
 
     pub fn get_column_family_names(&self) -> &'static [ColumnFamilyName] {
@@ -150,6 +217,8 @@ impl StorageVersion {
             StorageVersion::V1 => &VEC_PREFIX_NAME_V1,
             StorageVersion::V2 => &VEC_PREFIX_NAME_V2,
             StorageVersion::V3 => &VEC_PREFIX_NAME_V3,
+            StorageVersion::V4 => &VEC_PREFIX_NAME_V4,
+            StorageVersion::V5 => &VEC_PREFIX_NAME_V5,
         }
     }
 }
@@ -177,6 +246,9 @@ pub trait BlockStore {
 
     fn get_block_header_by_hash(&self, block_id: HashValue) -> Result<Option<BlockHeader>>;
 
+    /// Save the header only, used by the light node which does not keep block bodies.
+    fn save_block_header(&self, header: BlockHeader) -> Result<()>;
+
     fn get_block_by_hash(&self, block_id: HashValue) -> Result<Option<Block>>;
 
     fn save_block_transaction_ids(
@@ -213,6 +285,17 @@ pub trait BlockStore {
 
     fn get_snapshot_range(&self) -> Result<Option<SnapshotRange>>;
     fn save_snapshot_range(&self, snapshot_height: SnapshotRange) -> Result<()>;
+
+    /// Get the lowest block number from which the main chain blocks have event index.
+    /// Blocks below it are committed before event index introduced, and wait for backfill.
+    /// If not set, all blocks are indexed.
+    fn get_event_index_start(&self) -> Result<Option<BlockNumber>>;
+    fn save_event_index_start(&self, block_number: BlockNumber) -> Result<()>;
+
+    /// Get the progress of the unfinished fast sync, return None if no fast sync is in progress.
+    fn get_fast_sync_progress(&self) -> Result<Option<FastSyncProgress>>;
+    fn save_fast_sync_progress(&self, progress: FastSyncProgress) -> Result<()>;
+    fn remove_fast_sync_progress(&self) -> Result<()>;
 }
 
 pub trait BlockTransactionInfoStore {
@@ -264,7 +347,15 @@ pub struct Storage {
     transaction_accumulator_storage: AccumulatorStorage<TransactionAccumulatorStorage>,
     block_info_storage: BlockInfoStorage,
     event_storage: ContractEventStorage,
+    event_bloom_storage: BlockEventBloomStorage,
+    event_key_index_storage: EventKeyIndexStorage,
+    state_node_ref_count_storage: StateNodeRefCountStorage,
+    stale_state_node_storage: StaleStateNodeStorage,
+    pruned_state_root_storage: PrunedStateRootStorage,
     chain_info_storage: ChainInfoStorage,
+    state_prune_enabled: Arc<AtomicBool>,
+    // serialize the read-modify-write of state node reference counts.
+    state_node_ref_lock: Arc<Mutex<()>>,
     // instance: StorageInstance,
 }
 
@@ -283,9 +374,20 @@ impl Storage {
                 AccumulatorStorage::new_transaction_accumulator_storage(instance.clone()),
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
+            event_bloom_storage: BlockEventBloomStorage::new(instance.clone()),
+            event_key_index_storage: EventKeyIndexStorage::new(instance.clone()),
+            state_node_ref_count_storage: StateNodeRefCountStorage::new(instance.clone()),
+            stale_state_node_storage: StaleStateNodeStorage::new(instance.clone()),
+            pruned_state_root_storage: PrunedStateRootStorage::new(instance.clone()),
             chain_info_storage: ChainInfoStorage::new(instance),
+            state_prune_enabled: Arc::new(AtomicBool::new(false)),
+            state_node_ref_lock: Arc::new(Mutex::new(())),
             // instance,
         };
+        storage.state_prune_enabled.store(
+            storage.chain_info_storage.get_state_prune_enabled()?,
+            Ordering::SeqCst,
+        );
         Ok(storage)
     }
 
@@ -313,6 +415,173 @@ impl StateNodeStore for Storage {
         let batch = CodecWriteBatch::new_puts(nodes.into_iter().collect());
         self.state_node_storage.write_batch(batch)
     }
+
+    fn write_nodes_with_created(
+        &self,
+        nodes: BTreeMap<HashValue, StateNode>,
+        created: Vec<HashValue>,
+    ) -> Result<()> {
+        if !self.is_state_prune_enabled() {
+            return self.write_nodes(nodes);
+        }
+        let mut increments: BTreeMap<HashValue, u64> = BTreeMap::new();
+        for node_key in created {
+            *increments.entry(node_key).or_default() += 1;
+        }
+        let _guard = self.state_node_ref_lock.lock();
+        let ref_counts = self
+            .state_node_ref_count_storage
+            .multiple_get(increments.keys().cloned().collect())?;
+        let mut ref_count_batch = Vec::with_capacity(increments.len());
+        for ((node_key, increment), ref_count) in increments.into_iter().zip(ref_counts) {
+            let ref_count = match ref_count {
+                Some(ref_count) if ref_count == PINNED_REF_COUNT => continue,
+                Some(ref_count) => ref_count.saturating_add(increment),
+                // the node is written before state pruning enabled, it may be referenced by any state.
+                None if self.state_node_storage.contains_key(node_key)? => PINNED_REF_COUNT,
+                None => increment,
+            };
+            ref_count_batch.push((node_key, ref_count));
+        }
+        // write nodes first, a crash between the two writes only leaves some nodes pinned.
+        self.write_nodes(nodes)?;
+        self.state_node_ref_count_storage.put_all(ref_count_batch)
+    }
+
+    fn is_state_pruned(&self, state_root: &HashValue) -> Result<bool> {
+        // a missing root which is never recorded by the pruner is unknown, not pruned.
+        Ok(self.is_state_prune_enabled()
+            && *state_root != *SPARSE_MERKLE_PLACEHOLDER_HASH
+            && !self.state_node_storage.contains_key(*state_root)?
+            && self.pruned_state_root_storage.contains_key(*state_root)?)
+    }
+}
+
+impl StatePruneStore for Storage {
+    fn is_state_prune_enabled(&self) -> bool {
+        self.state_prune_enabled.load(Ordering::SeqCst)
+    }
+
+    fn enable_state_prune(&self) -> Result<()> {
+        if self.is_state_prune_enabled() {
+            return Ok(());
+        }
+        // the blocks committed before enabled have no stale state nodes record,
+        // so the pruner starts from the current head.
+        if let Some(startup_info) = self.chain_info_storage.get_startup_info()? {
+            let head = self
+                .get_block_header_by_hash(startup_info.main)?
+                .ok_or_else(|| format_err!("Startup block {:?} should exist", startup_info.main))?;
+            self.chain_info_storage
+                .save_state_pruned_block_number(head.number())?;
+        }
+        self.chain_info_storage.save_state_prune_enabled()?;
+        self.state_prune_enabled.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn save_stale_state_nodes(
+        &self,
+        block_number: BlockNumber,
+        block_id: HashValue,
+        stale_nodes: Vec<HashValue>,
+    ) -> Result<()> {
+        if !self.is_state_prune_enabled() || stale_nodes.is_empty() {
+            return Ok(());
+        }
+        self.stale_state_node_storage
+            .put(StaleStateNodeKey::new(block_number, block_id), stale_nodes)
+    }
+
+    fn prune_state_nodes(
+        &self,
+        block_number: BlockNumber,
+        main_block_id: HashValue,
+    ) -> Result<usize> {
+        let mut pruned = 0;
+        for (key, stale_nodes) in self
+            .stale_state_node_storage
+            .get_by_block_number(block_number)?
+        {
+            // remove the record before releasing, so a crash never releases the nodes twice.
+            self.stale_state_node_storage.remove(key.clone())?;
+            // the stale records of other branches are dropped without applying,
+            // the nodes created by other branches are never deleted.
+            if key.block_id == main_block_id {
+                let deleted_nodes = self.release_state_nodes(stale_nodes)?;
+                pruned += deleted_nodes.len();
+                self.save_pruned_state_root(block_number, main_block_id, &deleted_nodes)?;
+            }
+        }
+        self.chain_info_storage
+            .save_state_pruned_block_number(block_number)?;
+        Ok(pruned)
+    }
+
+    fn get_state_pruned_block_number(&self) -> Result<Option<BlockNumber>> {
+        self.chain_info_storage.get_state_pruned_block_number()
+    }
+}
+
+impl Storage {
+    /// Release one reference of every stale node, and delete the nodes without reference.
+    /// Return the keys of the deleted nodes.
+    fn release_state_nodes(&self, stale_nodes: Vec<HashValue>) -> Result<Vec<HashValue>> {
+        let mut decrements: BTreeMap<HashValue, u64> = BTreeMap::new();
+        for node_key in stale_nodes {
+            *decrements.entry(node_key).or_default() += 1;
+        }
+        let _guard = self.state_node_ref_lock.lock();
+        let ref_counts = self
+            .state_node_ref_count_storage
+            .multiple_get(decrements.keys().cloned().collect())?;
+        let mut ref_count_batch = CodecWriteBatch::new();
+        let mut deleted_nodes = vec![];
+        for ((node_key, decrement), ref_count) in decrements.into_iter().zip(ref_counts) {
+            match ref_count {
+                // the node is written before state pruning enabled, keep it.
+                None => {}
+                Some(ref_count) if ref_count == PINNED_REF_COUNT => {}
+                Some(ref_count) if ref_count > decrement => {
+                    ref_count_batch.put(node_key, ref_count - decrement)?;
+                }
+                Some(_) => {
+                    ref_count_batch.delete(node_key)?;
+                    deleted_nodes.push(node_key);
+                }
+            }
+        }
+        // delete nodes first, a crash between the two writes only leaves some reference counts.
+        self.state_node_storage.delete_all(deleted_nodes.clone())?;
+        self.state_node_ref_count_storage
+            .write_batch(ref_count_batch)?;
+        Ok(deleted_nodes)
+    }
+
+    /// Record the state root replaced by executing block `block_id` if its node is deleted,
+    /// so the pruned state can be told apart from a state root which never exists.
+    /// The state is flushed once per block, the parent's state root is the only stale root of a block.
+    fn save_pruned_state_root(
+        &self,
+        block_number: BlockNumber,
+        block_id: HashValue,
+        deleted_nodes: &[HashValue],
+    ) -> Result<()> {
+        if deleted_nodes.is_empty() {
+            return Ok(());
+        }
+        let parent_hash = match self.get_block_header_by_hash(block_id)? {
+            Some(header) => header.parent_hash(),
+            None => return Ok(()),
+        };
+        if let Some(parent) = self.get_block_header_by_hash(parent_hash)? {
+            if deleted_nodes.contains(&parent.state_root()) {
+                self.pruned_state_root_storage
+                    .put(parent.state_root(), block_number)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Display for Storage {
@@ -390,6 +659,10 @@ impl BlockStore for Storage {
         self.block_storage.get_block_header_by_hash(block_id)
     }
 
+    fn save_block_header(&self, header: BlockHeader) -> Result<()> {
+        self.block_storage.save_header(header)
+    }
+
     fn get_block_by_hash(&self, block_id: HashValue) -> Result<Option<Block>> {
         self.block_storage.get_block_by_hash(block_id)
     }
@@ -450,6 +723,26 @@ impl BlockStore for Storage {
     fn save_snapshot_range(&self, snapshot_range: SnapshotRange) -> Result<()> {
         self.chain_info_storage.save_snapshot_range(snapshot_range)
     }
+
+    fn get_event_index_start(&self) -> Result<Option<BlockNumber>> {
+        self.chain_info_storage.get_event_index_start()
+    }
+
+    fn save_event_index_start(&self, block_number: BlockNumber) -> Result<()> {
+        self.chain_info_storage.save_event_index_start(block_number)
+    }
+
+    fn get_fast_sync_progress(&self) -> Result<Option<FastSyncProgress>> {
+        self.chain_info_storage.get_fast_sync_progress()
+    }
+
+    fn save_fast_sync_progress(&self, progress: FastSyncProgress) -> Result<()> {
+        self.chain_info_storage.save_fast_sync_progress(progress)
+    }
+
+    fn remove_fast_sync_progress(&self) -> Result<()> {
+        self.chain_info_storage.remove_fast_sync_progress()
+    }
 }
 
 impl BlockInfoStore for Storage {
@@ -532,6 +825,34 @@ impl ContractEventStore for Storage {
     }
 }
 
+impl EventIndexStore for Storage {
+    fn save_block_event_index(
+        &self,
+        block_id: HashValue,
+        block_number: BlockNumber,
+        txn_events: &[(HashValue, Vec<ContractEvent>)],
+    ) -> Result<(), Error> {
+        let bloom = EventBloom::from_events(txn_events.iter().flat_map(|(_, events)| events))?;
+        self.event_key_index_storage
+            .save_event_key_index(block_id, block_number, txn_events)?;
+        self.event_bloom_storage.save_event_bloom(block_id, bloom)
+    }
+
+    fn get_block_event_bloom(&self, block_id: HashValue) -> Result<Option<EventBloom>, Error> {
+        self.event_bloom_storage.get(block_id)
+    }
+
+    fn get_event_key_index(
+        &self,
+        event_key: EventKey,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<(BlockNumber, HashValue, Vec<HashValue>)>, Error> {
+        self.event_key_index_storage
+            .get_event_key_index(event_key, from_block, to_block)
+    }
+}
+
 impl TransactionStore for Storage {
     fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<Transaction>, Error> {
         self.transaction_storage.get(txn_hash)
@@ -561,6 +882,8 @@ pub trait Store:
     + TransactionStore
     + BlockTransactionInfoStore
     + ContractEventStore
+    + EventIndexStore
+    + StatePruneStore
     + IntoSuper<dyn StateNodeStore>
 {
     fn get_transaction_info_by_block_and_index(
@@ -596,6 +919,28 @@ pub trait Store:
         Ok(txn_infos)
     }
 
+    /// Build the event index of an already committed block from its saved events.
+    /// Used to backfill the event index of the blocks committed before the index introduced.
+    fn build_block_event_index(
+        &self,
+        block_id: HashValue,
+        block_number: BlockNumber,
+    ) -> Result<()> {
+        let txn_info_ids = self.get_block_txn_info_ids(block_id)?;
+        let mut txn_events = Vec::with_capacity(txn_info_ids.len());
+        for txn_info_id in txn_info_ids {
+            let events = self.get_contract_events(txn_info_id)?.ok_or_else(|| {
+                format_err!(
+                    "invalid state: events of txn info {} of block {} should exist",
+                    txn_info_id,
+                    block_id
+                )
+            })?;
+            txn_events.push((txn_info_id, events));
+        }
+        self.save_block_event_index(block_id, block_number, txn_events.as_slice())
+    }
+
     fn get_accumulator_store(
         &self,
         accumulator_type: AccumulatorStoreType,
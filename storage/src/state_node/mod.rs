@@ -2,11 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::define_storage;
-use crate::storage::ValueCodec;
-use crate::STATE_NODE_PREFIX_NAME;
-use anyhow::Result;
+use crate::storage::{CodecKVStore, KeyCodec, ValueCodec};
+use crate::{
+    PRUNED_STATE_ROOT_PREFIX_NAME, STALE_STATE_NODE_PREFIX_NAME, STATE_NODE_PREFIX_NAME,
+    STATE_NODE_REF_COUNT_PREFIX_NAME,
+};
+use anyhow::{ensure, Result};
+use byteorder::{BigEndian, ReadBytesExt};
 use crypto::HashValue;
 use starcoin_state_store_api::StateNode;
+use starcoin_types::block::BlockNumber;
 
 define_storage!(StateStorage, HashValue, StateNode, STATE_NODE_PREFIX_NAME);
 
@@ -18,3 +23,118 @@ impl ValueCodec for StateNode {
         Ok(StateNode(data.to_vec()))
     }
 }
+
+/// The reference count of a node which can never be pruned.
+/// Nodes written before state pruning enabled have unknown references, so they are pinned.
+pub const PINNED_REF_COUNT: u64 = u64::MAX;
+
+// value is how many times the node is created by state tree flush,
+// the node is unreachable when all of its references become stale.
+define_storage!(
+    StateNodeRefCountStorage,
+    HashValue,
+    u64,
+    STATE_NODE_REF_COUNT_PREFIX_NAME
+);
+
+/// Key of the stale state nodes of a block, ordered by block number.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct StaleStateNodeKey {
+    pub block_number: BlockNumber,
+    pub block_id: HashValue,
+}
+
+impl StaleStateNodeKey {
+    pub fn new(block_number: BlockNumber, block_id: HashValue) -> Self {
+        Self {
+            block_number,
+            block_id,
+        }
+    }
+}
+
+impl KeyCodec for StaleStateNodeKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut key = self.block_number.to_be_bytes().to_vec();
+        key.extend(self.block_id.to_vec());
+        Ok(key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == 8 + HashValue::LENGTH,
+            "invalid stale state node key length: {}",
+            data.len()
+        );
+        let (mut block_number, block_id) = data.split_at(8);
+        Ok(Self {
+            block_number: block_number.read_u64::<BigEndian>()?,
+            block_id: HashValue::from_slice(block_id)?,
+        })
+    }
+}
+
+// value is the keys of the state nodes which become stale by executing the block.
+define_storage!(
+    StaleStateNodeStorage,
+    StaleStateNodeKey,
+    Vec<HashValue>,
+    STALE_STATE_NODE_PREFIX_NAME
+);
+
+// value is the number of the block whose pruning deletes the state root.
+define_storage!(
+    PrunedStateRootStorage,
+    HashValue,
+    BlockNumber,
+    PRUNED_STATE_ROOT_PREFIX_NAME
+);
+
+impl StaleStateNodeStorage {
+    /// Get the stale state nodes of all the blocks at `block_number`, include the blocks of other branches.
+    pub fn get_by_block_number(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Vec<(StaleStateNodeKey, Vec<HashValue>)>> {
+        let mut iter = self.iter()?;
+        iter.seek(block_number.to_be_bytes().to_vec())?;
+        let mut result = vec![];
+        for item in iter {
+            let (key, stale_nodes) = item?;
+            if key.block_number != block_number {
+                break;
+            }
+            result.push((key, stale_nodes));
+        }
+        Ok(result)
+    }
+}
+
+pub trait StatePruneStore {
+    /// Whether the references of state nodes are tracked for state pruning.
+    /// Once enabled, it is kept by the storage, even if the pruner does not run anymore.
+    fn is_state_prune_enabled(&self) -> bool;
+
+    fn enable_state_prune(&self) -> Result<()>;
+
+    /// Save the keys of the state nodes which become stale by executing block `block_id`.
+    /// Do nothing if state pruning is not enabled.
+    fn save_stale_state_nodes(
+        &self,
+        block_number: BlockNumber,
+        block_id: HashValue,
+        stale_nodes: Vec<HashValue>,
+    ) -> Result<()>;
+
+    /// Prune the state nodes which become unreachable at `block_number` of the main chain,
+    /// the stale records of other branches at the same height are dropped.
+    /// Return the count of deleted state nodes.
+    fn prune_state_nodes(
+        &self,
+        block_number: BlockNumber,
+        main_block_id: HashValue,
+    ) -> Result<usize>;
+
+    /// Get the highest block number whose stale state nodes have been pruned.
+    fn get_state_pruned_block_number(&self) -> Result<Option<BlockNumber>>;
+}
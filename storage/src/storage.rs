@@ -501,6 +501,17 @@ impl KeyCodec for u64 {
     }
 }
 
+impl ValueCodec for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    #[allow(clippy::redundant_slicing)]
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok((&data[..]).read_u64::<BigEndian>()?)
+    }
+}
+
 impl KeyCodec for HashValue {
     fn encode_key(&self) -> Result<Vec<u8>> {
         Ok(self.to_vec())
@@ -3,4 +3,6 @@
 mod test_accumulator;
 mod test_batch;
 mod test_block;
+mod test_event_index;
+mod test_state_prune;
 mod test_storage;
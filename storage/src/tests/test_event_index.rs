@@ -0,0 +1,94 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cache_storage::CacheStorage;
+use crate::db_storage::DBStorage;
+use crate::event_index::{EventBloom, EventIndexStore};
+use crate::storage::StorageInstance;
+use crate::Storage;
+use anyhow::Result;
+use crypto::HashValue;
+use starcoin_config::RocksdbConfig;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event::EventKey;
+use starcoin_types::filter::Filter;
+use starcoin_types::language_storage::TypeTag;
+
+fn random_event(type_tag: TypeTag) -> ContractEvent {
+    ContractEvent::new(EventKey::random(), 0, type_tag, vec![])
+}
+
+#[test]
+fn test_event_bloom() -> Result<()> {
+    let event = random_event(TypeTag::U64);
+    let bloom = EventBloom::from_events(vec![&event])?;
+    assert!(!bloom.is_empty());
+
+    let filter = Filter {
+        event_keys: vec![*event.key()],
+        addrs: vec![event.key().get_creator_address()],
+        type_tags: vec![TypeTag::U64],
+        ..Default::default()
+    };
+    assert!(bloom.may_match(&filter)?);
+    assert!(bloom.may_match(&Filter::default())?);
+
+    let other_filter = Filter {
+        addrs: vec![AccountAddress::random()],
+        ..Default::default()
+    };
+    let other_tag_filter = Filter {
+        type_tags: vec![TypeTag::Address],
+        ..Default::default()
+    };
+    // may false positive, but it is very unlikely with a single event.
+    assert!(!bloom.may_match(&other_filter)? || !bloom.may_match(&other_tag_filter)?);
+
+    // a block without events never matches.
+    assert!(!EventBloom::new().may_match(&Filter::default())?);
+    Ok(())
+}
+
+#[test]
+fn test_event_key_index() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    ))?;
+    let event = random_event(TypeTag::U64);
+    let other_event = random_event(TypeTag::Bool);
+    let txn_info_id = HashValue::random();
+    let other_txn_info_id = HashValue::random();
+
+    let mut block_ids = vec![];
+    for block_number in 0..5u64 {
+        let block_id = HashValue::random();
+        let txn_events = if block_number % 2 == 0 {
+            vec![
+                (txn_info_id, vec![event.clone()]),
+                (other_txn_info_id, vec![other_event.clone()]),
+            ]
+        } else {
+            vec![(other_txn_info_id, vec![other_event.clone()])]
+        };
+        storage.save_block_event_index(block_id, block_number, txn_events.as_slice())?;
+        block_ids.push(block_id);
+        assert!(storage.get_block_event_bloom(block_id)?.is_some());
+    }
+
+    let entries = storage.get_event_key_index(*event.key(), 1, 4)?;
+    assert_eq!(
+        entries,
+        vec![
+            (2, block_ids[2], vec![txn_info_id]),
+            (4, block_ids[4], vec![txn_info_id])
+        ]
+    );
+    let entries = storage.get_event_key_index(*other_event.key(), 0, 10)?;
+    assert_eq!(entries.len(), 5);
+    let entries = storage.get_event_key_index(EventKey::random(), 0, 10)?;
+    assert!(entries.is_empty());
+    Ok(())
+}
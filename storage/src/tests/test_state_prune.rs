@@ -0,0 +1,99 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cache_storage::CacheStorage;
+use crate::db_storage::DBStorage;
+use crate::state_node::StatePruneStore;
+use crate::storage::StorageInstance;
+use crate::{BlockStore, Storage};
+use anyhow::Result;
+use crypto::HashValue;
+use starcoin_config::RocksdbConfig;
+use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_types::block::BlockHeaderBuilder;
+use std::collections::BTreeMap;
+
+fn random_nodes(count: usize) -> BTreeMap<HashValue, StateNode> {
+    (0..count)
+        .map(|_| (HashValue::random(), StateNode(HashValue::random().to_vec())))
+        .collect()
+}
+
+fn write_nodes(storage: &Storage, nodes: &BTreeMap<HashValue, StateNode>) -> Result<()> {
+    storage.write_nodes_with_created(nodes.clone(), nodes.keys().cloned().collect())
+}
+
+#[test]
+fn test_state_prune() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    ))?;
+    // nodes written before enabled are never pruned.
+    let legacy_nodes = random_nodes(2);
+    storage.write_nodes(legacy_nodes.clone())?;
+    storage.enable_state_prune()?;
+    assert!(storage.is_state_prune_enabled());
+
+    let nodes = random_nodes(3);
+    write_nodes(&storage, &nodes)?;
+    // the first node is shared by a later state, and a legacy node is reused.
+    let shared_node = nodes.keys().next().cloned().unwrap();
+    let legacy_node = legacy_nodes.keys().next().cloned().unwrap();
+    let mut reused_nodes = BTreeMap::new();
+    reused_nodes.insert(shared_node, nodes.get(&shared_node).cloned().unwrap());
+    reused_nodes.insert(
+        legacy_node,
+        legacy_nodes.get(&legacy_node).cloned().unwrap(),
+    );
+    write_nodes(&storage, &reused_nodes)?;
+
+    // the second node is the state root of the parent block, which is replaced by executing block 1.
+    let state_root = nodes.keys().nth(1).cloned().unwrap();
+    let parent = BlockHeaderBuilder::random()
+        .with_number(0)
+        .with_state_root(state_root)
+        .build();
+    let header = BlockHeaderBuilder::random()
+        .with_number(1)
+        .with_parent_hash(parent.id())
+        .build();
+    storage.save_block_header(parent)?;
+    let main_block_id = header.id();
+    storage.save_block_header(header)?;
+    let fork_block_id = HashValue::random();
+    let mut stale_nodes: Vec<_> = nodes.keys().cloned().collect();
+    stale_nodes.extend(legacy_nodes.keys().cloned());
+    storage.save_stale_state_nodes(1, main_block_id, stale_nodes.clone())?;
+    storage.save_stale_state_nodes(1, fork_block_id, stale_nodes)?;
+
+    let pruned = storage.prune_state_nodes(1, main_block_id)?;
+    assert_eq!(pruned, 2);
+    assert_eq!(storage.get_state_pruned_block_number()?, Some(1));
+    for (node_key, _) in nodes
+        .iter()
+        .filter(|(node_key, _)| **node_key != shared_node)
+    {
+        assert!(StateNodeStore::get(&storage, node_key)?.is_none());
+        // only the deleted state root is reported as pruned.
+        assert_eq!(storage.is_state_pruned(node_key)?, *node_key == state_root);
+    }
+    // a state root which never exists is not pruned.
+    assert!(!storage.is_state_pruned(&HashValue::random())?);
+    assert!(StateNodeStore::get(&storage, &shared_node)?.is_some());
+    for node_key in legacy_nodes.keys() {
+        assert!(StateNodeStore::get(&storage, node_key)?.is_some());
+        assert!(!storage.is_state_pruned(node_key)?);
+    }
+
+    // the records of the block number are all removed, prune again does nothing.
+    assert_eq!(storage.prune_state_nodes(1, fork_block_id)?, 0);
+    assert!(StateNodeStore::get(&storage, &shared_node)?.is_some());
+
+    storage.save_stale_state_nodes(2, main_block_id, vec![shared_node, legacy_node])?;
+    assert_eq!(storage.prune_state_nodes(2, main_block_id)?, 1);
+    assert!(StateNodeStore::get(&storage, &shared_node)?.is_none());
+    assert!(StateNodeStore::get(&storage, &legacy_node)?.is_some());
+    Ok(())
+}
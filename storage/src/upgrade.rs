@@ -138,6 +138,26 @@ impl DBUpgrade {
         Ok(())
     }
 
+    fn db_upgrade_v3_v4(instance: &mut StorageInstance) -> Result<()> {
+        // blocks committed before V4 have no event index, they are indexed by backfill later.
+        let chain_info_storage = ChainInfoStorage::new(instance.clone());
+        let block_storage = BlockStorage::new(instance.clone());
+        let event_index_start = match chain_info_storage.get_startup_info()? {
+            Some(startup_info) => {
+                let head = block_storage
+                    .get_block_header_by_hash(startup_info.main)?
+                    .ok_or_else(|| {
+                        format_err!("Startup block {:?} should exist", startup_info.main)
+                    })?;
+                head.number().saturating_add(1)
+            }
+            None => 0,
+        };
+        chain_info_storage.save_event_index_start(event_index_start)?;
+        info!("event index start from block {}", event_index_start);
+        Ok(())
+    }
+
     fn db_upgrade_v2_v3(instance: &mut StorageInstance) -> Result<()> {
         // https://github.com/facebook/rocksdb/issues/1295
         instance
@@ -173,6 +193,39 @@ impl DBUpgrade {
             (StorageVersion::V2, StorageVersion::V3) => {
                 Self::db_upgrade_v2_v3(instance)?;
             }
+
+            (StorageVersion::V1, StorageVersion::V4) => {
+                Self::db_upgrade_v1_v2(instance)?;
+                Self::db_upgrade_v2_v3(instance)?;
+                Self::db_upgrade_v3_v4(instance)?;
+            }
+
+            (StorageVersion::V2, StorageVersion::V4) => {
+                Self::db_upgrade_v2_v3(instance)?;
+                Self::db_upgrade_v3_v4(instance)?;
+            }
+
+            (StorageVersion::V3, StorageVersion::V4) => {
+                Self::db_upgrade_v3_v4(instance)?;
+            }
+
+            // V5 only adds the column families of state pruning, no data to migrate.
+            (StorageVersion::V1, StorageVersion::V5) => {
+                Self::db_upgrade_v1_v2(instance)?;
+                Self::db_upgrade_v2_v3(instance)?;
+                Self::db_upgrade_v3_v4(instance)?;
+            }
+
+            (StorageVersion::V2, StorageVersion::V5) => {
+                Self::db_upgrade_v2_v3(instance)?;
+                Self::db_upgrade_v3_v4(instance)?;
+            }
+
+            (StorageVersion::V3, StorageVersion::V5) => {
+                Self::db_upgrade_v3_v4(instance)?;
+            }
+
+            (StorageVersion::V4, StorageVersion::V5) => {}
             _ => bail!(
                 "Can not upgrade db from {:?} to {:?}",
                 version_in_db,
@@ -0,0 +1,48 @@
+use starcoin_metrics::{register, Opts, PrometheusError, Registry, UIntCounterVec, UIntGaugeVec};
+
+#[derive(Clone)]
+pub struct StratumMetrics {
+    pub stratum_share_total: UIntCounterVec,
+    pub stratum_worker_hash_rate: UIntGaugeVec,
+    pub stratum_share_difficulty: UIntGaugeVec,
+}
+
+impl StratumMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        let stratum_share_total = register(
+            UIntCounterVec::new(
+                Opts::new(
+                    "stratum_share_total",
+                    "Counters of shares submitted by stratum workers, such as valid|stale|invalid",
+                ),
+                &["worker", "status"],
+            )?,
+            registry,
+        )?;
+        let stratum_worker_hash_rate = register(
+            UIntGaugeVec::new(
+                Opts::new(
+                    "stratum_worker_hash_rate",
+                    "Hash rate of stratum workers estimated from their shares",
+                ),
+                &["worker"],
+            )?,
+            registry,
+        )?;
+        let stratum_share_difficulty = register(
+            UIntGaugeVec::new(
+                Opts::new(
+                    "stratum_share_difficulty",
+                    "Share difficulty of stratum workers",
+                ),
+                &["worker"],
+            )?,
+            registry,
+        )?;
+        Ok(Self {
+            stratum_share_total,
+            stratum_worker_hash_rate,
+            stratum_share_difficulty,
+        })
+    }
+}
@@ -14,6 +14,7 @@ use starcoin_miner::SubmitSealRequest as MinerSubmitSealRequest;
 use starcoin_service_registry::{ServiceRef, ServiceRequest};
 use starcoin_types::block::BlockHeaderExtra;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::U256;
 use std::borrow::BorrowMut;
 use std::convert::TryInto;
 use std::io::Write;
@@ -205,7 +206,12 @@ impl StratumJob {
 }
 
 impl StratumJobResponse {
-    pub fn from(e: &MintBlockEvent, login: Option<LoginRequest>, worker_id: [u8; 4]) -> Self {
+    pub fn from(
+        e: &MintBlockEvent,
+        login: Option<LoginRequest>,
+        worker_id: [u8; 4],
+        share_difficulty: U256,
+    ) -> Self {
         let mut minting_blob = e.minting_blob.clone();
         let _ = minting_blob[35..39].borrow_mut().write_all(&worker_id);
         let worker_id_hex = hex::encode(&worker_id);
@@ -217,7 +223,7 @@ impl StratumJobResponse {
             job: StratumJob {
                 height: 0,
                 id: worker_id_hex,
-                target: difficulty_to_target_hex(e.difficulty),
+                target: difficulty_to_target_hex(share_difficulty),
                 job_id,
                 blob: hex::encode(&minting_blob),
             },
@@ -1,32 +1,69 @@
+use crate::metrics::StratumMetrics;
 use crate::rpc::*;
-use anyhow::Result;
+use crate::worker::{ShareStatus, VarDiffConfig, Worker, WorkerStats};
+use anyhow::{bail, format_err, Result};
+use consensus::{difficult_to_target, Consensus};
 use futures::channel::mpsc;
 use futures::StreamExt;
 use jsonrpc_pubsub::SubscriptionId;
+use starcoin_config::NodeConfig;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{
-    MinerService, SubmitSealRequest as MinerSubmitSealRequest, UpdateSubscriberNumRequest,
+    MinerService, SubmitHashRateRequest, SubmitSealRequest as MinerSubmitSealRequest,
+    UpdateSubscriberNumRequest,
 };
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRef,
+    ServiceRequest,
 };
 use starcoin_types::system_events::MintBlockEvent;
-use std::collections::HashMap;
+use starcoin_types::U256;
+use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::sync::atomic;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Share difficulty of the workers is adjusted, and their hash rate estimated, once per interval.
+const RETARGET_INTERVAL: Duration = Duration::from_secs(30);
+// A login without subscription is forgotten, with its share statistics, once idle for so long.
+const WORKER_IDLE_EXPIRE: Duration = Duration::from_secs(600);
 
 pub struct Stratum {
     uid: atomic::AtomicU32,
     mint_block_subscribers: HashMap<u32, (mpsc::UnboundedSender<StratumJobResponse>, LoginRequest)>,
     miner_service: ServiceRef<MinerService>,
+    vardiff: VarDiffConfig,
+    workers: HashMap<String, Worker>,
+    current_job: Option<MintBlockEvent>,
+    metrics: Option<StratumMetrics>,
+}
+
+#[derive(Debug)]
+pub(crate) struct RetargetEvent;
+
+/// Get the share statistics of the workers, one entry per login.
+#[derive(Debug)]
+pub struct WorkerStatsRequest;
+
+impl ServiceRequest for WorkerStatsRequest {
+    type Response = Vec<WorkerStats>;
 }
 
 impl Stratum {
-    fn new(miner_service: ServiceRef<MinerService>) -> Self {
+    fn new(
+        miner_service: ServiceRef<MinerService>,
+        vardiff: VarDiffConfig,
+        metrics: Option<StratumMetrics>,
+    ) -> Self {
         Self {
             miner_service,
             uid: atomic::AtomicU32::new(1),
             mint_block_subscribers: Default::default(),
+            vardiff,
+            workers: Default::default(),
+            current_job: None,
+            metrics,
         }
     }
     fn next_id(&self) -> u32 {
@@ -35,15 +72,39 @@ impl Stratum {
     fn sync_current_job(&mut self) -> Result<Option<MintBlockEvent>> {
         let service = self.miner_service.clone();
         let subscribers_num = self.mint_block_subscribers.len() as u32;
-        futures::executor::block_on(service.send(UpdateSubscriberNumRequest {
+        let current_job = futures::executor::block_on(service.send(UpdateSubscriberNumRequest {
             number: Some(subscribers_num),
-        }))
+        }))?;
+        self.current_job = current_job.clone();
+        Ok(current_job)
+    }
+    fn worker(&mut self, login: &str) -> &mut Worker {
+        let vardiff = self.vardiff;
+        self.workers
+            .entry(login.to_string())
+            .or_insert_with(|| Worker::new(&vardiff))
     }
-    fn send_to_all(&mut self, event: MintBlockEvent) {
+    /// Issue the job to `login`, return the share difficulty of it.
+    fn issue_job(&mut self, login: &str, event: &MintBlockEvent) -> U256 {
+        let vardiff = self.vardiff;
+        self.worker(login)
+            .issue_job(&vardiff, &job_id(event), event.difficulty)
+    }
+    /// Send the job to the subscribers of `login`, or to all subscribers if `login` is `None`.
+    fn send_job(&mut self, event: &MintBlockEvent, login: Option<&str>) {
+        let vardiff = self.vardiff;
         let mut remove_outdated = vec![];
-        for (id, (ch, login)) in self.mint_block_subscribers.iter() {
-            let worker_id = login.get_worker_id(*id);
-            let job = StratumJobResponse::from(&event, None, worker_id);
+        for (id, (ch, sub_login)) in self.mint_block_subscribers.iter() {
+            if matches!(login, Some(login) if login != sub_login.login) {
+                continue;
+            }
+            let worker_id = sub_login.get_worker_id(*id);
+            let share_difficulty = self
+                .workers
+                .entry(sub_login.login.clone())
+                .or_insert_with(|| Worker::new(&vardiff))
+                .issue_job(&vardiff, &job_id(event), event.difficulty);
+            let job = StratumJobResponse::from(event, None, worker_id, share_difficulty);
             if let Err(err) = ch.unbounded_send(job) {
                 if err.is_disconnected() {
                     remove_outdated.push(*id);
@@ -56,12 +117,34 @@ impl Stratum {
             self.mint_block_subscribers.remove(&id);
         }
     }
+    fn login_of(&self, worker_id: &str) -> Option<String> {
+        let worker_id = hex::decode(worker_id).ok()?;
+        self.mint_block_subscribers
+            .iter()
+            .find(|(id, (_, login))| login.get_worker_id(**id)[..] == worker_id[..])
+            .map(|(_, (_, login))| login.login.clone())
+    }
+    fn record_share(&mut self, login: &str, status: ShareStatus, share_difficulty: U256) {
+        self.worker(login).record(status, share_difficulty);
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics
+                .stratum_share_total
+                .with_label_values(&[login, status.as_str()])
+                .inc();
+        }
+    }
+}
+
+/// The job id the job is sent to the workers with, see `StratumJobResponse::from`.
+fn job_id(event: &MintBlockEvent) -> String {
+    hex::encode(&event.minting_blob[0..8])
 }
 
 impl ActorService for Stratum {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.set_mailbox_capacity(1024);
         ctx.subscribe::<MintBlockEvent>();
+        ctx.run_interval(RETARGET_INTERVAL, |ctx| ctx.notify(RetargetEvent));
         Ok(())
     }
 
@@ -73,7 +156,82 @@ impl ActorService for Stratum {
 
 impl EventHandler<Self, MintBlockEvent> for Stratum {
     fn handle_event(&mut self, event: MintBlockEvent, _ctx: &mut ServiceContext<Stratum>) {
-        self.send_to_all(event);
+        self.send_job(&event, None);
+        self.current_job = Some(event);
+    }
+}
+
+impl EventHandler<Self, RetargetEvent> for Stratum {
+    fn handle_event(&mut self, _event: RetargetEvent, _ctx: &mut ServiceContext<Stratum>) {
+        let block_difficulty = self
+            .current_job
+            .as_ref()
+            .map(|job| job.difficulty)
+            .unwrap_or_else(U256::max_value);
+        let mut retargeted = vec![];
+        for (login, worker) in self.workers.iter_mut() {
+            if let Some(difficulty) = worker.retarget(&self.vardiff) {
+                debug!(target: "stratum", "retarget share difficulty of {} to {}", login, difficulty);
+                retargeted.push(login.clone());
+            }
+            if let Some(metrics) = self.metrics.as_ref() {
+                let share_difficulty = worker
+                    .share_difficulty(&self.vardiff, block_difficulty)
+                    .min(U256::from(u64::max_value()))
+                    .low_u64();
+                metrics
+                    .stratum_share_difficulty
+                    .with_label_values(&[login.as_str()])
+                    .set(share_difficulty);
+                metrics
+                    .stratum_worker_hash_rate
+                    .with_label_values(&[login.as_str()])
+                    .set(worker.hash_rate());
+            }
+            // Only connected workers are reported, the miner service expires the others.
+            if self
+                .mint_block_subscribers
+                .values()
+                .any(|(_, sub_login)| &sub_login.login == login)
+            {
+                if let Err(e) = self.miner_service.try_send(SubmitHashRateRequest {
+                    worker: login.clone(),
+                    hash_rate: worker.hash_rate(),
+                }) {
+                    warn!(target: "stratum", "Failed to report hash rate of {}: {}", login, e);
+                }
+            }
+        }
+        if let Some(job) = self.current_job.clone() {
+            for login in retargeted {
+                self.send_job(&job, Some(&login));
+            }
+        }
+        let subscribed = self
+            .mint_block_subscribers
+            .values()
+            .map(|(_, sub_login)| sub_login.login.as_str())
+            .collect::<HashSet<_>>();
+        let idle = self
+            .workers
+            .iter()
+            .filter(|(login, worker)| {
+                !subscribed.contains(login.as_str()) && worker.is_idle(WORKER_IDLE_EXPIRE)
+            })
+            .map(|(login, _)| login.clone())
+            .collect::<Vec<_>>();
+        for login in idle {
+            debug!(target: "stratum", "remove idle worker {}", login);
+            self.workers.remove(&login);
+            if let Some(metrics) = self.metrics.as_ref() {
+                let _ = metrics
+                    .stratum_share_difficulty
+                    .remove_label_values(&[login.as_str()]);
+                let _ = metrics
+                    .stratum_worker_hash_rate
+                    .remove_label_values(&[login.as_str()]);
+            }
+        }
     }
 }
 
@@ -128,9 +286,11 @@ impl ServiceHandler<Self, SubscribeJobEvent> for Stratum {
             }
         });
         if let Ok(Some(event)) = self.sync_current_job() {
+            let share_difficulty = self.issue_job(&login.login, &event);
             ctx.spawn(async move {
                 let worker_id = login.get_worker_id(sub_id);
-                let stratum_result = StratumJobResponse::from(&event, Some(login), worker_id);
+                let stratum_result =
+                    StratumJobResponse::from(&event, Some(login), worker_id, share_difficulty);
                 if let Err(err) = sender.unbounded_send(stratum_result) {
                     error!(target: "stratum", "Failed to send MintBlockEvent: {}", err);
                 }
@@ -144,27 +304,104 @@ impl ServiceHandler<Self, SubscribeJobEvent> for Stratum {
 impl ServiceHandler<Self, SubmitShareEvent> for Stratum {
     fn handle(&mut self, msg: SubmitShareEvent, _ctx: &mut ServiceContext<Self>) -> Result<()> {
         info!(target: "stratum", "received submit share event:{:?}", &msg.0);
+        let share = msg.0;
+        let login = self
+            .login_of(&share.id)
+            .ok_or_else(|| format_err!("Unknown stratum worker: {}", share.id))?;
         if let Some(current_mint_event) = self.sync_current_job()? {
-            let job_id = hex::encode(&current_mint_event.minting_blob[0..8]);
-            let submit_job_id = msg.0.job_id.clone();
-            if submit_job_id != job_id {
-                warn!(target: "stratum", "received job mismatch with current job,{},{}", submit_job_id, job_id);
-                return Ok(());
+            let vardiff = self.vardiff;
+            let job_id = job_id(&current_mint_event);
+            let submit_job_id = share.job_id.clone();
+            let worker = self.worker(&login);
+            // The share is validated against the difficulty of the job issued to the worker.
+            let share_difficulty = match worker.job_difficulty(&submit_job_id) {
+                Some(share_difficulty) if submit_job_id == job_id => share_difficulty,
+                _ => {
+                    warn!(target: "stratum", "received job mismatch with current job,{},{}", submit_job_id, job_id);
+                    let share_difficulty =
+                        worker.share_difficulty(&vardiff, current_mint_event.difficulty);
+                    self.record_share(&login, ShareStatus::Stale, share_difficulty);
+                    return Ok(());
+                }
             };
-            let mut seal: MinerSubmitSealRequest = msg.0.try_into()?;
-
-            seal.minting_blob = current_mint_event.minting_blob;
-            let _ = self.miner_service.try_send(seal)?;
+            let seal: Result<MinerSubmitSealRequest> = share.try_into();
+            let mut seal = match seal {
+                Ok(seal)
+                    if self
+                        .worker(&login)
+                        .submit_seal(&job_id, seal.extra, seal.nonce) =>
+                {
+                    seal
+                }
+                Ok(seal) => {
+                    self.record_share(&login, ShareStatus::Invalid, share_difficulty);
+                    bail!("Duplicate share of {}, nonce: {}", login, seal.nonce);
+                }
+                Err(e) => {
+                    self.record_share(&login, ShareStatus::Invalid, share_difficulty);
+                    return Err(e);
+                }
+            };
+            let pow_hash: Result<U256> = current_mint_event
+                .strategy
+                .calculate_pow_hash(&current_mint_event.minting_blob, seal.nonce, &seal.extra)
+                .map(Into::into);
+            match pow_hash {
+                Ok(pow_hash) if pow_hash <= difficult_to_target(share_difficulty) => {
+                    self.record_share(&login, ShareStatus::Valid, share_difficulty);
+                    if pow_hash <= difficult_to_target(current_mint_event.difficulty) {
+                        seal.minting_blob = current_mint_event.minting_blob;
+                        let _ = self.miner_service.try_send(seal)?;
+                    }
+                }
+                Ok(_) => {
+                    self.record_share(&login, ShareStatus::Invalid, share_difficulty);
+                    bail!(
+                        "Share of {} does not meet the share target, nonce: {}",
+                        login,
+                        seal.nonce
+                    );
+                }
+                Err(e) => {
+                    self.record_share(&login, ShareStatus::Invalid, share_difficulty);
+                    return Err(e);
+                }
+            }
         }
         Ok(())
     }
 }
 
+impl ServiceHandler<Self, WorkerStatsRequest> for Stratum {
+    fn handle(
+        &mut self,
+        _msg: WorkerStatsRequest,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<WorkerStats> {
+        let mut stats = self
+            .workers
+            .iter()
+            .map(|(login, worker)| worker.stats(login.clone()))
+            .collect::<Vec<_>>();
+        stats.sort_by(|a, b| a.login.cmp(&b.login));
+        stats
+    }
+}
+
 pub struct StratumFactory;
 
 impl ServiceFactory<Stratum> for StratumFactory {
     fn create(ctx: &mut ServiceContext<Stratum>) -> Result<Stratum> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         let miner_service = ctx.service_ref::<MinerService>()?.clone();
-        Ok(Stratum::new(miner_service))
+        let vardiff = VarDiffConfig {
+            share_time: config.stratum.share_time(),
+            min_difficulty: U256::from(config.stratum.min_share_difficulty()),
+        };
+        let metrics = config
+            .metrics
+            .registry()
+            .and_then(|registry| StratumMetrics::register(registry).ok());
+        Ok(Stratum::new(miner_service, vardiff, metrics))
     }
 }
@@ -0,0 +1,316 @@
+use starcoin_types::block::BlockHeaderExtra;
+use starcoin_types::U256;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Bound of the share difficulty change in one retarget, avoid oscillating on a few lucky shares.
+const MAX_RETARGET_FACTOR: u64 = 4;
+
+#[derive(Clone, Copy, Debug)]
+pub struct VarDiffConfig {
+    /// Expected time between two shares, zero disables vardiff.
+    pub share_time: Duration,
+    pub min_difficulty: U256,
+}
+
+impl VarDiffConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.share_time.is_zero()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkerStats {
+    pub login: String,
+    pub share_difficulty: U256,
+    pub valid_shares: u64,
+    pub stale_shares: u64,
+    pub invalid_shares: u64,
+    /// Hash rate estimated from the valid shares in the last retarget window.
+    pub hash_rate: u64,
+    pub last_share: Option<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShareStatus {
+    Valid,
+    Stale,
+    Invalid,
+}
+
+impl ShareStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareStatus::Valid => "valid",
+            ShareStatus::Stale => "stale",
+            ShareStatus::Invalid => "invalid",
+        }
+    }
+}
+
+/// The job last issued to a login, its shares are validated against the difficulty it was issued
+/// with.
+#[derive(Clone, Debug)]
+struct IssuedJob {
+    job_id: String,
+    difficulty: U256,
+    /// The seals submitted for the job, each one is only accepted once.
+    seals: HashSet<(BlockHeaderExtra, u32)>,
+}
+
+/// Share accounting and variable difficulty of one login.
+#[derive(Clone, Debug)]
+pub(crate) struct Worker {
+    share_difficulty: U256,
+    job: Option<IssuedJob>,
+    last_active: Instant,
+    valid_shares: u64,
+    stale_shares: u64,
+    invalid_shares: u64,
+    hash_rate: u64,
+    last_share: Option<Instant>,
+    window_start: Instant,
+    window_shares: u64,
+    window_work: U256,
+}
+
+impl Worker {
+    pub(crate) fn new(config: &VarDiffConfig) -> Self {
+        Self {
+            share_difficulty: config.min_difficulty,
+            job: None,
+            last_active: Instant::now(),
+            valid_shares: 0,
+            stale_shares: 0,
+            invalid_shares: 0,
+            hash_rate: 0,
+            last_share: None,
+            window_start: Instant::now(),
+            window_shares: 0,
+            window_work: U256::zero(),
+        }
+    }
+
+    /// The difficulty shares are validated against, never harder than the block difficulty so
+    /// that every block solution is also a valid share.
+    pub(crate) fn share_difficulty(&self, config: &VarDiffConfig, block_difficulty: U256) -> U256 {
+        if config.is_enabled() {
+            self.share_difficulty.min(block_difficulty)
+        } else {
+            block_difficulty
+        }
+    }
+
+    /// Issue the job to the worker, return the share difficulty it is issued with.
+    pub(crate) fn issue_job(
+        &mut self,
+        config: &VarDiffConfig,
+        job_id: &str,
+        block_difficulty: U256,
+    ) -> U256 {
+        let difficulty = self.share_difficulty(config, block_difficulty);
+        self.last_active = Instant::now();
+        match self.job.as_mut() {
+            // The job is issued again after a retarget, shares of the previous difficulty may
+            // still be in flight.
+            Some(job) if job.job_id == job_id => job.difficulty = job.difficulty.min(difficulty),
+            _ => {
+                self.job = Some(IssuedJob {
+                    job_id: job_id.to_string(),
+                    difficulty,
+                    seals: HashSet::new(),
+                })
+            }
+        }
+        difficulty
+    }
+
+    /// The share difficulty of the job, none if the job is not the one last issued to the worker.
+    pub(crate) fn job_difficulty(&self, job_id: &str) -> Option<U256> {
+        self.job
+            .as_ref()
+            .filter(|job| job.job_id == job_id)
+            .map(|job| job.difficulty)
+    }
+
+    /// Remember the seal submitted for the job, return false if it has been submitted before.
+    pub(crate) fn submit_seal(
+        &mut self,
+        job_id: &str,
+        extra: BlockHeaderExtra,
+        nonce: u32,
+    ) -> bool {
+        match self.job.as_mut() {
+            Some(job) if job.job_id == job_id => job.seals.insert((extra, nonce)),
+            _ => false,
+        }
+    }
+
+    /// Whether the worker has been issued no job and submitted no share for `expire`.
+    pub(crate) fn is_idle(&self, expire: Duration) -> bool {
+        self.last_active.elapsed() >= expire
+    }
+
+    pub(crate) fn hash_rate(&self) -> u64 {
+        self.hash_rate
+    }
+
+    pub(crate) fn record(&mut self, status: ShareStatus, share_difficulty: U256) {
+        self.last_share = Some(Instant::now());
+        self.last_active = Instant::now();
+        match status {
+            ShareStatus::Valid => {
+                self.valid_shares += 1;
+                self.window_shares += 1;
+                self.window_work = self.window_work.saturating_add(share_difficulty);
+            }
+            ShareStatus::Stale => self.stale_shares += 1,
+            ShareStatus::Invalid => self.invalid_shares += 1,
+        }
+    }
+
+    /// Close the current window: estimate the hash rate from the work done in it, and move the
+    /// share difficulty toward one share per `share_time`. Return the new difficulty if it changed.
+    pub(crate) fn retarget(&mut self, config: &VarDiffConfig) -> Option<U256> {
+        let elapsed_ms = (self.window_start.elapsed().as_millis() as u64).max(1);
+        let hash_rate =
+            self.window_work.saturating_mul(U256::from(1000u64)) / U256::from(elapsed_ms);
+        self.hash_rate = hash_rate.min(U256::from(u64::max_value())).low_u64();
+        let shares = self.window_shares;
+        self.window_start = Instant::now();
+        self.window_shares = 0;
+        self.window_work = U256::zero();
+        if !config.is_enabled() {
+            return None;
+        }
+
+        let current = self.share_difficulty;
+        let target = if shares == 0 {
+            current / U256::from(2u64)
+        } else {
+            let share_time_ms = config.share_time.as_millis() as u64;
+            current
+                .saturating_mul(U256::from(shares))
+                .saturating_mul(U256::from(share_time_ms))
+                / U256::from(elapsed_ms)
+        };
+        let factor = U256::from(MAX_RETARGET_FACTOR);
+        let target = target
+            .max(current / factor)
+            .min(current.saturating_mul(factor))
+            .max(config.min_difficulty);
+        if target == current {
+            None
+        } else {
+            self.share_difficulty = target;
+            Some(target)
+        }
+    }
+
+    pub(crate) fn stats(&self, login: String) -> WorkerStats {
+        WorkerStats {
+            login,
+            share_difficulty: self.share_difficulty,
+            valid_shares: self.valid_shares,
+            stale_shares: self.stale_shares,
+            invalid_shares: self.invalid_shares,
+            hash_rate: self.hash_rate,
+            last_share: self.last_share.map(|instant| instant.elapsed()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> VarDiffConfig {
+        VarDiffConfig {
+            share_time: Duration::from_secs(10),
+            min_difficulty: U256::from(1024u64),
+        }
+    }
+
+    #[test]
+    fn test_retarget_without_shares() {
+        let config = config();
+        let mut worker = Worker::new(&config);
+        worker.share_difficulty = U256::from(8192u64);
+        assert_eq!(worker.retarget(&config), Some(U256::from(4096u64)));
+        assert_eq!(worker.retarget(&config), Some(U256::from(2048u64)));
+        assert_eq!(worker.retarget(&config), Some(U256::from(1024u64)));
+        // Never below the minimum difficulty.
+        assert_eq!(worker.retarget(&config), None);
+    }
+
+    #[test]
+    fn test_retarget_with_fast_shares() {
+        let config = config();
+        let mut worker = Worker::new(&config);
+        let difficulty = worker.share_difficulty(&config, U256::max_value());
+        for _ in 0..100 {
+            worker.record(ShareStatus::Valid, difficulty);
+        }
+        worker.record(ShareStatus::Stale, difficulty);
+        worker.record(ShareStatus::Invalid, difficulty);
+        // Far too many shares for the window, the difficulty rises by the bounded factor.
+        assert_eq!(
+            worker.retarget(&config),
+            Some(difficulty * U256::from(MAX_RETARGET_FACTOR))
+        );
+        assert!(worker.hash_rate() > 0);
+        let stats = worker.stats("alice".to_string());
+        assert_eq!(stats.valid_shares, 100);
+        assert_eq!(stats.stale_shares, 1);
+        assert_eq!(stats.invalid_shares, 1);
+        // Share difficulty is capped by the block difficulty.
+        assert_eq!(
+            worker.share_difficulty(&config, U256::from(16u64)),
+            U256::from(16u64)
+        );
+    }
+
+    #[test]
+    fn test_issued_job() {
+        let config = config();
+        let mut worker = Worker::new(&config);
+        let extra = BlockHeaderExtra::new([1, 2, 3, 4]);
+        assert_eq!(worker.job_difficulty("job1"), None);
+        assert!(!worker.submit_seal("job1", extra, 1));
+
+        let difficulty = worker.issue_job(&config, "job1", U256::max_value());
+        assert_eq!(worker.job_difficulty("job1"), Some(difficulty));
+        assert!(worker.submit_seal("job1", extra, 1));
+        // The same seal is only accepted once.
+        assert!(!worker.submit_seal("job1", extra, 1));
+        assert!(worker.submit_seal("job1", BlockHeaderExtra::new([0; 4]), 1));
+
+        // Issued again with a higher difficulty, the in flight shares of the lower one are valid.
+        worker.share_difficulty = difficulty * U256::from(2u64);
+        worker.issue_job(&config, "job1", U256::max_value());
+        assert_eq!(worker.job_difficulty("job1"), Some(difficulty));
+        assert!(!worker.submit_seal("job1", extra, 1));
+
+        // A new job replaces the previous one.
+        worker.issue_job(&config, "job2", U256::from(16u64));
+        assert_eq!(worker.job_difficulty("job1"), None);
+        assert_eq!(worker.job_difficulty("job2"), Some(U256::from(16u64)));
+        assert!(worker.submit_seal("job2", extra, 1));
+        assert!(!worker.is_idle(Duration::from_secs(60)));
+        assert!(worker.is_idle(Duration::from_secs(0)));
+    }
+
+    #[test]
+    fn test_vardiff_disabled() {
+        let config = VarDiffConfig {
+            share_time: Duration::from_secs(0),
+            min_difficulty: U256::from(1024u64),
+        };
+        let mut worker = Worker::new(&config);
+        assert_eq!(
+            worker.share_difficulty(&config, U256::from(1_000_000u64)),
+            U256::from(1_000_000u64)
+        );
+        assert_eq!(worker.retarget(&config), None);
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block_connector::{ExecuteRequest, ResetRequest, WriteBlockChainService};
+use crate::block_connector::{
+    ExecuteRequest, ResetRequest, SwitchHeadRequest, WriteBlockChainService,
+};
 use crate::sync::{CheckSyncEvent, SyncService};
 use crate::tasks::BlockConnectedEvent;
 use anyhow::{format_err, Result};
@@ -181,6 +183,16 @@ impl ServiceHandler<Self, ResetRequest> for BlockConnectorService {
     }
 }
 
+impl ServiceHandler<Self, SwitchHeadRequest> for BlockConnectorService {
+    fn handle(
+        &mut self,
+        msg: SwitchHeadRequest,
+        _ctx: &mut ServiceContext<BlockConnectorService>,
+    ) -> Result<()> {
+        self.chain_service.switch_head(msg.block_hash)
+    }
+}
+
 impl ServiceHandler<Self, ExecuteRequest> for BlockConnectorService {
     fn handle(
         &mut self,
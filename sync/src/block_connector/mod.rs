@@ -32,6 +32,17 @@ impl ServiceRequest for ResetRequest {
     type Response = anyhow::Result<()>;
 }
 
+/// Switch the main chain head to a block which is not connected by the main chain,
+/// but its state is synced, such as the fast sync pivot.
+#[derive(Debug, Clone)]
+pub struct SwitchHeadRequest {
+    pub block_hash: HashValue,
+}
+
+impl ServiceRequest for SwitchHeadRequest {
+    type Response = anyhow::Result<()>;
+}
+
 #[derive(Debug, Clone)]
 pub struct ExecuteRequest {
     pub block: Block,
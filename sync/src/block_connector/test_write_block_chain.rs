@@ -4,16 +4,19 @@
 use crate::block_connector::WriteBlockChainService;
 use config::NodeConfig;
 use consensus::Consensus;
+use futures::StreamExt;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_chain_api::ConnectBlockError;
 use starcoin_chain_service::WriteableChainService;
 use starcoin_genesis::Genesis as StarcoinGenesis;
-use starcoin_service_registry::bus::BusService;
-use starcoin_service_registry::{RegistryAsyncService, RegistryService};
+use starcoin_service_registry::bus::{Bus, BusService};
+use starcoin_service_registry::{RegistryAsyncService, RegistryService, ServiceRef};
 use starcoin_storage::Store;
 use starcoin_txpool_mock_service::MockTxPoolService;
 use starcoin_types::block::Block;
 use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::system_events::ChainReorgEvent;
 use starcoin_vm_types::time::TimeService;
 use std::sync::Arc;
 
@@ -21,6 +24,17 @@ pub async fn create_writeable_block_chain() -> (
     WriteBlockChainService<MockTxPoolService>,
     Arc<NodeConfig>,
     Arc<dyn Store>,
+) {
+    let (writeable_block_chain_service, node_config, storage, _bus) =
+        create_writeable_block_chain_with_bus().await;
+    (writeable_block_chain_service, node_config, storage)
+}
+
+pub async fn create_writeable_block_chain_with_bus() -> (
+    WriteBlockChainService<MockTxPoolService>,
+    Arc<NodeConfig>,
+    Arc<dyn Store>,
+    ServiceRef<BusService>,
 ) {
     let node_config = NodeConfig::random_for_test();
     let node_config = Arc::new(node_config);
@@ -36,12 +50,13 @@ pub async fn create_writeable_block_chain() -> (
             StartupInfo::new(chain_info.head().id()),
             storage.clone(),
             txpool_service,
-            bus,
+            bus.clone(),
             None,
         )
         .unwrap(),
         node_config,
         storage,
+        bus,
     )
 }
 
@@ -138,6 +153,84 @@ fn gen_fork_block_chain(
     }
 }
 
+fn new_fork_block(
+    fork_number: u64,
+    node_config: Arc<NodeConfig>,
+    writeable_block_chain_service: &WriteBlockChainService<MockTxPoolService>,
+) -> Block {
+    let net = node_config.net();
+    let parent = writeable_block_chain_service
+        .get_main()
+        .get_header_by_number(fork_number)
+        .unwrap()
+        .unwrap();
+    let block_chain = BlockChain::new(
+        net.time_service(),
+        parent.id(),
+        writeable_block_chain_service.get_main().get_storage(),
+        None,
+    )
+    .unwrap();
+    let (block_template, _) = block_chain
+        .create_block_template(
+            *AccountInfo::random().address(),
+            None,
+            Vec::new(),
+            vec![],
+            None,
+        )
+        .unwrap();
+    block_chain
+        .consensus()
+        .create_block(block_template, net.time_service().as_ref())
+        .unwrap()
+}
+
+#[stest::test]
+async fn test_block_chain_fork_point_pruned() -> anyhow::Result<()> {
+    let times = 10;
+    let (mut writeable_block_chain_service, node_config, storage) =
+        create_writeable_block_chain().await;
+    let net = node_config.net();
+    gen_blocks(
+        times,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let pruned_fork_block = new_fork_block(2, node_config.clone(), &writeable_block_chain_service);
+    // the pruner starts from the current head when state pruning is enabled.
+    storage.enable_state_prune()?;
+    assert_eq!(storage.get_state_pruned_block_number()?, Some(times));
+    gen_blocks(
+        2,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+
+    let err = writeable_block_chain_service
+        .try_connect(pruned_fork_block)
+        .unwrap_err();
+    match err.downcast::<ConnectBlockError>()? {
+        ConnectBlockError::ForkPointPruned(_, fork_number, pruned_number) => {
+            assert_eq!(fork_number, 2);
+            assert_eq!(pruned_number, times);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    // the fork point at the pruned block number still has its state.
+    let fork_block = new_fork_block(times, node_config, &writeable_block_chain_service);
+    writeable_block_chain_service.try_connect(fork_block)?;
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .number(),
+        times + 2
+    );
+    Ok(())
+}
+
 #[stest::test]
 async fn test_block_chain_forks() {
     let times = 10;
@@ -209,7 +302,9 @@ async fn test_block_chain_switch_main() {
 #[stest::test]
 async fn test_block_chain_reset() -> anyhow::Result<()> {
     let times = 10;
-    let (mut writeable_block_chain_service, node_config, _) = create_writeable_block_chain().await;
+    let (mut writeable_block_chain_service, node_config, _, bus) =
+        create_writeable_block_chain_with_bus().await;
+    let mut reorg_receiver = bus.channel::<ChainReorgEvent>().await?;
     let net = node_config.net();
     gen_blocks(
         times,
@@ -227,7 +322,14 @@ async fn test_block_chain_reset() -> anyhow::Result<()> {
         .get_main()
         .get_block_by_number(3)?
         .unwrap();
+    let old_head = writeable_block_chain_service.get_main().current_header();
     writeable_block_chain_service.reset(block.id())?;
+    let reorg = reorg_receiver.next().await.unwrap();
+    assert_eq!(reorg.common_ancestor, block.id());
+    assert!(reorg.enacted.is_empty());
+    assert_eq!(reorg.retracted.len() as u64, times - 3);
+    assert_eq!(reorg.retracted.last().unwrap().id(), old_head.id());
+    assert!(!reorg.truncated);
     assert_eq!(
         writeable_block_chain_service
             .get_main()
@@ -17,7 +17,7 @@ use starcoin_types::block::BlockInfo;
 use starcoin_types::{
     block::{Block, BlockHeader, ExecutedBlock},
     startup_info::StartupInfo,
-    system_events::{NewBranch, NewHeadBlock},
+    system_events::{ChainReorgEvent, NewBranch, NewHeadBlock},
 };
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::fmt::Formatter;
@@ -162,6 +162,32 @@ where
         Ok((block_info, block_chain))
     }
 
+    /// Reject the block whose branch forks from the main chain below the pruned block number,
+    /// the state nodes shared by the branch may have been deleted by state pruning.
+    fn ensure_fork_point_not_pruned(&self, header: &BlockHeader) -> Result<()> {
+        let pruned_block_number = match self.storage.get_state_pruned_block_number()? {
+            Some(block_number) => block_number,
+            None => return Ok(()),
+        };
+        let mut parent_hash = header.parent_hash();
+        while let Some(parent) = self.storage.get_block_header_by_hash(parent_hash)? {
+            if parent.number() < pruned_block_number {
+                return Err(ConnectBlockError::ForkPointPruned(
+                    Box::new(header.clone()),
+                    parent.number(),
+                    pruned_block_number,
+                )
+                .into());
+            }
+            if self.main.get_hash_by_number(parent.number())? == Some(parent_hash) {
+                return Ok(());
+            }
+            parent_hash = parent.parent_hash();
+        }
+        // the parent does not exist, let the connect handle it.
+        Ok(())
+    }
+
     fn block_exist(&self, block_id: HashValue) -> Result<bool> {
         Ok(matches!(self.storage.get_block_info(block_id)?, Some(_)))
     }
@@ -177,13 +203,29 @@ where
         let parent_is_main_head = self.is_main_head(&executed_block.header().parent_hash());
 
         if branch_total_difficulty > main_total_difficulty {
-            let (enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
+            let (common_ancestor, enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
                 if !parent_is_main_head {
                     self.find_ancestors_from_accumulator(&new_branch)?
                 } else {
-                    (1, vec![executed_block.block.clone()], 0, vec![])
+                    (
+                        executed_block.header().parent_hash(),
+                        1,
+                        vec![executed_block.block.clone()],
+                        0,
+                        vec![],
+                    )
                 };
             self.main = new_branch;
+            if !retracted_blocks.is_empty() {
+                let truncated = enacted_count > enacted_blocks.len() as u64
+                    || retracted_count > retracted_blocks.len() as u64;
+                self.broadcast_chain_reorg(
+                    common_ancestor,
+                    enacted_blocks.clone(),
+                    retracted_blocks.clone(),
+                    truncated,
+                );
+            }
 
             self.do_new_head(
                 executed_block,
@@ -250,6 +292,11 @@ where
             self.vm_metrics.clone(),
         )?;
 
+        // collect the retracted blocks before they are deleted.
+        let old_head = self.main.current_header();
+        let (retracted_blocks, truncated) =
+            self.find_blocks_until(old_head.id(), block_id, MAX_ROLL_BACK_BLOCK)?;
+
         // delete block since from block.number + 1 to latest.
         let start = new_head_block.header().number().saturating_add(1);
         let latest = self.main.status().head.number();
@@ -266,6 +313,10 @@ where
 
         self.main = new_branch;
 
+        if !retracted_blocks.is_empty() {
+            // the new head is the common ancestor, so no block is enacted.
+            self.broadcast_chain_reorg(block_id, vec![], retracted_blocks, truncated);
+        }
         let (enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
             (1, vec![executed_block.block.clone()], 0, vec![]);
         self.do_new_head(
@@ -278,6 +329,44 @@ where
         Ok(())
     }
 
+    /// Switch the main chain to `block_id` directly, the block and its state should exist in storage.
+    pub fn switch_head(&mut self, block_id: HashValue) -> Result<()> {
+        let new_main = BlockChain::new(
+            self.config.net().time_service(),
+            block_id,
+            self.storage.clone(),
+            self.vm_metrics.clone(),
+        )?;
+        let executed_block = new_main.head_block();
+        let ancestor = self.main.find_ancestor(&new_main)?.ok_or_else(|| {
+            format_err!(
+                "Can not find ancestors between main chain: {:?} and new head: {:?}",
+                self.main.status(),
+                new_main.status()
+            )
+        })?;
+        let (retracted_blocks, retracted_truncated) = self.find_blocks_until(
+            self.main.current_header().id(),
+            ancestor.id,
+            MAX_ROLL_BACK_BLOCK,
+        )?;
+        self.main = new_main;
+        let enacted_blocks = vec![executed_block.block.clone()];
+        if !retracted_blocks.is_empty() {
+            // the blocks between the ancestor and the new head may be absent from storage,
+            // such as after fast sync, so only the new head is reported as enacted.
+            let truncated =
+                retracted_truncated || executed_block.header().parent_hash() != ancestor.id;
+            self.broadcast_chain_reorg(
+                ancestor.id,
+                enacted_blocks.clone(),
+                retracted_blocks,
+                truncated,
+            );
+        }
+        self.do_new_head(executed_block, 1, enacted_blocks, 0, vec![])
+    }
+
     ///Directly execute the block and save result, do not try to connect.
     pub fn execute(&mut self, block: Block) -> Result<ExecutedBlock> {
         let chain = BlockChain::new(
@@ -308,7 +397,7 @@ where
     fn find_ancestors_from_accumulator(
         &self,
         new_branch: &BlockChain,
-    ) -> Result<(u64, Vec<Block>, u64, Vec<Block>)> {
+    ) -> Result<(HashValue, u64, Vec<Block>, u64, Vec<Block>)> {
         let ancestor = self.main.find_ancestor(new_branch)?.ok_or_else(|| {
             format_err!(
                 "Can not find ancestors between main chain: {:?} and branch: {:?}",
@@ -336,30 +425,41 @@ where
         let block_enacted = new_branch.current_header().id();
         let block_retracted = self.main.current_header().id();
 
-        let enacted = self.find_blocks_until(block_enacted, ancestor.id, MAX_ROLL_BACK_BLOCK)?;
-        let retracted =
+        let (enacted, _) =
+            self.find_blocks_until(block_enacted, ancestor.id, MAX_ROLL_BACK_BLOCK)?;
+        let (retracted, _) =
             self.find_blocks_until(block_retracted, ancestor.id, MAX_ROLL_BACK_BLOCK)?;
 
         debug!(
             "Commit block count:{}, rollback block count:{}",
             enacted_count, retracted_count,
         );
-        Ok((enacted_count, enacted, retracted_count, retracted))
+        Ok((
+            ancestor.id,
+            enacted_count,
+            enacted,
+            retracted_count,
+            retracted,
+        ))
     }
 
+    /// Find the blocks after `until` to `from` in ascending order of block number, at most `max_size`
+    /// blocks near `from` are returned, and the returned bool is true if the blocks are truncated.
     fn find_blocks_until(
         &self,
         from: HashValue,
         until: HashValue,
         max_size: usize,
-    ) -> Result<Vec<Block>> {
+    ) -> Result<(Vec<Block>, bool)> {
         let mut blocks: Vec<Block> = Vec::new();
         let mut block_id = from;
+        let mut truncated = false;
         loop {
             if block_id == until {
                 break;
             }
             if blocks.len() >= max_size {
+                truncated = true;
                 break;
             }
             let block = self
@@ -371,7 +471,7 @@ where
         }
         blocks.reverse();
 
-        Ok(blocks)
+        Ok((blocks, truncated))
     }
 
     fn broadcast_new_head(&self, block: ExecutedBlock) {
@@ -387,6 +487,29 @@ where
         }
     }
 
+    fn broadcast_chain_reorg(
+        &self,
+        common_ancestor: HashValue,
+        enacted: Vec<Block>,
+        retracted: Vec<Block>,
+        truncated: bool,
+    ) {
+        if truncated {
+            warn!(
+                "[chain] Reorg from common ancestor {} is deeper than {} blocks, the blocks in ChainReorgEvent are truncated.",
+                common_ancestor, MAX_ROLL_BACK_BLOCK
+            );
+        }
+        if let Err(e) = self.bus.broadcast(ChainReorgEvent {
+            common_ancestor,
+            retracted: retracted.into(),
+            enacted: enacted.into(),
+            truncated,
+        }) {
+            error!("Broadcast ChainReorgEvent error: {:?}", e);
+        }
+    }
+
     fn broadcast_new_branch(&self, block: ExecutedBlock) {
         if let Some(metrics) = self.metrics.as_ref() {
             metrics
@@ -413,6 +536,9 @@ where
             self.do_new_head(executed_block, 1, enacted_blocks, 0, vec![])?;
             return Ok(ConnectOk::ExeConnectMain);
         }
+        if !self.is_main_head(&block.header().parent_hash()) {
+            self.ensure_fork_point_not_pruned(block.header())?;
+        }
         let (block_info, fork) = self.find_or_fork(block.header())?;
         match (block_info, fork) {
             //block has bean processed in some branch, so just trigger a head select.
@@ -0,0 +1,285 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use consensus::difficulty::get_next_work_required_by;
+use consensus::{target_to_difficulty, Consensus};
+use logger::prelude::*;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_crypto::HashValue;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_types::block::{BlockHeader, BlockInfo, BlockNumber};
+use starcoin_types::startup_info::{ChainStatus, StartupInfo};
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::sync::Arc;
+
+/// The main chain of a light node, only headers and block infos are kept.
+///
+/// Every header is linked to its parent, and verified by the difficulty and the pow,
+/// the block info is verified by the header and the local block accumulator,
+/// so the state root and txn accumulator root of the headers can be trusted.
+pub struct LightChain {
+    storage: Arc<Storage>,
+    status: ChainStatus,
+    block_accumulator: MerkleAccumulator,
+    /// The epoch at the state of the head block.
+    epoch: Option<Epoch>,
+    /// A branch verifies headers without changing the head in the startup info, see `switch_to`.
+    branch: bool,
+}
+
+impl LightChain {
+    pub fn new(storage: Arc<Storage>) -> Result<Self> {
+        let startup_info = storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?;
+        Self::new_with_head(storage, startup_info.main)
+    }
+
+    fn new_with_head(storage: Arc<Storage>, head_id: HashValue) -> Result<Self> {
+        let header = storage
+            .get_block_header_by_hash(head_id)?
+            .ok_or_else(|| format_err!("Can not find block header by id: {}", head_id))?;
+        let block_info = storage
+            .get_block_info(head_id)?
+            .ok_or_else(|| format_err!("Can not find block info by id: {}", head_id))?;
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            block_info.block_accumulator_info.clone(),
+            storage.get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        Ok(Self {
+            storage,
+            status: ChainStatus::new(header, block_info),
+            block_accumulator,
+            epoch: None,
+            branch: false,
+        })
+    }
+
+    /// Fork a branch at the block `block_id` on the main chain, headers applied to the branch
+    /// are verified and saved, but the main chain is not changed until `switch_to` the branch.
+    pub fn fork(&self, block_id: HashValue) -> Result<Self> {
+        ensure!(
+            self.get_header(block_id)?.is_some(),
+            "Can not fork at block {} which is not on the main chain",
+            block_id
+        );
+        let mut branch = Self::new_with_head(self.storage.clone(), block_id)?;
+        if block_id == self.current_header().id() {
+            branch.epoch = self.epoch.clone();
+        }
+        branch.branch = true;
+        Ok(branch)
+    }
+
+    /// Switch the main chain to the `branch` if its total difficulty is greater than the head's,
+    /// return whether the head is switched.
+    pub fn switch_to(&mut self, branch: &LightChain) -> Result<bool> {
+        let head = branch.current_header().id();
+        if head == self.current_header().id()
+            || branch.current_block_info().total_difficulty
+                <= self.current_block_info().total_difficulty
+        {
+            return Ok(false);
+        }
+        *self = Self::new_with_head(self.storage.clone(), head)?;
+        self.epoch = branch.epoch.clone();
+        self.storage.save_startup_info(StartupInfo::new(head))?;
+        info!(
+            "[light] Switch head to {}, number: {}",
+            head,
+            self.current_header().number()
+        );
+        Ok(true)
+    }
+
+    pub fn status(&self) -> ChainStatus {
+        self.status.clone()
+    }
+
+    pub fn current_header(&self) -> &BlockHeader {
+        self.status.head()
+    }
+
+    pub fn current_block_info(&self) -> &BlockInfo {
+        self.status.info()
+    }
+
+    /// The epoch to verify the next header, None if it should be read from the head state.
+    pub fn epoch(&self) -> Option<&Epoch> {
+        self.epoch
+            .as_ref()
+            .filter(|epoch| self.current_header().number() < epoch.end_block_number())
+    }
+
+    pub fn get_hash_by_number(&self, number: BlockNumber) -> Result<Option<HashValue>> {
+        self.block_accumulator.get_leaf(number)
+    }
+
+    pub fn get_header_by_number(&self, number: BlockNumber) -> Result<Option<BlockHeader>> {
+        match self.get_hash_by_number(number)? {
+            Some(id) => self.storage.get_block_header_by_hash(id),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the header by id, only return the header on the main chain.
+    pub fn get_header(&self, id: HashValue) -> Result<Option<BlockHeader>> {
+        match self.storage.get_block_header_by_hash(id)? {
+            Some(header) if self.get_hash_by_number(header.number())? == Some(id) => {
+                Ok(Some(header))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn get_block_info(&self, id: HashValue) -> Result<Option<BlockInfo>> {
+        match self.get_header(id)? {
+            Some(_) => self.storage.get_block_info(id),
+            None => Ok(None),
+        }
+    }
+
+    /// Verify the header and the block info of the next block, and append it to the head.
+    /// `epoch` is the epoch at the state of the current head.
+    pub fn apply(
+        &mut self,
+        header: BlockHeader,
+        block_info: BlockInfo,
+        epoch: Epoch,
+    ) -> Result<()> {
+        self.verify_header(&header, &epoch)?;
+        let block_accumulator = self.verify_block_info(&header, &block_info)?;
+
+        let block_id = header.id();
+        self.storage.save_block_header(header.clone())?;
+        self.storage.save_block_info(block_info.clone())?;
+        block_accumulator.flush()?;
+        if !self.branch {
+            self.storage.save_startup_info(StartupInfo::new(block_id))?;
+        }
+        debug!(
+            "[light] Apply header: {}, number: {}",
+            block_id,
+            header.number()
+        );
+
+        self.status = ChainStatus::new(header, block_info);
+        self.block_accumulator = block_accumulator;
+        self.epoch = Some(epoch);
+        Ok(())
+    }
+
+    /// Reset the head to the block `block_id` on the main chain, used to switch to a fork.
+    pub fn rollback_to(&mut self, block_id: HashValue) -> Result<()> {
+        ensure!(
+            self.get_header(block_id)?.is_some(),
+            "Can not rollback to block {} which is not on the main chain",
+            block_id
+        );
+        *self = Self::new_with_head(self.storage.clone(), block_id)?;
+        self.storage.save_startup_info(StartupInfo::new(block_id))?;
+        info!("[light] Rollback head to {}", block_id);
+        Ok(())
+    }
+
+    fn verify_header(&self, header: &BlockHeader, epoch: &Epoch) -> Result<()> {
+        let parent = self.current_header();
+        let parent_info = self.current_block_info();
+        ensure!(
+            header.parent_hash() == parent.id(),
+            "Header {} is not the child of the head {}",
+            header.id(),
+            parent.id()
+        );
+        ensure!(
+            Some(header.number()) == parent.number().checked_add(1),
+            "Invalid header number {}, parent number is {}",
+            header.number(),
+            parent.number()
+        );
+        ensure!(
+            header.chain_id() == parent.chain_id(),
+            "Invalid header chain id {}, expect {}",
+            header.chain_id(),
+            parent.chain_id()
+        );
+        ensure!(
+            header.timestamp() > parent.timestamp(),
+            "Invalid header timestamp {}, parent timestamp is {}",
+            header.timestamp(),
+            parent.timestamp()
+        );
+        ensure!(
+            header.number() > epoch.start_block_number()
+                && header.number() <= epoch.end_block_number(),
+            "Header number {} is out of the epoch [{}, {}]",
+            header.number(),
+            epoch.start_block_number(),
+            epoch.end_block_number()
+        );
+        ensure!(
+            header.block_accumulator_root() == parent_info.block_accumulator_info.accumulator_root,
+            "Invalid block accumulator root of header {}",
+            header.id()
+        );
+
+        let strategy = epoch.strategy();
+        // The dummy consensus does not verify the difficulty, same as the full node.
+        if strategy != ConsensusStrategy::Dummy {
+            let target = get_next_work_required_by(epoch, parent, |number| {
+                self.get_header_by_number(number)
+            })?;
+            strategy.verify_header_difficulty(target_to_difficulty(target), header)?;
+        }
+        Ok(())
+    }
+
+    fn verify_block_info(
+        &self,
+        header: &BlockHeader,
+        block_info: &BlockInfo,
+    ) -> Result<MerkleAccumulator> {
+        let parent_info = self.current_block_info();
+        ensure!(
+            block_info.block_id == header.id(),
+            "Block info {} mismatch with header {}",
+            block_info.block_id,
+            header.id()
+        );
+        let total_difficulty = parent_info
+            .total_difficulty
+            .checked_add(header.difficulty())
+            .ok_or_else(|| format_err!("Total difficulty overflow"))?;
+        ensure!(
+            block_info.total_difficulty == total_difficulty,
+            "Invalid total difficulty {} of block {}, expect {}",
+            block_info.total_difficulty,
+            header.id(),
+            total_difficulty
+        );
+        ensure!(
+            block_info.txn_accumulator_info.accumulator_root == header.txn_accumulator_root(),
+            "Invalid txn accumulator root of block info {}",
+            header.id()
+        );
+        ensure!(
+            block_info.txn_accumulator_info.num_leaves
+                > parent_info.txn_accumulator_info.num_leaves,
+            "Invalid txn accumulator leaves of block info {}",
+            header.id()
+        );
+
+        let block_accumulator = self.block_accumulator.fork(None);
+        block_accumulator.append(&[header.id()])?;
+        ensure!(
+            block_accumulator.get_info() == block_info.block_accumulator_info,
+            "Invalid block accumulator info of block info {}",
+            header.id()
+        );
+        Ok(block_accumulator)
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::light::LightChain;
+use crate::tasks::SyncFetcher;
+use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
+use anyhow::{ensure, format_err, Result};
+use logger::prelude::*;
+use network::NetworkServiceRef;
+use network_api::{PeerProvider, PeerSelector, PeerStrategy};
+use parking_lot::RwLock;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
+use starcoin_accumulator::inmemory::InMemoryAccumulator;
+use starcoin_crypto::HashValue;
+use starcoin_state_api::StateWithProof;
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::{Block, BlockHeader, BlockInfo, BlockNumber};
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::startup_info::ChainStatus;
+use starcoin_types::transaction::RichTransactionInfo;
+use starcoin_vm_types::account_config::{genesis_address, AccountResource};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// Max headers requested from peer in one batch.
+const HEADER_BATCH_SIZE: u64 = 100;
+/// Max depth to search the common ancestor when the best peer is on a fork.
+const MAX_FORK_DEPTH: u64 = 1000;
+
+/// Light client, keeps the verified header chain, and reads other data from full peers
+/// by the network rpc, every response is verified against the verified headers.
+#[derive(Clone)]
+pub struct LightClient {
+    chain: Arc<RwLock<LightChain>>,
+    network: NetworkServiceRef,
+    peer_strategy: PeerStrategy,
+}
+
+impl LightClient {
+    pub fn new(chain: LightChain, network: NetworkServiceRef, peer_strategy: PeerStrategy) -> Self {
+        Self {
+            chain: Arc::new(RwLock::new(chain)),
+            network,
+            peer_strategy,
+        }
+    }
+
+    pub fn status(&self) -> ChainStatus {
+        self.chain.read().status()
+    }
+
+    pub fn current_header(&self) -> BlockHeader {
+        self.chain.read().current_header().clone()
+    }
+
+    pub fn get_header(&self, id: HashValue) -> Result<Option<BlockHeader>> {
+        self.chain.read().get_header(id)
+    }
+
+    pub fn get_header_by_number(&self, number: BlockNumber) -> Result<Option<BlockHeader>> {
+        self.chain.read().get_header_by_number(number)
+    }
+
+    pub fn get_block_info(&self, id: HashValue) -> Result<Option<BlockInfo>> {
+        self.chain.read().get_block_info(id)
+    }
+
+    pub fn get_block_info_by_number(&self, number: BlockNumber) -> Result<Option<BlockInfo>> {
+        let chain = self.chain.read();
+        match chain.get_hash_by_number(number)? {
+            Some(id) => chain.get_block_info(id),
+            None => Ok(None),
+        }
+    }
+
+    async fn rpc_client(&self) -> Result<VerifiedRpcClient> {
+        let peers = self.network.peer_set().await?;
+        let peer_selector = PeerSelector::new(peers, self.peer_strategy, None);
+        peer_selector.retain_rpc_peers();
+        ensure!(!peer_selector.is_empty(), "[light] No peers to request.");
+        Ok(VerifiedRpcClient::new(peer_selector, self.network.clone()))
+    }
+
+    /// Get the state at `state_root` with proof, the proof is verified by the rpc client.
+    pub async fn get_state_with_proof(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> Result<StateWithProof> {
+        let (_, state_with_proof) = self
+            .rpc_client()
+            .await?
+            .get_state_with_proof(state_root, access_path)
+            .await?;
+        Ok(state_with_proof)
+    }
+
+    pub async fn get_state(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .get_state_with_proof(state_root, access_path)
+            .await?
+            .state)
+    }
+
+    /// Get the account state at `state_root`, the account state is verified by the account proof.
+    pub async fn get_account_state(
+        &self,
+        state_root: HashValue,
+        address: AccountAddress,
+    ) -> Result<Option<AccountState>> {
+        let state_with_proof = self
+            .get_state_with_proof(
+                state_root,
+                AccessPath::new(address, AccountResource::resource_path()),
+            )
+            .await?;
+        state_with_proof
+            .proof
+            .account_state
+            .map(|blob| AccountState::try_from(blob.as_ref()))
+            .transpose()
+    }
+
+    /// Get the block of a verified header, the body is verified by the body hash of the header.
+    pub async fn get_block(&self, id: HashValue) -> Result<Option<Block>> {
+        let header = match self.get_header(id)? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let rpc_client = self.rpc_client().await?;
+        let (block, peer) = rpc_client
+            .get_blocks(vec![id])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| format_err!("[light] Can not get block {} from peers", id))?;
+        if block.header != header || block.body.hash() != header.body_hash() {
+            let peer = peer.ok_or_else(|| format_err!("[light] Invalid block body of {}", id))?;
+            return Err(RpcVerifyError::new(peer, format!("Invalid block body of {}", id)).into());
+        }
+        Ok(Some(block))
+    }
+
+    /// Get the txn infos of a verified block, the txn infos are verified by appending them to
+    /// the parent's txn accumulator, which should be equal to the block's txn accumulator.
+    pub async fn get_block_txn_infos(&self, id: HashValue) -> Result<Vec<RichTransactionInfo>> {
+        let (header, block_info, parent_accumulator) = {
+            let chain = self.chain.read();
+            let header = chain
+                .get_header(id)?
+                .ok_or_else(|| format_err!("[light] Can not find header by id: {}", id))?;
+            let block_info = chain
+                .get_block_info(id)?
+                .ok_or_else(|| format_err!("[light] Can not find block info by id: {}", id))?;
+            // the genesis block's txns are appended to an empty accumulator.
+            let parent_accumulator = if header.is_genesis() {
+                AccumulatorInfo::default()
+            } else {
+                chain
+                    .get_block_info(header.parent_hash())?
+                    .ok_or_else(|| {
+                        format_err!(
+                            "[light] Can not find block info by id: {}",
+                            header.parent_hash()
+                        )
+                    })?
+                    .txn_accumulator_info
+            };
+            (header, block_info, parent_accumulator)
+        };
+        let rpc_client = self.rpc_client().await?;
+        let (peer, txn_infos) = rpc_client.get_txn_infos(id).await?;
+        let txn_infos = txn_infos
+            .ok_or_else(|| format_err!("[light] Can not get txn infos of {} from peers", id))?;
+
+        let accumulator = InMemoryAccumulator::new(
+            parent_accumulator.frozen_subtree_roots,
+            parent_accumulator.num_leaves,
+        )?
+        .append(
+            txn_infos
+                .iter()
+                .map(|txn_info| txn_info.id())
+                .collect::<Vec<_>>()
+                .as_slice(),
+        );
+        if accumulator.root_hash() != block_info.txn_accumulator_info.accumulator_root {
+            return Err(RpcVerifyError::new(peer, format!("Invalid txn infos of {}", id)).into());
+        }
+
+        txn_infos
+            .into_iter()
+            .enumerate()
+            .map(|(index, txn_info)| {
+                let global_index = parent_accumulator
+                    .num_leaves
+                    .checked_add(index as u64)
+                    .ok_or_else(|| format_err!("txn global index overflow"))?;
+                Ok(RichTransactionInfo::new(
+                    id,
+                    header.number(),
+                    txn_info,
+                    index as u32,
+                    global_index,
+                ))
+            })
+            .collect()
+    }
+
+    /// Sync headers from the best peer, return the new head if the chain is updated.
+    pub async fn sync(&self) -> Result<Option<ChainStatus>> {
+        let current = self.status();
+        let rpc_client = self.rpc_client().await?;
+        let target = match rpc_client.get_best_target(current.info().get_total_difficulty())? {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        rpc_client.selector().retain(target.peers.as_slice());
+        let peer = target
+            .peers
+            .first()
+            .cloned()
+            .ok_or_else(|| format_err!("[light] Sync target has no peers"))?;
+        info!(
+            "[light] Sync to target: {:?}, total difficulty: {}",
+            target.target_id, target.block_info.total_difficulty
+        );
+
+        // the headers are verified on a branch, the head is only switched to the branch when
+        // its verified total difficulty is greater.
+        let mut branch = self.fork_to_target(&rpc_client, &peer, &target).await?;
+        loop {
+            let head = branch.current_header().clone();
+            if head.number() >= target.target_id.number() {
+                break;
+            }
+            let start = head
+                .number()
+                .checked_add(1)
+                .ok_or_else(|| format_err!("block number overflow"))?;
+            let ids = rpc_client
+                .get_block_ids(Some(peer.clone()), start, false, HEADER_BATCH_SIZE)
+                .await?;
+            if ids.is_empty() {
+                break;
+            }
+            let headers = rpc_client.get_headers_by_hash(ids.clone()).await?;
+            let block_infos = rpc_client
+                .get_block_infos_from_peer(Some(peer.clone()), ids.clone())
+                .await?;
+            for ((id, header), block_info) in ids.into_iter().zip(headers).zip(block_infos) {
+                let header =
+                    header.ok_or_else(|| format_err!("[light] Can not get header {}", id))?;
+                let block_info = block_info
+                    .ok_or_else(|| format_err!("[light] Can not get block info {}", id))?;
+                let epoch = Self::epoch(&rpc_client, &branch).await?;
+                if let Err(e) = branch.apply(header, block_info, epoch) {
+                    return Err(RpcVerifyError::new(
+                        peer,
+                        format!("[light] Verify block {} failed: {}", id, e),
+                    )
+                    .into());
+                }
+            }
+            // keep the progress of a long sync once the branch is better than the head.
+            self.chain.write().switch_to(&branch)?;
+        }
+        if self.current_header().id() != branch.current_header().id() {
+            return Err(RpcVerifyError::new(
+                peer,
+                format!(
+                    "[light] Total difficulty {} of the verified branch {} is not greater than the head",
+                    branch.current_block_info().total_difficulty,
+                    branch.current_header().id()
+                ),
+            )
+            .into());
+        }
+        let status = self.status();
+        info!(
+            "[light] Sync to head: {}, number: {}",
+            status.head().id(),
+            status.head().number()
+        );
+        Ok(Some(status))
+    }
+
+    /// The epoch to verify the next header of the `chain`, read it from the head state if not
+    /// cached.
+    async fn epoch(rpc_client: &VerifiedRpcClient, chain: &LightChain) -> Result<Epoch> {
+        if let Some(epoch) = chain.epoch() {
+            return Ok(epoch.clone());
+        }
+        let head = chain.current_header();
+        let access_path = AccessPath::new(genesis_address(), Epoch::resource_path());
+        let (_, state_with_proof) = rpc_client
+            .get_state_with_proof(head.state_root(), access_path)
+            .await?;
+        let state = state_with_proof
+            .state
+            .ok_or_else(|| format_err!("[light] Epoch should exist at block {}", head.id()))?;
+        bcs_ext::from_bytes::<Epoch>(state.as_slice())
+    }
+
+    /// Fork a branch at the common ancestor of the head and the chain of the target.
+    async fn fork_to_target(
+        &self,
+        rpc_client: &VerifiedRpcClient,
+        peer: &PeerId,
+        target: &SyncTarget,
+    ) -> Result<LightChain> {
+        let head = self.current_header();
+        let start = head.number().min(target.target_id.number());
+        let ids = rpc_client
+            .get_block_ids(Some(peer.clone()), start, true, MAX_FORK_DEPTH)
+            .await?;
+        let mut number = start;
+        for id in ids {
+            let chain = self.chain.read();
+            if chain.get_hash_by_number(number)? == Some(id) {
+                return chain.fork(id);
+            }
+            number = match number.checked_sub(1) {
+                Some(number) => number,
+                None => break,
+            };
+        }
+        Err(RpcVerifyError::new(
+            peer.clone(),
+            format!(
+                "[light] Can not find common ancestor with peer in {} blocks",
+                MAX_FORK_DEPTH
+            ),
+        )
+        .into())
+    }
+}
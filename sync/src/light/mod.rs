@@ -0,0 +1,15 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Light client mode: only sync and verify the block headers and block infos,
+//! other data is read from full peers, and verified by the proofs against the verified headers.
+
+mod chain;
+mod client;
+mod service;
+#[cfg(test)]
+mod tests;
+
+pub use chain::LightChain;
+pub use client::LightClient;
+pub use service::LightSyncService;
@@ -0,0 +1,77 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::light::{LightChain, LightClient};
+use crate::verified_rpc_client::RpcVerifyError;
+use anyhow::Result;
+use config::NodeConfig;
+use logger::prelude::*;
+use network::NetworkServiceRef;
+use network_api::{PeerProvider, ReputationChange};
+use starcoin_service_registry::{ActorService, ServiceContext, ServiceFactory};
+use starcoin_storage::Storage;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Interval to check the best peer and sync headers.
+const LIGHT_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Periodically sync the verified headers from the best peer,
+/// the `LightClient` is put to the registry as shared for other services.
+pub struct LightSyncService {
+    client: LightClient,
+    network: NetworkServiceRef,
+    syncing: Arc<AtomicBool>,
+}
+
+impl ServiceFactory<Self> for LightSyncService {
+    fn create(ctx: &mut ServiceContext<Self>) -> Result<LightSyncService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let network = ctx.get_shared::<NetworkServiceRef>()?;
+        let client = LightClient::new(
+            LightChain::new(storage)?,
+            network.clone(),
+            config.sync.peer_select_strategy(),
+        );
+        ctx.put_shared(client.clone())?;
+        Ok(Self {
+            client,
+            network,
+            syncing: Arc::new(AtomicBool::new(false)),
+        })
+    }
+}
+
+impl ActorService for LightSyncService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        let client = self.client.clone();
+        let network = self.network.clone();
+        let syncing = self.syncing.clone();
+        ctx.run_interval(LIGHT_SYNC_INTERVAL, move |ctx| {
+            // only one sync task at the same time.
+            if syncing.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let client = client.clone();
+            let network = network.clone();
+            let syncing = syncing.clone();
+            ctx.spawn(async move {
+                if let Err(err) = client.sync().await {
+                    if let Some(rpc_verify_err) = err.downcast_ref::<RpcVerifyError>() {
+                        for peer_id in rpc_verify_err.peers.as_slice() {
+                            network.report_peer(
+                                peer_id.clone(),
+                                ReputationChange::new_fatal("invalid_response"),
+                            )
+                        }
+                    }
+                    warn!("[light] Sync headers failed: {:?}", err);
+                }
+                syncing.store(false, Ordering::SeqCst);
+            });
+        });
+        Ok(())
+    }
+}
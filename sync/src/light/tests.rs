@@ -0,0 +1,205 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::integer_arithmetic)]
+use crate::light::LightChain;
+use anyhow::Result;
+use config::{BuiltinNetworkID, ChainNetwork, GenesisBlockParameter, GenesisBlockParameterConfig};
+use consensus::{Consensus, ConsensusVerifyError};
+use starcoin_chain_api::ChainReader;
+use starcoin_chain_mock::MockChain;
+use starcoin_genesis::Genesis;
+use starcoin_types::block::{BlockHeader, BlockInfo, BlockNumber};
+use starcoin_types::U256;
+use starcoin_vm_types::genesis_config::{ChainId, ConsensusStrategy};
+use starcoin_vm_types::on_chain_resource::Epoch;
+use starcoin_vm_types::time::{duration_since_epoch, TimeServiceType};
+
+fn block_of(mock: &MockChain, number: BlockNumber) -> Result<(BlockHeader, BlockInfo, Epoch)> {
+    let header = mock
+        .head()
+        .get_header_by_number(number)?
+        .expect("header should exist");
+    let block_info = mock
+        .head()
+        .get_block_info(Some(header.id()))?
+        .expect("block info should exist");
+    let epoch = mock
+        .fork_new_branch(Some(header.parent_hash()))?
+        .epoch()
+        .clone();
+    Ok((header, block_info, epoch))
+}
+
+fn new_light_chain(net: &ChainNetwork) -> Result<LightChain> {
+    let (storage, _, _) = Genesis::init_storage_for_test(net)?;
+    LightChain::new(storage)
+}
+
+#[stest::test]
+fn test_light_chain_apply_headers() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut mock = MockChain::new(net.clone())?;
+    mock.produce_and_apply_times(10)?;
+
+    let (storage, _, _) = Genesis::init_storage_for_test(&net)?;
+    let mut light = LightChain::new(storage.clone())?;
+    for number in 1..=10 {
+        let (header, block_info, epoch) = block_of(&mock, number)?;
+        light.apply(header, block_info, epoch)?;
+    }
+    assert_eq!(light.status(), mock.head().status());
+
+    // the verified headers are persisted.
+    let light = LightChain::new(storage)?;
+    assert_eq!(light.status(), mock.head().status());
+    for number in 0..=10 {
+        assert_eq!(
+            light.get_header_by_number(number)?,
+            mock.head().get_header_by_number(number)?
+        );
+    }
+    assert!(light.get_header_by_number(11)?.is_none());
+    Ok(())
+}
+
+#[stest::test]
+fn test_light_chain_reject_invalid_header() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut mock = MockChain::new(net.clone())?;
+    mock.produce_and_apply_times(2)?;
+    let mut light = new_light_chain(&net)?;
+    let genesis_status = light.status();
+
+    // not linked to the head.
+    let (header, block_info, epoch) = block_of(&mock, 2)?;
+    assert!(light.apply(header, block_info, epoch).is_err());
+
+    // block info mismatch with the header.
+    let (header, mut block_info, epoch) = block_of(&mock, 1)?;
+    block_info.total_difficulty = block_info.total_difficulty + U256::from(1u64);
+    assert!(light.apply(header, block_info, epoch).is_err());
+    assert_eq!(light.status(), genesis_status);
+
+    let (header, block_info, epoch) = block_of(&mock, 1)?;
+    light.apply(header, block_info, epoch)?;
+    assert_eq!(light.current_header().number(), 1);
+    Ok(())
+}
+
+#[stest::test]
+fn test_light_chain_rollback() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut mock = MockChain::new(net.clone())?;
+    mock.produce_and_apply_times(6)?;
+    let mut light = new_light_chain(&net)?;
+    for number in 1..=6 {
+        let (header, block_info, epoch) = block_of(&mock, number)?;
+        light.apply(header, block_info, epoch)?;
+    }
+
+    let (ancestor, _, _) = block_of(&mock, 3)?;
+    light.rollback_to(ancestor.id())?;
+    assert_eq!(light.current_header().id(), ancestor.id());
+    assert!(light.get_header_by_number(4)?.is_none());
+
+    for number in 4..=6 {
+        let (header, block_info, epoch) = block_of(&mock, number)?;
+        light.apply(header, block_info, epoch)?;
+    }
+    assert_eq!(light.status(), mock.head().status());
+    Ok(())
+}
+
+#[stest::test]
+fn test_light_chain_reject_invalid_pow() -> Result<()> {
+    let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+    genesis_config.genesis_block_parameter =
+        GenesisBlockParameterConfig::Static(GenesisBlockParameter {
+            parent_hash: Default::default(),
+            timestamp: duration_since_epoch().as_millis() as u64,
+            difficulty: 10.into(),
+        });
+    genesis_config.time_service_type = TimeServiceType::RealTimeService;
+    genesis_config.consensus_config.strategy = ConsensusStrategy::CryptoNight.value();
+    let net =
+        ChainNetwork::new_custom("light_test".to_string(), ChainId::new(100), genesis_config)?;
+    let mut mock = MockChain::new(net.clone())?;
+    mock.produce_and_apply_times(1)?;
+    let mut light = new_light_chain(&net)?;
+    let genesis_status = light.status();
+    let (header, block_info, epoch) = block_of(&mock, 1)?;
+
+    // the difficulty is not the one required by the parent.
+    let invalid = header
+        .as_builder()
+        .with_difficulty(header.difficulty() + U256::from(1u64))
+        .build();
+    let err = light
+        .apply(invalid, block_info.clone(), epoch.clone())
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ConsensusVerifyError>(),
+        Some(ConsensusVerifyError::VerifyDifficultyError { .. })
+    ));
+
+    // the pow hash of the nonce does not meet the difficulty.
+    let invalid = (1..)
+        .map(|delta| {
+            header
+                .as_builder()
+                .with_nonce(header.nonce().wrapping_add(delta))
+                .build()
+        })
+        .find(|invalid| {
+            ConsensusStrategy::CryptoNight
+                .verify_header_difficulty(header.difficulty(), invalid)
+                .is_err()
+        })
+        .expect("an invalid nonce should exist");
+    let err = light
+        .apply(invalid, block_info.clone(), epoch.clone())
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ConsensusVerifyError>(),
+        Some(ConsensusVerifyError::VerifyNonceError { .. })
+    ));
+    assert_eq!(light.status(), genesis_status);
+
+    light.apply(header, block_info, epoch)?;
+    assert_eq!(light.status(), mock.head().status());
+    Ok(())
+}
+
+#[stest::test]
+fn test_light_chain_switch_to_branch() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut mock = MockChain::new(net.clone())?;
+    mock.produce_and_apply_times(5)?;
+    let (storage, _, _) = Genesis::init_storage_for_test(&net)?;
+    let mut light = LightChain::new(storage.clone())?;
+    for number in 1..=4 {
+        let (header, block_info, epoch) = block_of(&mock, number)?;
+        light.apply(header, block_info, epoch)?;
+    }
+    let head_status = light.status();
+
+    let (ancestor, _, _) = block_of(&mock, 2)?;
+    let mut branch = light.fork(ancestor.id())?;
+    assert_eq!(branch.current_header().id(), ancestor.id());
+    for number in 3..=4 {
+        let (header, block_info, epoch) = block_of(&mock, number)?;
+        branch.apply(header, block_info, epoch)?;
+        // the branch is not better than the head.
+        assert!(!light.switch_to(&branch)?);
+        assert_eq!(light.status(), head_status);
+    }
+    let (header, block_info, epoch) = block_of(&mock, 5)?;
+    branch.apply(header, block_info, epoch)?;
+    // the head in the storage is not changed by the branch.
+    assert_eq!(LightChain::new(storage.clone())?.status(), head_status);
+
+    assert!(light.switch_to(&branch)?);
+    assert_eq!(light.status(), mock.head().status());
+    assert_eq!(LightChain::new(storage)?.status(), mock.head().status());
+    Ok(())
+}
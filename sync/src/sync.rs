@@ -1,9 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block_connector::BlockConnectorService;
+use crate::block_connector::{BlockConnectorService, SwitchHeadRequest};
 use crate::sync_metrics::SyncMetrics;
-use crate::tasks::{full_sync_task, AncestorEvent, SyncFetcher};
+use crate::tasks::{
+    fast_sync_task, full_sync_task, select_fast_sync_pivot, verify_fast_sync_pivot, AncestorEvent,
+    SyncFetcher,
+};
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
 use config::NodeConfig;
@@ -17,6 +20,7 @@ use network_api::peer_score::PeerScoreMetrics;
 use network_api::{PeerProvider, PeerSelector, PeerStrategy, ReputationChange};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::ChainReader;
+use starcoin_crypto::HashValue;
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
 };
@@ -223,6 +227,47 @@ impl SyncService {
             {
                 info!("[sync] Find target({}), total_difficulty:{}, current head({})'s total_difficulty({})", target.target_id.id(), target.block_info.total_difficulty, current_block_id, current_block_info.total_difficulty);
 
+                if let Some(pivot) = Self::fast_sync_pivot(
+                    &config,
+                    storage.clone(),
+                    rpc_client.as_ref(),
+                    &target,
+                    current_block_id,
+                )
+                .await?
+                {
+                    info!("[sync] Fast sync to pivot block {:?}", pivot);
+                    let (fut, task_handle, task_event_handle) = fast_sync_task(
+                        pivot,
+                        target.clone(),
+                        storage.clone(),
+                        rpc_client.clone(),
+                        config.sync.max_retry_times(),
+                    );
+                    self_ref.notify(SyncBeginEvent {
+                        target,
+                        task_handle,
+                        task_event_handle,
+                        peer_selector,
+                    })?;
+                    if let Some(sync_task_total) = sync_task_total.as_ref() {
+                        sync_task_total.with_label_values(&["start"]).inc();
+                    }
+                    let pivot = fut.await?;
+                    connector_service
+                        .send(SwitchHeadRequest {
+                            block_hash: pivot.id,
+                        })
+                        .await??;
+                    // the blocks after the pivot are synced by the next full sync.
+                    return Ok(Some(BlockChain::new(
+                        config.net().time_service(),
+                        pivot.id,
+                        storage.clone(),
+                        vm_metrics.clone(),
+                    )?));
+                }
+
                 let (fut, task_handle, task_event_handle) = full_sync_task(
                     current_block_id,
                     target.clone(),
@@ -344,6 +389,42 @@ impl SyncService {
         Ok(())
     }
 
+    /// Return the pivot if the node should do fast sync, resume the unfinished fast sync first.
+    /// A new fast sync only starts when the node is at genesis.
+    async fn fast_sync_pivot(
+        config: &NodeConfig,
+        storage: Arc<Storage>,
+        rpc_client: &VerifiedRpcClient,
+        target: &SyncTarget,
+        current_block_id: HashValue,
+    ) -> Result<Option<BlockIdAndNumber>> {
+        if !config.sync.fast_sync() {
+            return Ok(None);
+        }
+        if let Some(progress) = storage.get_fast_sync_progress()? {
+            let pivot = BlockIdAndNumber::new(progress.pivot(), progress.pivot_number());
+            rpc_client.selector().retain(target.peers.as_slice());
+            match verify_fast_sync_pivot(rpc_client, target, pivot).await {
+                Ok(()) => return Ok(Some(pivot)),
+                Err(e) => {
+                    warn!(
+                        "[sync] Drop the fast sync progress of pivot {:?}: {:?}",
+                        pivot, e
+                    );
+                    storage.remove_fast_sync_progress()?;
+                }
+            }
+        }
+        let current_block_header = storage
+            .get_block_header_by_hash(current_block_id)?
+            .ok_or_else(|| format_err!("Can not find block header by id: {}", current_block_id))?;
+        if !current_block_header.is_genesis() {
+            return Ok(None);
+        }
+        rpc_client.selector().retain(target.peers.as_slice());
+        select_fast_sync_pivot(rpc_client, target, current_block_header.number()).await
+    }
+
     fn task_handle(&self) -> Option<&SyncTaskHandle> {
         match &self.stage {
             SyncStage::Synchronizing(handle) => Some(handle),
@@ -0,0 +1,795 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tasks::{StateSyncFetcher, SyncFetcher};
+use crate::verified_rpc_client::RpcVerifyError;
+use anyhow::{ensure, format_err, Error, Result};
+use consensus::Consensus;
+use forkable_jellyfish_merkle::node_type::Node;
+use forkable_jellyfish_merkle::RawKey;
+use futures::future::{try_join_all, BoxFuture};
+use futures::FutureExt;
+use futures_timer::Delay;
+use logger::prelude::*;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::node_index::NodeIndex;
+use starcoin_accumulator::proof::AccumulatorProof;
+use starcoin_accumulator::{Accumulator, AccumulatorNode, MerkleAccumulator};
+use starcoin_crypto::hash::{ACCUMULATOR_PLACEHOLDER_HASH, SPARSE_MERKLE_PLACEHOLDER_HASH};
+use starcoin_crypto::HashValue;
+use starcoin_state_api::AccountStateReader;
+use starcoin_state_tree::StateNode;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::Store;
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::{Block, BlockHeader, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::startup_info::{FastSyncNodeKind, FastSyncProgress, StartupInfo};
+use starcoin_vm_types::access_path::{AccessPath, ModuleName};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::Duration;
+use stream_task::{TaskError, TaskEventCounterHandle, TaskEventHandle, TaskFuture, TaskHandle};
+
+/// Fast sync pick the pivot at least such many blocks before the sync target,
+/// the blocks after the pivot are synced by full sync.
+pub const FAST_SYNC_PIVOT_DISTANCE: u64 = 128;
+/// How many nodes are downloaded concurrently, the progress is saved after every batch.
+const NODE_BATCH_SIZE: usize = 32;
+const BLOCK_BATCH_SIZE: u64 = 10;
+const DELAY_MILLISECONDS_ON_ERROR: u64 = 100;
+const FAST_SYNC_TASK_NAME: &str = "FastSyncTask";
+
+/// Select the pivot block of fast sync for `target`.
+/// The pivot is the start block of the epoch which contains the block `FAST_SYNC_PIVOT_DISTANCE` blocks
+/// before the target, so the node can build the epoch uncles without the history state.
+/// Return None if the pivot is not after `current_number`, then the node should do full sync.
+pub async fn select_fast_sync_pivot<F>(
+    fetcher: &F,
+    target: &SyncTarget,
+    current_number: BlockNumber,
+) -> Result<Option<BlockIdAndNumber>>
+where
+    F: SyncFetcher + StateSyncFetcher,
+{
+    let sample_number = target
+        .target_id
+        .number()
+        .saturating_sub(FAST_SYNC_PIVOT_DISTANCE);
+    if sample_number <= current_number {
+        return Ok(None);
+    }
+    let sample_block = fetch_target_block(fetcher, target, sample_number).await?;
+    let epoch = fetch_epoch(fetcher, sample_block.header()).await?;
+    verify_header_pow(epoch.strategy(), sample_block.header())?;
+    let pivot_number = epoch.start_block_number();
+    if pivot_number <= current_number {
+        return Ok(None);
+    }
+    let pivot_block = fetch_target_block(fetcher, target, pivot_number).await?;
+    verify_header_pow(epoch.strategy(), pivot_block.header())?;
+    Ok(Some(BlockIdAndNumber::new(pivot_block.id(), pivot_number)))
+}
+
+/// Check the `pivot` is the block of the `target` chain at the pivot number.
+pub async fn verify_fast_sync_pivot<F>(
+    fetcher: &F,
+    target: &SyncTarget,
+    pivot: BlockIdAndNumber,
+) -> Result<()>
+where
+    F: StateSyncFetcher,
+{
+    let block_id = fetch_block_id_with_proof(
+        fetcher,
+        &target.block_info.block_accumulator_info,
+        pivot.number,
+    )
+    .await?;
+    ensure!(
+        block_id == pivot.id,
+        "Pivot block {:?} is not on the target chain, the block at the pivot number is {}",
+        pivot,
+        block_id
+    );
+    Ok(())
+}
+
+/// Fetch the block of the `target` chain at `number`. The block id is the leaf of the target's
+/// block accumulator, which is proved by an accumulator proof against the accumulator root,
+/// so the block can not be chosen by a single peer.
+async fn fetch_target_block<F>(
+    fetcher: &F,
+    target: &SyncTarget,
+    number: BlockNumber,
+) -> Result<Block>
+where
+    F: SyncFetcher + StateSyncFetcher,
+{
+    let block_id =
+        fetch_block_id_with_proof(fetcher, &target.block_info.block_accumulator_info, number)
+            .await?;
+    let (block, peer_id) = fetcher
+        .fetch_blocks(vec![block_id])
+        .await?
+        .pop()
+        .ok_or_else(|| format_err!("Can not find block by id: {}", block_id))?;
+    if block.id() != block_id {
+        return Err(verify_err(
+            peer_id,
+            format!("Block id {} mismatch with request {}", block.id(), block_id),
+        ));
+    }
+    Ok(block)
+}
+
+/// Fetch the leaf `leaf_index` of the block accumulator described by `accumulator_info`.
+/// The nodes from the root to the leaf are downloaded and checked by their hashes,
+/// then the siblings of the path are verified as an accumulator proof of the leaf.
+async fn fetch_block_id_with_proof<F>(
+    fetcher: &F,
+    accumulator_info: &AccumulatorInfo,
+    leaf_index: u64,
+) -> Result<HashValue>
+where
+    F: StateSyncFetcher,
+{
+    ensure!(
+        leaf_index < accumulator_info.num_leaves,
+        "Block number {} is out of the accumulator, leaves count: {}",
+        leaf_index,
+        accumulator_info.num_leaves
+    );
+    let leaf = NodeIndex::from_leaf_index(leaf_index);
+    let mut index = NodeIndex::root_from_leaf_count(accumulator_info.num_leaves);
+    let mut node_key = accumulator_info.accumulator_root;
+    let mut siblings = vec![];
+    while index != leaf {
+        let (accumulator_node, peer_id) = fetcher
+            .fetch_accumulator_node(node_key, AccumulatorStoreType::Block)
+            .await?;
+        if accumulator_node.hash() != node_key {
+            return Err(verify_err(
+                peer_id,
+                format!(
+                    "Accumulator node hash {} and node key {} mismatch",
+                    accumulator_node.hash(),
+                    node_key
+                ),
+            ));
+        }
+        let internal = match accumulator_node {
+            AccumulatorNode::Internal(internal) => internal,
+            _ => {
+                return Err(verify_err(
+                    peer_id,
+                    format!("Accumulator node {} should be an internal node", node_key),
+                ))
+            }
+        };
+        if leaf.to_inorder_index() < index.to_inorder_index() {
+            siblings.push(internal.right());
+            node_key = internal.left();
+            index = index.left_child();
+        } else {
+            siblings.push(internal.left());
+            node_key = internal.right();
+            index = index.right_child();
+        }
+    }
+    // the siblings of a proof are ordered from the bottom level to the root level.
+    siblings.reverse();
+    AccumulatorProof::new(siblings).verify(
+        accumulator_info.accumulator_root,
+        node_key,
+        leaf_index,
+    )?;
+    Ok(node_key)
+}
+
+/// Fetch the epoch at the block `header`, the state proof is verified by the header's state root.
+async fn fetch_epoch<F>(fetcher: &F, header: &BlockHeader) -> Result<Epoch>
+where
+    F: StateSyncFetcher,
+{
+    let access_path = AccessPath::resource_access_path(genesis_address(), Epoch::struct_tag());
+    let state_with_proof = fetcher
+        .fetch_state_with_proof(header.state_root(), access_path)
+        .await?;
+    Ok(bcs_ext::from_bytes(
+        state_with_proof
+            .get_state()
+            .as_ref()
+            .ok_or_else(|| format_err!("Epoch is none at block {}.", header.id()))?
+            .as_slice(),
+    )?)
+}
+
+/// Check the header's nonce satisfies its difficulty. The difficulty itself is checked by
+/// the difficulty window blocks in `sync_blocks`, as the total difficulty of the block infos.
+fn verify_header_pow(strategy: ConsensusStrategy, header: &BlockHeader) -> Result<()> {
+    // The dummy consensus does not verify the difficulty, same as the full node.
+    if strategy != ConsensusStrategy::Dummy {
+        strategy.verify_header_difficulty(header.difficulty(), header)?;
+    }
+    Ok(())
+}
+
+/// Download the state and the accumulators at the `pivot` block node by node,
+/// then the blocks which are required for building the chain at the pivot,
+/// and set the pivot as the startup block.
+/// The pivot is verified against the `target`'s block accumulator before downloading any state.
+/// The output is the pivot, the blocks after it should be synced by full sync.
+pub fn fast_sync_task<F>(
+    pivot: BlockIdAndNumber,
+    target: SyncTarget,
+    storage: Arc<dyn Store>,
+    fetcher: Arc<F>,
+    max_retry_times: u64,
+) -> (
+    BoxFuture<'static, Result<BlockIdAndNumber, TaskError>>,
+    TaskHandle,
+    Arc<TaskEventCounterHandle>,
+)
+where
+    F: SyncFetcher + StateSyncFetcher + 'static,
+{
+    let event_handle = Arc::new(TaskEventCounterHandle::new());
+    let task = FastSyncTask {
+        target,
+        storage,
+        fetcher,
+        event_handle: event_handle.clone(),
+        max_retry_times,
+    };
+    let fut = async move { task.sync(pivot).await.map_err(into_task_error) };
+    let (fut, handle) = TaskFuture::new(fut.boxed()).with_handle();
+    (fut, handle, event_handle)
+}
+
+fn into_task_error(err: Error) -> TaskError {
+    match err.downcast::<TaskError>() {
+        Ok(task_err) => task_err,
+        Err(err) => TaskError::BreakError(err),
+    }
+}
+
+fn verify_err(peer_id: Option<PeerId>, msg: String) -> Error {
+    match peer_id {
+        Some(peer_id) => TaskError::BreakError(RpcVerifyError::new(peer_id, msg).into()).into(),
+        None => format_err!("{}", msg),
+    }
+}
+
+enum DownloadedNode {
+    State(HashValue, StateNode),
+    Accumulator(AccumulatorStoreType, AccumulatorNode),
+}
+
+struct SyncedNode {
+    /// None if the node already exists in local storage.
+    downloaded: Option<DownloadedNode>,
+    children: Vec<(HashValue, FastSyncNodeKind)>,
+}
+
+struct FastSyncTask<F> {
+    target: SyncTarget,
+    storage: Arc<dyn Store>,
+    fetcher: Arc<F>,
+    event_handle: Arc<TaskEventCounterHandle>,
+    max_retry_times: u64,
+}
+
+impl<F> FastSyncTask<F>
+where
+    F: SyncFetcher + StateSyncFetcher + 'static,
+{
+    async fn sync(&self, pivot: BlockIdAndNumber) -> Result<BlockIdAndNumber> {
+        let (pivot_block, pivot_info) = self.fetch_pivot(pivot).await?;
+        let progress = match self.storage.get_fast_sync_progress()? {
+            Some(progress) if progress.pivot() == pivot.id => progress,
+            _ => {
+                let roots = vec![
+                    (
+                        *pivot_info.block_accumulator_info.get_accumulator_root(),
+                        FastSyncNodeKind::BlockAccumulator,
+                    ),
+                    (
+                        *pivot_info.txn_accumulator_info.get_accumulator_root(),
+                        FastSyncNodeKind::TxnAccumulator,
+                    ),
+                    (
+                        pivot_block.header().state_root(),
+                        FastSyncNodeKind::AccountState,
+                    ),
+                ]
+                .into_iter()
+                .filter(|(root, _)| !is_placeholder(root))
+                .collect();
+                let progress = FastSyncProgress::new(pivot.id, pivot.number, roots);
+                self.storage.save_fast_sync_progress(progress.clone())?;
+                progress
+            }
+        };
+        info!("[sync] Start fast sync, {}", progress);
+        self.event_handle
+            .on_start(FAST_SYNC_TASK_NAME.to_string(), None);
+        self.sync_nodes(progress).await?;
+        self.sync_blocks(pivot_block, pivot_info).await?;
+        self.event_handle.on_finish(FAST_SYNC_TASK_NAME.to_string());
+        info!("[sync] Fast sync to pivot block {:?} done.", pivot);
+        Ok(pivot)
+    }
+
+    /// Fetch the pivot block and its block info. The pivot and its child are proved by
+    /// the target's block accumulator, the pivot header's PoW is checked by the consensus of
+    /// the epoch at the pivot, and the accumulator roots of the block info are checked
+    /// by the pivot header and the header of its child.
+    async fn fetch_pivot(&self, pivot: BlockIdAndNumber) -> Result<(Block, BlockInfo)> {
+        let pivot_block =
+            fetch_target_block(self.fetcher.as_ref(), &self.target, pivot.number).await?;
+        ensure!(
+            pivot_block.id() == pivot.id,
+            "Pivot block {:?} is not on the target chain, the block at the pivot number is {}",
+            pivot,
+            pivot_block.id()
+        );
+        let epoch = fetch_epoch(self.fetcher.as_ref(), pivot_block.header()).await?;
+        verify_header_pow(epoch.strategy(), pivot_block.header())?;
+        let child_block = fetch_target_block(
+            self.fetcher.as_ref(),
+            &self.target,
+            pivot.number.saturating_add(1),
+        )
+        .await?;
+        ensure!(
+            child_block.header().parent_hash() == pivot.id,
+            "Block {}'s parent is not pivot block {}",
+            child_block.id(),
+            pivot.id
+        );
+        let pivot_info = self
+            .fetcher
+            .fetch_block_info(None, pivot.id)
+            .await?
+            .ok_or_else(|| format_err!("Can not find block info by id: {}", pivot.id))?;
+        ensure!(
+            pivot_info.block_id == pivot.id
+                && child_block.header().block_accumulator_root()
+                    == *pivot_info.block_accumulator_info.get_accumulator_root()
+                && pivot_block.header().txn_accumulator_root()
+                    == *pivot_info.txn_accumulator_info.get_accumulator_root()
+                && pivot_info.block_accumulator_info.get_num_leaves()
+                    == pivot.number.saturating_add(1),
+            "Pivot block {}'s block info mismatch with headers",
+            pivot.id
+        );
+        Ok((pivot_block, pivot_info))
+    }
+
+    async fn sync_nodes(&self, mut progress: FastSyncProgress) -> Result<()> {
+        while !progress.is_nodes_synced() {
+            let mut pending_nodes = progress.pending_nodes().to_vec();
+            let batch =
+                pending_nodes.split_off(pending_nodes.len().saturating_sub(NODE_BATCH_SIZE));
+            let synced_nodes = try_join_all(
+                batch
+                    .into_iter()
+                    .map(|(node_key, kind)| self.sync_node(node_key, kind)),
+            )
+            .await?;
+
+            let mut state_nodes = BTreeMap::new();
+            let mut created = vec![];
+            let mut accumulator_nodes: HashMap<AccumulatorStoreType, Vec<AccumulatorNode>> =
+                HashMap::new();
+            let mut downloaded_count = 0u64;
+            for synced_node in synced_nodes {
+                match synced_node.downloaded {
+                    Some(DownloadedNode::State(node_key, state_node)) => {
+                        state_nodes.insert(node_key, state_node);
+                        created.push(node_key);
+                        downloaded_count = downloaded_count.saturating_add(1);
+                    }
+                    Some(DownloadedNode::Accumulator(accumulator_type, accumulator_node)) => {
+                        accumulator_nodes
+                            .entry(accumulator_type)
+                            .or_default()
+                            .push(accumulator_node);
+                        downloaded_count = downloaded_count.saturating_add(1);
+                    }
+                    None => {}
+                }
+                pending_nodes.extend(synced_node.children);
+            }
+            // save the nodes before the progress, so the pending nodes' parents always exist.
+            if !state_nodes.is_empty() {
+                self.storage
+                    .write_nodes_with_created(state_nodes, created)?;
+            }
+            for (accumulator_type, nodes) in accumulator_nodes {
+                self.storage
+                    .get_accumulator_store(accumulator_type)
+                    .save_nodes(nodes)?;
+            }
+            progress.update_pending_nodes(pending_nodes, downloaded_count);
+            self.storage.save_fast_sync_progress(progress.clone())?;
+        }
+        Ok(())
+    }
+
+    async fn sync_node(&self, node_key: HashValue, kind: FastSyncNodeKind) -> Result<SyncedNode> {
+        let synced_node = match kind {
+            FastSyncNodeKind::BlockAccumulator | FastSyncNodeKind::TxnAccumulator => {
+                let accumulator_type = if kind == FastSyncNodeKind::BlockAccumulator {
+                    AccumulatorStoreType::Block
+                } else {
+                    AccumulatorStoreType::Transaction
+                };
+                let local_node = self
+                    .storage
+                    .get_accumulator_store(accumulator_type)
+                    .get_node(node_key)?;
+                let (accumulator_node, downloaded) = match local_node {
+                    Some(accumulator_node) => (accumulator_node, false),
+                    None => (
+                        self.fetch_accumulator_node(node_key, accumulator_type)
+                            .await?,
+                        true,
+                    ),
+                };
+                let children = match &accumulator_node {
+                    AccumulatorNode::Internal(internal) => vec![internal.left(), internal.right()]
+                        .into_iter()
+                        .filter(|child| !is_placeholder(child))
+                        .map(|child| (child, kind))
+                        .collect(),
+                    _ => vec![],
+                };
+                SyncedNode {
+                    downloaded: if downloaded {
+                        Some(DownloadedNode::Accumulator(
+                            accumulator_type,
+                            accumulator_node,
+                        ))
+                    } else {
+                        None
+                    },
+                    children,
+                }
+            }
+            _ => {
+                let (state_node, downloaded) = match self.storage.get(&node_key)? {
+                    Some(state_node) => (state_node, false),
+                    None => (self.fetch_state_node(node_key, kind).await?, true),
+                };
+                let children = state_node_children(&state_node, kind)?;
+                SyncedNode {
+                    downloaded: if downloaded {
+                        Some(DownloadedNode::State(node_key, state_node))
+                    } else {
+                        None
+                    },
+                    children,
+                }
+            }
+        };
+        self.event_handle.on_item();
+        Ok(synced_node)
+    }
+
+    async fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> Result<AccumulatorNode> {
+        let mut retry_times = 0;
+        loop {
+            let result = self
+                .fetcher
+                .fetch_accumulator_node(node_key, accumulator_type)
+                .await
+                .and_then(|(accumulator_node, peer_id)| {
+                    if accumulator_node.hash() != node_key {
+                        return Err(verify_err(
+                            peer_id,
+                            format!(
+                                "Accumulator node hash {} and node key {} mismatch",
+                                accumulator_node.hash(),
+                                node_key
+                            ),
+                        ));
+                    }
+                    Ok(accumulator_node)
+                });
+            match self.check_retry(result, &mut retry_times).await? {
+                Some(accumulator_node) => return Ok(accumulator_node),
+                None => continue,
+            }
+        }
+    }
+
+    async fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+        kind: FastSyncNodeKind,
+    ) -> Result<StateNode> {
+        let mut retry_times = 0;
+        loop {
+            let result =
+                self.fetcher
+                    .fetch_state_node(node_key)
+                    .await
+                    .and_then(|(state_node, peer_id)| {
+                        match verify_state_node(node_key, &state_node, kind) {
+                            Ok(()) => Ok(state_node),
+                            Err(e) => Err(verify_err(peer_id, e.to_string())),
+                        }
+                    });
+            match self.check_retry(result, &mut retry_times).await? {
+                Some(state_node) => return Ok(state_node),
+                None => continue,
+            }
+        }
+    }
+
+    /// Return Ok(None) if the fetch should be retried.
+    async fn check_retry<T>(&self, result: Result<T>, retry_times: &mut u64) -> Result<Option<T>> {
+        match result {
+            Ok(t) => {
+                self.event_handle.on_ok();
+                Ok(Some(t))
+            }
+            Err(e) => {
+                self.event_handle.on_error();
+                if *retry_times >= self.max_retry_times {
+                    return Err(e);
+                }
+                *retry_times = retry_times.saturating_add(1);
+                debug!(
+                    "[sync] Fast sync fetch node failed: {:?}, retry times: {}",
+                    e, retry_times
+                );
+                self.event_handle.on_retry();
+                Delay::new(Duration::from_millis(DELAY_MILLISECONDS_ON_ERROR)).await;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Save the blocks from the start of the pivot's epoch and its difficulty window to the pivot
+    /// with their block infos, they are required for building the chain at the pivot,
+    /// then set the pivot as the startup block.
+    async fn sync_blocks(&self, pivot_block: Block, pivot_info: BlockInfo) -> Result<()> {
+        let pivot_number = pivot_block.header().number();
+        let chain_state = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(pivot_block.header().state_root()),
+        );
+        let epoch = AccountStateReader::new(&chain_state)
+            .get_resource::<Epoch>(genesis_address())?
+            .ok_or_else(|| format_err!("Epoch is none at pivot block {}.", pivot_block.id()))?;
+        let start_number = std::cmp::min(
+            epoch.start_block_number(),
+            pivot_number
+                .saturating_sub(epoch.block_difficulty_window())
+                .saturating_add(1),
+        );
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            pivot_info.block_accumulator_info.clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let mut headers = vec![];
+        let mut number = start_number;
+        while number < pivot_number {
+            let max_size = std::cmp::min(BLOCK_BATCH_SIZE, pivot_number.saturating_sub(number));
+            let leaves = block_accumulator.get_leaves(number, false, max_size)?;
+            let local_blocks = self.storage.get_blocks(leaves.clone())?;
+            let block_ids: Vec<HashValue> = leaves
+                .iter()
+                .zip(local_blocks.iter())
+                .filter_map(|(block_id, block)| match block {
+                    Some(_) => None,
+                    None => Some(*block_id),
+                })
+                .collect();
+            let mut fetched_blocks = if block_ids.is_empty() {
+                vec![]
+            } else {
+                self.fetcher.fetch_blocks(block_ids.clone()).await?
+            }
+            .into_iter();
+            for (block_id, local_block) in leaves.into_iter().zip(local_blocks) {
+                let block = match local_block {
+                    Some(block) => block,
+                    None => {
+                        let (block, peer_id) = fetched_blocks
+                            .next()
+                            .ok_or_else(|| format_err!("Can not find block by id: {}", block_id))?;
+                        // the block ids are the leaves of the verified block accumulator.
+                        if block.id() != block_id {
+                            return Err(verify_err(
+                                peer_id,
+                                format!(
+                                    "Block id {} mismatch with request {}",
+                                    block.id(),
+                                    block_id
+                                ),
+                            ));
+                        }
+                        self.storage.commit_block(block.clone())?;
+                        block
+                    }
+                };
+                headers.push(block.header().clone());
+            }
+            number = number.saturating_add(max_size);
+        }
+        self.sync_block_infos(headers, pivot_block.header(), &pivot_info)
+            .await?;
+        let pivot_id = pivot_block.id();
+        self.storage.commit_block(pivot_block)?;
+        self.storage.save_block_info(pivot_info)?;
+        self.storage.save_startup_info(StartupInfo::new(pivot_id))?;
+        self.storage.remove_fast_sync_progress()
+    }
+
+    /// Fetch and save the block infos of the `headers` which are the blocks before the pivot.
+    /// The block infos are verified backward from the pivot: the accumulator roots by the headers,
+    /// and the total difficulty by the difficulty of the child block.
+    async fn sync_block_infos(
+        &self,
+        headers: Vec<BlockHeader>,
+        pivot_header: &BlockHeader,
+        pivot_info: &BlockInfo,
+    ) -> Result<()> {
+        let block_ids: Vec<HashValue> = headers.iter().map(|header| header.id()).collect();
+        let local_infos = self.storage.get_block_infos(block_ids.clone())?;
+        let missing_ids: Vec<HashValue> = block_ids
+            .iter()
+            .zip(local_infos.iter())
+            .filter_map(|(block_id, info)| match info {
+                Some(_) => None,
+                None => Some(*block_id),
+            })
+            .collect();
+        let mut fetched_infos = HashMap::new();
+        for chunk in missing_ids.chunks(BLOCK_BATCH_SIZE as usize) {
+            for block_info in self
+                .fetcher
+                .fetch_block_infos(None, chunk.to_vec())
+                .await?
+                .into_iter()
+                .flatten()
+            {
+                fetched_infos.insert(block_info.block_id, block_info);
+            }
+        }
+
+        let mut child_header = pivot_header;
+        let mut child_total_difficulty = pivot_info.total_difficulty;
+        let mut block_infos = vec![];
+        for (header, local_info) in headers.iter().zip(local_infos).rev() {
+            let block_info = match local_info {
+                Some(block_info) => block_info,
+                None => fetched_infos
+                    .remove(&header.id())
+                    .ok_or_else(|| format_err!("Can not find block info by id: {}", header.id()))?,
+            };
+            ensure!(
+                block_info.block_id == header.id()
+                    && block_info.block_accumulator_info.get_num_leaves()
+                        == header.number().saturating_add(1)
+                    && *block_info.block_accumulator_info.get_accumulator_root()
+                        == child_header.block_accumulator_root()
+                    && *block_info.txn_accumulator_info.get_accumulator_root()
+                        == header.txn_accumulator_root()
+                    && block_info
+                        .total_difficulty
+                        .checked_add(child_header.difficulty())
+                        == Some(child_total_difficulty),
+                "Block {}'s block info mismatch with headers",
+                header.id()
+            );
+            child_header = header;
+            child_total_difficulty = block_info.total_difficulty;
+            block_infos.push(block_info);
+        }
+        for block_info in block_infos {
+            self.storage.save_block_info(block_info)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_placeholder(node_key: &HashValue) -> bool {
+    *node_key == *ACCUMULATOR_PLACEHOLDER_HASH || *node_key == *SPARSE_MERKLE_PLACEHOLDER_HASH
+}
+
+fn verify_state_node(
+    node_key: HashValue,
+    state_node: &StateNode,
+    kind: FastSyncNodeKind,
+) -> Result<()> {
+    let node_hash = match kind {
+        FastSyncNodeKind::AccountState => state_node_hash::<AccountAddress>(state_node)?,
+        FastSyncNodeKind::ResourceState => state_node_hash::<StructTag>(state_node)?,
+        FastSyncNodeKind::CodeState => state_node_hash::<ModuleName>(state_node)?,
+        _ => return Err(format_err!("{:?} is not a state node", kind)),
+    };
+    ensure!(
+        node_hash == node_key,
+        "State node hash {} and node key {} mismatch",
+        node_hash,
+        node_key
+    );
+    Ok(())
+}
+
+/// The leaf node's hash is computed by the blob hash, so rebuild the leaf to verify its blob.
+fn state_node_hash<K: RawKey>(state_node: &StateNode) -> Result<HashValue> {
+    Ok(match Node::<K>::decode(state_node.0.as_slice())? {
+        Node::Leaf(leaf) => Node::new_leaf(leaf.raw_key().clone(), leaf.blob().clone()).hash(),
+        node => node.hash(),
+    })
+}
+
+fn state_node_children(
+    state_node: &StateNode,
+    kind: FastSyncNodeKind,
+) -> Result<Vec<(HashValue, FastSyncNodeKind)>> {
+    let children = match kind {
+        FastSyncNodeKind::AccountState => {
+            match Node::<AccountAddress>::decode(state_node.0.as_slice())? {
+                Node::Internal(internal) => internal
+                    .all_child()
+                    .into_iter()
+                    .map(|child| (child, kind))
+                    .collect(),
+                Node::Leaf(leaf) => {
+                    let account_state = AccountState::try_from(leaf.blob().as_ref())?;
+                    let mut children = vec![(
+                        account_state.resource_root(),
+                        FastSyncNodeKind::ResourceState,
+                    )];
+                    if let Some(code_root) = account_state.code_root() {
+                        children.push((code_root, FastSyncNodeKind::CodeState));
+                    }
+                    children
+                }
+                Node::Null => vec![],
+            }
+        }
+        FastSyncNodeKind::ResourceState => internal_children::<StructTag>(state_node, kind)?,
+        FastSyncNodeKind::CodeState => internal_children::<ModuleName>(state_node, kind)?,
+        _ => return Err(format_err!("{:?} is not a state node", kind)),
+    };
+    Ok(children
+        .into_iter()
+        .filter(|(child, _)| !is_placeholder(child))
+        .collect())
+}
+
+fn internal_children<K: RawKey>(
+    state_node: &StateNode,
+    kind: FastSyncNodeKind,
+) -> Result<Vec<(HashValue, FastSyncNodeKind)>> {
+    Ok(match Node::<K>::decode(state_node.0.as_slice())? {
+        Node::Internal(internal) => internal
+            .all_child()
+            .into_iter()
+            .map(|child| (child, kind))
+            .collect(),
+        _ => vec![],
+    })
+}
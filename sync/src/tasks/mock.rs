@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::tasks::{
-    BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator, SyncFetcher,
+    BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator,
+    StateSyncFetcher, SyncFetcher,
 };
 use anyhow::{format_err, Context, Result};
 use async_std::task::JoinHandle;
@@ -15,15 +16,20 @@ use network_api::messages::NotificationMessage;
 use network_api::{PeerInfo, PeerSelector, PeerStrategy};
 use network_rpc_core::{NetRpcError, RpcErrorCode};
 use rand::Rng;
-use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, AccumulatorNode, MerkleAccumulator};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::ChainReader;
 use starcoin_chain_mock::MockChain;
 use starcoin_crypto::HashValue;
 use starcoin_network_rpc_api::G_RPC_INFO;
+use starcoin_state_api::{ChainStateReader, StateWithProof};
+use starcoin_state_tree::StateNode;
+use starcoin_statedb::ChainStateDB;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
+use starcoin_vm_types::access_path::AccessPath;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -347,3 +353,66 @@ impl BlockInfoFetcher for SyncNodeMocker {
 }
 
 impl SyncFetcher for SyncNodeMocker {}
+
+impl StateSyncFetcher for SyncNodeMocker {
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(StateNode, Option<PeerId>)>> {
+        let result = self
+            .chain()
+            .get_storage()
+            .get(&node_key)
+            .and_then(|state_node| {
+                state_node
+                    .ok_or_else(|| format_err!("Can not find state node by key: {}", node_key))
+            });
+        async move {
+            let _ = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            Ok((result?, None))
+        }
+        .boxed()
+    }
+
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(AccumulatorNode, Option<PeerId>)>> {
+        let result = self
+            .chain()
+            .get_storage()
+            .get_accumulator_store(accumulator_type)
+            .get_node(node_key)
+            .and_then(|accumulator_node| {
+                accumulator_node.ok_or_else(|| {
+                    format_err!("Can not find accumulator node by key: {}", node_key)
+                })
+            });
+        async move {
+            let _ = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            Ok((result?, None))
+        }
+        .boxed()
+    }
+
+    fn fetch_state_with_proof(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>> {
+        let result = ChainStateDB::new(
+            self.chain().get_storage().into_super_arc(),
+            Some(state_root),
+        )
+        .get_with_proof(&access_path);
+        async move {
+            let _ = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            result
+        }
+        .boxed()
+    }
+}
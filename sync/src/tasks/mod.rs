@@ -12,16 +12,19 @@ use logger::prelude::*;
 use network_api::{PeerProvider, PeerSelector};
 use network_rpc_core::{NetRpcError, RpcErrorCode};
 use starcoin_accumulator::node::AccumulatorStoreType;
-use starcoin_accumulator::MerkleAccumulator;
+use starcoin_accumulator::{AccumulatorNode, MerkleAccumulator};
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceRef};
+use starcoin_state_api::StateWithProof;
+use starcoin_state_tree::StateNode;
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::U256;
+use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::time::TimeService;
 use std::str::FromStr;
 use std::sync::mpsc::Sender;
@@ -361,6 +364,94 @@ impl BlockInfoFetcher for VerifiedRpcClient {
 
 impl SyncFetcher for VerifiedRpcClient {}
 
+pub trait StateSyncFetcher: Send + Sync {
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(StateNode, Option<PeerId>)>>;
+
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(AccumulatorNode, Option<PeerId>)>>;
+
+    /// Fetch the state of `access_path` at `state_root`, the proof should be verified.
+    fn fetch_state_with_proof(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>>;
+}
+
+impl<T> StateSyncFetcher for Arc<T>
+where
+    T: StateSyncFetcher,
+{
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(StateNode, Option<PeerId>)>> {
+        StateSyncFetcher::fetch_state_node(self.as_ref(), node_key)
+    }
+
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(AccumulatorNode, Option<PeerId>)>> {
+        StateSyncFetcher::fetch_accumulator_node(self.as_ref(), node_key, accumulator_type)
+    }
+
+    fn fetch_state_with_proof(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>> {
+        StateSyncFetcher::fetch_state_with_proof(self.as_ref(), state_root, access_path)
+    }
+}
+
+impl StateSyncFetcher for VerifiedRpcClient {
+    fn fetch_state_node(
+        &self,
+        node_key: HashValue,
+    ) -> BoxFuture<Result<(StateNode, Option<PeerId>)>> {
+        self.get_state_node_by_node_hash(node_key)
+            .and_then(|(peer_id, state_node)| async move {
+                let state_node = state_node
+                    .ok_or_else(|| format_err!("State node is none by node key {:?}.", node_key))?;
+                Ok((state_node, Some(peer_id)))
+            })
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+
+    fn fetch_accumulator_node(
+        &self,
+        node_key: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<(AccumulatorNode, Option<PeerId>)>> {
+        self.get_accumulator_node_by_node_hash(node_key, accumulator_type)
+            .and_then(
+                |(peer_id, accumulator_node)| async move { Ok((accumulator_node, Some(peer_id))) },
+            )
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+
+    fn fetch_state_with_proof(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>> {
+        self.get_state_with_proof(state_root, access_path)
+            .and_then(|(_peer_id, state_with_proof)| async move { Ok(state_with_proof) })
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
 pub trait BlockLocalStore: Send + Sync {
     fn get_block_with_info(&self, block_ids: Vec<HashValue>) -> Result<Vec<Option<SyncBlockData>>>;
 }
@@ -502,6 +593,7 @@ where
 
 mod accumulator_sync_task;
 mod block_sync_task;
+mod fast_sync_task;
 mod find_ancestor_task;
 mod inner_sync_task;
 #[cfg(test)]
@@ -513,6 +605,9 @@ use crate::sync_metrics::SyncMetrics;
 pub use accumulator_sync_task::{AccumulatorCollector, BlockAccumulatorSyncTask};
 pub use block_sync_task::{BlockCollector, BlockSyncTask};
 use executor::VMMetrics;
+pub use fast_sync_task::{
+    fast_sync_task, select_fast_sync_pivot, verify_fast_sync_pivot, FAST_SYNC_PIVOT_DISTANCE,
+};
 pub use find_ancestor_task::{AncestorCollector, FindAncestorTask};
 
 pub fn full_sync_task<H, A, F, N>(
@@ -5,8 +5,9 @@
 use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
-    full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
-    BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask, FindAncestorTask, SyncFetcher,
+    fast_sync_task, full_sync_task, select_fast_sync_pivot, AccumulatorCollector,
+    AncestorCollector, BlockAccumulatorSyncTask, BlockCollector, BlockFetcher, BlockLocalStore,
+    BlockSyncTask, FindAncestorTask, SyncFetcher, FAST_SYNC_PIVOT_DISTANCE,
 };
 use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::Context;
@@ -27,6 +28,7 @@ use starcoin_chain_api::ChainReader;
 use starcoin_chain_mock::MockChain;
 use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis;
+use starcoin_storage::block_info::BlockInfoStore;
 use starcoin_storage::BlockStore;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::peer_info::PeerInfo;
@@ -120,6 +122,92 @@ pub async fn test_full_sync_new_node() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_fast_sync_new_node() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 0)?;
+    node1.produce_block(FAST_SYNC_PIVOT_DISTANCE + 72)?;
+    let arc_node1 = Arc::new(node1);
+    let target = arc_node1.sync_target();
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2.clone(), 1, 0)?;
+    let storage = node2.chain().get_storage();
+
+    let pivot = select_fast_sync_pivot(arc_node1.as_ref(), &target, 0)
+        .await?
+        .ok_or_else(|| format_err!("Fast sync pivot should exist."))?;
+    assert!(pivot.number() > 0);
+    assert!(pivot.number() <= target.target_id.number() - FAST_SYNC_PIVOT_DISTANCE);
+
+    // a pivot which is not on the target chain is rejected before downloading the state.
+    let fake_pivot = BlockIdAndNumber::new(HashValue::random(), pivot.number());
+    let (sync_task, _task_handle, _task_event_counter) = fast_sync_task(
+        fake_pivot,
+        target.clone(),
+        storage.clone(),
+        arc_node1.clone(),
+        15,
+    );
+    assert!(sync_task.await.is_err());
+    assert!(storage.get_fast_sync_progress()?.is_none());
+
+    let (sync_task, _task_handle, task_event_counter) = fast_sync_task(
+        pivot,
+        target.clone(),
+        storage.clone(),
+        arc_node1.clone(),
+        15,
+    );
+    assert_eq!(sync_task.await?, pivot);
+    assert!(storage.get_fast_sync_progress()?.is_none());
+    assert_eq!(storage.get_startup_info()?.unwrap().main, pivot.id());
+    let report = task_event_counter
+        .get_report()
+        .ok_or_else(|| format_err!("Fast sync report should exist."))?;
+    debug!("report: {}", report);
+
+    // the blocks before the pivot are saved with their block infos.
+    let parent_id = storage
+        .get_block_header_by_hash(pivot.id())?
+        .expect("pivot block should exist")
+        .parent_hash();
+    assert_eq!(
+        storage.get_block_info(parent_id)?,
+        arc_node1.chain().get_block_info(Some(parent_id))?
+    );
+
+    let pivot_chain = BlockChain::new(net2.time_service(), pivot.id(), storage.clone(), None)?;
+    assert_eq!(
+        pivot_chain.status().info,
+        arc_node1
+            .chain()
+            .get_block_info(Some(pivot.id()))?
+            .expect("pivot block info should exist")
+    );
+
+    // continue with full sync after the pivot.
+    let (sender_1, _receiver_1) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+        pivot.id(),
+        target.clone(),
+        false,
+        net2.time_service(),
+        storage.clone(),
+        sender_1,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+        None,
+        None,
+    )?;
+    let branch = sync_task.await?;
+    assert_eq!(branch.current_header().id(), target.target_id.id());
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_sync_invalid_target() -> Result<()> {
     let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
@@ -10,8 +10,9 @@ use starcoin_accumulator::AccumulatorNode;
 use starcoin_crypto::hash::HashValue;
 use starcoin_network_rpc_api::{
     gen_client::NetworkRpcClient, BlockBody, GetAccumulatorNodeByNodeHash, GetBlockHeadersByNumber,
-    GetBlockIds, GetTxnsWithHash, RawRpcClient,
+    GetBlockIds, GetStateWithProof, GetTxnsWithHash, RawRpcClient,
 };
+use starcoin_state_api::StateWithProof;
 use starcoin_state_tree::StateNode;
 use starcoin_types::block::Block;
 use starcoin_types::peer_info::PeerInfo;
@@ -21,6 +22,7 @@ use starcoin_types::{
     peer_info::PeerId,
     transaction::TransactionInfo,
 };
+use starcoin_vm_types::access_path::AccessPath;
 use std::fmt::Debug;
 use std::time::Instant;
 use thiserror::Error;
@@ -323,6 +325,33 @@ impl VerifiedRpcClient {
         ))
     }
 
+    /// Get the state of `access_path` at `state_root`, and verify it by the proof.
+    pub async fn get_state_with_proof(
+        &self,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> Result<(PeerId, StateWithProof)> {
+        let peer_id = self.select_a_peer()?;
+        let state_with_proof = self
+            .client
+            .get_state_with_proof(
+                peer_id.clone(),
+                GetStateWithProof {
+                    state_root,
+                    access_path: access_path.clone(),
+                },
+            )
+            .await?;
+        if let Err(e) = state_with_proof.verify(state_root, access_path) {
+            return Err(RpcVerifyError::new(
+                peer_id,
+                format!("Verify state proof of root {} failed: {}", state_root, e),
+            )
+            .into());
+        }
+        Ok((peer_id, state_with_proof))
+    }
+
     pub async fn get_accumulator_node_by_node_hash(
         &self,
         node_key: HashValue,
@@ -9,10 +9,27 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_types::{
     account_address::AccountAddress, block::Block, transaction, transaction::SignedUserTransaction,
 };
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 pub type TxnStatusFullEvent = Arc<[(HashValue, transaction::TxStatus)]>;
 
+/// Max number of pending txns sampled when suggesting a gas price.
+pub const GAS_PRICE_SAMPLE_SIZE: u64 = 1000;
+/// The percentile of the sampled pending gas prices used as the suggested gas price.
+pub const GAS_PRICE_SUGGEST_PERCENTILE: usize = 60;
+
+/// Suggest a gas price from the gas prices of pending txns:
+/// the `GAS_PRICE_SUGGEST_PERCENTILE` percentile of them, but never less than `min_gas_price`.
+pub fn suggest_gas_price(mut gas_prices: Vec<u64>, min_gas_price: u64) -> u64 {
+    if gas_prices.is_empty() {
+        return min_gas_price;
+    }
+    gas_prices.sort_unstable();
+    let index = (gas_prices.len() - 1) * GAS_PRICE_SUGGEST_PERCENTILE / 100;
+    gas_prices[index].max(min_gas_price)
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TxPoolStatus {
     pub txn_count: usize,
@@ -21,6 +38,12 @@ pub struct TxPoolStatus {
     pub mem_max: usize,
     pub senders: usize,
     pub is_full: bool,
+    /// The ordering policy of the pool.
+    pub ordering: String,
+    /// Counts of the rejected txns, by reason.
+    pub rejected: BTreeMap<String, u64>,
+    /// Counts of the txns evicted from the pool, by reason.
+    pub evicted: BTreeMap<String, u64>,
 }
 
 pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
@@ -29,6 +52,15 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
         txns: Vec<SignedUserTransaction>,
     ) -> Vec<Result<(), transaction::TransactionError>>;
 
+    /// Add txns submitted by local users, such as by rpc.
+    /// The pool may persist them in the journal to survive node restart.
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        self.add_txns(txns)
+    }
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -61,6 +93,10 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     /// Tx Pool status
     fn status(&self) -> TxPoolStatus;
 
+    /// Gas price suggested by the pool, derived from the gas prices of the pending txns.
+    /// `now` is the current timestamp in secs, same as in `get_pending_txns`.
+    fn suggested_gas_price(&self, now: Option<u64>) -> u64;
+
     fn find_txn(&self, hash: &HashValue) -> Option<SignedUserTransaction>;
     fn txns_of_sender(
         &self,
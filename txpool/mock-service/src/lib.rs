@@ -4,7 +4,7 @@
 use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{suggest_gas_price, TxPoolStatus, TxPoolSyncService};
 use std::{
     iter::Iterator,
     sync::{Arc, Mutex},
@@ -90,6 +90,17 @@ impl TxPoolSyncService for MockTxPoolService {
         unimplemented!()
     }
 
+    fn suggested_gas_price(&self, _now: Option<u64>) -> u64 {
+        let gas_prices = self
+            .pool
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|txn| txn.gas_unit_price())
+            .collect();
+        suggest_gas_price(gas_prices, 1)
+    }
+
     fn find_txn(&self, _hash: &HashValue) -> Option<SignedUserTransaction> {
         unimplemented!()
     }
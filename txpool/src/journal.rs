@@ -0,0 +1,128 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! On-disk journal of locally submitted transactions.
+//!
+//! The journal keeps the local transactions which are still in the pool, so they can be
+//! re-imported after node restart. The whole journal is rewritten to a temp file and renamed on
+//! every change, the number of local transactions is bounded by the pool limits.
+
+use anyhow::Result;
+use bcs_ext::BCSCodec;
+use crypto::hash::HashValue;
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use types::transaction::SignedUserTransaction;
+
+#[derive(Clone)]
+pub(crate) struct TxnJournal {
+    path: PathBuf,
+    txns: Arc<Mutex<LinkedHashMap<HashValue, SignedUserTransaction>>>,
+}
+
+impl fmt::Debug for TxnJournal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TxnJournal {{ path: {:?}, txns: {} }}",
+            self.path,
+            self.txns.lock().len()
+        )
+    }
+}
+
+impl TxnJournal {
+    /// Open the journal at `path`, an unreadable journal is dropped with a warning,
+    /// the journal only serves as a best effort backup of the pool.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut txns = LinkedHashMap::new();
+        if path.exists() {
+            match fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| Vec::<SignedUserTransaction>::decode(bytes.as_slice()))
+            {
+                Ok(journal_txns) => {
+                    for txn in journal_txns {
+                        txns.insert(txn.id(), txn);
+                    }
+                }
+                Err(e) => {
+                    warn!("Discard broken txpool journal {:?}: {:?}", path, e);
+                }
+            }
+        }
+        Ok(Self {
+            path,
+            txns: Arc::new(Mutex::new(txns)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    pub fn len(&self) -> usize {
+        self.txns.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txns.lock().is_empty()
+    }
+
+    pub fn contains(&self, txn_hash: &HashValue) -> bool {
+        self.txns.lock().contains_key(txn_hash)
+    }
+
+    /// All journaled txns, in insertion order.
+    pub fn txns(&self) -> Vec<SignedUserTransaction> {
+        self.txns.lock().values().cloned().collect()
+    }
+
+    pub fn insert(&self, new_txns: Vec<SignedUserTransaction>) -> Result<()> {
+        if new_txns.is_empty() {
+            return Ok(());
+        }
+        let mut txns = self.txns.lock();
+        for txn in new_txns {
+            txns.insert(txn.id(), txn);
+        }
+        self.flush(&txns)
+    }
+
+    /// Only keep the txns which satisfy `f`, return the count of pruned txns.
+    pub fn retain<F>(&self, f: F) -> Result<usize>
+    where
+        F: Fn(&HashValue, &SignedUserTransaction) -> bool,
+    {
+        let mut txns = self.txns.lock();
+        let pruned: Vec<HashValue> = txns
+            .iter()
+            .filter(|(hash, txn)| !f(hash, txn))
+            .map(|(hash, _)| *hash)
+            .collect();
+        if pruned.is_empty() {
+            return Ok(0);
+        }
+        for hash in &pruned {
+            txns.remove(hash);
+        }
+        self.flush(&txns)?;
+        Ok(pruned.len())
+    }
+
+    fn flush(&self, txns: &LinkedHashMap<HashValue, SignedUserTransaction>) -> Result<()> {
+        let txns: Vec<SignedUserTransaction> = txns.values().cloned().collect();
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, txns.encode()?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@ use types::{
     transaction::SignedUserTransaction,
 };
 
+mod journal;
 mod metrics;
 mod pool;
 mod pool_client;
@@ -4,6 +4,7 @@
 mod client;
 pub(crate) mod listener;
 pub(crate) mod local_transactions;
+pub(crate) mod policy;
 pub(crate) mod queue;
 pub(crate) mod ready;
 pub(crate) mod replace;
@@ -12,6 +13,7 @@ pub(crate) mod verifier;
 
 pub use client::{AccountSeqNumberClient, Client};
 use crypto::hash::HashValue;
+pub use policy::{OrderingPolicy, SenderLimits};
 pub use queue::{Status, TransactionQueue};
 use std::ops::Deref;
 use transaction_pool as tx_pool;
@@ -160,6 +162,8 @@ pub struct VerifiedTransaction {
     sender: AccountAddress,
     priority: Priority,
     insertion_id: usize,
+    /// Local timestamp in seconds when the txn enters the pool.
+    imported_at: u64,
 }
 
 impl VerifiedTransaction {
@@ -177,6 +181,7 @@ impl VerifiedTransaction {
             sender,
             priority: Priority::Retracted,
             insertion_id: 0,
+            imported_at: 0,
         }
     }
 
@@ -185,6 +190,11 @@ impl VerifiedTransaction {
         self.insertion_id
     }
 
+    /// Gets the local timestamp in seconds when the txn enters the pool.
+    pub(crate) fn imported_at(&self) -> u64 {
+        self.imported_at
+    }
+
     /// Gets wrapped `SignedTransaction`
     pub fn signed(&self) -> &transaction::SignedUserTransaction {
         &self.transaction
@@ -223,6 +233,9 @@ pub trait ScoredTransaction {
 
     /// Gets transaction seq number.
     fn seq_number(&self) -> u64;
+
+    /// Gets the order the transaction enters the pool.
+    fn insertion_id(&self) -> usize;
 }
 
 impl ScoredTransaction for VerifiedTransaction {
@@ -239,15 +252,10 @@ impl ScoredTransaction for VerifiedTransaction {
     fn seq_number(&self) -> SeqNumber {
         self.transaction.sequence_number()
     }
-}
 
-/// How to prioritize transactions in the pool
-///
-/// TODO: Implement more strategies.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum PrioritizationStrategy {
-    /// Simple gas-price based prioritization.
-    GasPriceOnly,
+    fn insertion_id(&self) -> usize {
+        self.insertion_id
+    }
 }
 
 /// Transaction ordering when requesting pending set.
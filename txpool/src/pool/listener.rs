@@ -1,11 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt, sync::Arc};
+use std::{collections::BTreeMap, fmt, sync::Arc};
 
 use super::{TxStatus, VerifiedTransaction as Transaction};
 use crypto::hash::HashValue as H256;
 use futures_channel::mpsc;
+use parking_lot::RwLock;
 use starcoin_logger::prelude::*;
 use transaction_pool as tx_pool;
 use tx_pool::VerifiedTransaction;
@@ -128,6 +129,57 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     }
 }
 
+/// Counts of the txns rejected or evicted by the pool, grouped by reason.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TxnStats {
+    pub rejected: BTreeMap<String, u64>,
+    pub evicted: BTreeMap<String, u64>,
+}
+
+impl TxnStats {
+    pub fn reject(&mut self, reason: &str) {
+        *self.rejected.entry(reason.to_string()).or_default() += 1;
+    }
+
+    pub fn evict(&mut self, reason: &str, count: u64) {
+        if count > 0 {
+            *self.evicted.entry(reason.to_string()).or_default() += count;
+        }
+    }
+}
+
+/// Records the txns evicted by the pool, culled txns are recorded by the queue,
+/// which knows why they are culled.
+#[derive(Default, Debug)]
+pub struct EvictionRecorder {
+    stats: Arc<RwLock<TxnStats>>,
+}
+
+impl EvictionRecorder {
+    pub fn new(stats: Arc<RwLock<TxnStats>>) -> Self {
+        Self { stats }
+    }
+}
+
+impl tx_pool::Listener<Transaction> for EvictionRecorder {
+    fn added(&mut self, _tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
+        if old.is_some() {
+            self.stats.write().evict("replaced", 1);
+        }
+    }
+
+    fn dropped(&mut self, _tx: &Arc<Transaction>, new: Option<&Transaction>) {
+        match new {
+            Some(_) => self.stats.write().evict("pool_full", 1),
+            None => self.stats.write().evict("dropped", 1),
+        }
+    }
+
+    fn invalid(&mut self, _tx: &Arc<Transaction>) {
+        self.stats.write().evict("invalid", 1);
+    }
+}
+
 /// Transactions pool notifier
 #[derive(Default)]
 pub struct TransactionsPoolNotifier {
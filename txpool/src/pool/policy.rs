@@ -0,0 +1,151 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ordering and Admission Policies
+//!
+//! The ordering policy scores the transactions in the pool, pending transactions are
+//! ordered by the score, and the transaction with the lowest score is evicted first
+//! when the pool is full.
+//!
+//! The sender limits decide how many transactions of a sender are admitted to the pool,
+//! and how long they can stay in it.
+
+use super::{GasPrice, Priority, ScoredTransaction};
+use starcoin_config::{TxPoolConfig, TxPoolOrdering};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use types::account_address::AccountAddress as Address;
+
+/// Scores of txns from priority senders start from this, above any regular txn.
+const PRIORITY_SCORE_BASE: u64 = 1 << 62;
+
+/// Policy to order the transactions in the pool.
+pub trait OrderingPolicy: fmt::Debug + Send + Sync {
+    /// Name of the policy, shown in pool status.
+    fn name(&self) -> &'static str;
+
+    /// Score of the txn, the higher the score the earlier the txn is packed,
+    /// and the later it is evicted.
+    fn score(&self, sender: &Address, txn: &dyn ScoredTransaction) -> u64;
+}
+
+/// Order by gas price, boost the local and retracted txns.
+#[derive(Debug, Default)]
+pub struct GasPricePolicy;
+
+impl GasPricePolicy {
+    fn boost(gas_price: GasPrice, priority: Priority) -> u64 {
+        let boost = match priority {
+            Priority::Local => 15,
+            Priority::Retracted => 10,
+            Priority::Regular => 0,
+        };
+        gas_price << boost
+    }
+}
+
+impl OrderingPolicy for GasPricePolicy {
+    fn name(&self) -> &'static str {
+        "gas_price"
+    }
+
+    fn score(&self, _sender: &Address, txn: &dyn ScoredTransaction) -> u64 {
+        Self::boost(txn.gas_price(), txn.priority())
+    }
+}
+
+/// First in, first out: the earlier a txn enters the pool, the higher its score.
+#[derive(Debug, Default)]
+pub struct FifoPolicy;
+
+impl OrderingPolicy for FifoPolicy {
+    fn name(&self) -> &'static str {
+        "fifo"
+    }
+
+    fn score(&self, _sender: &Address, txn: &dyn ScoredTransaction) -> u64 {
+        u64::max_value() - txn.insertion_id() as u64
+    }
+}
+
+/// Txns of local or allow-listed senders go before other txns, each group is ordered by gas price.
+#[derive(Debug, Default)]
+pub struct PrioritySendersPolicy {
+    senders: HashSet<Address>,
+}
+
+impl PrioritySendersPolicy {
+    pub fn new(senders: impl IntoIterator<Item = Address>) -> Self {
+        Self {
+            senders: senders.into_iter().collect(),
+        }
+    }
+
+    pub fn is_prioritized(&self, sender: &Address, txn: &dyn ScoredTransaction) -> bool {
+        txn.priority().is_local() || self.senders.contains(sender)
+    }
+}
+
+impl OrderingPolicy for PrioritySendersPolicy {
+    fn name(&self) -> &'static str {
+        "priority"
+    }
+
+    fn score(&self, sender: &Address, txn: &dyn ScoredTransaction) -> u64 {
+        if self.is_prioritized(sender, txn) {
+            PRIORITY_SCORE_BASE.saturating_add(txn.gas_price())
+        } else {
+            txn.gas_price().min(PRIORITY_SCORE_BASE - 1)
+        }
+    }
+}
+
+/// Create the builtin ordering policy.
+pub fn new_policy(
+    ordering: TxPoolOrdering,
+    priority_senders: Vec<Address>,
+) -> Arc<dyn OrderingPolicy> {
+    match ordering {
+        TxPoolOrdering::GasPrice => Arc::new(GasPricePolicy),
+        TxPoolOrdering::Fifo => Arc::new(FifoPolicy),
+        TxPoolOrdering::Priority => Arc::new(PrioritySendersPolicy::new(priority_senders)),
+    }
+}
+
+/// Per sender admission limits of the pool.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SenderLimits {
+    /// Max txns of the sender in the pool, override the `max_per_sender` of the pool.
+    pub quotas: HashMap<Address, usize>,
+    /// Seconds a txn can stay in the pool, `None` means no limit.
+    pub expiry: Option<u64>,
+    /// Per sender expiry, override the default `expiry`.
+    pub sender_expiry: HashMap<Address, u64>,
+}
+
+impl SenderLimits {
+    pub fn from_config(config: &TxPoolConfig) -> Self {
+        Self {
+            quotas: config
+                .sender_quotas()
+                .into_iter()
+                .map(|(sender, quota)| (sender, quota as usize))
+                .collect(),
+            expiry: config.txn_expiry(),
+            sender_expiry: config.sender_expiry().into_iter().collect(),
+        }
+    }
+
+    pub fn quota(&self, sender: &Address) -> Option<usize> {
+        self.quotas.get(sender).cloned()
+    }
+
+    pub fn expiry(&self, sender: &Address) -> Option<u64> {
+        self.sender_expiry.get(sender).cloned().or(self.expiry)
+    }
+
+    pub fn has_expiry(&self) -> bool {
+        self.expiry.is_some() || !self.sender_expiry.is_empty()
+    }
+}
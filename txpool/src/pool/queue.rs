@@ -4,8 +4,9 @@
 //! Transaction Queue
 
 use super::{
-    client, listener, local_transactions::LocalTransactionsList, ready, replace, scoring, verifier,
-    PendingOrdering, PendingSettings, PrioritizationStrategy, SeqNumber, TxStatus,
+    client, listener, listener::TxnStats, local_transactions::LocalTransactionsList, ready,
+    replace, scoring, verifier, OrderingPolicy, PendingOrdering, PendingSettings, SenderLimits,
+    SeqNumber, TxStatus,
 };
 use crate::pool::ready::Expiration;
 use crate::{pool, pool::PoolTransaction};
@@ -23,16 +24,19 @@ use std::{
     },
 };
 use tx_pool::{self, Verifier};
-use types::{account_address::AccountAddress as Address, transaction};
+use types::{account_address::AccountAddress as Address, time::TimeService, transaction};
 
 type Listener = (
     LocalTransactionsList,
     (
         listener::TransactionsPoolNotifier,
-        (listener::Logger, listener::StatusLogger),
+        (
+            listener::Logger,
+            (listener::StatusLogger, listener::EvictionRecorder),
+        ),
     ),
 );
-type Pool = tx_pool::Pool<pool::VerifiedTransaction, scoring::SeqNumberAndGasPrice, Listener>;
+type Pool = tx_pool::Pool<pool::VerifiedTransaction, scoring::SeqNumberAndPolicy, Listener>;
 
 /// Max cache time in milliseconds for pending transactions.
 ///
@@ -59,6 +63,10 @@ pub struct Status {
     pub status: tx_pool::LightStatus,
     /// Current limits of the transaction pool.
     pub limits: tx_pool::Options,
+    /// Name of the ordering policy.
+    pub ordering: String,
+    /// Counts of rejected and evicted transactions by reason.
+    pub stats: TxnStats,
 }
 
 impl Status {
@@ -94,6 +102,9 @@ impl Into<TxPoolStatus> for Status {
             mem_max: self.limits.max_mem_usage / 1024,
             senders: self.status.senders,
             is_full: self.is_full(),
+            ordering: self.ordering,
+            rejected: self.stats.rejected,
+            evicted: self.stats.evicted,
         }
     }
 }
@@ -223,21 +234,45 @@ pub struct TransactionQueue {
     options: RwLock<verifier::Options>,
     cached_pending: RwLock<CachedPending>,
     recently_rejected: RecentlyRejected,
+    limits: Arc<SenderLimits>,
+    stats: Arc<RwLock<TxnStats>>,
+    time_service: Arc<dyn TimeService>,
 }
 
 impl TransactionQueue {
-    /// Create new queue with given pool limits and initial verification options.
+    /// Create new queue with given pool limits, initial verification options and ordering policy.
+    ///
+    /// A txn with the same sender and seq number as a pooled one replaces it only if its gas price
+    /// is higher by `price_bump_percent`.
     pub fn new(
         limits: tx_pool::Options,
         verification_options: verifier::Options,
-        strategy: PrioritizationStrategy,
+        policy: Arc<dyn OrderingPolicy>,
+        price_bump_percent: u64,
+        sender_limits: SenderLimits,
+        time_service: Arc<dyn TimeService>,
     ) -> Self {
         let max_count = limits.max_count;
+        let sender_limits = Arc::new(sender_limits);
+        let stats = Arc::new(RwLock::new(TxnStats::default()));
+        let listener = (
+            LocalTransactionsList::default(),
+            (
+                listener::TransactionsPoolNotifier::default(),
+                (
+                    listener::Logger,
+                    (
+                        listener::StatusLogger,
+                        listener::EvictionRecorder::new(stats.clone()),
+                    ),
+                ),
+            ),
+        );
         TransactionQueue {
             insertion_id: Default::default(),
             pool: RwLock::new(tx_pool::Pool::new(
-                Default::default(),
-                scoring::SeqNumberAndGasPrice(strategy),
+                listener,
+                scoring::SeqNumberAndPolicy::new(policy, price_bump_percent, sender_limits.clone()),
                 limits,
             )),
             options: RwLock::new(verification_options),
@@ -246,6 +281,9 @@ impl TransactionQueue {
                 MIN_REJECTED_CACHE_SIZE,
                 max_count / 4,
             )),
+            limits: sender_limits,
+            stats,
+            time_service,
         }
     }
 
@@ -300,6 +338,7 @@ impl TransactionQueue {
             options,
             self.insertion_id.clone(),
             transaction_to_replace,
+            self.time_service.now_secs(),
         );
 
         let replace =
@@ -321,6 +360,7 @@ impl TransactionQueue {
             let imported = verifier
                 .verify_transaction(transaction)
                 .and_then(|verified| {
+                    self.check_sender_quota(&verified)?;
                     self.pool
                         .write()
                         .import(verified, &replace)
@@ -330,6 +370,7 @@ impl TransactionQueue {
             results.push(match imported {
                 Ok(_) => Ok(()),
                 Err(err) => {
+                    self.stats.write().reject(rejection_reason(&err));
                     self.recently_rejected.insert(hash, &err);
                     Err(err)
                 }
@@ -345,6 +386,36 @@ impl TransactionQueue {
         results
     }
 
+    /// Reject the txn if its sender has a quota, and the pooled txns of the sender reach it.
+    /// The txn replacing a pooled one is not limited.
+    fn check_sender_quota(
+        &self,
+        txn: &pool::VerifiedTransaction,
+    ) -> Result<(), transaction::TransactionError> {
+        let sender = txn.signed().sender();
+        let quota = match self.limits.quota(&sender) {
+            Some(quota) => quota,
+            None => return Ok(()),
+        };
+        let seq_number = txn.signed().sequence_number();
+        let mut pooled = 0;
+        for pooled_txn in self
+            .pool
+            .read()
+            .pending_from_sender(Expiration::new(0), &sender)
+        {
+            if pooled_txn.signed().sequence_number() == seq_number {
+                return Ok(());
+            }
+            pooled += 1;
+        }
+        if pooled >= quota {
+            Err(transaction::TransactionError::LimitReached)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn txns_of_sender(
         &self,
         sender: &Address,
@@ -430,18 +501,30 @@ impl TransactionQueue {
         self.recently_rejected.clear();
 
         let mut removed = 0;
+        let mut expired = 0;
         let senders: Vec<_> = {
             let pool = &self.pool.read();
             pool.senders().cloned().collect()
         };
+        let local_now = self.time_service.now_secs();
         for chunk in senders.chunks(CULL_SENDERS_CHUNK) {
             trace_time!("pool::cull::chunk");
+            // remove the txns staying in the pool longer than the expiry of their senders.
+            if self.limits.has_expiry() {
+                let lifetime = ready::Lifetime::new(local_now, self.limits.as_ref());
+                expired += self.pool.write().cull(Some(chunk), lifetime);
+            }
             let state_readiness = ready::State::new(client.clone(), stale_id);
             // also remove expired txns.
             let readiness = (ready::Expiration::new(now), state_readiness);
             removed += self.pool.write().cull(Some(chunk), readiness);
         }
-        debug!(target: "txqueue", "Removed {} stalled transactions. {}", removed, self.status());
+        {
+            let mut stats = self.stats.write();
+            stats.evict("expired", expired as u64);
+            stats.evict("stale", removed as u64);
+        }
+        debug!(target: "txqueue", "Removed {} stalled and {} expired transactions. {}", removed, expired, self.status());
     }
 
     /// Returns next valid sequence number for given sender
@@ -530,6 +613,8 @@ impl TransactionQueue {
             options,
             status,
             limits,
+            ordering: pool.scoring().policy().name().to_string(),
+            stats: self.stats.read().clone(),
         }
     }
 
@@ -575,6 +660,30 @@ impl TransactionQueue {
     }
 }
 
+/// The reason shown in pool status of a rejected txn.
+fn rejection_reason(err: &transaction::TransactionError) -> &'static str {
+    use transaction::TransactionError;
+
+    match err {
+        TransactionError::AlreadyImported => "already_imported",
+        TransactionError::Old => "old",
+        TransactionError::LimitReached => "limit_reached",
+        TransactionError::InsufficientGasPrice { .. } => "insufficient_gas_price",
+        TransactionError::TooCheapToReplace { .. } => "too_cheap_to_replace",
+        TransactionError::InsufficientGas { .. } => "insufficient_gas",
+        TransactionError::InsufficientBalance { .. } => "insufficient_balance",
+        TransactionError::GasLimitExceeded { .. } => "gas_limit_exceeded",
+        TransactionError::SenderBanned => "sender_banned",
+        TransactionError::RecipientBanned => "recipient_banned",
+        TransactionError::CodeBanned => "code_banned",
+        TransactionError::InvalidChainId => "invalid_chain_id",
+        TransactionError::NotAllowed => "not_allowed",
+        TransactionError::InvalidSignature(_) => "invalid_signature",
+        TransactionError::TooBig => "too_big",
+        TransactionError::CallErr(_) => "call_error",
+    }
+}
+
 fn convert_error<H: fmt::Debug + fmt::LowerHex>(
     err: tx_pool::Error<H>,
 ) -> transaction::TransactionError {
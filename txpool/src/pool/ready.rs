@@ -30,7 +30,7 @@ use std::{cmp, collections::HashMap};
 use tx_pool::{self, VerifiedTransaction as PoolVerifiedTransaction};
 use types::{account_address::AccountAddress as Address, transaction};
 
-use super::{client::AccountSeqNumberClient, SeqNumber, VerifiedTransaction};
+use super::{client::AccountSeqNumberClient, SenderLimits, SeqNumber, VerifiedTransaction};
 
 /// Checks readiness of transactions by comparing the nonce to state nonce.
 #[derive(Debug)]
@@ -110,6 +110,31 @@ impl tx_pool::Ready<VerifiedTransaction> for Expiration {
     }
 }
 
+/// Checks how long transactions have stayed in the pool, by the expiry of their senders.
+#[derive(Debug)]
+pub struct Lifetime<'a> {
+    now: u64,
+    limits: &'a SenderLimits,
+}
+
+impl<'a> Lifetime<'a> {
+    /// Create a new lifetime checker given current local timestamp in seconds.
+    pub fn new(now: u64, limits: &'a SenderLimits) -> Self {
+        Lifetime { now, limits }
+    }
+}
+
+impl<'a> tx_pool::Ready<VerifiedTransaction> for Lifetime<'a> {
+    fn is_ready(&mut self, tx: &VerifiedTransaction) -> tx_pool::Readiness {
+        match self.limits.expiry(tx.sender()) {
+            Some(expiry) if tx.imported_at().saturating_add(expiry) <= self.now => {
+                tx_pool::Readiness::Stale
+            }
+            _ => tx_pool::Readiness::Ready,
+        }
+    }
+}
+
 /// Checks readines of Pending transactions by comparing it with current time and block number.
 #[derive(Debug)]
 pub struct Condition {
@@ -7,10 +7,10 @@
 //! in the pool. The decision whether to reject, replace or retain both is
 //! delegated to an implementation of `ShouldReplace`.
 //!
-//! Here we decide based on the sender, the nonce and gas price, and finally
-//! on the `Readiness` of the transactions when comparing them
+//! Here we decide based on the sender, the nonce and the score given by the
+//! ordering policy, and finally on the `Readiness` of the transactions when comparing them
 
-use crate::pool::{client, ScoredTransaction};
+use crate::pool::{client, scoring::SeqNumberAndPolicy, ScoredTransaction};
 use std::cmp;
 use tx_pool::{
     self,
@@ -34,10 +34,9 @@ impl<S, C> ReplaceByScoreAndReadiness<S, C> {
     }
 }
 
-impl<T, S, C> tx_pool::ShouldReplace<T> for ReplaceByScoreAndReadiness<S, C>
+impl<T, C> tx_pool::ShouldReplace<T> for ReplaceByScoreAndReadiness<SeqNumberAndPolicy, C>
 where
     T: VerifiedTransaction<Sender = Address> + ScoredTransaction + PartialEq,
-    S: Scoring<T> + Sync,
     C: client::AccountSeqNumberClient,
 {
     fn should_replace<'r>(
@@ -49,7 +48,7 @@ where
         if old.sender() == new.sender() {
             // prefer earliest transaction
             match new.seq_number().cmp(&old.seq_number()) {
-                cmp::Ordering::Equal => self.scoring.choose(old, new),
+                cmp::Ordering::Equal => Scoring::<T>::choose(&self.scoring, old, new),
                 _ if both_local => Choice::InsertNew,
                 cmp::Ordering::Less => Choice::ReplaceOld,
                 cmp::Ordering::Greater => Choice::RejectNew,
@@ -57,8 +56,8 @@ where
         } else if both_local {
             Choice::InsertNew
         } else {
-            let old_score = (old.priority(), old.gas_price());
-            let new_score = (new.priority(), new.gas_price());
+            let old_score = self.scoring.score::<T>(old);
+            let new_score = self.scoring.score::<T>(new);
             if new_score <= old_score {
                 Choice::RejectNew
             } else {
@@ -68,8 +67,10 @@ where
                 // we don't need to remove `old` (worst transaction in the pool) since `new` will replace
                 // some other transaction in the pool so we will never go above limit anyway.
                 if let Some(txs) = new.pooled_by_sender {
-                    if let Ok(index) = txs.binary_search_by(|old| self.scoring.compare(old, new)) {
-                        return match self.scoring.choose(&txs[index], new) {
+                    if let Ok(index) =
+                        txs.binary_search_by(|old| Scoring::<T>::compare(&self.scoring, old, new))
+                    {
+                        return match Scoring::<T>::choose(&self.scoring, &txs[index], new) {
                             Choice::ReplaceOld => Choice::InsertNew,
                             choice => choice,
                         };
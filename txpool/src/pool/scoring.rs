@@ -2,29 +2,68 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::cmp;
+use std::sync::Arc;
 
-use super::{GasPrice, PrioritizationStrategy, ScoredTransaction, VerifiedTransaction};
+use super::{
+    policy::OrderingPolicy, GasPrice, ScoredTransaction, SenderLimits, VerifiedTransaction,
+};
 use tx_pool::{self, scoring};
-/// Transaction with the same (sender, nonce) can be replaced only if
-/// `new_gas_price >= old_gas_price + old_gas_price >> SHIFT`
-const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
+use types::account_address::AccountAddress as Address;
 
-/// TODO: re-consider this, after we settle down the unit of gas price.
 /// Calculate minimal gas price requirement.
+/// Transaction with the same (sender, nonce) can be replaced only if
+/// `new_gas_price >= old_gas_price + old_gas_price * bump_percent / 100`
 #[inline]
-fn bump_gas_price(old_gp: GasPrice) -> GasPrice {
-    old_gp
-    // old_gp.saturating_add(old_gp >> GAS_PRICE_BUMP_SHIFT as u64)
+fn bump_gas_price(old_gp: GasPrice, bump_percent: u64) -> GasPrice {
+    let bump = (old_gp as u128 * bump_percent as u128 / 100).min(GasPrice::max_value() as u128);
+    old_gp.saturating_add(bump as GasPrice)
 }
 
-/// Simple, gas-price based scoring for transactions.
+/// Scoring for transactions, the transactions of a sender are ordered by seq number,
+/// and scored by the ordering policy.
 ///
 /// NOTE: Currently penalization does not apply to new transactions that enter the pool.
 /// We might want to store penalization status in some persistent state.
 #[derive(Debug, Clone)]
-pub struct SeqNumberAndGasPrice(pub PrioritizationStrategy);
+pub struct SeqNumberAndPolicy {
+    policy: Arc<dyn OrderingPolicy>,
+    price_bump_percent: u64,
+    limits: Arc<SenderLimits>,
+}
+
+impl SeqNumberAndPolicy {
+    pub fn new(
+        policy: Arc<dyn OrderingPolicy>,
+        price_bump_percent: u64,
+        limits: Arc<SenderLimits>,
+    ) -> Self {
+        Self {
+            policy,
+            price_bump_percent,
+            limits,
+        }
+    }
+
+    pub fn policy(&self) -> &dyn OrderingPolicy {
+        self.policy.as_ref()
+    }
+
+    pub fn price_bump_percent(&self) -> u64 {
+        self.price_bump_percent
+    }
+
+    pub fn limits(&self) -> &SenderLimits {
+        self.limits.as_ref()
+    }
+
+    /// Score of the txn given by the ordering policy.
+    pub fn score<P>(&self, txn: &P) -> u64
+    where
+        P: ScoredTransaction + tx_pool::VerifiedTransaction<Sender = Address>,
+    {
+        self.policy.score(txn.sender(), txn)
+    }
 
-impl SeqNumberAndGasPrice {
     /// Decide if the transaction should even be considered into the pool (if the pool is full).
     ///
     /// Used by Verifier to quickly reject transactions that don't have any chance to get into the pool later on,
@@ -49,9 +88,9 @@ impl SeqNumberAndGasPrice {
     //    }
 }
 
-impl<P> tx_pool::Scoring<P> for SeqNumberAndGasPrice
+impl<P> tx_pool::Scoring<P> for SeqNumberAndPolicy
 where
-    P: ScoredTransaction + tx_pool::VerifiedTransaction,
+    P: ScoredTransaction + tx_pool::VerifiedTransaction<Sender = Address>,
 {
     type Event = ();
     type Score = u64;
@@ -68,7 +107,7 @@ where
         let old_gp = old.gas_price();
         let new_gp = new.gas_price();
 
-        let min_required_gp = bump_gas_price(old_gp);
+        let min_required_gp = bump_gas_price(old_gp, self.price_bump_percent);
 
         match min_required_gp.cmp(&new_gp) {
             cmp::Ordering::Greater => scoring::Choice::RejectNew,
@@ -91,13 +130,7 @@ where
                 assert!(i < txs.len());
                 assert!(i < scores.len());
 
-                scores[i] = txs[i].transaction.gas_price();
-                let boost = match txs[i].priority() {
-                    super::Priority::Local => 15,
-                    super::Priority::Retracted => 10,
-                    super::Priority::Regular => 0,
-                };
-                scores[i] <<= boost;
+                scores[i] = self.score(txs[i].transaction.as_ref());
             }
             // We are only sending an event in case of penalization.
             // So just lower the priority of all non-local transactions.
@@ -112,8 +145,8 @@ where
         }
     }
 
-    fn should_ignore_sender_limit(&self, _new: &P) -> bool {
-        false
-        // new.priority().is_local()
+    /// The senders with quota are limited by their quota rather than the `max_per_sender` of the pool.
+    fn should_ignore_sender_limit(&self, new: &P) -> bool {
+        self.limits.quota(new.sender()).is_some()
     }
 }
@@ -33,27 +33,31 @@ pub struct Verifier<C, S, V> {
     options: Options,
     id: Arc<AtomicUsize>,
     transaction_to_replace: Option<(S, Arc<V>)>,
+    now: u64,
 }
 
 impl<C, S, V> Verifier<C, S, V> {
-    /// Creates new transaction verfier with specified options.
+    /// Creates new transaction verfier with specified options,
+    /// `now` is the local timestamp in seconds the verified txns enter the pool.
     pub fn new(
         client: C,
         options: Options,
         id: Arc<AtomicUsize>,
         transaction_to_replace: Option<(S, Arc<V>)>,
+        now: u64,
     ) -> Self {
         Verifier {
             client,
             options,
             id,
             transaction_to_replace,
+            now,
         }
     }
 }
 
 impl<C: Client> tx_pool::Verifier<PoolTransaction>
-    for Verifier<C, scoring::SeqNumberAndGasPrice, VerifiedTransaction>
+    for Verifier<C, scoring::SeqNumberAndPolicy, VerifiedTransaction>
 {
     type Error = transaction::TransactionError;
     type VerifiedTransaction = VerifiedTransaction;
@@ -108,6 +112,7 @@ impl<C: Client> tx_pool::Verifier<PoolTransaction>
             sender,
             priority,
             insertion_id: self.id.fetch_add(1, std::sync::atomic::Ordering::AcqRel),
+            imported_at: self.now,
         })
     }
 }
@@ -2,17 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::pool::AccountSeqNumberClient;
-use crate::TxStatus;
+use crate::{TxPoolService, TxStatus};
 use anyhow::Result;
 use crypto::keygen::KeyGen;
 use network_api::messages::{PeerTransactionsMessage, TransactionsMessage};
 use network_api::PeerId;
 use parking_lot::RwLock;
-use starcoin_config::{MetricsConfig, NodeConfig};
+use starcoin_config::{MetricsConfig, NodeConfig, TxPoolOrdering};
 use starcoin_executor::{
     create_signed_txn_with_association_account, encode_transfer_script_function,
     DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
 };
+use starcoin_genesis::Genesis;
 use starcoin_open_block::OpenedBlock;
 use starcoin_state_api::ChainStateWriter;
 use starcoin_statedb::ChainStateDB;
@@ -295,15 +296,154 @@ async fn test_txpool_actor_service() {
 }
 
 fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
+    generate_txn_with_gas_price(config, seq, 1)
+}
+
+fn generate_txn_with_gas_price(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+) -> SignedUserTransaction {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let txn = create_signed_txn_with_association_account(
         TransactionPayload::ScriptFunction(encode_transfer_script_function(account_address, 10000)),
         seq,
         DEFAULT_MAX_GAS_AMOUNT,
-        1,
+        gas_price,
         2,
         config.net(),
     );
     txn
 }
+
+#[stest::test]
+async fn test_txpool_journal() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_journal(true);
+    let config = Arc::new(config);
+    let (storage, _chain_info, _) = Genesis::init_storage_for_test(config.net())?;
+    let start_pool = || -> Result<TxPoolService> {
+        let main = storage.get_startup_info()?.unwrap().main;
+        let header = storage.get_block_header_by_hash(main)?.unwrap();
+        Ok(TxPoolService::new(
+            config.clone(),
+            storage.clone(),
+            header,
+            None,
+        ))
+    };
+
+    let pool = start_pool()?;
+    let local_txn = generate_txn(config.clone(), 0);
+    let peer_txn = generate_txn(config.clone(), 1);
+    pool.add_local_txns(vec![local_txn.clone()])
+        .pop()
+        .unwrap()?;
+    pool.add_txns(vec![peer_txn]).pop().unwrap()?;
+    assert_eq!(pool.get_pending_txns(None, Some(0)).len(), 2);
+    assert_eq!(pool.get_inner().journal().unwrap().len(), 1);
+    drop(pool);
+
+    // only the local txn is restored after restart.
+    let pool = start_pool()?;
+    let pending = pool.get_pending_txns(None, Some(0));
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id(), local_txn.id());
+
+    // the txn which is no longer in the pool is pruned from journal.
+    pool.remove_txn(local_txn.id(), false).unwrap();
+    pool.chain_new_block(vec![], vec![])?;
+    assert!(pool.get_inner().journal().unwrap().is_empty());
+    drop(pool);
+
+    let pool = start_pool()?;
+    assert!(pool.get_pending_txns(None, Some(0)).is_empty());
+    Ok(())
+}
+
+fn start_pool_with_config(config: Arc<NodeConfig>) -> Result<TxPoolService> {
+    let (storage, _chain_info, _) = Genesis::init_storage_for_test(config.net())?;
+    let main = storage.get_startup_info()?.unwrap().main;
+    let header = storage.get_block_header_by_hash(main)?.unwrap();
+    Ok(TxPoolService::new(config, storage, header, None))
+}
+
+#[stest::test]
+async fn test_txpool_replace_and_quota() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_price_bump_percent(50);
+    config
+        .tx_pool
+        .set_sender_quotas(vec![(account_config::association_address(), 2)]);
+    let config = Arc::new(config);
+    let pool = start_pool_with_config(config.clone())?;
+
+    pool.add_txns(vec![generate_txn_with_gas_price(config.clone(), 0, 10)])
+        .pop()
+        .unwrap()?;
+    // the gas price is not bumped enough to replace the pooled txn.
+    let cheap = generate_txn_with_gas_price(config.clone(), 0, 14);
+    assert!(pool.add_txns(vec![cheap]).pop().unwrap().is_err());
+    let bumped = generate_txn_with_gas_price(config.clone(), 0, 15);
+    pool.add_txns(vec![bumped.clone()]).pop().unwrap()?;
+    pool.add_txns(vec![generate_txn_with_gas_price(config.clone(), 1, 10)])
+        .pop()
+        .unwrap()?;
+    // the quota of the sender is reached.
+    let over_quota = generate_txn_with_gas_price(config.clone(), 2, 10);
+    assert!(pool.add_txns(vec![over_quota]).pop().unwrap().is_err());
+
+    assert_eq!(pool.find_txn(&bumped.id()), Some(bumped));
+    let status = pool.status();
+    assert_eq!(status.txn_count, 2);
+    assert_eq!(status.ordering, "gas_price");
+    assert_eq!(status.rejected.get("too_cheap_to_replace"), Some(&1));
+    assert_eq!(status.rejected.get("limit_reached"), Some(&1));
+    assert_eq!(status.evicted.get("replaced"), Some(&1));
+    Ok(())
+}
+
+#[stest::test]
+async fn test_txpool_suggested_gas_price() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let pool = start_pool_with_config(config.clone())?;
+    // an empty pool suggests the min gas price.
+    assert_eq!(
+        pool.suggested_gas_price(Some(0)),
+        config.tx_pool.min_gas_price()
+    );
+
+    let txns = vec![1, 10, 20, 30, 40]
+        .into_iter()
+        .enumerate()
+        .map(|(seq, gas_price)| generate_txn_with_gas_price(config.clone(), seq as u64, gas_price))
+        .collect::<Vec<_>>();
+    for result in pool.add_txns(txns) {
+        result?;
+    }
+    assert_eq!(pool.suggested_gas_price(Some(0)), 20);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_txpool_fifo_and_expiry() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_ordering(TxPoolOrdering::Fifo);
+    config.tx_pool.set_txn_expiry(0);
+    let config = Arc::new(config);
+    let pool = start_pool_with_config(config.clone())?;
+
+    pool.add_txns(vec![generate_txn(config.clone(), 0)])
+        .pop()
+        .unwrap()?;
+    assert_eq!(pool.status().ordering, "fifo");
+    assert_eq!(pool.get_pending_txns(None, Some(0)).len(), 1);
+
+    // the txn is evicted when the pool is culled on new block.
+    pool.chain_new_block(vec![], vec![])?;
+    let status = pool.status();
+    assert_eq!(status.txn_count, 0);
+    assert_eq!(status.evicted.get("expired"), Some(&1));
+    Ok(())
+}
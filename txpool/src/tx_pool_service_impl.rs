@@ -4,12 +4,13 @@
 use crate::{
     pool,
     pool::{
-        PendingOrdering, PendingSettings, PoolTransaction, PrioritizationStrategy, Status,
-        TxStatus, UnverifiedUserTransaction, VerifiedTransaction,
+        policy, PendingOrdering, PendingSettings, PoolTransaction, SenderLimits, Status, TxStatus,
+        UnverifiedUserTransaction, VerifiedTransaction,
     },
     pool_client::{NonceCache, PoolClient},
 };
 
+use crate::journal::TxnJournal;
 use crate::metrics::TxPoolMetrics;
 use crate::pool::{Client, TransactionQueue};
 use anyhow::Result;
@@ -19,7 +20,9 @@ use parking_lot::RwLock;
 use starcoin_config::NodeConfig;
 use starcoin_executor::VMMetrics;
 use starcoin_statedb::ChainStateDB;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{
+    suggest_gas_price, TxPoolStatus, TxPoolSyncService, GAS_PRICE_SAMPLE_SIZE,
+};
 use std::sync::Arc;
 use storage::Store;
 use types::{
@@ -57,9 +60,24 @@ impl TxPoolService {
                 max_per_sender: pool_config.max_per_sender() as usize,
             },
             verifier_options,
-            PrioritizationStrategy::GasPriceOnly,
+            policy::new_policy(pool_config.ordering(), pool_config.priority_senders()),
+            pool_config.price_bump_percent(),
+            SenderLimits::from_config(pool_config),
+            node_config.net().time_service(),
         );
         let queue = Arc::new(queue);
+        let journal = if pool_config.journal() {
+            let journal_path = pool_config.journal_path();
+            match TxnJournal::open(journal_path.as_path()) {
+                Ok(journal) => Some(journal),
+                Err(e) => {
+                    error!("Open txpool journal {:?} failed: {:?}", journal_path, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
         let inner = Inner {
             node_config,
             queue,
@@ -68,7 +86,9 @@ impl TxPoolService {
             sequence_number_cache: NonceCache::new(128),
             metrics,
             vm_metrics,
+            journal,
         };
+        inner.restore_journal();
 
         Self { inner }
     }
@@ -110,6 +130,19 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.import_txns(txns)
     }
 
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let _timer = self.inner.metrics.as_ref().map(|metrics| {
+            metrics
+                .txpool_service_time
+                .with_label_values(&["add_local_txns"])
+                .start_timer()
+        });
+        self.inner.import_local_txns(txns)
+    }
+
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction> {
         let _timer = self.inner.metrics.as_ref().map(|metrics| {
             metrics
@@ -191,6 +224,18 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.queue.status().into()
     }
 
+    fn suggested_gas_price(&self, current_timestamp_secs: Option<u64>) -> u64 {
+        let current_timestamp_secs = current_timestamp_secs
+            .unwrap_or_else(|| self.inner.node_config.net().time_service().now_secs());
+        let gas_prices = self
+            .inner
+            .get_pending(GAS_PRICE_SAMPLE_SIZE, current_timestamp_secs)
+            .into_iter()
+            .map(|t| t.signed().gas_unit_price())
+            .collect();
+        suggest_gas_price(gas_prices, self.inner.node_config.tx_pool.min_gas_price())
+    }
+
     fn find_txn(&self, hash: &HashValue) -> Option<SignedUserTransaction> {
         self.inner
             .queue
@@ -221,6 +266,7 @@ pub(crate) struct Inner {
     sequence_number_cache: NonceCache,
     pub(crate) metrics: Option<TxPoolMetrics>,
     vm_metrics: Option<VMMetrics>,
+    journal: Option<TxnJournal>,
 }
 impl std::fmt::Debug for Inner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -273,6 +319,73 @@ impl Inner {
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
         self.queue.import(self.get_pool_client(), txns)
     }
+
+    /// Import txns submitted by local users, the accepted txns are recorded in the journal if enabled.
+    pub(crate) fn import_local_txns(
+        &self,
+        txns: Vec<transaction::SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let journal = match self.journal.as_ref() {
+            Some(journal) => journal,
+            None => return self.import_txns(txns),
+        };
+        let results = self.import_txns(txns.clone());
+        let accepted = txns
+            .into_iter()
+            .zip(results.iter())
+            .filter(|(_, result)| result.is_ok())
+            .map(|(txn, _)| txn)
+            .collect();
+        if let Err(e) = journal.insert(accepted) {
+            error!("Write txpool journal failed: {:?}", e);
+        }
+        results
+    }
+
+    /// Re-import the journaled txns into the pool after restart.
+    /// The txns are verified against the current chain state, invalid ones are pruned.
+    pub(crate) fn restore_journal(&self) {
+        let journal = match self.journal.as_ref() {
+            Some(journal) => journal,
+            None => return,
+        };
+        let txns = journal.txns();
+        if txns.is_empty() {
+            return;
+        }
+        let total = txns.len();
+        for result in self.import_txns(txns) {
+            if let Err(err) = result {
+                debug!("Restore journal transaction fail: {}", err);
+            }
+        }
+        let pruned = self.prune_journal();
+        info!(
+            "Restore {} txns from txpool journal {:?}, pruned {}",
+            total - pruned,
+            journal.path(),
+            pruned
+        );
+    }
+
+    /// Remove the txns which are no longer in the pool(included in block, culled or removed) from journal.
+    pub(crate) fn prune_journal(&self) -> usize {
+        match self.journal.as_ref() {
+            Some(journal) => match journal.retain(|hash, _| self.queue.find(hash).is_some()) {
+                Ok(pruned) => pruned,
+                Err(e) => {
+                    error!("Prune txpool journal failed: {:?}", e);
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    pub(crate) fn journal(&self) -> Option<&TxnJournal> {
+        self.journal.as_ref()
+    }
+
     pub(crate) fn remove_txn(
         &self,
         txn_hash: HashValue,
@@ -352,6 +465,9 @@ impl Inner {
                 debug!("retracted transaction fail: {}", err);
             }
         }
+
+        // prune the included or outdated txns from journal.
+        self.prune_journal();
     }
 
     fn get_pool_client(&self) -> PoolClient {
@@ -189,7 +189,8 @@ impl BlockHeader {
     }
 
     // the author_auth_key field is deprecated, but keep this fn for compat with old block.
-    fn new_with_auth_key(
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_auth_key(
         parent_hash: HashValue,
         timestamp: u64,
         number: BlockNumber,
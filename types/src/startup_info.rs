@@ -234,3 +234,102 @@ impl TryInto<Vec<u8>> for SnapshotRange {
         self.encode()
     }
 }
+
+/// The kind of a node downloaded by fast sync.
+#[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Copy, Debug)]
+pub enum FastSyncNodeKind {
+    BlockAccumulator,
+    TxnAccumulator,
+    /// A node of the global state tree, the leaves are account states.
+    AccountState,
+    /// A node of an account's resource tree.
+    ResourceState,
+    /// A node of an account's code tree.
+    CodeState,
+}
+
+/// The progress of fast sync, saved after every batch of nodes, so the sync can resume after restart.
+#[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug)]
+pub struct FastSyncProgress {
+    /// the pivot block which fast sync download state and accumulators at.
+    pivot: HashValue,
+    pivot_number: BlockNumber,
+    /// the nodes wait to download, children of a node are pushed after the node is saved.
+    /// all nodes are downloaded if it is empty.
+    pending_nodes: Vec<(HashValue, FastSyncNodeKind)>,
+    synced_nodes: u64,
+}
+
+impl fmt::Display for FastSyncProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FastSyncProgress pivot: {}({}), synced nodes: {}, pending nodes: {}",
+            self.pivot,
+            self.pivot_number,
+            self.synced_nodes,
+            self.pending_nodes.len()
+        )?;
+        Ok(())
+    }
+}
+
+impl FastSyncProgress {
+    pub fn new(
+        pivot: HashValue,
+        pivot_number: BlockNumber,
+        pending_nodes: Vec<(HashValue, FastSyncNodeKind)>,
+    ) -> Self {
+        Self {
+            pivot,
+            pivot_number,
+            pending_nodes,
+            synced_nodes: 0,
+        }
+    }
+
+    pub fn pivot(&self) -> HashValue {
+        self.pivot
+    }
+
+    pub fn pivot_number(&self) -> BlockNumber {
+        self.pivot_number
+    }
+
+    pub fn pending_nodes(&self) -> &[(HashValue, FastSyncNodeKind)] {
+        self.pending_nodes.as_slice()
+    }
+
+    pub fn synced_nodes(&self) -> u64 {
+        self.synced_nodes
+    }
+
+    pub fn is_nodes_synced(&self) -> bool {
+        self.pending_nodes.is_empty()
+    }
+
+    pub fn update_pending_nodes(
+        &mut self,
+        pending_nodes: Vec<(HashValue, FastSyncNodeKind)>,
+        new_synced_nodes: u64,
+    ) {
+        self.pending_nodes = pending_nodes;
+        self.synced_nodes = self.synced_nodes.saturating_add(new_synced_nodes);
+    }
+}
+
+impl TryFrom<Vec<u8>> for FastSyncProgress {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        FastSyncProgress::decode(value.as_slice())
+    }
+}
+
+impl TryInto<Vec<u8>> for FastSyncProgress {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+}
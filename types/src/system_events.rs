@@ -16,6 +16,22 @@ pub struct NewHeadBlock(pub Arc<ExecutedBlock>);
 #[derive(Clone, Debug)]
 pub struct NewBranch(pub Arc<ExecutedBlock>);
 
+/// Fire this event when the main chain switch to another branch,
+/// before the `NewHeadBlock` event of the new head.
+#[derive(Clone, Debug)]
+pub struct ChainReorgEvent {
+    /// The common ancestor of the old and the new main chain.
+    pub common_ancestor: HashValue,
+    /// Blocks removed from the main chain, in ascending order of block number.
+    pub retracted: Arc<[Block]>,
+    /// Blocks added to the main chain, in ascending order of block number, the last one is the new head.
+    /// It is empty if the new head is the common ancestor, such as the chain is reset to an old block.
+    pub enacted: Arc<[Block]>,
+    /// Whether `retracted` or `enacted` only contains the blocks near the heads,
+    /// because the reorg is too deep or the blocks are absent from storage.
+    pub truncated: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct MinedBlock(pub Arc<Block>);
 
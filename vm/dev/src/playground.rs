@@ -1,22 +1,29 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_abi_types::TypeInstantiation;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
-use starcoin_state_api::StateNodeStore;
+use starcoin_state_api::{ChainStateWriter, StateNodeStore, StateReaderExt};
 use starcoin_statedb::ChainStateDB;
+use starcoin_vm_runtime::execution_trace::ExecutionTrace;
 use starcoin_vm_runtime::metrics::VMMetrics;
+use starcoin_vm_runtime::profiler::ExecutionProfile;
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
 use starcoin_vm_types::state_view::StateView;
-use starcoin_vm_types::transaction::{DryRunTransaction, TransactionOutput};
+use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, RawUserTransaction, SignedUserTransaction, Transaction, TransactionOutput,
+    TransactionStatus,
+};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
 use starcoin_vm_types::vm_status::VMStatus;
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -40,7 +47,7 @@ impl PlaygroudService {
         state_root: HashValue,
         txn: DryRunTransaction,
     ) -> Result<(VMStatus, TransactionOutput)> {
-        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        let state_view = ChainStateDB::try_new(self.state.clone(), state_root)?;
         dry_run(&state_view, txn, self.metrics.clone())
     }
 
@@ -52,7 +59,7 @@ impl PlaygroudService {
         type_args: Vec<TypeTag>,
         args: Vec<TransactionArgument>,
     ) -> Result<Vec<AnnotatedMoveValue>> {
-        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        let state_view = ChainStateDB::try_new(self.state.clone(), state_root)?;
         let rets = call_contract(
             &state_view,
             module_id,
@@ -72,7 +79,7 @@ impl PlaygroudService {
         struct_tag: &StructTag,
         data: &[u8],
     ) -> Result<AnnotatedMoveStruct> {
-        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        let state_view = ChainStateDB::try_new(self.state.clone(), state_root)?;
         view_resource(&state_view, struct_tag.clone(), data)
     }
 }
@@ -96,6 +103,168 @@ pub fn dry_run(
     vm.dry_run_transaction(state_view, txn)
 }
 
+/// Dry run the txn, and return the execution trace.
+pub fn trace_dry_run(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+    metrics: Option<VMMetrics>,
+) -> Result<(VMStatus, TransactionOutput, ExecutionTrace)> {
+    let mut vm = StarcoinVM::new(metrics);
+    vm.trace_dry_run_transaction(state_view, txn)
+}
+
+/// Dry run the txn, and return the gas and time profile.
+pub fn profile_dry_run(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+    metrics: Option<VMMetrics>,
+) -> Result<(VMStatus, TransactionOutput, ExecutionProfile)> {
+    let mut vm = StarcoinVM::new(metrics);
+    vm.profile_dry_run_transaction(state_view, txn)
+}
+
+/// Replay the `txns` of a block before `txn_index` on the parent state of the block,
+/// then trace the user txn at `txn_index`.
+/// The `parent_state` is changed in memory by the replay, but never flushed to the storage.
+pub fn replay_and_trace(
+    parent_state: &ChainStateDB,
+    txns: Vec<Transaction>,
+    txn_index: usize,
+    metrics: Option<VMMetrics>,
+) -> Result<(VMStatus, TransactionOutput, ExecutionTrace)> {
+    let txn = replay_before(parent_state, txns, txn_index, metrics.clone())?;
+    let mut vm = StarcoinVM::new(metrics);
+    vm.trace_user_transaction(parent_state, txn)
+}
+
+/// Replay the `txns` of a block before `txn_index` like `replay_and_trace`,
+/// then profile the user txn at `txn_index`.
+pub fn replay_and_profile(
+    parent_state: &ChainStateDB,
+    txns: Vec<Transaction>,
+    txn_index: usize,
+    metrics: Option<VMMetrics>,
+) -> Result<(VMStatus, TransactionOutput, ExecutionProfile)> {
+    let txn = replay_before(parent_state, txns, txn_index, metrics.clone())?;
+    let mut vm = StarcoinVM::new(metrics);
+    vm.profile_user_transaction(parent_state, txn)
+}
+
+/// Replay the `txns` before `txn_index` on the `parent_state`, return the user txn at `txn_index`.
+fn replay_before(
+    parent_state: &ChainStateDB,
+    mut txns: Vec<Transaction>,
+    txn_index: usize,
+    metrics: Option<VMMetrics>,
+) -> Result<SignedUserTransaction> {
+    if txn_index >= txns.len() {
+        return Err(format_err!(
+            "Transaction index {} out of range, the block has {} transactions",
+            txn_index,
+            txns.len()
+        ));
+    }
+    let txn = match txns.split_off(txn_index).remove(0) {
+        Transaction::UserTransaction(txn) => txn,
+        _ => return Err(format_err!("Only user transaction can be traced")),
+    };
+    let outputs = StarcoinVM::new(metrics).execute_block_transactions(parent_state, txns, None)?;
+    for (_, output) in outputs {
+        if let TransactionStatus::Keep(_) = output.status() {
+            parent_state.apply_write_set(output.write_set().clone())?;
+            parent_state.commit()?;
+        }
+    }
+    Ok(txn)
+}
+
+/// Estimate the smallest max gas amount the txn can be executed with, by dry running the txn.
+/// The max gas amount of the txn is ignored, the estimation is bounded by the
+/// `maximum_number_of_gas_units` of the gas schedule and the gas the sender can afford.
+/// Return the estimated max gas amount with the status and output of the dry run with it,
+/// if the txn can not be executed, return the upper bound with the failed status and output.
+pub fn estimate_gas(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+    metrics: Option<VMMetrics>,
+) -> Result<(u64, VMStatus, TransactionOutput)> {
+    let mut vm = StarcoinVM::new(metrics);
+    vm.load_configs(state_view)?;
+    let max_number_of_gas_units = vm
+        .get_gas_schedule()
+        .map_err(|e| format_err!("Failed to load gas schedule: {:?}", e))?
+        .gas_constants
+        .maximum_number_of_gas_units
+        .get();
+    let DryRunTransaction {
+        raw_txn,
+        public_key,
+    } = txn;
+    let gas_unit_price = raw_txn.gas_unit_price();
+    let upper_bound = if gas_unit_price == 0 {
+        max_number_of_gas_units
+    } else {
+        let balance = state_view
+            .get_balance_by_token_code(
+                raw_txn.sender(),
+                TokenCode::from_str(raw_txn.gas_token_code().as_str())?,
+            )?
+            .unwrap_or_default();
+        std::cmp::min(
+            max_number_of_gas_units as u128,
+            balance / gas_unit_price as u128,
+        ) as u64
+    };
+
+    let mut dry_run_with_max_gas = |max_gas_amount: u64| {
+        let raw_txn = RawUserTransaction::new(
+            raw_txn.sender(),
+            raw_txn.sequence_number(),
+            raw_txn.payload().clone(),
+            max_gas_amount,
+            gas_unit_price,
+            raw_txn.expiration_timestamp_secs(),
+            raw_txn.chain_id(),
+            raw_txn.gas_token_code(),
+        );
+        vm.dry_run_transaction(
+            state_view,
+            DryRunTransaction {
+                raw_txn,
+                public_key: public_key.clone(),
+            },
+        )
+        .map(|(status, output)| (max_gas_amount, status, output))
+    };
+
+    // The whole txn is executed on every try, so the gas of side effects such as
+    // creating the receiver account or accepting the token is included.
+    let mut succeeded = dry_run_with_max_gas(upper_bound)?;
+    if !matches!(succeeded.1, VMStatus::Executed) {
+        return Ok(succeeded);
+    }
+    let gas_used = succeeded.2.gas_used();
+    if gas_used >= upper_bound {
+        return Ok(succeeded);
+    }
+    // Most txns can be executed with the gas used, try it first.
+    let first_try = dry_run_with_max_gas(gas_used)?;
+    if matches!(first_try.1, VMStatus::Executed) {
+        return Ok(first_try);
+    }
+    let mut failed_max_gas = gas_used;
+    while succeeded.0 - failed_max_gas > 1 {
+        let max_gas_amount = failed_max_gas + (succeeded.0 - failed_max_gas) / 2;
+        let result = dry_run_with_max_gas(max_gas_amount)?;
+        if matches!(result.1, VMStatus::Executed) {
+            succeeded = result;
+        } else {
+            failed_max_gas = max_gas_amount;
+        }
+    }
+    Ok(succeeded)
+}
+
 pub fn call_contract(
     state_view: &dyn StateView,
     module_id: ModuleId,
@@ -105,8 +274,53 @@ pub fn call_contract(
     metrics: Option<VMMetrics>,
 ) -> Result<Vec<(TypeTag, Vec<u8>)>> {
     let function_name = IdentStr::new(func)?;
+    let ret_tys = check_contract_call(state_view, &module_id, function_name, &type_args, &args)?;
+
+    let mut vm = StarcoinVM::new(metrics);
+    let rets = vm.execute_readonly_function(
+        state_view,
+        &module_id,
+        function_name,
+        type_args,
+        convert_txn_args(&args),
+    )?;
+    zip_return_values(ret_tys, rets)
+}
+
+/// Call the contract like `call_contract`, and return the gas and time profile.
+pub fn profile_call_contract(
+    state_view: &dyn StateView,
+    module_id: ModuleId,
+    func: &str,
+    type_args: Vec<TypeTag>,
+    args: Vec<TransactionArgument>,
+    metrics: Option<VMMetrics>,
+) -> Result<(Vec<(TypeTag, Vec<u8>)>, ExecutionProfile)> {
+    let function_name = IdentStr::new(func)?;
+    let ret_tys = check_contract_call(state_view, &module_id, function_name, &type_args, &args)?;
+
+    let mut vm = StarcoinVM::new(metrics);
+    let (rets, profile) = vm.profile_readonly_function(
+        state_view,
+        &module_id,
+        function_name,
+        type_args,
+        convert_txn_args(&args),
+    )?;
+    Ok((zip_return_values(ret_tys, rets)?, profile))
+}
+
+/// Check the type args and args of the contract call by the function abi,
+/// return the types of the return values.
+fn check_contract_call(
+    state_view: &dyn StateView,
+    module_id: &ModuleId,
+    function_name: &IdentStr,
+    type_args: &[TypeTag],
+    args: &[TransactionArgument],
+) -> Result<Vec<TypeTag>> {
     let abi_resolver = ABIResolver::new(state_view);
-    let func_abi = abi_resolver.resolve_function(&module_id, function_name)?;
+    let func_abi = abi_resolver.resolve_function(module_id, function_name)?;
 
     // validate params
     {
@@ -126,7 +340,6 @@ pub fn call_contract(
     }
 
     let ty_tags_abi = type_args
-        .as_slice()
         .iter()
         .map(|t| abi_resolver.resolve_type_tag(t))
         .collect::<Result<Vec<_>>>()?;
@@ -134,7 +347,7 @@ pub fn call_contract(
 
     // after instantiate the function, we check the arg types.
     {
-        for (i, (abi, v)) in func_instantiation.args().iter().zip(&args).enumerate() {
+        for (i, (abi, v)) in func_instantiation.args().iter().zip(args).enumerate() {
             match (abi.type_abi(), &v) {
                 (TypeInstantiation::U8, TransactionArgument::U8(_))
                 | (TypeInstantiation::U64, TransactionArgument::U64(_))
@@ -156,20 +369,14 @@ pub fn call_contract(
         }
     }
 
-    let mut vm = StarcoinVM::new(metrics);
-    let rets = vm.execute_readonly_function(
-        state_view,
-        &module_id,
-        function_name,
-        type_args,
-        convert_txn_args(&args),
-    )?;
-
-    let ret_tys = func_instantiation
+    func_instantiation
         .returns()
         .iter()
         .map(|r| r.type_tag())
-        .collect::<Result<Vec<_>>>()?;
+        .collect::<Result<Vec<_>>>()
+}
+
+fn zip_return_values(ret_tys: Vec<TypeTag>, rets: Vec<Vec<u8>>) -> Result<Vec<(TypeTag, Vec<u8>)>> {
     anyhow::ensure!(
         ret_tys.len() == rets.len(),
         "length of return values mismatch, expect: {}, got: {}",
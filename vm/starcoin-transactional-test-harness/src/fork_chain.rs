@@ -0,0 +1,667 @@
+use crate::fork_state::ForkChainStateService;
+use anyhow::{bail, format_err, Result};
+use futures_channel::mpsc;
+use log::warn;
+use parking_lot::{Mutex, RwLock};
+use starcoin_accumulator::tree_store::mock::MockAccumulatorStore;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_chain_api::{ChainAsyncService, TransactionInfoWithProof};
+use starcoin_crypto::HashValue;
+use starcoin_executor::{block_execute, validate_transaction};
+use starcoin_state_api::{ChainStateWriter, StateReaderExt};
+use starcoin_state_tree::StateNodeStore;
+use starcoin_statedb::ChainStateDB;
+use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{
+    Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber,
+};
+use starcoin_types::block_metadata::BlockMetadata;
+use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
+use starcoin_types::error::BlockExecutorError;
+use starcoin_types::filter::Filter;
+use starcoin_types::genesis_config::ChainId;
+use starcoin_types::startup_info::{ChainStatus, StartupInfo};
+use starcoin_types::time::TimeService;
+use starcoin_types::transaction::{
+    CallError, RichTransactionInfo, SignedUserTransaction, Transaction, TransactionError,
+};
+use starcoin_types::vm_error::{StatusCode, VMStatus};
+use std::cmp::max;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// A chain which continues a remote chain from the fork block in memory.
+///
+/// Every submitted transaction is executed on the forked state at once and sealed in a new block,
+/// the blocks before the fork block are not available locally, only the header and block info
+/// of the fork block are kept.
+pub struct ForkBlockChain {
+    chain_id: ChainId,
+    time_service: Arc<dyn TimeService>,
+    state_store: Arc<dyn StateNodeStore>,
+    state_root: Arc<Mutex<HashValue>>,
+    status: ChainStatus,
+    fork_number: BlockNumber,
+    txn_accumulator: MerkleAccumulator,
+    block_accumulator: MerkleAccumulator,
+    headers: HashMap<HashValue, BlockHeader>,
+    blocks: HashMap<HashValue, Block>,
+    block_infos: HashMap<HashValue, BlockInfo>,
+    number_to_hash: BTreeMap<BlockNumber, HashValue>,
+    txns: HashMap<HashValue, Transaction>,
+    txn_infos: HashMap<HashValue, RichTransactionInfo>,
+    txn_infos_by_global_index: BTreeMap<u64, HashValue>,
+    block_txns: HashMap<HashValue, Vec<HashValue>>,
+    events: HashMap<HashValue, Vec<ContractEvent>>,
+}
+
+impl ForkBlockChain {
+    pub fn new(
+        chain_id: ChainId,
+        time_service: Arc<dyn TimeService>,
+        state_store: Arc<dyn StateNodeStore>,
+        fork_header: BlockHeader,
+        fork_block_info: BlockInfo,
+    ) -> Result<Self> {
+        if fork_header.id() != fork_block_info.block_id {
+            bail!(
+                "fork block info {} does not belong to the fork block {}",
+                fork_block_info.block_id,
+                fork_header.id()
+            );
+        }
+        let accumulator_store = Arc::new(MockAccumulatorStore::new());
+        let txn_accumulator = MerkleAccumulator::new_with_info(
+            fork_block_info.txn_accumulator_info.clone(),
+            accumulator_store.clone(),
+        );
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            fork_block_info.block_accumulator_info.clone(),
+            accumulator_store,
+        );
+        let fork_id = fork_header.id();
+        let fork_number = fork_header.number();
+        let mut chain = Self {
+            chain_id,
+            time_service,
+            state_store,
+            state_root: Arc::new(Mutex::new(fork_header.state_root())),
+            status: ChainStatus::new(fork_header.clone(), fork_block_info.clone()),
+            fork_number,
+            txn_accumulator,
+            block_accumulator,
+            headers: HashMap::new(),
+            blocks: HashMap::new(),
+            block_infos: HashMap::new(),
+            number_to_hash: BTreeMap::new(),
+            txns: HashMap::new(),
+            txn_infos: HashMap::new(),
+            txn_infos_by_global_index: BTreeMap::new(),
+            block_txns: HashMap::new(),
+            events: HashMap::new(),
+        };
+        chain.headers.insert(fork_id, fork_header);
+        chain.block_infos.insert(fork_id, fork_block_info);
+        chain.number_to_hash.insert(fork_number, fork_id);
+        Ok(chain)
+    }
+
+    pub fn fork_number(&self) -> BlockNumber {
+        self.fork_number
+    }
+
+    pub fn status(&self) -> ChainStatus {
+        self.status.clone()
+    }
+
+    pub fn state_service(&self) -> ForkChainStateService {
+        ForkChainStateService::new(self.state_store.clone(), self.state_root.clone())
+    }
+
+    fn head_state(&self) -> ChainStateDB {
+        ChainStateDB::new(
+            self.state_store.clone(),
+            Some(self.status.head.state_root()),
+        )
+    }
+
+    /// Validate the txn on the head state, like the txpool does.
+    pub fn validate_transaction(
+        &self,
+        txn: &SignedUserTransaction,
+    ) -> Result<(), TransactionError> {
+        if self.txns.contains_key(&txn.id()) {
+            return Err(TransactionError::AlreadyImported);
+        }
+        match validate_transaction(&self.head_state(), txn.clone(), None) {
+            None => Ok(()),
+            Some(status) => Err(TransactionError::CallErr(CallError::ExecutionError(status))),
+        }
+    }
+
+    /// Execute the txn on the head state and seal it in a new block.
+    pub fn apply_transaction(&mut self, txn: SignedUserTransaction) -> Result<Block> {
+        let parent = self.status.head.clone();
+        let parent_info = self.status.info.clone();
+        let number = parent.number() + 1;
+        let timestamp = max(
+            self.time_service.now_millis(),
+            parent.timestamp().saturating_add(1),
+        );
+        let block_metadata = BlockMetadata::new(
+            parent.id(),
+            timestamp,
+            parent.author(),
+            None,
+            0,
+            number,
+            self.chain_id,
+            parent.gas_used(),
+        );
+        let transactions = vec![
+            Transaction::BlockMetadata(block_metadata),
+            Transaction::UserTransaction(txn.clone()),
+        ];
+
+        let state = self.head_state();
+        let block_gas_limit = state.get_epoch()?.block_gas_limit();
+        let executed_data = block_execute(&state, transactions.clone(), block_gas_limit, None)?;
+        if executed_data.txn_infos.len() != transactions.len() {
+            bail!("txn {} is not executed in the block", txn.id());
+        }
+        state.flush()?;
+
+        let transaction_global_index = self.txn_accumulator.num_leaves();
+        let txn_info_ids: Vec<_> = executed_data
+            .txn_infos
+            .iter()
+            .map(|info| info.id())
+            .collect();
+        let txn_accumulator_root = self.txn_accumulator.append(&txn_info_ids)?;
+        let gas_used = executed_data
+            .txn_infos
+            .iter()
+            .fold(0u64, |acc, i| acc.saturating_add(i.gas_used()));
+        let body = BlockBody::new(vec![txn], None);
+        let header = BlockHeader::new(
+            parent.id(),
+            timestamp,
+            number,
+            parent.author(),
+            txn_accumulator_root,
+            parent_info.block_accumulator_info.accumulator_root,
+            executed_data.state_root,
+            gas_used,
+            parent.difficulty(),
+            body.hash(),
+            self.chain_id,
+            0,
+            BlockHeaderExtra::new([0u8; 4]),
+        );
+        let block = Block::new(header.clone(), body);
+        let block_id = block.id();
+        self.block_accumulator.append(&[block_id])?;
+        let block_info = BlockInfo::new(
+            block_id,
+            parent_info.total_difficulty + header.difficulty(),
+            self.txn_accumulator.get_info(),
+            self.block_accumulator.get_info(),
+        );
+
+        let mut txn_hashes = vec![];
+        for (idx, ((transaction, txn_info), events)) in transactions
+            .into_iter()
+            .zip(executed_data.txn_infos)
+            .zip(executed_data.txn_events)
+            .enumerate()
+        {
+            let txn_hash = transaction.id();
+            let global_index = transaction_global_index + idx as u64;
+            self.txn_infos.insert(
+                txn_hash,
+                RichTransactionInfo::new(block_id, number, txn_info, idx as u32, global_index),
+            );
+            self.txn_infos_by_global_index
+                .insert(global_index, txn_hash);
+            self.txns.insert(txn_hash, transaction);
+            self.events.insert(txn_hash, events);
+            txn_hashes.push(txn_hash);
+        }
+        self.block_txns.insert(block_id, txn_hashes);
+        self.headers.insert(block_id, header.clone());
+        self.blocks.insert(block_id, block.clone());
+        self.block_infos.insert(block_id, block_info.clone());
+        self.number_to_hash.insert(number, block_id);
+        *self.state_root.lock() = header.state_root();
+        self.status = ChainStatus::new(header, block_info);
+        Ok(block)
+    }
+
+    pub fn get_header_by_hash(&self, hash: &HashValue) -> Option<BlockHeader> {
+        self.headers.get(hash).cloned()
+    }
+
+    pub fn get_block_by_hash(&self, hash: &HashValue) -> Option<Block> {
+        self.blocks.get(hash).cloned()
+    }
+
+    pub fn get_block_info_by_hash(&self, hash: &HashValue) -> Option<BlockInfo> {
+        self.block_infos.get(hash).cloned()
+    }
+
+    pub fn get_hash_by_number(&self, number: BlockNumber) -> Option<HashValue> {
+        self.number_to_hash.get(&number).cloned()
+    }
+
+    pub fn get_transaction(&self, txn_hash: &HashValue) -> Option<Transaction> {
+        self.txns.get(txn_hash).cloned()
+    }
+
+    pub fn get_transaction_info(&self, txn_hash: &HashValue) -> Option<RichTransactionInfo> {
+        self.txn_infos.get(txn_hash).cloned()
+    }
+
+    pub fn get_block_txn_infos(&self, block_id: &HashValue) -> Vec<RichTransactionInfo> {
+        self.block_txns
+            .get(block_id)
+            .map(|txn_hashes| {
+                txn_hashes
+                    .iter()
+                    .filter_map(|txn_hash| self.get_transaction_info(txn_hash))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_events_by_txn_hash(&self, txn_hash: &HashValue) -> Vec<ContractEventInfo> {
+        match (self.txn_infos.get(txn_hash), self.events.get(txn_hash)) {
+            (Some(txn_info), Some(events)) => events
+                .iter()
+                .enumerate()
+                .map(|(idx, event)| ContractEventInfo {
+                    block_hash: txn_info.block_id(),
+                    block_number: txn_info.block_number,
+                    transaction_hash: *txn_hash,
+                    transaction_index: txn_info.transaction_index,
+                    transaction_global_index: txn_info.transaction_global_index,
+                    event_index: idx as u32,
+                    event: event.clone(),
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// All the events emitted in the block.
+    pub fn get_block_events(&self, block_id: &HashValue) -> Vec<ContractEventInfo> {
+        self.block_txns
+            .get(block_id)
+            .map(|txn_hashes| {
+                txn_hashes
+                    .iter()
+                    .flat_map(|txn_hash| self.get_events_by_txn_hash(txn_hash))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn main_blocks_by_number(&self, number: Option<BlockNumber>, count: u64) -> Vec<Block> {
+        let end = number.unwrap_or_else(|| self.status.head.number());
+        self.number_to_hash
+            .range(..=end)
+            .rev()
+            .filter_map(|(_, id)| self.get_block_by_hash(id))
+            .take(count as usize)
+            .collect()
+    }
+
+    pub fn get_block_ids(
+        &self,
+        start_number: BlockNumber,
+        reverse: bool,
+        max_size: u64,
+    ) -> Vec<HashValue> {
+        if reverse {
+            self.number_to_hash
+                .range(..=start_number)
+                .rev()
+                .take(max_size as usize)
+                .map(|(_, id)| *id)
+                .collect()
+        } else {
+            self.number_to_hash
+                .range(start_number..)
+                .take(max_size as usize)
+                .map(|(_, id)| *id)
+                .collect()
+        }
+    }
+
+    pub fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Vec<RichTransactionInfo> {
+        let txn_hashes: Vec<_> = if reverse {
+            self.txn_infos_by_global_index
+                .range(..=start_index)
+                .rev()
+                .take(max_size as usize)
+                .map(|(_, txn_hash)| *txn_hash)
+                .collect()
+        } else {
+            self.txn_infos_by_global_index
+                .range(start_index..)
+                .take(max_size as usize)
+                .map(|(_, txn_hash)| *txn_hash)
+                .collect()
+        };
+        txn_hashes
+            .iter()
+            .filter_map(|txn_hash| self.get_transaction_info(txn_hash))
+            .collect()
+    }
+
+    pub fn filter_events(&self, filter: Filter) -> Vec<ContractEventInfo> {
+        let mut event_infos: Vec<_> = self
+            .number_to_hash
+            .range(filter.from_block..=filter.to_block)
+            .flat_map(|(_, block_id)| self.get_block_events(block_id))
+            .filter(|info| filter.matching(info.block_number, &info.event))
+            .collect();
+        if filter.reverse {
+            event_infos.reverse();
+        }
+        if let Some(limit) = filter.limit {
+            event_infos.truncate(limit);
+        }
+        event_infos
+    }
+}
+
+/// `ChainAsyncService` on the forked chain.
+#[derive(Clone)]
+pub struct ForkChainService {
+    chain: Arc<RwLock<ForkBlockChain>>,
+}
+
+impl ForkChainService {
+    pub fn new(chain: Arc<RwLock<ForkBlockChain>>) -> Self {
+        Self { chain }
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainAsyncService for ForkChainService {
+    async fn get_header_by_hash(&self, hash: &HashValue) -> Result<Option<BlockHeader>> {
+        Ok(self.chain.read().get_header_by_hash(hash))
+    }
+
+    async fn get_block_by_hash(&self, hash: HashValue) -> Result<Option<Block>> {
+        Ok(self.chain.read().get_block_by_hash(&hash))
+    }
+
+    async fn get_blocks(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<Block>>> {
+        let chain = self.chain.read();
+        Ok(hashes
+            .iter()
+            .map(|hash| chain.get_block_by_hash(hash))
+            .collect())
+    }
+
+    async fn get_headers(&self, hashes: Vec<HashValue>) -> Result<Vec<BlockHeader>> {
+        let chain = self.chain.read();
+        Ok(hashes
+            .iter()
+            .filter_map(|hash| chain.get_header_by_hash(hash))
+            .collect())
+    }
+
+    async fn get_block_info_by_hash(&self, hash: &HashValue) -> Result<Option<BlockInfo>> {
+        Ok(self.chain.read().get_block_info_by_hash(hash))
+    }
+
+    async fn get_block_info_by_number(&self, number: u64) -> Result<Option<BlockInfo>> {
+        let chain = self.chain.read();
+        Ok(chain
+            .get_hash_by_number(number)
+            .and_then(|id| chain.get_block_info_by_hash(&id)))
+    }
+
+    async fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<Transaction>> {
+        Ok(self.chain.read().get_transaction(&txn_hash))
+    }
+
+    async fn get_transaction_info(
+        &self,
+        txn_hash: HashValue,
+    ) -> Result<Option<RichTransactionInfo>> {
+        Ok(self.chain.read().get_transaction_info(&txn_hash))
+    }
+
+    async fn get_transaction_block(&self, txn_hash: HashValue) -> Result<Option<Block>> {
+        let chain = self.chain.read();
+        Ok(chain
+            .get_transaction_info(&txn_hash)
+            .and_then(|info| chain.get_block_by_hash(&info.block_id())))
+    }
+
+    async fn get_block_txn_infos(&self, block_hash: HashValue) -> Result<Vec<RichTransactionInfo>> {
+        Ok(self.chain.read().get_block_txn_infos(&block_hash))
+    }
+
+    async fn get_txn_info_by_block_and_index(
+        &self,
+        block_hash: HashValue,
+        idx: u64,
+    ) -> Result<Option<RichTransactionInfo>> {
+        Ok(self
+            .chain
+            .read()
+            .get_block_txn_infos(&block_hash)
+            .into_iter()
+            .nth(idx as usize))
+    }
+
+    async fn get_events_by_txn_hash(&self, txn_hash: HashValue) -> Result<Vec<ContractEventInfo>> {
+        Ok(self.chain.read().get_events_by_txn_hash(&txn_hash))
+    }
+
+    async fn main_head_header(&self) -> Result<BlockHeader> {
+        Ok(self.chain.read().status().head)
+    }
+
+    async fn main_head_block(&self) -> Result<Block> {
+        let chain = self.chain.read();
+        let head_id = chain.status().head.id();
+        chain.get_block_by_hash(&head_id).ok_or_else(|| {
+            format_err!(
+                "the fork block {} is not kept locally, query it on the forked node",
+                head_id
+            )
+        })
+    }
+
+    async fn main_block_by_number(&self, number: BlockNumber) -> Result<Option<Block>> {
+        let chain = self.chain.read();
+        Ok(chain
+            .get_hash_by_number(number)
+            .and_then(|id| chain.get_block_by_hash(&id)))
+    }
+
+    async fn main_blocks_by_number(
+        &self,
+        number: Option<BlockNumber>,
+        count: u64,
+    ) -> Result<Vec<Block>> {
+        Ok(self.chain.read().main_blocks_by_number(number, count))
+    }
+
+    async fn main_block_header_by_number(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<BlockHeader>> {
+        let chain = self.chain.read();
+        Ok(chain
+            .get_hash_by_number(number)
+            .and_then(|id| chain.get_header_by_hash(&id)))
+    }
+
+    async fn main_startup_info(&self) -> Result<StartupInfo> {
+        Ok(StartupInfo::new(self.chain.read().status().head.id()))
+    }
+
+    async fn main_status(&self) -> Result<ChainStatus> {
+        Ok(self.chain.read().status())
+    }
+
+    async fn main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
+        Ok(self.chain.read().filter_events(filter))
+    }
+
+    async fn get_block_ids(
+        &self,
+        start_number: BlockNumber,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<HashValue>> {
+        Ok(self
+            .chain
+            .read()
+            .get_block_ids(start_number, reverse, max_size))
+    }
+
+    async fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<RichTransactionInfo>> {
+        Ok(self
+            .chain
+            .read()
+            .get_transaction_infos(start_index, reverse, max_size))
+    }
+
+    async fn get_transaction_proof(
+        &self,
+        _block_id: HashValue,
+        _transaction_global_index: u64,
+        _event_index: Option<u64>,
+        _access_path: Option<AccessPath>,
+    ) -> Result<Option<TransactionInfoWithProof>> {
+        bail!("transaction proof is not supported by the forked chain")
+    }
+
+    async fn get_block_infos(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<BlockInfo>>> {
+        let chain = self.chain.read();
+        Ok(hashes
+            .iter()
+            .map(|hash| chain.get_block_info_by_hash(hash))
+            .collect())
+    }
+}
+
+/// Called with every block sealed by the forked chain.
+pub type NewBlockListener = Arc<dyn Fn(&Block, Vec<ContractEventInfo>) + Send + Sync>;
+
+/// The txpool of the forked chain, it has no pending txns:
+/// a txn is validated and then sealed in a new block as soon as it is added.
+#[derive(Clone)]
+pub struct ForkTxPoolService {
+    chain: Arc<RwLock<ForkBlockChain>>,
+    listener: Option<NewBlockListener>,
+}
+
+impl ForkTxPoolService {
+    pub fn new(chain: Arc<RwLock<ForkBlockChain>>, listener: Option<NewBlockListener>) -> Self {
+        Self { chain, listener }
+    }
+
+    fn add_txn(&self, txn: SignedUserTransaction) -> Result<(), TransactionError> {
+        let mut chain = self.chain.write();
+        chain.validate_transaction(&txn)?;
+        let txn_hash = txn.id();
+        let block = chain.apply_transaction(txn).map_err(|e| {
+            warn!("Fork chain fail to apply txn {}: {:?}", txn_hash, e);
+            let status_code = match e.downcast_ref::<BlockExecutorError>() {
+                Some(BlockExecutorError::BlockTransactionDiscard(status_code, _)) => *status_code,
+                _ => StatusCode::UNKNOWN_STATUS,
+            };
+            TransactionError::CallErr(CallError::ExecutionError(VMStatus::Error(status_code)))
+        })?;
+        let events = chain.get_block_events(&block.id());
+        drop(chain);
+        if let Some(listener) = &self.listener {
+            listener(&block, events);
+        }
+        Ok(())
+    }
+}
+
+impl TxPoolSyncService for ForkTxPoolService {
+    fn add_txns(&self, txns: Vec<SignedUserTransaction>) -> Vec<Result<(), TransactionError>> {
+        txns.into_iter().map(|txn| self.add_txn(txn)).collect()
+    }
+
+    fn remove_txn(&self, _txn_hash: HashValue, _is_invalid: bool) -> Option<SignedUserTransaction> {
+        None
+    }
+
+    fn get_pending_txns(
+        &self,
+        _max_len: Option<u64>,
+        _now: Option<u64>,
+    ) -> Vec<SignedUserTransaction> {
+        vec![]
+    }
+
+    fn next_sequence_number(&self, _address: AccountAddress) -> Option<u64> {
+        None
+    }
+
+    fn subscribe_txns(&self) -> mpsc::UnboundedReceiver<TxnStatusFullEvent> {
+        mpsc::unbounded().1
+    }
+
+    fn subscribe_pending_txn(&self) -> mpsc::UnboundedReceiver<Arc<[HashValue]>> {
+        mpsc::unbounded().1
+    }
+
+    fn chain_new_block(&self, _enacted: Vec<Block>, _retracted: Vec<Block>) -> Result<()> {
+        Ok(())
+    }
+
+    fn status(&self) -> TxPoolStatus {
+        TxPoolStatus {
+            txn_count: 0,
+            txn_max_count: 0,
+            mem: 0,
+            mem_max: 0,
+            senders: 0,
+            is_full: false,
+            ordering: String::new(),
+            rejected: Default::default(),
+            evicted: Default::default(),
+        }
+    }
+
+    fn suggested_gas_price(&self, _now: Option<u64>) -> u64 {
+        1
+    }
+
+    fn find_txn(&self, _hash: &HashValue) -> Option<SignedUserTransaction> {
+        None
+    }
+
+    fn txns_of_sender(
+        &self,
+        _sender: &AccountAddress,
+        _max_len: Option<usize>,
+    ) -> Vec<SignedUserTransaction> {
+        vec![]
+    }
+}
@@ -0,0 +1,382 @@
+use crate::fork_chain::{ForkBlockChain, ForkChainService, ForkTxPoolService};
+use crate::fork_state::{ForkStateNodeStore, RemoteRpcClient};
+use anyhow::{anyhow, format_err, Result};
+use futures::future::TryFutureExt;
+use jsonrpc_pubsub::typed::{Sink, Subscriber};
+use jsonrpc_pubsub::SubscriptionId;
+use log::{error, info};
+use parking_lot::RwLock;
+use starcoin_account_service::AccountService;
+use starcoin_config::{Api, NodeConfig, StarcoinOpt};
+use starcoin_crypto::HashValue;
+use starcoin_dev::playground::PlaygroudService;
+use starcoin_rpc_api::chain::ChainApi;
+use starcoin_rpc_api::contract_api::ContractApi;
+use starcoin_rpc_api::metadata::Metadata;
+use starcoin_rpc_api::node::{NodeApi, NodeInfo};
+use starcoin_rpc_api::pubsub::StarcoinPubSub;
+use starcoin_rpc_api::state::StateApi;
+use starcoin_rpc_api::txpool::TxPoolApi;
+use starcoin_rpc_api::types::pubsub;
+use starcoin_rpc_api::types::{
+    BlockView, PeerInfoView, TransactionEventResponse, TransactionEventView,
+};
+use starcoin_rpc_api::{errors, FutureResult};
+use starcoin_rpc_server::api_registry::ApiRegistry;
+use starcoin_rpc_server::module::{ChainRpcImpl, ContractRpcImpl, StateRpcImpl, TxPoolRpcImpl};
+use starcoin_rpc_server::service::RpcService;
+use starcoin_service_registry::ServiceRef;
+use starcoin_state_tree::StateNodeStore;
+use starcoin_types::block::{Block, BlockHeader, BlockInfo, BlockNumber};
+use starcoin_types::contract_event::ContractEventInfo;
+use starcoin_types::filter::Filter;
+use starcoin_types::startup_info::ChainInfo;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// `NodeApi` of the fork node, which has no network.
+pub struct ForkNodeRpcImpl {
+    config: Arc<NodeConfig>,
+    genesis_hash: HashValue,
+    chain: Arc<RwLock<ForkBlockChain>>,
+}
+
+impl ForkNodeRpcImpl {
+    pub fn new(
+        config: Arc<NodeConfig>,
+        genesis_hash: HashValue,
+        chain: Arc<RwLock<ForkBlockChain>>,
+    ) -> Self {
+        Self {
+            config,
+            genesis_hash,
+            chain,
+        }
+    }
+}
+
+impl NodeApi for ForkNodeRpcImpl {
+    fn status(&self) -> jsonrpc_core::Result<bool> {
+        Ok(true)
+    }
+
+    fn info(&self) -> FutureResult<NodeInfo> {
+        let net = self.config.net().clone();
+        let chain_info = ChainInfo::new(
+            net.chain_id(),
+            self.genesis_hash,
+            self.chain.read().status(),
+        );
+        let peer_info = PeerInfoView {
+            peer_id: self.config.network.self_peer_id(),
+            chain_info: chain_info.into(),
+            notif_protocols: String::new(),
+            rpc_protocols: String::new(),
+        };
+        let node_info = NodeInfo::new(
+            peer_info,
+            self.config.network.self_address().to_string(),
+            net.id().clone(),
+            net.genesis_config().consensus(),
+            net.time_service().now_secs(),
+        );
+        Box::pin(futures::future::ok(node_info))
+    }
+
+    fn peers(&self) -> FutureResult<Vec<PeerInfoView>> {
+        Box::pin(futures::future::ok(vec![]))
+    }
+
+    fn metrics(&self) -> jsonrpc_core::Result<HashMap<String, String>> {
+        Ok(HashMap::new())
+    }
+}
+
+#[derive(Default)]
+struct ForkPubSubInner {
+    next_id: AtomicU64,
+    new_heads: RwLock<HashMap<SubscriptionId, Sink<pubsub::Result>>>,
+    events: RwLock<HashMap<SubscriptionId, (Filter, Sink<pubsub::Result>)>>,
+}
+
+/// The pubsub of the fork node, only `newHeads` and `events` subscriptions are supported,
+/// and the event data is never decoded.
+#[derive(Clone, Default)]
+pub struct ForkPubSub {
+    inner: Arc<ForkPubSubInner>,
+}
+
+impl ForkPubSub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_id(&self) -> SubscriptionId {
+        SubscriptionId::Number(self.inner.next_id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Notify the subscribers of the new block sealed by the fork chain.
+    pub fn notify_new_block(&self, block: &Block, events: Vec<ContractEventInfo>) {
+        let block_view = match BlockView::try_from_block(block.clone(), true) {
+            Ok(view) => view,
+            Err(e) => {
+                error!("Convert block {} to view failed: {:?}", block.id(), e);
+                return;
+            }
+        };
+        self.inner.new_heads.write().retain(|_, sink| {
+            sink.notify(Ok(pubsub::Result::Block(Box::new(block_view.clone()))))
+                .is_ok()
+        });
+        self.inner.events.write().retain(|_, (filter, sink)| {
+            events
+                .iter()
+                .filter(|info| filter.matching(info.block_number, &info.event))
+                .all(|info| {
+                    sink.notify(Ok(pubsub::Result::Event(Box::new(
+                        TransactionEventResponse {
+                            decode_event_data: None,
+                            event: TransactionEventView::from(info.clone()),
+                            removed: false,
+                        },
+                    ))))
+                    .is_ok()
+                })
+        });
+    }
+}
+
+impl StarcoinPubSub for ForkPubSub {
+    type Metadata = Metadata;
+
+    fn subscribe(
+        &self,
+        _meta: Metadata,
+        subscriber: Subscriber<pubsub::Result>,
+        kind: pubsub::Kind,
+        params: Option<pubsub::Params>,
+    ) {
+        let id = self.next_id();
+        match (kind, params) {
+            (pubsub::Kind::NewHeads, None) => {
+                if let Ok(sink) = subscriber.assign_id(id.clone()) {
+                    self.inner.new_heads.write().insert(id, sink);
+                }
+            }
+            (pubsub::Kind::NewHeads, _) => {
+                let _ = subscriber.reject(errors::invalid_params(
+                    "newHeads",
+                    "Expected no parameters.",
+                ));
+            }
+            (pubsub::Kind::Events, Some(pubsub::Params::Events(param))) => {
+                match param.filter.try_into() {
+                    Ok(filter) => {
+                        if let Ok(sink) = subscriber.assign_id(id.clone()) {
+                            self.inner.events.write().insert(id, (filter, sink));
+                        }
+                    }
+                    Err(e) => {
+                        let _ = subscriber.reject(e);
+                    }
+                }
+            }
+            (pubsub::Kind::Events, _) => {
+                let _ = subscriber.reject(errors::invalid_params(
+                    "events",
+                    "Expected a filter object.",
+                ));
+            }
+            (kind, _) => {
+                let _ = subscriber.reject(jsonrpc_core::Error::invalid_params(format!(
+                    "subscription {:?} is not supported by the fork node",
+                    kind
+                )));
+            }
+        }
+    }
+
+    fn unsubscribe(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool> {
+        let removed_head = self.inner.new_heads.write().remove(&id).is_some();
+        let removed_events = self.inner.events.write().remove(&id).is_some();
+        Ok(removed_head || removed_events)
+    }
+}
+
+/// A local node which forks a remote chain at a block.
+///
+/// The state is read from the remote node lazily and cached locally, txns submitted to the
+/// node are executed on the forked state and sealed in new blocks at once.
+/// It serves the node, chain, state, txpool, contract and pubsub rpc apis.
+pub struct ForkNode {
+    config: Arc<NodeConfig>,
+    chain: Arc<RwLock<ForkBlockChain>>,
+    rpc_service: RpcService,
+    _rt: Arc<Runtime>,
+}
+
+impl ForkNode {
+    /// Fork the chain of the node at `rpc_url` at `block_number`, default is the head block.
+    /// The network of `opt` is overridden by the remote network.
+    pub fn launch(
+        rpc_url: &str,
+        block_number: Option<BlockNumber>,
+        opt: &StarcoinOpt,
+    ) -> Result<Self> {
+        let rt = Arc::new(
+            tokio::runtime::Builder::new_multi_thread()
+                .thread_name("fork-remote-worker")
+                .enable_all()
+                .build()?,
+        );
+        let remote = rt.block_on(RemoteRpcClient::connect(rpc_url))?;
+        let (net, genesis_hash, fork_header, fork_block_info) =
+            rt.block_on(Self::fetch_fork_point(&remote, block_number))?;
+
+        let mut opt = opt.clone();
+        opt.net = Some(net);
+        let config = Arc::new(NodeConfig::load_with_opt(&opt)?);
+        info!(
+            "Fork chain {} at block {}({}) from {}",
+            config.net().id(),
+            fork_header.number(),
+            fork_header.id(),
+            rpc_url
+        );
+
+        let state_store: Arc<dyn StateNodeStore> = Arc::new(ForkStateNodeStore::new(
+            remote.state_client.clone(),
+            fork_header.state_root(),
+            rt.clone(),
+        )?);
+        let chain = Arc::new(RwLock::new(ForkBlockChain::new(
+            config.net().chain_id(),
+            config.net().time_service(),
+            state_store.clone(),
+            fork_header,
+            fork_block_info,
+        )?));
+        let chain_service = ForkChainService::new(chain.clone());
+        let state_service = chain.read().state_service();
+        let pubsub = ForkPubSub::new();
+        let listener = {
+            let pubsub = pubsub.clone();
+            Arc::new(move |block: &Block, events: Vec<ContractEventInfo>| {
+                pubsub.notify_new_block(block, events)
+            })
+        };
+        let txpool_service = ForkTxPoolService::new(chain.clone(), Some(listener));
+
+        let mut api_registry = ApiRegistry::new(config.rpc.api_quotas.clone(), None);
+        api_registry.register(
+            Api::Node,
+            NodeApi::to_delegate(ForkNodeRpcImpl::new(
+                config.clone(),
+                genesis_hash,
+                chain.clone(),
+            )),
+        );
+        api_registry.register(
+            Api::Chain,
+            ChainApi::to_delegate(ChainRpcImpl::new(
+                config.clone(),
+                genesis_hash,
+                state_store.clone(),
+                chain_service.clone(),
+            )),
+        );
+        api_registry.register(
+            Api::TxPool,
+            TxPoolApi::to_delegate(TxPoolRpcImpl::new(txpool_service.clone())),
+        );
+        api_registry.register(
+            Api::State,
+            StateApi::to_delegate(StateRpcImpl::new(
+                state_service.clone(),
+                chain_service.clone(),
+                state_store.clone(),
+            )),
+        );
+        api_registry.register(
+            Api::Contract,
+            ContractApi::to_delegate(ContractRpcImpl::new(
+                config.clone(),
+                None::<ServiceRef<AccountService>>,
+                txpool_service,
+                state_service,
+                chain_service,
+                PlaygroudService::new(state_store.clone(), None),
+                state_store,
+            )),
+        );
+        api_registry.register(Api::PubSub, StarcoinPubSub::to_delegate(pubsub));
+
+        let mut rpc_service = RpcService::new(config.clone(), api_registry);
+        rpc_service.start()?;
+        Ok(Self {
+            config,
+            chain,
+            rpc_service,
+            _rt: rt,
+        })
+    }
+
+    async fn fetch_fork_point(
+        remote: &RemoteRpcClient,
+        block_number: Option<BlockNumber>,
+    ) -> Result<(
+        starcoin_config::ChainNetworkID,
+        HashValue,
+        BlockHeader,
+        BlockInfo,
+    )> {
+        let node_info = remote
+            .node_client
+            .info()
+            .map_err(|e| anyhow!(format!("{}", e)))
+            .await?;
+        let chain_info = remote
+            .chain_client
+            .info()
+            .map_err(|e| anyhow!(format!("{}", e)))
+            .await?;
+        let (header, block_info) = match block_number {
+            None => (chain_info.head.into(), chain_info.block_info.into()),
+            Some(number) => {
+                let block = remote
+                    .chain_client
+                    .get_block_by_number(number, None)
+                    .map_err(|e| anyhow!(format!("{}", e)))
+                    .await?
+                    .ok_or_else(|| format_err!("cannot find block of height {}", number))?;
+                let block_info = remote
+                    .chain_client
+                    .get_block_info_by_number(number)
+                    .map_err(|e| anyhow!(format!("{}", e)))
+                    .await?
+                    .ok_or_else(|| format_err!("cannot find block info of height {}", number))?;
+                (block.header.into(), block_info.into())
+            }
+        };
+        Ok((node_info.net, chain_info.genesis_hash, header, block_info))
+    }
+
+    pub fn config(&self) -> Arc<NodeConfig> {
+        self.config.clone()
+    }
+
+    pub fn chain(&self) -> Arc<RwLock<ForkBlockChain>> {
+        self.chain.clone()
+    }
+
+    pub fn stop(mut self) {
+        self.rpc_service.close();
+    }
+}
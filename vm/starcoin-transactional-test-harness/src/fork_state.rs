@@ -0,0 +1,269 @@
+use anyhow::{anyhow, ensure, Result};
+use bcs_ext::BCSCodec;
+use forkable_jellyfish_merkle::node_type::Node;
+use jsonrpc_client_transports::RpcChannel;
+use parking_lot::{Mutex, RwLock};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::chain::ChainApiClient;
+use starcoin_rpc_api::node::NodeApiClient;
+use starcoin_rpc_api::state::{ListCodeOption, ListResourceOption, StateApiClient};
+use starcoin_state_api::{ChainStateAsyncService, ChainStateReader, StateView, StateWithProof};
+use starcoin_state_tree::mock::MockStateNodeStore;
+use starcoin_state_tree::{StateNode, StateNodeStore, StateTree};
+use starcoin_statedb::ChainStateDB;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::AccountResource;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::language_storage::StructTag;
+use starcoin_types::state_set::AccountStateSet;
+use starcoin_vm_types::access_path::ModuleName;
+use starcoin_vm_types::move_resource::MoveResource;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// The rpc clients of the remote node which the local chain is forked from.
+#[derive(Clone)]
+pub struct RemoteRpcClient {
+    pub node_client: NodeApiClient,
+    pub chain_client: ChainApiClient,
+    pub state_client: StateApiClient,
+}
+
+impl RemoteRpcClient {
+    /// Connect to the remote node, `rpc_url` can be a http or websocket url.
+    pub async fn connect(rpc_url: &str) -> Result<Self> {
+        let rpc_channel: RpcChannel = if rpc_url.starts_with("ws") {
+            jsonrpc_client_transports::transports::ws::try_connect(rpc_url)
+                .map_err(|e| anyhow!(format!("{}", e)))?
+                .await
+                .map_err(|e| anyhow!(format!("{}", e)))?
+        } else {
+            jsonrpc_client_transports::transports::http::connect(rpc_url)
+                .await
+                .map_err(|e| anyhow!(format!("{}", e)))?
+        };
+        Ok(Self {
+            node_client: rpc_channel.clone().into(),
+            chain_client: rpc_channel.clone().into(),
+            state_client: rpc_channel.into(),
+        })
+    }
+}
+
+/// A `StateNodeStore` which overlays the state of a remote node at the fork block.
+///
+/// Nodes written by the local chain are kept in memory. An account missing in the local
+/// state tree is loaded from the remote node by `state.get_with_proof_by_root`, `state.list_code`
+/// and `state.list_resource` on the fork state root, its account state is verified by the proof,
+/// and its storage trees are rebuilt locally and checked against the verified account state.
+/// So a `ChainStateDB` on this store only pulls the accounts it touches.
+///
+/// The state root of the fork block is served as an empty local tree, every account of the
+/// fork state is read through the remote node until the local chain writes it.
+pub struct ForkStateNodeStore {
+    local: RwLock<HashMap<HashValue, StateNode>>,
+    accounts: RwLock<HashMap<AccountAddress, Option<Vec<u8>>>>,
+    remote: StateApiClient,
+    fork_state_root: HashValue,
+    rt: Arc<Runtime>,
+}
+
+impl ForkStateNodeStore {
+    pub fn new(
+        remote: StateApiClient,
+        fork_state_root: HashValue,
+        rt: Arc<Runtime>,
+    ) -> Result<Self> {
+        let mut local = HashMap::new();
+        local.insert(
+            fork_state_root,
+            StateNode::try_from(Node::<AccountAddress>::new_null())?,
+        );
+        Ok(Self {
+            local: RwLock::new(local),
+            accounts: RwLock::new(HashMap::new()),
+            remote,
+            fork_state_root,
+            rt,
+        })
+    }
+
+    /// Run `f` on the fork runtime and wait for its result.
+    /// The store is called by sync code which may run in a worker of the rpc server runtime,
+    /// so leave the async context of the caller before blocking the thread.
+    fn block_on<F: Future>(&self, f: F) -> F::Output {
+        match tokio::runtime::Handle::try_current() {
+            Ok(_) => tokio::task::block_in_place(|| self.rt.block_on(f)),
+            Err(_) => self.rt.block_on(f),
+        }
+    }
+
+    fn get_remote_account_state(&self, address: AccountAddress) -> Result<Option<AccountState>> {
+        let client = self.remote.clone();
+        let state_root = self.fork_state_root;
+        let access_path = AccessPath::resource_access_path(address, AccountResource::struct_tag());
+        let state_with_proof: StateWithProof = {
+            let access_path = access_path.clone();
+            self.block_on(async move {
+                client
+                    .get_with_proof_by_root(access_path, state_root)
+                    .await
+                    .map_err(|e| anyhow!(format!("{}", e)))
+            })?
+            .into_state_proof()
+        };
+        state_with_proof.verify(state_root, access_path)?;
+        let account_state = match state_with_proof.proof.account_state {
+            Some(blob) => AccountState::try_from(blob.as_ref())?,
+            None => return Ok(None),
+        };
+
+        let client = self.remote.clone();
+        let (codes, resources) = self.block_on(async move {
+            let codes = client
+                .list_code(
+                    address,
+                    Some(ListCodeOption {
+                        state_root: Some(state_root),
+                        ..Default::default()
+                    }),
+                )
+                .await
+                .map_err(|e| anyhow!(format!("{}", e)))?;
+            let resources = client
+                .list_resource(
+                    address,
+                    Some(ListResourceOption {
+                        state_root: Some(state_root),
+                        ..Default::default()
+                    }),
+                )
+                .await
+                .map_err(|e| anyhow!(format!("{}", e)))?;
+            Ok::<_, anyhow::Error>((codes, resources))
+        })?;
+
+        // rebuild the storage trees of the account, and only accept them when the roots
+        // are the same as the verified account state.
+        let store = Arc::new(MockStateNodeStore::new());
+        let code_root = match account_state.code_root() {
+            Some(_) => {
+                let code_tree = StateTree::<ModuleName>::new(store.clone(), None);
+                for (name, code) in codes.codes {
+                    code_tree.put(name, code.code.0);
+                }
+                code_tree.commit()?;
+                code_tree.flush()?;
+                Some(code_tree.root_hash())
+            }
+            None => None,
+        };
+        let resource_tree = StateTree::<StructTag>::new(store.clone(), None);
+        for (struct_tag, resource) in resources.resources {
+            resource_tree.put(struct_tag.0, resource.raw.0);
+        }
+        resource_tree.commit()?;
+        resource_tree.flush()?;
+        ensure!(
+            AccountState::new(code_root, resource_tree.root_hash()) == account_state,
+            "the state of account {} from the remote node does not match its proof",
+            address
+        );
+        self.local.write().extend(store.all_nodes());
+        Ok(Some(account_state))
+    }
+}
+
+impl StateNodeStore for ForkStateNodeStore {
+    fn get(&self, hash: &HashValue) -> Result<Option<StateNode>> {
+        Ok(self.local.read().get(hash).cloned())
+    }
+
+    fn put(&self, key: HashValue, node: StateNode) -> Result<()> {
+        self.local.write().insert(key, node);
+        Ok(())
+    }
+
+    fn write_nodes(&self, nodes: BTreeMap<HashValue, StateNode>) -> Result<()> {
+        self.local.write().extend(nodes);
+        Ok(())
+    }
+
+    fn get_missing_account_state(&self, account_address: &[u8]) -> Result<Option<Vec<u8>>> {
+        let address = AccountAddress::try_from(account_address)?;
+        if let Some(account_state) = self.accounts.read().get(&address) {
+            return Ok(account_state.clone());
+        }
+        let account_state = self
+            .get_remote_account_state(address)?
+            .map(|account_state| account_state.encode())
+            .transpose()?;
+        self.accounts.write().insert(address, account_state.clone());
+        Ok(account_state)
+    }
+}
+
+/// `ChainStateAsyncService` on the head state of the forked chain.
+#[derive(Clone)]
+pub struct ForkChainStateService {
+    store: Arc<dyn StateNodeStore>,
+    state_root: Arc<Mutex<HashValue>>,
+}
+
+impl ForkChainStateService {
+    pub fn new(store: Arc<dyn StateNodeStore>, state_root: Arc<Mutex<HashValue>>) -> Self {
+        Self { store, state_root }
+    }
+
+    fn state_db(&self, state_root: Option<HashValue>) -> ChainStateDB {
+        let state_root = state_root.unwrap_or_else(|| *self.state_root.lock());
+        ChainStateDB::new(self.store.clone(), Some(state_root))
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainStateAsyncService for ForkChainStateService {
+    async fn get(self, access_path: AccessPath) -> Result<Option<Vec<u8>>> {
+        self.state_db(None).get(&access_path)
+    }
+
+    async fn get_with_proof(self, access_path: AccessPath) -> Result<StateWithProof> {
+        self.state_db(None).get_with_proof(&access_path)
+    }
+
+    async fn get_account_state(self, address: AccountAddress) -> Result<Option<AccountState>> {
+        self.state_db(None).get_account_state(&address)
+    }
+
+    async fn get_account_state_set(
+        self,
+        address: AccountAddress,
+        state_root: Option<HashValue>,
+    ) -> Result<Option<AccountStateSet>> {
+        self.state_db(state_root).get_account_state_set(&address)
+    }
+
+    async fn state_root(self) -> Result<HashValue> {
+        Ok(*self.state_root.lock())
+    }
+
+    async fn get_with_proof_by_root(
+        self,
+        access_path: AccessPath,
+        state_root: HashValue,
+    ) -> Result<StateWithProof> {
+        self.state_db(Some(state_root)).get_with_proof(&access_path)
+    }
+
+    async fn get_account_state_by_root(
+        self,
+        address: AccountAddress,
+        state_root: HashValue,
+    ) -> Result<Option<AccountState>> {
+        self.state_db(Some(state_root)).get_account_state(&address)
+    }
+}
@@ -5,6 +5,7 @@ use starcoin_types::state_set::ChainStateSet;
 use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::state_view::StateView;
 use starcoin_vm_types::write_set::{WriteOp, WriteSet, WriteSetMut};
+use std::collections::HashMap;
 
 pub struct InMemoryStateCache<V> {
     data_map: DashMap<AccessPath, Option<Vec<u8>>>,
@@ -37,6 +38,22 @@ impl<V> InMemoryStateCache<V> {
             }
         }
     }
+
+    /// Take a copy of the local updates, which can be restored by `revert`.
+    pub(crate) fn snapshot(&self) -> HashMap<AccessPath, Option<Vec<u8>>> {
+        self.data_map
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// Drop the local updates made after the `snapshot` was taken.
+    pub(crate) fn revert(&self, snapshot: HashMap<AccessPath, Option<Vec<u8>>>) {
+        self.data_map.clear();
+        for (ap, data) in snapshot {
+            self.data_map.insert(ap, data);
+        }
+    }
 }
 
 impl<V> ChainStateWriter for InMemoryStateCache<V> {
@@ -1,6 +1,6 @@
 use crate::in_memory_state_cache::InMemoryStateCache;
 use crate::remote_state::{RemoteStateView, SelectableStateView};
-use anyhow::{bail, Result};
+use anyhow::{bail, format_err, Result};
 use itertools::Itertools;
 use move_binary_format::{file_format::CompiledScript, CompiledModule};
 use move_compiler::compiled_unit::CompiledUnitEnum;
@@ -31,21 +31,21 @@ use starcoin_crypto::{
 use starcoin_dev::playground::call_contract;
 use starcoin_genesis::Genesis;
 use starcoin_rpc_api::types::{
-    ContractCall, FunctionIdView, TransactionArgumentView, TransactionEventView,
-    TransactionOutputAction, TypeTagView,
+    ContractCall, FunctionIdView, StrView, StructTagView, TransactionArgumentView,
+    TransactionEventView, TransactionOutputAction, TypeTagView,
 };
 use starcoin_state_api::{ChainStateWriter, StateReaderExt};
 use starcoin_statedb::ChainStateDB;
-use starcoin_types::account::{Account, AccountData};
+use starcoin_types::account::Account;
 use starcoin_types::{
     access_path::AccessPath,
     account_config::{genesis_address, AccountResource},
     transaction::RawUserTransaction,
 };
+use starcoin_vm_runtime::profiler::{ExecutionProfile, ProfileWeight};
 use starcoin_vm_runtime::{data_cache::RemoteStorage, starcoin_vm::StarcoinVM};
-use starcoin_vm_types::account_config::{
-    association_address, core_code_address, STC_TOKEN_CODE_STR,
-};
+use starcoin_vm_types::account_config::{association_address, core_code_address};
+use starcoin_vm_types::contract_event::ContractEvent;
 
 use clap::Parser;
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
@@ -65,9 +65,17 @@ use starcoin_vm_types::{
     vm_status::KeptVMStatus,
 };
 use std::convert::TryFrom;
-use std::{collections::BTreeMap, convert::TryInto, path::Path, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryInto,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use stdlib::{starcoin_framework_named_addresses, G_PRECOMPILED_STARCOIN_FRAMEWORK};
 
+pub mod fork_chain;
+pub mod fork_node;
+pub mod fork_state;
 mod in_memory_state_cache;
 pub mod remote_state;
 
@@ -155,6 +163,21 @@ pub struct StarcoinRunArgs {
     #[clap(short, long)]
     /// print detailed outputs
     verbose: bool,
+
+    #[clap(long)]
+    /// profile the gas of the txn, and print the gas spent in each module.
+    /// A release build prints the call level profile only, the gas of each function or bytecode
+    /// offset is not measured. The profile is at the bytecode level only if the `MOVE_VM_TRACE`
+    /// env is set in a debug build, the gas and time of the modules are estimates then.
+    profile: bool,
+
+    #[clap(long, parse(from_os_str), requires = "profile")]
+    /// write the profile as collapsed stacks to the file, the input of flamegraph tools.
+    flamegraph: Option<PathBuf>,
+
+    #[clap(long, default_value = "gas", requires = "flamegraph")]
+    /// the weight of the collapsed stacks, gas or time.
+    weight: ProfileWeight,
 }
 
 #[derive(clap::Args, Debug)]
@@ -191,6 +214,63 @@ struct CallSub {
     type_args: Vec<TypeTagView>,
 }
 
+#[derive(clap::Args, Debug)]
+#[clap(name = "set-resource")]
+struct SetResourceSub {
+    #[clap(long="addr", parse(try_from_str=RawAddress::parse))]
+    address: RawAddress,
+    #[clap(long = "resource")]
+    resource: StructTagView,
+    #[clap(long = "value")]
+    /// bcs bytes of the resource value, in hex.
+    value: StrView<Vec<u8>>,
+}
+
+#[derive(clap::Args, Debug)]
+#[clap(name = "warp")]
+struct WarpSub {
+    #[clap(long, required_unless_present("seconds"))]
+    /// the new global time in milliseconds.
+    timestamp: Option<u64>,
+    #[clap(long, conflicts_with("timestamp"))]
+    /// advance the global time by seconds.
+    seconds: Option<u64>,
+}
+
+#[derive(clap::Args, Debug)]
+#[clap(name = "impersonate")]
+struct ImpersonateSub {
+    #[clap(long="addr", parse(try_from_str=RawAddress::parse))]
+    address: RawAddress,
+}
+
+#[derive(clap::Args, Debug)]
+#[clap(name = "snapshot")]
+struct SnapshotSub {
+    #[clap(long)]
+    name: String,
+}
+
+#[derive(clap::Args, Debug)]
+#[clap(name = "revert")]
+struct RevertSub {
+    #[clap(long)]
+    name: String,
+}
+
+#[derive(clap::Args, Debug)]
+#[clap(name = "assert-events")]
+struct AssertEventsSub {
+    #[clap(long = "type-tag")]
+    type_tag: Option<TypeTagView>,
+    #[clap(long = "data")]
+    /// bcs bytes of the event data, in hex.
+    data: Option<StrView<Vec<u8>>>,
+    #[clap(long)]
+    /// the exact number of the matched events, default is at least one.
+    count: Option<usize>,
+}
+
 #[derive(Parser, Debug)]
 pub enum StarcoinSubcommands {
     #[clap(name = "faucet")]
@@ -223,6 +303,52 @@ pub enum StarcoinSubcommands {
         #[clap(long = "type-args", short = 't')]
         type_args: Vec<TypeTagView>,
     },
+    /// Overwrite the resource at the address with the bcs value.
+    #[clap(name = "set-resource")]
+    SetResource {
+        #[clap(long="addr", parse(try_from_str=RawAddress::parse))]
+        address: RawAddress,
+        #[clap(long = "resource")]
+        resource: StructTagView,
+        #[clap(long = "value")]
+        value: StrView<Vec<u8>>,
+    },
+    /// Change the global time without a new block.
+    #[clap(name = "warp")]
+    Warp {
+        #[clap(long, required_unless_present("seconds"))]
+        timestamp: Option<u64>,
+        #[clap(long, conflicts_with("timestamp"))]
+        seconds: Option<u64>,
+    },
+    /// Run the txns of the address without its key.
+    #[clap(name = "impersonate")]
+    Impersonate {
+        #[clap(long="addr", parse(try_from_str=RawAddress::parse))]
+        address: RawAddress,
+    },
+    /// Save the current state by name.
+    #[clap(name = "snapshot")]
+    Snapshot {
+        #[clap(long)]
+        name: String,
+    },
+    /// Restore the state saved by `snapshot`.
+    #[clap(name = "revert")]
+    Revert {
+        #[clap(long)]
+        name: String,
+    },
+    /// Assert on the events emitted by the last txn.
+    #[clap(name = "assert-events")]
+    AssertEvents {
+        #[clap(long = "type-tag")]
+        type_tag: Option<TypeTagView>,
+        #[clap(long = "data")]
+        data: Option<StrView<Vec<u8>>>,
+        #[clap(long)]
+        count: Option<usize>,
+    },
 }
 
 impl From<FaucetSub> for StarcoinSubcommands {
@@ -256,13 +382,76 @@ impl From<CallSub> for StarcoinSubcommands {
     }
 }
 
+impl From<SetResourceSub> for StarcoinSubcommands {
+    fn from(sub: SetResourceSub) -> Self {
+        Self::SetResource {
+            address: sub.address,
+            resource: sub.resource,
+            value: sub.value,
+        }
+    }
+}
+
+impl From<WarpSub> for StarcoinSubcommands {
+    fn from(sub: WarpSub) -> Self {
+        Self::Warp {
+            timestamp: sub.timestamp,
+            seconds: sub.seconds,
+        }
+    }
+}
+
+impl From<ImpersonateSub> for StarcoinSubcommands {
+    fn from(sub: ImpersonateSub) -> Self {
+        Self::Impersonate {
+            address: sub.address,
+        }
+    }
+}
+
+impl From<SnapshotSub> for StarcoinSubcommands {
+    fn from(sub: SnapshotSub) -> Self {
+        Self::Snapshot { name: sub.name }
+    }
+}
+
+impl From<RevertSub> for StarcoinSubcommands {
+    fn from(sub: RevertSub) -> Self {
+        Self::Revert { name: sub.name }
+    }
+}
+
+impl From<AssertEventsSub> for StarcoinSubcommands {
+    fn from(sub: AssertEventsSub) -> Self {
+        Self::AssertEvents {
+            type_tag: sub.type_tag,
+            data: sub.data,
+            count: sub.count,
+        }
+    }
+}
+
 impl clap::Args for StarcoinSubcommands {
     fn augment_args(cmd: clap::Command<'_>) -> clap::Command<'_> {
         let faucet = FaucetSub::augment_args(clap::Command::new("faucet"));
         let block = BlockSub::augment_args(clap::Command::new("block"));
         let call = CallSub::augment_args(clap::Command::new("call"));
-
-        cmd.subcommand(faucet).subcommand(block).subcommand(call)
+        let set_resource = SetResourceSub::augment_args(clap::Command::new("set-resource"));
+        let warp = WarpSub::augment_args(clap::Command::new("warp"));
+        let impersonate = ImpersonateSub::augment_args(clap::Command::new("impersonate"));
+        let snapshot = SnapshotSub::augment_args(clap::Command::new("snapshot"));
+        let revert = RevertSub::augment_args(clap::Command::new("revert"));
+        let assert_events = AssertEventsSub::augment_args(clap::Command::new("assert-events"));
+
+        cmd.subcommand(faucet)
+            .subcommand(block)
+            .subcommand(call)
+            .subcommand(set_resource)
+            .subcommand(warp)
+            .subcommand(impersonate)
+            .subcommand(snapshot)
+            .subcommand(revert)
+            .subcommand(assert_events)
     }
 
     fn augment_args_for_update(_cmd: clap::Command<'_>) -> clap::Command<'_> {
@@ -278,6 +467,17 @@ struct TransactionResult {
     write_set: Option<Vec<TransactionOutputAction>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     events: Option<Vec<TransactionEventView>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<Vec<ModuleGasProfile>>,
+}
+
+/// The gas spent in a module, the time is not included to keep the output deterministic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModuleGasProfile {
+    module: String,
+    functions: usize,
+    instructions: u64,
+    gas: u64,
 }
 
 pub struct StarcoinTestAdapter<'a> {
@@ -286,6 +486,18 @@ pub struct StarcoinTestAdapter<'a> {
     default_syntax: SyntaxChoice,
     public_key_mapping: BTreeMap<Identifier, AccountPublicKey>,
     association_public_key: AccountPublicKey,
+    /// public keys of the anonymous addresses impersonated by `impersonate`.
+    impersonated_keys: BTreeMap<AccountAddress, AccountPublicKey>,
+    snapshots: BTreeMap<String, StateSnapshot>,
+    /// events emitted by the last txn, checked by `assert-events`.
+    last_events: Vec<ContractEvent>,
+}
+
+/// A state saved by the `snapshot` subcommand.
+#[derive(Clone)]
+enum StateSnapshot {
+    StateRoot(HashValue),
+    Cache(HashMap<AccessPath, Option<Vec<u8>>>),
 }
 
 /// Parameters *required* to create a Starcoin transaction.
@@ -350,24 +562,23 @@ impl<'a> StarcoinTestAdapter<'a> {
         Ok(bcs::from_bytes(&balance_blob).unwrap())
     }
 
+    /// Replace the auth key of the account with the genesis key,
+    /// and return the public key which txns of the account can be run with.
+    /// Other fields of the account resource, and other resources of the account are kept.
+    fn impersonate_account(&self, addr: &AccountAddress) -> Result<AccountPublicKey> {
+        let account_resource = self.fetch_account_resource(addr)?;
+        let public_key = Account::new_genesis_account(*addr).public_key();
+        let account_resource =
+            account_resource.with_authentication_key(public_key.authentication_key().to_vec());
+        self.storage.set(
+            &AccessPath::resource_access_path(*addr, AccountResource::struct_tag()),
+            bcs_ext::to_bytes(&account_resource)?,
+        )?;
+        Ok(public_key)
+    }
+
     fn hack_genesis_account(&self) -> Result<()> {
-        let genesis_account = self.fetch_account_resource(&genesis_address())?;
-
-        let balance =
-            self.fetch_balance_resource(&genesis_address(), STC_TOKEN_CODE_STR.to_string())?;
-        let genesis_account_data = AccountData::with_account_and_event_counts(
-            Account::new_genesis_account(genesis_address()),
-            balance.token(),
-            STC_TOKEN_CODE_STR,
-            genesis_account.sequence_number(),
-            genesis_account.withdraw_events().count(),
-            genesis_account.deposit_events().count(),
-            genesis_account.accept_token_events().count(),
-            genesis_account.has_delegated_key_rotation_capability(),
-            genesis_account.has_delegated_withdrawal_capability(),
-        );
-        self.storage
-            .apply_write_set(genesis_account_data.to_writeset())?;
+        self.impersonate_account(&genesis_address())?;
 
         {
             let mut writes = WriteSetMut::default();
@@ -475,14 +686,99 @@ impl<'a> StarcoinTestAdapter<'a> {
                 public_key,
             },
         )?;
+        self.apply_output(&output)?;
+        Ok(output)
+    }
+
+    /// Perform a single Starcoin transaction like `run_transaction`, and return the profile.
+    fn profile_transaction(
+        &mut self,
+        txn: RawUserTransaction,
+        public_key: AccountPublicKey,
+    ) -> Result<(TransactionOutput, ExecutionProfile)> {
+        let mut vm = StarcoinVM::new(None);
+        let (_status, output, profile) = vm.profile_dry_run_transaction(
+            &self.storage,
+            DryRunTransaction {
+                raw_txn: txn,
+                public_key,
+            },
+        )?;
+        self.apply_output(&output)?;
+        Ok((output, profile))
+    }
+
+    fn apply_output(&mut self, output: &TransactionOutput) -> Result<()> {
         match output.status() {
             TransactionStatus::Keep(_kept_vm_status) => {
                 self.storage
                     .apply_write_set(output.clone().into_inner().0)?;
+                self.last_events = output.events().to_vec();
+            }
+            TransactionStatus::Discard(_) => {
+                self.last_events = vec![];
             }
-            TransactionStatus::Discard(_) => {}
         }
-        Ok(output)
+        Ok(())
+    }
+
+    /// Run the txn of the run command, profile it if required by the `extra_args`,
+    /// and format the result.
+    fn run_and_format(
+        &mut self,
+        txn: RawUserTransaction,
+        public_key: AccountPublicKey,
+        extra_args: &StarcoinRunArgs,
+    ) -> Result<String> {
+        let (output, profile) = if extra_args.profile {
+            let (output, profile) = self.profile_transaction(txn, public_key)?;
+            (output, Some(profile))
+        } else {
+            (self.run_transaction(txn, public_key)?, None)
+        };
+        let mut result = TransactionResult {
+            gas_used: output.gas_used(),
+            status: output.status().clone(),
+            write_set: None,
+            events: None,
+            profile: None,
+        };
+        if extra_args.verbose {
+            result.write_set = Some(
+                output
+                    .write_set()
+                    .clone()
+                    .into_iter()
+                    .map(TransactionOutputAction::from)
+                    .collect(),
+            );
+            result.events = Some(
+                output
+                    .events()
+                    .iter()
+                    .cloned()
+                    .map(TransactionEventView::from)
+                    .collect(),
+            );
+        }
+        if let Some(profile) = profile {
+            if let Some(flamegraph) = &extra_args.flamegraph {
+                std::fs::write(flamegraph, profile.to_collapsed_stacks(extra_args.weight))?;
+            }
+            result.profile = Some(
+                profile
+                    .module_summary()
+                    .into_iter()
+                    .map(|module| ModuleGasProfile {
+                        module: module.module,
+                        functions: module.functions,
+                        instructions: module.instructions,
+                        gas: module.gas,
+                    })
+                    .collect(),
+            );
+        }
+        Ok(serde_json::to_string_pretty(&result)?)
     }
 
     fn handle_contract_call(&self, call: ContractCall) -> Result<Option<String>> {
@@ -594,6 +890,7 @@ impl<'a> StarcoinTestAdapter<'a> {
                     status: output.status().clone(),
                     write_set: None,
                     events: None,
+                    profile: None,
                 };
                 Ok(Some(serde_json::to_string_pretty(&result)?))
             }
@@ -636,6 +933,138 @@ impl<'a> StarcoinTestAdapter<'a> {
         self.run_blockmeta(new_block_meta)?;
         Ok(None)
     }
+
+    fn handle_set_resource(
+        &mut self,
+        address: RawAddress,
+        resource: StructTag,
+        value: Vec<u8>,
+    ) -> Result<Option<String>> {
+        // make sure the value matches the layout of the resource before writing it.
+        {
+            let move_resolver = RemoteStorage::new(&self.storage);
+            let annotator = move_resource_viewer::MoveValueAnnotator::new(&move_resolver);
+            annotator.view_resource(&resource, &value)?;
+        }
+        let addr = self.compiled_state.resolve_address(&address);
+        self.storage
+            .set(&AccessPath::resource_access_path(addr, resource), value)?;
+        Ok(None)
+    }
+
+    fn handle_warp(
+        &mut self,
+        timestamp: Option<u64>,
+        seconds: Option<u64>,
+    ) -> Result<Option<String>> {
+        let now = self.storage.get_timestamp()?.milliseconds;
+        let timestamp = match (timestamp, seconds) {
+            (Some(timestamp), _) => timestamp,
+            (None, Some(seconds)) => seconds
+                .checked_mul(1000)
+                .and_then(|milliseconds| now.checked_add(milliseconds))
+                .ok_or_else(|| {
+                    format_err!(
+                        "Can not warp the global time by {} seconds, overflow",
+                        seconds
+                    )
+                })?,
+            (None, None) => bail!("warp needs either --timestamp or --seconds"),
+        };
+        if timestamp < now {
+            bail!(
+                "Can not warp the global time back, current: {}, target: {}",
+                now,
+                timestamp
+            );
+        }
+        self.storage.set(
+            &AccessPath::resource_access_path(
+                genesis_address(),
+                on_chain_resource::GlobalTimeOnChain::struct_tag(),
+            ),
+            bcs_ext::to_bytes(&on_chain_resource::GlobalTimeOnChain::new(timestamp))?,
+        )?;
+        Ok(None)
+    }
+
+    fn handle_impersonate(&mut self, address: RawAddress) -> Result<Option<String>> {
+        let addr = self.compiled_state.resolve_address(&address);
+        let public_key = self.impersonate_account(&addr)?;
+        if let RawAddress::Named(name) = &address {
+            self.public_key_mapping
+                .insert(name.clone(), public_key.clone());
+        }
+        self.impersonated_keys.insert(addr, public_key);
+        Ok(None)
+    }
+
+    fn handle_snapshot(&mut self, name: String) -> Result<Option<String>> {
+        let snapshot = match &self.storage {
+            SelectableStateView::A(db) => {
+                let state_root = db.commit()?;
+                db.flush()?;
+                StateSnapshot::StateRoot(state_root)
+            }
+            SelectableStateView::B(cache) => StateSnapshot::Cache(cache.snapshot()),
+        };
+        self.snapshots.insert(name, snapshot);
+        Ok(None)
+    }
+
+    fn handle_revert(&mut self, name: String) -> Result<Option<String>> {
+        let snapshot = self
+            .snapshots
+            .get(&name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Snapshot {} not found", name))?;
+        match (&mut self.storage, snapshot) {
+            (SelectableStateView::A(db), StateSnapshot::StateRoot(state_root)) => {
+                *db = db.fork_at(state_root);
+            }
+            (SelectableStateView::B(cache), StateSnapshot::Cache(data)) => {
+                cache.revert(data);
+            }
+            _ => bail!("Snapshot {} does not match the current state", name),
+        }
+        Ok(None)
+    }
+
+    fn handle_assert_events(
+        &self,
+        type_tag: Option<TypeTag>,
+        data: Option<Vec<u8>>,
+        count: Option<usize>,
+    ) -> Result<Option<String>> {
+        let matched = self
+            .last_events
+            .iter()
+            .filter(|event| type_tag.as_ref().map_or(true, |t| event.type_tag() == t))
+            .filter(|event| {
+                data.as_ref()
+                    .map_or(true, |d| event.event_data() == d.as_slice())
+            })
+            .count();
+        let passed = match count {
+            Some(count) => matched == count,
+            None => matched > 0,
+        };
+        if !passed {
+            let events = self
+                .last_events
+                .iter()
+                .cloned()
+                .map(TransactionEventView::from)
+                .collect::<Vec<_>>();
+            bail!(
+                "Assert events failed, expect {} matched events, got {}, events of the last txn:\n{}",
+                count.map_or_else(|| "at least 1".to_string(), |c| c.to_string()),
+                matched,
+                serde_json::to_string_pretty(&events)?
+            );
+        }
+        Ok(None)
+    }
 }
 fn panic_missing_public_key_named(cmd_name: &str, name: &IdentStr) -> ! {
     panic!(
@@ -788,6 +1217,9 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
             public_key_mapping,
             storage: store,
             association_public_key,
+            impersonated_keys: BTreeMap::default(),
+            snapshots: BTreeMap::default(),
+            last_events: vec![],
         };
         me.hack_genesis_account()
             .expect("hack genesis account failure");
@@ -817,7 +1249,10 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
                 Some(key) => key.clone(),
                 None => panic_missing_public_key_named("publish", &named_addr),
             },
-            (None, None) => panic_missing_public_key("publish"),
+            (None, None) => match self.impersonated_keys.get(signer) {
+                Some(public_key) => public_key.clone(),
+                None => panic_missing_public_key("publish"),
+            },
         };
 
         let txn = RawUserTransaction::new_module(
@@ -857,15 +1292,18 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
 
         let params = self.fetch_default_transaction_parameters(&sender)?;
 
-        let public_key = match (extra_args.public_key, &signers[0]) {
-            (Some(public_key), _) => self.resolve_public_key(&public_key),
+        let public_key = match (&extra_args.public_key, &signers[0]) {
+            (Some(public_key), _) => self.resolve_public_key(public_key),
             (None, RawAddress::Named(named_addr)) => {
                 match self.public_key_mapping.get(named_addr) {
                     Some(private_key) => private_key.clone(),
                     None => panic_missing_public_key_named("run", named_addr),
                 }
             }
-            (None, RawAddress::Anonymous(_)) => panic_missing_public_key("run"),
+            (None, RawAddress::Anonymous(addr)) => match self.impersonated_keys.get(addr) {
+                Some(public_key) => public_key.clone(),
+                None => panic_missing_public_key("run"),
+            },
         };
 
         let txn = RawUserTransaction::new_script(
@@ -878,32 +1316,7 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
             params.chainid,
         );
 
-        let output = self.run_transaction(txn, public_key)?;
-        let mut result = TransactionResult {
-            gas_used: output.gas_used(),
-            status: output.status().clone(),
-            write_set: None,
-            events: None,
-        };
-        if extra_args.verbose {
-            result.write_set = Some(
-                output
-                    .write_set()
-                    .clone()
-                    .into_iter()
-                    .map(TransactionOutputAction::from)
-                    .collect(),
-            );
-            result.events = Some(
-                output
-                    .events()
-                    .iter()
-                    .cloned()
-                    .map(TransactionEventView::from)
-                    .collect(),
-            );
-        }
-        Ok(Some(serde_json::to_string_pretty(&result)?))
+        Ok(Some(self.run_and_format(txn, public_key, &extra_args)?))
     }
 
     fn call_function(
@@ -926,15 +1339,18 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
 
         let params = self.fetch_default_transaction_parameters(&sender)?;
 
-        let public_key = match (extra_args.public_key, &signers[0]) {
-            (Some(public_key), _) => self.resolve_public_key(&public_key),
+        let public_key = match (&extra_args.public_key, &signers[0]) {
+            (Some(public_key), _) => self.resolve_public_key(public_key),
             (None, RawAddress::Named(named_addr)) => {
                 match self.public_key_mapping.get(named_addr) {
                     Some(private_key) => private_key.clone(),
                     None => panic_missing_public_key_named("run", named_addr),
                 }
             }
-            (None, RawAddress::Anonymous(_)) => panic_missing_public_key("run"),
+            (None, RawAddress::Anonymous(addr)) => match self.impersonated_keys.get(addr) {
+                Some(public_key) => public_key.clone(),
+                None => panic_missing_public_key("run"),
+            },
         };
 
         let txn = RawUserTransaction::new_script_function(
@@ -952,33 +1368,7 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
             params.chainid,
         );
 
-        let output = self.run_transaction(txn, public_key)?;
-
-        let mut result = TransactionResult {
-            gas_used: output.gas_used(),
-            status: output.status().clone(),
-            write_set: None,
-            events: None,
-        };
-        if extra_args.verbose {
-            result.write_set = Some(
-                output
-                    .write_set()
-                    .clone()
-                    .into_iter()
-                    .map(TransactionOutputAction::from)
-                    .collect(),
-            );
-            result.events = Some(
-                output
-                    .events()
-                    .iter()
-                    .cloned()
-                    .map(TransactionEventView::from)
-                    .collect(),
-            );
-        }
-        Ok(Some(serde_json::to_string_pretty(&result)?))
+        Ok(Some(self.run_and_format(txn, public_key, &extra_args)?))
     }
 
     fn view_data(
@@ -1017,6 +1407,22 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
                 args,
                 type_args,
             }),
+            StarcoinSubcommands::SetResource {
+                address,
+                resource,
+                value,
+            } => self.handle_set_resource(address, resource.0, value.0),
+            StarcoinSubcommands::Warp { timestamp, seconds } => {
+                self.handle_warp(timestamp, seconds)
+            }
+            StarcoinSubcommands::Impersonate { address } => self.handle_impersonate(address),
+            StarcoinSubcommands::Snapshot { name } => self.handle_snapshot(name),
+            StarcoinSubcommands::Revert { name } => self.handle_revert(name),
+            StarcoinSubcommands::AssertEvents {
+                type_tag,
+                data,
+                count,
+            } => self.handle_assert_events(type_tag.map(|t| t.0), data.map(|d| d.0), count),
         }
     }
 }
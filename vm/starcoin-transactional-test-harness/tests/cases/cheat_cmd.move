@@ -0,0 +1,36 @@
+//# init -n dev
+
+//# block --author=0x2
+
+//# snapshot --name before
+
+//# block
+
+//# call 0x1::Block::get_current_block_number
+
+//# revert --name before
+
+//# call 0x1::Block::get_current_block_number
+
+//# warp --timestamp 9999999999000
+
+//# call 0x1::Timestamp::now_milliseconds
+
+//# faucet --addr alice
+
+//# assert-events --type-tag 0x1::Account::DepositEvent --count 1
+
+//# set-resource --addr 0x1 --resource 0x1::Timestamp::CurrentTimeMilliseconds --value 0x00a0724e18090000
+
+//# call 0x1::Timestamp::now_milliseconds
+
+//# impersonate --addr 0xA550C18
+
+//# publish
+module 0xA550C18::Cheat {
+    public fun value(): u64 {
+        42
+    }
+}
+
+//# call 0xA550C18::Cheat::value
@@ -0,0 +1,88 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::{NodeConfig, StarcoinOpt};
+use starcoin_rpc_client::{RpcClient, StateRootOption};
+use starcoin_state_api::AccountStateReader;
+use starcoin_transaction_builder::peer_to_peer_txn_sent_as_association;
+use starcoin_transactional_test_harness::fork_node::ForkNode;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::association_address;
+use std::sync::Arc;
+
+#[stest::test(timeout = 120)]
+fn test_fork_node() -> Result<()> {
+    let remote_config = Arc::new(NodeConfig::random_for_test());
+    let remote_node = test_helper::run_node_by_config(remote_config.clone())?;
+    let remote_url = remote_config.rpc.get_ws_address().unwrap().to_string();
+    let remote_client = RpcClient::connect_websocket(remote_url.as_str())?;
+    let remote_head = remote_client.chain_info()?.head;
+
+    let fork_node = ForkNode::launch(remote_url.as_str(), None, &StarcoinOpt::default())?;
+    let fork_config = fork_node.config();
+    let fork_client = RpcClient::connect_websocket(
+        fork_config
+            .rpc
+            .get_ws_address()
+            .unwrap()
+            .to_string()
+            .as_str(),
+    )?;
+    assert_eq!(
+        fork_client.chain_info()?.head.block_hash,
+        remote_head.block_hash
+    );
+
+    // accounts untouched by the fork chain are read from the remote node.
+    let remote_association = remote_client.state_get_account_state(association_address())?;
+    assert!(remote_association.is_some());
+    assert_eq!(
+        fork_client.state_get_account_state(association_address())?,
+        remote_association
+    );
+    let sequence_number = {
+        let state_reader = fork_client.state_reader(StateRootOption::Latest)?;
+        AccountStateReader::new(&state_reader).get_sequence_number(association_address())?
+    };
+
+    let receiver = AccountAddress::random();
+    let amount = 1000;
+    let txn = peer_to_peer_txn_sent_as_association(
+        receiver,
+        sequence_number,
+        amount,
+        remote_head.timestamp.0 / 1000 + 3600,
+        fork_config.net(),
+    );
+    fork_client.submit_transaction(txn)?;
+
+    let fork_head = fork_client.chain_info()?.head;
+    assert_eq!(fork_head.number.0, remote_head.number.0 + 1);
+    {
+        let state_reader = fork_client.state_reader(StateRootOption::Latest)?;
+        let account_reader = AccountStateReader::new(&state_reader);
+        assert_eq!(account_reader.get_balance(&receiver)?, Some(amount));
+        assert_eq!(
+            account_reader.get_sequence_number(association_address())?,
+            sequence_number + 1
+        );
+    }
+
+    // the remote chain is not changed by the fork chain.
+    assert_eq!(
+        remote_client.chain_info()?.head.block_hash,
+        remote_head.block_hash
+    );
+    let remote_reader = remote_client.state_reader(StateRootOption::Latest)?;
+    assert_eq!(
+        AccountStateReader::new(&remote_reader).get_balance(&receiver)?,
+        None
+    );
+
+    fork_client.close();
+    remote_client.close();
+    fork_node.stop();
+    remote_node.stop()?;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+import { BcsSerializer } from "./bcs/mod.ts";
+import * as StarcoinTypes from "./starcoin_types/mod.ts";
+import {
+  ScriptFunctionCallVariantPeerToPeerWithMetadata,
+  Stdlib,
+} from "./starcoin_stdlib/mod.ts";
+
+function makeAddress(content: number[]): StarcoinTypes.AccountAddress {
+  if (content.length != 16) {
+    throw new Error("address must be 16 bytes");
+  }
+  return new StarcoinTypes.AccountAddress(content.map((b) => [b]));
+}
+
+function main() {
+  const token = new StarcoinTypes.TypeTagVariantstruct(
+    new StarcoinTypes.StructTag(
+      makeAddress([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+      new StarcoinTypes.Identifier("STC"),
+      new StarcoinTypes.Identifier("STC"),
+      [],
+    ),
+  );
+  const payee = makeAddress(new Array(16).fill(0x22));
+  const payeeAuthKey = new Uint8Array(32).fill(0x22);
+  const amount = BigInt(1234567);
+
+  // Now encode and decode a peer to peer transaction script function.
+  const payload = Stdlib.encodePeerToPeerWithMetadataScriptFunction(
+    token,
+    payee,
+    payeeAuthKey,
+    amount,
+    new Uint8Array(),
+  );
+  const call = Stdlib.decodeScriptFunctionPayload(payload);
+  if (!(call instanceof ScriptFunctionCallVariantPeerToPeerWithMetadata)) {
+    throw new Error("unexpected type of script function");
+  }
+  if (call.amount !== amount) {
+    throw new Error("unexpected amount " + call.amount);
+  }
+
+  const serializer = new BcsSerializer();
+  payload.serialize(serializer);
+  console.log(Array.from(serializer.getBytes()).map((b) => b + " ").join(""));
+}
+
+main();
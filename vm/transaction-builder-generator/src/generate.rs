@@ -22,10 +22,11 @@ enum Language {
     Cpp,
     Java,
     Dart,
+    TypeScript,
 }
 impl Language {
-    fn variants() -> [&'static str; 5] {
-        ["python3", "rust", "cpp", "java", "dart"]
+    fn variants() -> [&'static str; 6] {
+        ["python3", "rust", "cpp", "java", "dart", "typescript"]
     }
 }
 impl FromStr for Language {
@@ -37,6 +38,7 @@ impl FromStr for Language {
             "cpp" => Ok(Language::Cpp),
             "java" => Ok(Language::Java),
             "dart" => Ok(Language::Dart),
+            "typescript" => Ok(Language::TypeScript),
             _ => Err(format!("Unsupported language: {}", s)),
         }
     }
@@ -131,6 +133,7 @@ fn main() {
                     // buildgen::dart::output(&mut out, &abis, class_name).unwrap()
                     panic!("Code generation in dart requires --target_source_dir");
                 }
+                Language::TypeScript => buildgen::typescript::output(&mut out, &abis).unwrap(),
             }
             return;
         }
@@ -139,25 +142,6 @@ fn main() {
 
     // Diem types
     if let Some(registry_file) = options.with_diem_types {
-        let installer: Box<dyn serdegen::SourceInstaller<Error = Box<dyn std::error::Error>>> =
-            match options.language {
-                Language::Python3 => Box::new(serdegen::python3::Installer::new(
-                    install_dir.clone(),
-                    options.serde_package_name.clone(),
-                )),
-                Language::Rust => Box::new(serdegen::rust::Installer::new(install_dir.clone())),
-                Language::Cpp => Box::new(serdegen::cpp::Installer::new(install_dir.clone())),
-                Language::Java => Box::new(serdegen::java::Installer::new(install_dir.clone())),
-                Language::Dart => Box::new(serdegen::dart::Installer::new(install_dir.clone())),
-            };
-
-        match options.language {
-            Language::Rust => (), // In Rust, runtimes are deployed as crates.
-            _ => {
-                installer.install_serde_runtime().unwrap();
-                installer.install_bcs_runtime().unwrap();
-            }
-        }
         let content =
             std::fs::read_to_string(registry_file).expect("registry file must be readable");
         let registry = serde_yaml::from_str::<Registry>(content.as_str()).unwrap();
@@ -180,10 +164,40 @@ fn main() {
             &diem_package_path,
             options.with_custom_diem_code.into_iter(),
         );
-        let config = serdegen::CodeGeneratorConfig::new(diem_package_name)
-            .with_encodings(vec![serdegen::Encoding::Bcs])
-            .with_custom_code(custom_diem_code);
-        installer.install_module(&config, &registry).unwrap();
+        if let Language::TypeScript = options.language {
+            // The serde-generate fork has no TypeScript backend.
+            let installer = buildgen::typescript::TypesInstaller::new(install_dir.clone());
+            installer.install_serde_runtime().unwrap();
+            installer.install_bcs_runtime().unwrap();
+            installer
+                .install_module(diem_package_name, custom_diem_code, &registry)
+                .unwrap();
+        } else {
+            let installer: Box<dyn serdegen::SourceInstaller<Error = Box<dyn std::error::Error>>> =
+                match options.language {
+                    Language::Python3 => Box::new(serdegen::python3::Installer::new(
+                        install_dir.clone(),
+                        options.serde_package_name.clone(),
+                    )),
+                    Language::Rust => Box::new(serdegen::rust::Installer::new(install_dir.clone())),
+                    Language::Cpp => Box::new(serdegen::cpp::Installer::new(install_dir.clone())),
+                    Language::Java => Box::new(serdegen::java::Installer::new(install_dir.clone())),
+                    Language::Dart => Box::new(serdegen::dart::Installer::new(install_dir.clone())),
+                    Language::TypeScript => unreachable!(),
+                };
+
+            match options.language {
+                Language::Rust => (), // In Rust, runtimes are deployed as crates.
+                _ => {
+                    installer.install_serde_runtime().unwrap();
+                    installer.install_bcs_runtime().unwrap();
+                }
+            }
+            let config = serdegen::CodeGeneratorConfig::new(diem_package_name)
+                .with_encodings(vec![serdegen::Encoding::Bcs])
+                .with_custom_code(custom_diem_code);
+            installer.install_module(&config, &registry).unwrap();
+        }
     }
 
     // Transaction builders
@@ -201,6 +215,7 @@ fn main() {
             Language::Cpp => Box::new(buildgen::cpp::Installer::new(install_dir)),
             Language::Java => Box::new(buildgen::java::Installer::new(install_dir)),
             Language::Dart => Box::new(buildgen::dart::Installer::new(install_dir)),
+            Language::TypeScript => Box::new(buildgen::typescript::Installer::new(install_dir)),
         };
 
     if let Some(name) = options.module_name {
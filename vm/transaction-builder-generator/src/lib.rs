@@ -16,6 +16,8 @@ pub mod java;
 pub mod python3;
 /// Support for code-generation in Rust.
 pub mod rust;
+/// Support for code-generation in TypeScript.
+pub mod typescript;
 
 /// Internals shared between languages.
 mod common;
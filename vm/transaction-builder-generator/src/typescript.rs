@@ -0,0 +1,459 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common;
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, TypeTag},
+};
+use serde_generate::{
+    indent::{IndentConfig, IndentedWriter},
+    CustomCode,
+};
+use serde_generate_upstream::{typescript, CodeGeneratorConfig, Encoding, SourceInstaller as _};
+use serde_reflection::Registry;
+use starcoin_vm_types::transaction::{ScriptABI, ScriptFunctionABI};
+
+use heck::{CamelCase, MixedCase};
+use std::{
+    collections::BTreeMap,
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output transaction builders and decoders in TypeScript for the given ABIs.
+/// Only script functions are supported, transaction scripts are skipped.
+/// The generated module expects the `serde`, `bcs` and `starcoin_types` modules
+/// installed by `serde-generate` next to it.
+pub fn output(out: &mut dyn Write, abis: &[ScriptABI]) -> Result<()> {
+    let abis = common::script_function_abis(abis);
+    let mut emitter = TypeScriptEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(2)),
+    };
+
+    emitter.output_script_function_call_with_imports(&abis)?;
+
+    writeln!(emitter.out, "\nexport class Stdlib {{")?;
+    emitter.out.indent();
+    emitter.output_encode_method(&abis)?;
+    emitter.output_decode_method()?;
+
+    for abi in &abis {
+        emitter.output_script_function_encoder_function(abi)?;
+    }
+    for abi in &abis {
+        emitter.output_script_function_decoder_function(abi)?;
+    }
+    emitter.output_script_function_decoder_map(&abis)?;
+    emitter.output_decoding_helpers(&abis)?;
+    emitter.out.unindent();
+    writeln!(emitter.out, "}}")
+}
+
+/// Shared state for the TypeScript code generator.
+struct TypeScriptEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+}
+
+impl<T> TypeScriptEmitter<T>
+where
+    T: Write,
+{
+    fn output_script_function_call_with_imports(
+        &mut self,
+        abis: &[ScriptFunctionABI],
+    ) -> Result<()> {
+        let external_definitions = common::get_external_definitions("starcoin_types");
+        let abis = abis
+            .iter()
+            .cloned()
+            .map(ScriptABI::ScriptFunction)
+            .collect::<Vec<_>>();
+        let script_function_registry: BTreeMap<_, _> = vec![(
+            "ScriptFunctionCall".to_string(),
+            common::make_abi_enum_container(abis.as_slice()),
+        )]
+        .into_iter()
+        .collect();
+        let mut comments: BTreeMap<_, _> = abis
+            .iter()
+            .map(|abi| {
+                (
+                    vec![abi.name().to_camel_case()],
+                    common::prepare_doc_string(abi.doc()),
+                )
+            })
+            .collect();
+        comments.insert(
+            vec!["ScriptFunctionCall".to_string()],
+            "Structured representation of a call into a known Move script function.".into(),
+        );
+        let config = CodeGeneratorConfig::new("starcoin_stdlib".to_string())
+            .with_comments(comments)
+            .with_external_definitions(external_definitions)
+            .with_serialization(false);
+        let script_function_registry = to_upstream_registry(&script_function_registry)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, format!("{}", err)))?;
+        typescript::CodeGenerator::new(&config)
+            .output(&mut self.out, &script_function_registry)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, format!("{}", err)))?;
+        Ok(())
+    }
+
+    fn output_encode_method(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Build a Starcoin `TransactionPayload` from a structured object `ScriptFunctionCall`.
+ */
+static encodeScriptFunction(call: ScriptFunctionCall): StarcoinTypes.TransactionPayload {{"#
+        )?;
+        self.out.indent();
+        for abi in abis {
+            let params = abi
+                .ty_args()
+                .iter()
+                .map(|ty_arg| format!("call.{}", ty_arg.name()))
+                .chain(abi.args().iter().map(|arg| format!("call.{}", arg.name())))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                self.out,
+                r#"if (call instanceof ScriptFunctionCallVariant{0}) {{
+  return Stdlib.encode{0}ScriptFunction({1});
+}}"#,
+                abi.name().to_camel_case(),
+                params
+            )?;
+        }
+        writeln!(
+            self.out,
+            "throw new Error(\"Unknown script function call\");"
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_decode_method(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Try to recognize a Starcoin `TransactionPayload` and convert it into a structured object `ScriptFunctionCall`.
+ */
+static decodeScriptFunctionPayload(payload: StarcoinTypes.TransactionPayload): ScriptFunctionCall {{
+  if (!(payload instanceof StarcoinTypes.TransactionPayloadVariantScriptFunction)) {{
+    throw new Error("Transaction payload is not a script function");
+  }}
+  const script = payload.value;
+  const helper = Stdlib.SCRIPT_FUNCTION_DECODER_MAP[script.module.name.value + script.function.value];
+  if (!helper) {{
+    throw new Error("Unknown script function " + script.module.name.value + "::" + script.function.value);
+  }}
+  return helper(script);
+}}"#
+        )
+    }
+
+    fn output_script_function_encoder_function(&mut self, abi: &ScriptFunctionABI) -> Result<()> {
+        let params = abi
+            .ty_args()
+            .iter()
+            .map(|ty_arg| format!("{}: StarcoinTypes.TypeTag", ty_arg.name()))
+            .chain(
+                abi.args()
+                    .iter()
+                    .map(|arg| format!("{}: {}", arg.name(), Self::quote_type(arg.type_tag()))),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            self.out,
+            "\n{}static encode{}ScriptFunction({}): StarcoinTypes.TransactionPayload {{",
+            Self::quote_doc(abi.doc()),
+            abi.name().to_camel_case(),
+            params
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "const tyArgs: Seq<StarcoinTypes.TypeTag> = [{}];",
+            abi.ty_args()
+                .iter()
+                .map(|ty_arg| ty_arg.name().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        for arg in abi.args() {
+            writeln!(
+                self.out,
+                "const {0}Serializer = new BcsSerializer();\n{1}",
+                arg.name().to_mixed_case(),
+                Self::quote_serialize_argument(
+                    arg.type_tag(),
+                    arg.name(),
+                    &format!("{}Serializer", arg.name().to_mixed_case())
+                ),
+            )?;
+        }
+        writeln!(
+            self.out,
+            r#"const args: Seq<bytes> = [{}];
+const moduleId = {};
+const functionName = new StarcoinTypes.Identifier("{}");
+const script = new StarcoinTypes.ScriptFunction(moduleId, functionName, tyArgs, args);
+return new StarcoinTypes.TransactionPayloadVariantScriptFunction(script);"#,
+            abi.args()
+                .iter()
+                .map(|arg| format!("{}Serializer.getBytes()", arg.name().to_mixed_case()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            Self::quote_module_id(abi.module_name()),
+            abi.name(),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_decoder_function(&mut self, abi: &ScriptFunctionABI) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic decode{0}ScriptFunction({1}script: StarcoinTypes.ScriptFunction): ScriptFunctionCallVariant{0} {{",
+            abi.name().to_camel_case(),
+            // prevent warning "unused variable"
+            if abi.ty_args().is_empty() && abi.args().is_empty() {
+                "_"
+            } else {
+                ""
+            }
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "return new ScriptFunctionCallVariant{}(",
+            abi.name().to_camel_case()
+        )?;
+        self.out.indent();
+        for (index, _) in abi.ty_args().iter().enumerate() {
+            writeln!(self.out, "script.ty_args[{}],", index)?;
+        }
+        for (index, arg) in abi.args().iter().enumerate() {
+            writeln!(
+                self.out,
+                "Stdlib.decode{}Argument(script.args[{}]),",
+                Self::mangle_type(arg.type_tag()),
+                index
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, ");")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_decoder_map(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic SCRIPT_FUNCTION_DECODER_MAP: {{ [name: string]: (script: StarcoinTypes.ScriptFunction) => ScriptFunctionCall }} = {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "{0}{1}: Stdlib.decode{2}ScriptFunction,",
+                abi.module_name().name(),
+                abi.name(),
+                abi.name().to_camel_case()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")
+    }
+
+    fn output_decoding_helpers(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        let abis = abis
+            .iter()
+            .cloned()
+            .map(ScriptABI::ScriptFunction)
+            .collect::<Vec<_>>();
+        for required_type in common::get_required_decoding_helper_types(&abis) {
+            self.output_decoding_helper(required_type)?;
+        }
+        Ok(())
+    }
+
+    fn output_decoding_helper(&mut self, type_tag: &TypeTag) -> Result<()> {
+        use TypeTag::*;
+        let expr = match type_tag {
+            Bool => "deserializer.deserializeBool()",
+            U8 => "deserializer.deserializeU8()",
+            U64 => "deserializer.deserializeU64()",
+            U128 => "deserializer.deserializeU128()",
+            Address => "StarcoinTypes.AccountAddress.deserialize(deserializer)",
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "deserializer.deserializeBytes()",
+                _ => common::type_not_allowed(type_tag),
+            },
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        };
+        writeln!(
+            self.out,
+            r#"
+static decode{}Argument(arg: bytes): {} {{
+  const deserializer = new BcsDeserializer(arg);
+  return {};
+}}"#,
+            Self::mangle_type(type_tag),
+            Self::quote_type(type_tag),
+            expr,
+        )
+    }
+
+    fn quote_doc(doc: &str) -> String {
+        let doc = common::prepare_doc_string(doc);
+        let text = textwrap::indent(&doc, " * ").replace("\n\n", "\n *\n");
+        format!("/**\n{}\n */\n", text)
+    }
+
+    fn quote_address(address: &AccountAddress) -> String {
+        format!(
+            "new StarcoinTypes.AccountAddress([{}])",
+            address
+                .to_vec()
+                .iter()
+                .map(|x| format!("[{}]", x))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn quote_module_id(module_id: &ModuleId) -> String {
+        format!(
+            "new StarcoinTypes.ModuleId({}, new StarcoinTypes.Identifier(\"{}\"))",
+            Self::quote_address(module_id.address()),
+            module_id.name()
+        )
+    }
+
+    fn quote_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "bool".into(),
+            U8 => "uint8".into(),
+            U64 => "uint64".into(),
+            U128 => "uint128".into(),
+            Address => "StarcoinTypes.AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "bytes".into(),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        }
+    }
+
+    fn mangle_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "Bool".into(),
+            U8 => "U8".into(),
+            U64 => "U64".into(),
+            U128 => "U128".into(),
+            Address => "AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "U8Vector".into(),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        }
+    }
+
+    fn quote_serialize_argument(type_tag: &TypeTag, name: &str, serializer: &str) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => format!("{}.serializeBool({});", serializer, name),
+            U8 => format!("{}.serializeU8({});", serializer, name),
+            U64 => format!("{}.serializeU64({});", serializer, name),
+            U128 => format!("{}.serializeU128({});", serializer, name),
+            Address => format!("{}.serialize({});", name, serializer),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => format!("{}.serializeBytes({});", serializer, name),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        }
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[ScriptABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("mod.ts"))?;
+        output(&mut file, abis)?;
+        Ok(())
+    }
+}
+
+/// Convert a registry of the serde-reflection fork into the upstream one.
+/// Both crates serialize formats in the same way.
+fn to_upstream_registry(
+    registry: &Registry,
+) -> std::result::Result<serde_reflection_upstream::Registry, serde_yaml::Error> {
+    serde_yaml::from_value(serde_yaml::to_value(registry)?)
+}
+
+/// Installer for the serde and bcs runtimes and the Starcoin types in TypeScript.
+/// The serde-generate fork has no TypeScript backend, so this delegates to the upstream release.
+pub struct TypesInstaller {
+    installer: typescript::Installer,
+}
+
+impl TypesInstaller {
+    pub fn new(install_dir: PathBuf) -> Self {
+        TypesInstaller {
+            installer: typescript::Installer::new(install_dir),
+        }
+    }
+
+    pub fn install_module(
+        &self,
+        module_name: String,
+        custom_code: CustomCode,
+        registry: &Registry,
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let config = CodeGeneratorConfig::new(module_name)
+            .with_encodings(vec![Encoding::Bcs])
+            .with_custom_code(custom_code);
+        self.installer
+            .install_module(&config, &to_upstream_registry(registry)?)
+    }
+
+    pub fn install_serde_runtime(&self) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        self.installer.install_serde_runtime()
+    }
+
+    pub fn install_bcs_runtime(&self) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        self.installer.install_bcs_runtime()
+    }
+}
@@ -4,7 +4,7 @@
 use serde_generate as serdegen;
 use serde_generate::SourceInstaller as _;
 use serde_reflection::Registry;
-use starcoin_vm_types::transaction::ScriptABI;
+use starcoin_vm_types::transaction::{ScriptABI, TransactionPayload};
 use std::{io::Write, path::Path, process::Command};
 use tempfile::tempdir;
 use transaction_builder_generator as buildgen;
@@ -287,3 +287,64 @@ fn test_that_java_code_compiles_and_demo_runs() {
     assert!(output.status.success());
     assert_eq!(std::str::from_utf8(&output.stdout).unwrap(), OUTPUT);
 }
+
+#[ignore]
+#[test]
+fn test_that_typescript_code_compiles_and_demo_runs() {
+    let registry = get_starcoin_registry();
+    let abis = get_stdlib_script_abis();
+    let dir = tempdir().unwrap();
+
+    let installer = buildgen::typescript::TypesInstaller::new(dir.path().to_path_buf());
+    installer
+        .install_module("starcoin_types".to_string(), Default::default(), &registry)
+        .unwrap();
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+
+    let abi_installer = buildgen::typescript::Installer::new(dir.path().to_path_buf());
+    abi_installer
+        .install_transaction_builders("starcoin_stdlib", &abis)
+        .unwrap();
+
+    std::fs::copy(
+        "examples/typescript/stdlib_demo.ts",
+        dir.path().join("stdlib_demo.ts"),
+    )
+    .unwrap();
+
+    let output = Command::new("deno")
+        .arg("run")
+        .arg(dir.path().join("stdlib_demo.ts"))
+        .output()
+        .unwrap();
+    eprintln!(
+        "stderr: \n {}",
+        std::str::from_utf8(&output.stderr).unwrap()
+    );
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).unwrap();
+    assert_eq!(stdout, EXPECTED_OUTPUT);
+
+    // Decode the payload generated by TypeScript back in Rust.
+    let bytes = stdout
+        .split_whitespace()
+        .map(|b| b.parse::<u8>().unwrap())
+        .collect::<Vec<_>>();
+    let payload = bcs::from_bytes::<TransactionPayload>(&bytes).unwrap();
+    match payload {
+        TransactionPayload::ScriptFunction(script_function) => {
+            assert_eq!(script_function.module().name().as_str(), "TransferScripts");
+            assert_eq!(
+                script_function.function().as_str(),
+                "peer_to_peer_with_metadata"
+            );
+            assert_eq!(script_function.ty_args().len(), 1);
+            assert_eq!(
+                script_function.args()[2],
+                bcs::to_bytes(&1234567u128).unwrap()
+            );
+        }
+        _ => panic!("unexpected transaction payload"),
+    }
+}
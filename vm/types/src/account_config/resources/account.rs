@@ -85,6 +85,14 @@ impl AccountResource {
     pub fn accept_token_events(&self) -> &EventHandle {
         &self.accept_token_events
     }
+
+    /// Return the AccountResource with the authentication_key replaced, other fields are kept.
+    pub fn with_authentication_key(self, authentication_key: Vec<u8>) -> Self {
+        AccountResource {
+            authentication_key,
+            ..self
+        }
+    }
 }
 
 impl MoveResource for AccountResource {
@@ -121,6 +121,10 @@ pub mod file_format {
     pub use vm::file_format::*;
 }
 
+pub mod file_format_common {
+    pub use vm::file_format_common::*;
+}
+
 pub mod normalized {
     pub use vm::normalized::*;
 }
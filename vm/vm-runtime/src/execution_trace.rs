@@ -0,0 +1,204 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Execution trace of a user transaction.
+//!
+//! The trace records every call the vm adapter makes into Move for the transaction, that is the
+//! prologue, the script or script function (or the init script of a package) and the epilogue,
+//! with the arguments, the return values, the gas charged, the wall time and the state read by
+//! each call. The state written by the transaction is the write set of the transaction output.
+//!
+//! The Move functions called inside these calls are built into a call tree from the calls and
+//! returns in the Move VM instruction trace, see `profiler`, which is only written by the vm built
+//! with debug assertions. Move has no hook for the calls inside the vm, so the arguments, the
+//! return values and the state reads of a nested call are unknown, and its gas and time are
+//! apportioned from the enclosing top level call by the instruction costs. The write set items of
+//! the resources a call moves or mutably borrows are attributed to the call and its callers.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{ModuleId, TypeTag};
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::write_set::WriteOp;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CallKind {
+    Prologue,
+    Script,
+    ScriptFunction,
+    InitScript,
+    Epilogue,
+    /// The readonly function called outside a transaction.
+    Function,
+    /// The Move function called inside another call.
+    Call,
+}
+
+impl fmt::Display for CallKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            CallKind::Prologue => "prologue",
+            CallKind::Script => "script",
+            CallKind::ScriptFunction => "script_function",
+            CallKind::InitScript => "init_script",
+            CallKind::Epilogue => "epilogue",
+            CallKind::Function => "function",
+            CallKind::Call => "call",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateRead {
+    pub access_path: AccessPath,
+    pub value: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallTrace {
+    pub kind: CallKind,
+    /// None for script.
+    pub module: Option<ModuleId>,
+    /// None for script.
+    pub function: Option<Identifier>,
+    pub ty_args: Vec<TypeTag>,
+    /// BCS serialized arguments, the signers of script and script function are not included.
+    /// Empty for the nested calls.
+    pub args: Vec<Vec<u8>>,
+    /// BCS serialized return values, none if the call failed or is a nested call.
+    pub return_values: Option<Vec<Vec<u8>>>,
+    /// The gas used by the call and its callees, estimated for the nested calls.
+    pub gas_used: u64,
+    /// The wall time of the call, estimated for the nested calls.
+    pub time: Duration,
+    /// The state first read by the call, the state read by previous calls is cached.
+    /// The reads of the nested calls are counted in the top level call.
+    pub reads: Vec<StateRead>,
+    /// The write set items of the resources moved or mutably borrowed by the call and its callees.
+    pub writes: Vec<(AccessPath, WriteOp)>,
+    /// The error if the call failed, a nested call fails with the error of the top level call.
+    pub error: Option<VMStatus>,
+    /// The Move functions called by the call, in execution order.
+    pub children: Vec<CallTrace>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionTrace {
+    pub calls: Vec<CallTrace>,
+    /// The state read outside the calls, such as the on-chain configs loaded by the vm.
+    pub reads: Vec<StateRead>,
+}
+
+#[derive(Default)]
+struct TracerInner {
+    trace: ExecutionTrace,
+    /// The index of the running call, the gas left and the time when the call began.
+    current: Option<(usize, u64, Instant)>,
+}
+
+#[derive(Default)]
+pub(crate) struct ExecutionTracer {
+    inner: Mutex<TracerInner>,
+}
+
+impl ExecutionTracer {
+    pub fn begin_call(
+        &self,
+        kind: CallKind,
+        module: Option<ModuleId>,
+        function: Option<Identifier>,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+        gas_left: u64,
+    ) {
+        let mut guard = self.inner.lock();
+        let inner = &mut *guard;
+        inner.trace.calls.push(CallTrace {
+            kind,
+            module,
+            function,
+            ty_args,
+            args,
+            return_values: None,
+            gas_used: 0,
+            time: Duration::default(),
+            reads: vec![],
+            writes: vec![],
+            error: None,
+            children: vec![],
+        });
+        inner.current = Some((inner.trace.calls.len() - 1, gas_left, Instant::now()));
+    }
+
+    pub fn end_call(
+        &self,
+        gas_left: u64,
+        return_values: Option<Vec<Vec<u8>>>,
+        error: Option<VMStatus>,
+    ) {
+        let mut guard = self.inner.lock();
+        let inner = &mut *guard;
+        if let Some((idx, begin_gas_left, begin_time)) = inner.current.take() {
+            let call = &mut inner.trace.calls[idx];
+            call.gas_used = begin_gas_left.saturating_sub(gas_left);
+            call.time = begin_time.elapsed();
+            call.return_values = return_values;
+            call.error = error;
+        }
+    }
+
+    fn record_read(&self, access_path: &AccessPath, value: &Option<Vec<u8>>) {
+        let mut guard = self.inner.lock();
+        let inner = &mut *guard;
+        let read = StateRead {
+            access_path: access_path.clone(),
+            value: value.clone(),
+        };
+        match inner.current {
+            Some((idx, _, _)) => inner.trace.calls[idx].reads.push(read),
+            None => inner.trace.reads.push(read),
+        }
+    }
+
+    pub fn take_trace(&self) -> ExecutionTrace {
+        std::mem::take(&mut *self.inner.lock()).trace
+    }
+}
+
+/// A StateView records all the reads to the tracer.
+pub(crate) struct TracingStateView<'a> {
+    inner: &'a dyn StateView,
+    tracer: &'a ExecutionTracer,
+}
+
+impl<'a> TracingStateView<'a> {
+    pub fn new(inner: &'a dyn StateView, tracer: &'a ExecutionTracer) -> Self {
+        Self { inner, tracer }
+    }
+}
+
+impl<'a> StateView for TracingStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let value = self.inner.get(access_path)?;
+        self.tracer.record_read(access_path, &value);
+        Ok(value)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        let values = self.inner.multi_get(access_paths)?;
+        for (access_path, value) in access_paths.iter().zip(values.iter()) {
+            self.tracer.record_read(access_path, value);
+        }
+        Ok(values)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.inner.is_genesis()
+    }
+}
@@ -8,6 +8,9 @@ pub mod starcoin_vm;
 pub use move_vm_runtime::move_vm;
 mod access_path_cache;
 mod errors;
+pub mod execution_trace;
+mod parallel_executor;
+pub mod profiler;
 use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::language_storage::StructTag;
@@ -0,0 +1,458 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optimistic parallel execution of the user transactions of a block, in the Block-STM style.
+//!
+//! Every transaction is executed speculatively over a multi-version view of the chunk, which
+//! serves the values written by the lower transactions, and the versions it reads are recorded.
+//! A transaction is valid if all its reads still resolve to the same versions, an invalid one is
+//! aborted and re-executed with a new incarnation. When all the transactions are executed and
+//! validated, the outputs are the same as executing them one after another.
+//!
+//! The base `StateView` is only accessed from the calling thread, the workers send their reads
+//! through a channel, so the `StateView` is not required to be `Sync`.
+
+use crate::data_cache::StateViewCache;
+use crate::starcoin_vm::StarcoinVM;
+use anyhow::{format_err, Result};
+use crossbeam_channel::{bounded, unbounded, Sender};
+use parking_lot::{Condvar, Mutex, RwLock};
+use starcoin_types::transaction::{SignedUserTransaction, TransactionOutput, TransactionStatus};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::write_set::WriteOp;
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::thread;
+
+type TxnIndex = usize;
+type Incarnation = usize;
+type Version = (TxnIndex, Incarnation);
+type BaseReadRequest = (AccessPath, Sender<Result<Option<Vec<u8>>>>);
+
+/// The values written by the transactions of the chunk, indexed by access path and txn index.
+/// A `None` value is a deletion.
+#[derive(Default)]
+struct MVHashMap {
+    data: RwLock<HashMap<AccessPath, BTreeMap<TxnIndex, (Incarnation, Option<Vec<u8>>)>>>,
+}
+
+enum MVRead {
+    Storage,
+    Version(Version, Option<Vec<u8>>),
+}
+
+impl MVHashMap {
+    /// Read the value written by the highest transaction lower than `txn_idx`.
+    fn read(&self, access_path: &AccessPath, txn_idx: TxnIndex) -> MVRead {
+        let data = self.data.read();
+        match data
+            .get(access_path)
+            .and_then(|versions| versions.range(..txn_idx).next_back())
+        {
+            Some((idx, (incarnation, value))) => {
+                MVRead::Version((*idx, *incarnation), value.clone())
+            }
+            None => MVRead::Storage,
+        }
+    }
+
+    /// Replace the writes of `txn_idx` by the writes of a new incarnation.
+    fn apply(
+        &self,
+        txn_idx: TxnIndex,
+        incarnation: Incarnation,
+        writes: Vec<(AccessPath, Option<Vec<u8>>)>,
+        removed: &[AccessPath],
+    ) {
+        let mut data = self.data.write();
+        for access_path in removed {
+            if let Some(versions) = data.get_mut(access_path) {
+                versions.remove(&txn_idx);
+            }
+        }
+        for (access_path, value) in writes {
+            data.entry(access_path)
+                .or_default()
+                .insert(txn_idx, (incarnation, value));
+        }
+    }
+
+    fn validate(&self, reads: &[(AccessPath, ReadDescriptor)], txn_idx: TxnIndex) -> bool {
+        reads.iter().all(
+            |(access_path, read)| match (self.read(access_path, txn_idx), read) {
+                (MVRead::Storage, ReadDescriptor::Storage) => true,
+                (MVRead::Version(version, _), ReadDescriptor::Version(read_version)) => {
+                    version == *read_version
+                }
+                _ => false,
+            },
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ReadDescriptor {
+    Storage,
+    Version(Version),
+}
+
+/// The base state of the chunk, shared by all the workers.
+struct BaseView<'a> {
+    cache: &'a RwLock<HashMap<AccessPath, Option<Vec<u8>>>>,
+    requests: Sender<BaseReadRequest>,
+    is_genesis: bool,
+}
+
+impl<'a> BaseView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        if let Some(value) = self.cache.read().get(access_path) {
+            return Ok(value.clone());
+        }
+        let (sender, receiver) = bounded(1);
+        self.requests
+            .send((access_path.clone(), sender))
+            .map_err(|_| format_err!("Base state view of parallel execution is closed."))?;
+        let value = receiver
+            .recv()
+            .map_err(|_| format_err!("Base state view of parallel execution is closed."))??;
+        self.cache
+            .write()
+            .insert(access_path.clone(), value.clone());
+        Ok(value)
+    }
+}
+
+/// The view of one speculative execution, records the versions it reads.
+struct SpeculativeView<'a> {
+    base: &'a BaseView<'a>,
+    mv: &'a MVHashMap,
+    txn_idx: TxnIndex,
+    reads: RefCell<Vec<(AccessPath, ReadDescriptor)>>,
+}
+
+impl<'a> SpeculativeView<'a> {
+    fn new(base: &'a BaseView<'a>, mv: &'a MVHashMap, txn_idx: TxnIndex) -> Self {
+        Self {
+            base,
+            mv,
+            txn_idx,
+            reads: RefCell::new(vec![]),
+        }
+    }
+
+    fn into_reads(self) -> Vec<(AccessPath, ReadDescriptor)> {
+        self.reads.into_inner()
+    }
+}
+
+impl<'a> StateView for SpeculativeView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        match self.mv.read(access_path, self.txn_idx) {
+            MVRead::Version(version, value) => {
+                self.reads
+                    .borrow_mut()
+                    .push((access_path.clone(), ReadDescriptor::Version(version)));
+                Ok(value)
+            }
+            MVRead::Storage => {
+                self.reads
+                    .borrow_mut()
+                    .push((access_path.clone(), ReadDescriptor::Storage));
+                self.base.get(access_path)
+            }
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TxnStatus {
+    ReadyToExecute(Incarnation),
+    Executing(Incarnation),
+    Executed(Incarnation),
+}
+
+enum SchedulerTask {
+    Execute(TxnIndex, Incarnation),
+    Validate(TxnIndex, Incarnation),
+    Done,
+}
+
+struct SchedulerState {
+    execution_idx: TxnIndex,
+    validation_idx: TxnIndex,
+    num_active_tasks: usize,
+    done: bool,
+    status: Vec<TxnStatus>,
+}
+
+/// Hands out the execution and validation tasks, always prefers the lowest transaction.
+struct Scheduler {
+    num_txns: usize,
+    state: Mutex<SchedulerState>,
+    cvar: Condvar,
+}
+
+impl Scheduler {
+    fn new(num_txns: usize) -> Self {
+        Self {
+            num_txns,
+            state: Mutex::new(SchedulerState {
+                execution_idx: 0,
+                validation_idx: 0,
+                num_active_tasks: 0,
+                done: false,
+                status: vec![TxnStatus::ReadyToExecute(0); num_txns],
+            }),
+            cvar: Condvar::new(),
+        }
+    }
+
+    fn next_task(&self) -> SchedulerTask {
+        let mut state = self.state.lock();
+        loop {
+            if state.done {
+                return SchedulerTask::Done;
+            }
+            if state.validation_idx < state.execution_idx {
+                let idx = state.validation_idx;
+                state.validation_idx += 1;
+                if let TxnStatus::Executed(incarnation) = state.status[idx] {
+                    state.num_active_tasks += 1;
+                    return SchedulerTask::Validate(idx, incarnation);
+                }
+            } else if state.execution_idx < self.num_txns {
+                let idx = state.execution_idx;
+                state.execution_idx += 1;
+                if let TxnStatus::ReadyToExecute(incarnation) = state.status[idx] {
+                    state.status[idx] = TxnStatus::Executing(incarnation);
+                    state.num_active_tasks += 1;
+                    return SchedulerTask::Execute(idx, incarnation);
+                }
+            } else if state.num_active_tasks == 0 {
+                state.done = true;
+                self.cvar.notify_all();
+                return SchedulerTask::Done;
+            } else {
+                self.cvar.wait(&mut state);
+            }
+        }
+    }
+
+    fn finish_execution(
+        &self,
+        txn_idx: TxnIndex,
+        incarnation: Incarnation,
+        wrote_new_location: bool,
+    ) -> Option<SchedulerTask> {
+        let mut state = self.state.lock();
+        state.status[txn_idx] = TxnStatus::Executed(incarnation);
+        let mut task = None;
+        if state.validation_idx > txn_idx {
+            // A re-execution may change the values read by the higher transactions,
+            // so they need to be validated again.
+            if wrote_new_location || incarnation > 0 {
+                state.validation_idx = txn_idx;
+            } else {
+                task = Some(SchedulerTask::Validate(txn_idx, incarnation));
+            }
+        }
+        if task.is_none() {
+            state.num_active_tasks -= 1;
+        }
+        self.cvar.notify_all();
+        task
+    }
+
+    fn finish_validation(
+        &self,
+        txn_idx: TxnIndex,
+        incarnation: Incarnation,
+        valid: bool,
+    ) -> Option<SchedulerTask> {
+        let mut state = self.state.lock();
+        // Only the first failed validation of an incarnation aborts it.
+        if !valid && state.status[txn_idx] == TxnStatus::Executed(incarnation) {
+            state.status[txn_idx] = TxnStatus::Executing(incarnation + 1);
+            state.validation_idx = cmp::min(state.validation_idx, txn_idx + 1);
+            self.cvar.notify_all();
+            return Some(SchedulerTask::Execute(txn_idx, incarnation + 1));
+        }
+        state.num_active_tasks -= 1;
+        self.cvar.notify_all();
+        None
+    }
+
+    /// Stop all the workers, when one of them panics.
+    fn halt(&self) {
+        let mut state = self.state.lock();
+        state.done = true;
+        self.cvar.notify_all();
+    }
+}
+
+struct HaltOnPanic<'a>(&'a Scheduler);
+
+impl<'a> Drop for HaltOnPanic<'a> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.0.halt();
+        }
+    }
+}
+
+#[derive(Default)]
+struct TxnSlot {
+    reads: Vec<(AccessPath, ReadDescriptor)>,
+    writes: HashSet<AccessPath>,
+    output: Option<(VMStatus, TransactionOutput)>,
+}
+
+pub(crate) struct ParallelExecutor<'a> {
+    vm: &'a StarcoinVM,
+    concurrency_level: usize,
+}
+
+impl<'a> ParallelExecutor<'a> {
+    pub fn new(vm: &'a StarcoinVM, concurrency_level: usize) -> Self {
+        Self {
+            vm,
+            concurrency_level,
+        }
+    }
+
+    /// Execute the user transactions over `state_view`, return the same outputs as sequential execution.
+    /// The caller should apply the outputs in order, and handle the block gas limit and reconfiguration.
+    pub fn execute_transactions(
+        &self,
+        state_view: &dyn StateView,
+        txns: &[SignedUserTransaction],
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+        let num_txns = txns.len();
+        let scheduler = Scheduler::new(num_txns);
+        let mv = MVHashMap::default();
+        let slots: Vec<Mutex<TxnSlot>> = (0..num_txns).map(|_| Mutex::default()).collect();
+        let base_cache = RwLock::new(HashMap::new());
+        let is_genesis = state_view.is_genesis();
+        let (request_sender, request_receiver) = unbounded::<BaseReadRequest>();
+
+        crossbeam_utils::thread::scope(|s| {
+            for _ in 0..cmp::min(self.concurrency_level, num_txns) {
+                let base = BaseView {
+                    cache: &base_cache,
+                    requests: request_sender.clone(),
+                    is_genesis,
+                };
+                let mut vm = self.vm.clone();
+                let (scheduler, mv, slots) = (&scheduler, &mv, &slots);
+                s.spawn(move |_| {
+                    let _guard = HaltOnPanic(scheduler);
+                    Self::work(&mut vm, &base, scheduler, mv, slots, txns)
+                });
+            }
+            drop(request_sender);
+            // Serve the base reads until all the workers exit.
+            for (access_path, reply) in request_receiver.iter() {
+                let _ = reply.send(state_view.get(&access_path));
+            }
+        })
+        .map_err(|_| format_err!("Parallel execution worker panicked."))?;
+
+        slots
+            .into_iter()
+            .enumerate()
+            .map(|(idx, slot)| {
+                slot.into_inner()
+                    .output
+                    .ok_or_else(|| format_err!("Transaction {} is not executed.", idx))
+            })
+            .collect()
+    }
+
+    fn work(
+        vm: &mut StarcoinVM,
+        base: &BaseView,
+        scheduler: &Scheduler,
+        mv: &MVHashMap,
+        slots: &[Mutex<TxnSlot>],
+        txns: &[SignedUserTransaction],
+    ) {
+        let mut task = scheduler.next_task();
+        loop {
+            let next = match task {
+                SchedulerTask::Execute(idx, incarnation) => {
+                    let wrote_new_location =
+                        Self::execute(vm, base, mv, &slots[idx], idx, incarnation, &txns[idx]);
+                    scheduler.finish_execution(idx, incarnation, wrote_new_location)
+                }
+                SchedulerTask::Validate(idx, incarnation) => {
+                    let valid = mv.validate(&slots[idx].lock().reads, idx);
+                    scheduler.finish_validation(idx, incarnation, valid)
+                }
+                SchedulerTask::Done => return,
+            };
+            task = next.unwrap_or_else(|| scheduler.next_task());
+        }
+    }
+
+    /// Execute one incarnation of the txn and publish its writes, return whether it wrote to a
+    /// location which is not written by the previous incarnation.
+    fn execute(
+        vm: &mut StarcoinVM,
+        base: &BaseView,
+        mv: &MVHashMap,
+        slot: &Mutex<TxnSlot>,
+        txn_idx: TxnIndex,
+        incarnation: Incarnation,
+        txn: &SignedUserTransaction,
+    ) -> bool {
+        let view = SpeculativeView::new(base, mv, txn_idx);
+        let (status, output) = {
+            let mut data_cache = StateViewCache::new(&view);
+            vm.execute_user_transaction(txn.clone(), &mut data_cache)
+        };
+        let reads = view.into_reads();
+        let writes: Vec<(AccessPath, Option<Vec<u8>>)> = match output.status() {
+            TransactionStatus::Keep(_) => output
+                .write_set()
+                .iter()
+                .map(|(access_path, write_op)| {
+                    let value = match write_op {
+                        WriteOp::Value(blob) => Some(blob.clone()),
+                        WriteOp::Deletion => None,
+                    };
+                    (access_path.clone(), value)
+                })
+                .collect(),
+            TransactionStatus::Discard(_) => vec![],
+        };
+
+        let mut slot = slot.lock();
+        let locations: HashSet<AccessPath> = writes
+            .iter()
+            .map(|(access_path, _)| access_path.clone())
+            .collect();
+        let removed: Vec<AccessPath> = slot.writes.difference(&locations).cloned().collect();
+        let wrote_new_location = locations
+            .iter()
+            .any(|access_path| !slot.writes.contains(access_path));
+        mv.apply(txn_idx, incarnation, writes, &removed);
+        slot.reads = reads;
+        slot.writes = locations;
+        slot.output = Some((status, output));
+        wrote_new_location
+    }
+}
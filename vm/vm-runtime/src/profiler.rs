@@ -0,0 +1,793 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas and time profile of a user transaction or a readonly function call.
+//!
+//! Only the gas and the wall time of every call the vm adapter makes into Move are measured, see
+//! `execution_trace`. The gas is charged by the concrete `GasStatus` of the Move VM, which has no
+//! hook to meter a function or an instruction, so release builds give the call level profile
+//! only. The bytecode level samples come from the instruction trace the Move VM writes to the
+//! file set by the `MOVE_VM_TRACE` env, which is only written by the vm built with debug
+//! assertions, the trace is ignored in release builds. Each line of the instruction trace is
+//! `<function>,<offset>`, without gas and time, so the gas and the time of a call are apportioned
+//! to its instructions by their costs in the gas schedule: the gas and the time of the bytecode
+//! level samples are estimates, only their sum over a call is measured.
+//!
+//! All the vms of the process write to the same instruction trace, so the bytecode level profile
+//! is only usable when no other transaction is executed at the same time, such as on a dev node
+//! or in the transactional test harness. If the instruction trace is not available or does not
+//! match the calls, the profile falls back to the call level.
+
+use crate::execution_trace::{CallKind, CallTrace};
+use anyhow::{format_err, Result};
+use starcoin_logger::prelude::*;
+use starcoin_vm_types::access::{ModuleAccess, ScriptAccess};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::file_format::{Bytecode, CompiledModule, CompiledScript};
+use starcoin_vm_types::file_format_common::instruction_key;
+use starcoin_vm_types::gas_schedule::{CostTable, GasAlgebra};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{FunctionId, ModuleId};
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::transaction::TransactionPayload;
+use starcoin_vm_types::write_set::{WriteOp, WriteSet};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Write};
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The env to set the instruction trace file of the Move VM.
+pub const MOVE_VM_TRACE_ENV: &str = "MOVE_VM_TRACE";
+
+/// The frame name of the script in the stacks.
+const SCRIPT_FRAME: &str = "<script>";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProfileWeight {
+    Gas,
+    Time,
+}
+
+impl FromStr for ProfileWeight {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "gas" => Ok(ProfileWeight::Gas),
+            "time" => Ok(ProfileWeight::Time),
+            _ => Err(format_err!(
+                "Unknown profile weight {}, should be gas or time",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ProfileWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileWeight::Gas => write!(f, "gas"),
+            ProfileWeight::Time => write!(f, "time"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileSample {
+    /// The call stack, outermost first. The first frame is the kind of the call the vm adapter
+    /// makes, such as `prologue` or `script_function`.
+    pub stack: Vec<String>,
+    /// The bytecode offset in the innermost function, none for the call level samples.
+    pub offset: Option<u16>,
+    /// The instruction at the offset.
+    pub instruction: Option<String>,
+    /// The times the offset is executed.
+    pub count: u64,
+    /// The gas, measured for the call level samples, estimated for the bytecode level samples.
+    pub gas: u64,
+    /// The wall time, measured for the call level samples, estimated for the bytecode level
+    /// samples.
+    pub time: Duration,
+}
+
+impl ProfileSample {
+    /// The innermost function of the stack.
+    pub fn function(&self) -> &str {
+        self.stack.last().map(String::as_str).unwrap_or_default()
+    }
+
+    /// The module of the innermost function, `<script>` for script.
+    pub fn module(&self) -> &str {
+        let function = self.function();
+        match function.rsplit_once("::") {
+            Some((module, _)) => module,
+            None => function,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ModuleProfile {
+    pub module: String,
+    /// The number of distinct functions executed.
+    pub functions: usize,
+    /// The instructions executed, zero if the profile is at the call level.
+    pub instructions: u64,
+    pub gas: u64,
+    pub time: Duration,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionProfile {
+    pub samples: Vec<ProfileSample>,
+    /// Whether the samples are at the bytecode level, their gas and time are estimates.
+    pub bytecode_level: bool,
+}
+
+impl ExecutionProfile {
+    pub fn gas(&self) -> u64 {
+        self.samples.iter().map(|sample| sample.gas).sum()
+    }
+
+    pub fn time(&self) -> Duration {
+        self.samples.iter().map(|sample| sample.time).sum()
+    }
+
+    /// Export the samples as collapsed stacks, the input format of flamegraph tools,
+    /// one line for each sample: `frame;frame;...;offset:instruction weight`.
+    /// The time is in nanoseconds, and the samples of zero weight are omitted.
+    pub fn to_collapsed_stacks(&self, weight: ProfileWeight) -> String {
+        let mut out = String::new();
+        for sample in &self.samples {
+            let value = match weight {
+                ProfileWeight::Gas => sample.gas as u128,
+                ProfileWeight::Time => sample.time.as_nanos(),
+            };
+            if value == 0 {
+                continue;
+            }
+            out.push_str(&sample.stack.join(";"));
+            if let Some(offset) = sample.offset {
+                // the space is the separator of the weight.
+                let instruction = sample
+                    .instruction
+                    .as_deref()
+                    .unwrap_or_default()
+                    .replace(' ', "");
+                let _ = write!(out, ";{}:{}", offset, instruction);
+            }
+            let _ = writeln!(out, " {}", value);
+        }
+        out
+    }
+
+    /// Summarize the gas and the time spent in each module, exclusive of the callees in other
+    /// modules, sorted by the gas descending.
+    pub fn module_summary(&self) -> Vec<ModuleProfile> {
+        let mut modules: BTreeMap<&str, (ModuleProfile, Vec<&str>)> = BTreeMap::new();
+        for sample in &self.samples {
+            let (module, functions) = modules.entry(sample.module()).or_insert_with(|| {
+                (
+                    ModuleProfile {
+                        module: sample.module().to_string(),
+                        ..Default::default()
+                    },
+                    vec![],
+                )
+            });
+            if sample.offset.is_some() {
+                module.instructions += sample.count;
+            }
+            module.gas += sample.gas;
+            module.time += sample.time;
+            if !functions.contains(&sample.function()) {
+                functions.push(sample.function());
+            }
+        }
+        let mut summary: Vec<_> = modules
+            .into_iter()
+            .map(|(_, (mut module, functions))| {
+                module.functions = functions.len();
+                module
+            })
+            .collect();
+        // the sort is stable, the modules of the same gas are in the order of name.
+        summary.sort_by(|a, b| b.gas.cmp(&a.gas));
+        summary
+    }
+
+    /// Format the module summary as a text table, the time column is omitted if `with_time` is
+    /// false, so the table is deterministic.
+    pub fn summary_table(&self, with_time: bool) -> String {
+        let summary = self.module_summary();
+        let total_gas = self.gas().max(1);
+        let width = summary
+            .iter()
+            .map(|module| module.module.len())
+            .chain(std::iter::once("module".len()))
+            .max()
+            .unwrap_or_default();
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{:<width$} {:>9} {:>12} {:>12} {:>7}",
+            "module",
+            "functions",
+            "instructions",
+            "gas",
+            "gas%",
+            width = width
+        );
+        if with_time {
+            let _ = write!(out, " {:>12}", "time(us)");
+        }
+        out.push('\n');
+        for module in summary {
+            let _ = write!(
+                out,
+                "{:<width$} {:>9} {:>12} {:>12} {:>6.2}%",
+                module.module,
+                module.functions,
+                module.instructions,
+                module.gas,
+                module.gas as f64 * 100.0 / total_gas as f64,
+                width = width
+            );
+            if with_time {
+                let _ = write!(out, " {:>12}", module.time.as_micros());
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// A line of the instruction trace.
+#[derive(Clone, Debug)]
+struct TracedInstruction {
+    function: String,
+    offset: u16,
+}
+
+/// Read the instruction trace written after the reader is created.
+pub(crate) struct InstructionTraceReader {
+    path: Option<PathBuf>,
+    start: u64,
+}
+
+impl InstructionTraceReader {
+    pub fn new() -> Self {
+        // the vm built without debug assertions never writes the instruction trace.
+        let path = if cfg!(debug_assertions) {
+            std::env::var_os(MOVE_VM_TRACE_ENV).map(PathBuf::from)
+        } else {
+            None
+        };
+        let start = path
+            .as_ref()
+            .and_then(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        Self { path, start }
+    }
+
+    fn read(&self) -> Result<Vec<TracedInstruction>> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(vec![]),
+        };
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        file.seek(SeekFrom::Start(self.start))?;
+        let mut instructions = vec![];
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let mut fields = line.split(',');
+            if let (Some(function), Some(offset)) = (fields.next(), fields.next()) {
+                if let Ok(offset) = offset.trim().parse() {
+                    instructions.push(TracedInstruction {
+                        function: function.trim().to_string(),
+                        offset,
+                    });
+                }
+            }
+        }
+        Ok(instructions)
+    }
+}
+
+/// Resolve the bytecode of the functions in the instruction trace.
+struct CodeResolver<'a> {
+    state_view: &'a dyn StateView,
+    modules: HashMap<ModuleId, Option<CompiledModule>>,
+    script: Option<CompiledScript>,
+}
+
+impl<'a> CodeResolver<'a> {
+    fn new(state_view: &'a dyn StateView, payload: Option<&TransactionPayload>) -> Self {
+        let mut modules = HashMap::new();
+        let mut script = None;
+        match payload {
+            Some(TransactionPayload::Script(s)) => {
+                script = CompiledScript::deserialize(s.code()).ok();
+            }
+            // the modules of the package are not in the state before the txn is applied.
+            Some(TransactionPayload::Package(package)) => {
+                for module in package.modules() {
+                    if let Ok(module) = CompiledModule::deserialize(module.code()) {
+                        modules.insert(module.self_id(), Some(module));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Self {
+            state_view,
+            modules,
+            script,
+        }
+    }
+
+    fn module(&mut self, module_id: &ModuleId) -> Option<&CompiledModule> {
+        let state_view = self.state_view;
+        self.modules
+            .entry(module_id.clone())
+            .or_insert_with(|| {
+                let code = state_view
+                    .get(&AccessPath::code_access_path(
+                        *module_id.address(),
+                        module_id.name().to_owned(),
+                    ))
+                    .ok()??;
+                CompiledModule::deserialize(code.as_slice()).ok()
+            })
+            .as_ref()
+    }
+
+    fn code(&mut self, function: &Frame) -> Option<&[Bytecode]> {
+        match &function.id {
+            None => self
+                .script
+                .as_ref()
+                .map(|script| script.code().code.as_slice()),
+            Some(id) => {
+                let module = self.module(&id.module)?;
+                module
+                    .function_defs()
+                    .iter()
+                    .find(|def| {
+                        module.identifier_at(module.function_handle_at(def.function).name)
+                            == id.function.as_ident_str()
+                    })
+                    .and_then(|def| def.code.as_ref())
+                    .map(|code| code.code.as_slice())
+            }
+        }
+    }
+
+    /// The resource mutated by the instruction of the function, without the type arguments.
+    /// A resource can only be moved or mutably borrowed in the module which defines it.
+    fn mutated_resource(
+        &mut self,
+        function: &Frame,
+        instruction: &Bytecode,
+    ) -> Option<(ModuleId, Identifier)> {
+        let id = function.id.as_ref()?;
+        let module = self.module(&id.module)?;
+        let struct_def = match instruction {
+            Bytecode::MoveTo(idx) | Bytecode::MoveFrom(idx) | Bytecode::MutBorrowGlobal(idx) => {
+                *idx
+            }
+            Bytecode::MoveToGeneric(idx)
+            | Bytecode::MoveFromGeneric(idx)
+            | Bytecode::MutBorrowGlobalGeneric(idx) => module.struct_instantiation_at(*idx).def,
+            _ => return None,
+        };
+        let struct_handle = module.struct_handle_at(module.struct_def_at(struct_def).struct_handle);
+        Some((
+            id.module.clone(),
+            module.identifier_at(struct_handle.name).to_owned(),
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Frame {
+    /// None for script.
+    id: Option<FunctionId>,
+    name: String,
+}
+
+impl Frame {
+    fn parse(function: &str) -> Self {
+        match FunctionId::from_str(function) {
+            Ok(id) => Self::function(id),
+            Err(_) => Self {
+                id: None,
+                name: SCRIPT_FRAME.to_string(),
+            },
+        }
+    }
+
+    fn function(id: FunctionId) -> Self {
+        Self {
+            name: id.to_string(),
+            id: Some(id),
+        }
+    }
+
+    fn of_call(call: &CallTrace) -> Self {
+        match (&call.module, &call.function) {
+            (Some(module), Some(function)) => Self::function(FunctionId {
+                module: module.clone(),
+                function: function.clone(),
+            }),
+            _ => Self {
+                id: None,
+                name: SCRIPT_FRAME.to_string(),
+            },
+        }
+    }
+}
+
+/// A Move function call in the instruction trace.
+struct CallNode {
+    frame: Frame,
+    /// The indexes of the calls made by the call, in execution order.
+    children: Vec<usize>,
+    /// The cost of the instructions executed by the call and its callees.
+    cost: u64,
+    /// Whether the transaction aborted while the call was on the stack.
+    aborted: bool,
+    /// The resources mutated by the instructions of the call, excluding its callees.
+    mutated: Vec<(ModuleId, Identifier)>,
+}
+
+impl CallNode {
+    fn new(frame: Frame) -> Self {
+        Self {
+            frame,
+            children: vec![],
+            cost: 0,
+            aborted: false,
+            mutated: vec![],
+        }
+    }
+}
+
+/// The instructions of a call the vm adapter makes into Move, aggregated by the stack and offset.
+#[derive(Default)]
+struct Segment {
+    first: Option<Frame>,
+    /// (stack, offset) -> (instruction, count, cost)
+    samples: BTreeMap<(Vec<String>, u16), (Option<String>, u64, u64)>,
+    /// The calls made in the segment, the first one is the call the vm adapter makes.
+    calls: Vec<CallNode>,
+}
+
+impl Segment {
+    /// Push a call made by the innermost call of `call_stack`.
+    fn push_call(&mut self, call_stack: &mut Vec<usize>, frame: Frame) {
+        let idx = self.calls.len();
+        self.calls.push(CallNode::new(frame));
+        if let Some(caller) = call_stack.last() {
+            self.calls[*caller].children.push(idx);
+        }
+        call_stack.push(idx);
+    }
+
+    fn abort(&mut self, call_stack: &mut Vec<usize>) {
+        for idx in call_stack.drain(..) {
+            self.calls[idx].aborted = true;
+        }
+    }
+}
+
+/// Split the instruction trace into the calls the vm adapter makes, by the calls and returns.
+fn split_segments(
+    instructions: Vec<TracedInstruction>,
+    code: &mut CodeResolver,
+    cost_table: &CostTable,
+) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut current = Segment::default();
+    let mut stack: Vec<Frame> = vec![];
+    // the indexes of the calls of the frames in the `stack`.
+    let mut call_stack: Vec<usize> = vec![];
+    let mut last: Option<Bytecode> = None;
+    for traced in instructions {
+        let frame = Frame::parse(traced.function.as_str());
+        let last_is_call = matches!(
+            last,
+            Some(Bytecode::Call(_)) | Some(Bytecode::CallGeneric(_))
+        );
+        let same_frame = stack.last() == Some(&frame);
+        if stack.is_empty() {
+            stack.push(frame.clone());
+            current.push_call(&mut call_stack, frame.clone());
+        } else if same_frame && !(last_is_call && traced.offset == 0) {
+            // the next instruction of the frame, or the instruction after a native call.
+        } else if last_is_call || (last.is_none() && traced.offset == 0) {
+            stack.push(frame.clone());
+            current.push_call(&mut call_stack, frame.clone());
+        } else if let Some(pos) = stack.iter().rposition(|f| f == &frame) {
+            // returned to the caller, the return is missed because the code is unknown.
+            stack.truncate(pos + 1);
+            call_stack.truncate(pos + 1);
+        } else {
+            // the previous call aborted without the abort instruction, such as a native abort.
+            current.abort(&mut call_stack);
+            segments.push(std::mem::take(&mut current));
+            stack = vec![frame.clone()];
+            current.push_call(&mut call_stack, frame.clone());
+        }
+        if current.first.is_none() {
+            current.first = Some(frame);
+        }
+        let instruction = code
+            .code(stack.last().expect("stack is not empty"))
+            .and_then(|code| code.get(traced.offset as usize))
+            .cloned();
+        let cost = instruction
+            .as_ref()
+            .map(|instr| {
+                cost_table
+                    .instruction_cost(instruction_key(instr))
+                    .total()
+                    .get()
+            })
+            .unwrap_or_default()
+            .max(1);
+        let names = stack.iter().map(|f| f.name.clone()).collect();
+        let sample = current
+            .samples
+            .entry((names, traced.offset))
+            .or_insert_with(|| (instruction.as_ref().map(|i| format!("{:?}", i)), 0, 0));
+        sample.1 += 1;
+        sample.2 += cost;
+        for idx in &call_stack {
+            current.calls[*idx].cost += cost;
+        }
+        if let Some(resource) = instruction.as_ref().and_then(|instr| {
+            code.mutated_resource(stack.last().expect("stack is not empty"), instr)
+        }) {
+            let call = &mut current.calls[*call_stack.last().expect("stack is not empty")];
+            if !call.mutated.contains(&resource) {
+                call.mutated.push(resource);
+            }
+        }
+        match &instruction {
+            Some(Bytecode::Ret) => {
+                stack.pop();
+                call_stack.pop();
+            }
+            Some(Bytecode::Abort) => {
+                stack.clear();
+                current.abort(&mut call_stack);
+            }
+            _ => {}
+        }
+        if stack.is_empty() {
+            segments.push(std::mem::take(&mut current));
+        }
+        last = instruction;
+    }
+    if current.first.is_some() {
+        segments.push(current);
+    }
+    segments
+}
+
+/// Estimate the parts of `total` by the `weights`, the remainder goes to the heaviest one.
+fn apportion(total: u128, weights: &[u64]) -> Vec<u128> {
+    let sum: u128 = weights.iter().map(|w| *w as u128).sum();
+    if sum == 0 {
+        return vec![0; weights.len()];
+    }
+    let mut parts: Vec<u128> = weights.iter().map(|w| total * (*w as u128) / sum).collect();
+    let remainder = total - parts.iter().sum::<u128>();
+    if let Some((idx, _)) = weights.iter().enumerate().max_by_key(|(_, w)| **w) {
+        parts[idx] += remainder;
+    }
+    parts
+}
+
+/// Match the segments to the calls in order, a call may execute no instruction,
+/// such as a script function failed to load. Return None if some segment matches no call.
+fn match_segments(calls: &[CallTrace], segments: Vec<Segment>) -> Option<Vec<Option<Segment>>> {
+    let mut matched: Vec<Option<Segment>> = calls.iter().map(|_| None).collect();
+    let mut next_call = 0;
+    for segment in segments {
+        let pos = calls[next_call..]
+            .iter()
+            .position(|call| segment.first.as_ref() == Some(&Frame::of_call(call)))?;
+        matched[next_call + pos] = Some(segment);
+        next_call += pos + 1;
+    }
+    Some(matched)
+}
+
+fn call_level_sample(call: &CallTrace) -> ProfileSample {
+    ProfileSample {
+        stack: vec![call.kind.to_string(), Frame::of_call(call).name],
+        offset: None,
+        instruction: None,
+        count: 1,
+        gas: call.gas_used,
+        time: call.time,
+    }
+}
+
+/// Build the profile of the `calls`, with the instruction trace read by `reader` if available.
+pub(crate) fn build_profile(
+    state_view: &dyn StateView,
+    payload: Option<&TransactionPayload>,
+    cost_table: &CostTable,
+    calls: &[CallTrace],
+    reader: InstructionTraceReader,
+) -> Result<ExecutionProfile> {
+    let instructions = reader.read()?;
+    if instructions.is_empty() {
+        return Ok(ExecutionProfile {
+            samples: calls.iter().map(call_level_sample).collect(),
+            bytecode_level: false,
+        });
+    }
+    let mut code = CodeResolver::new(state_view, payload);
+    let segments = split_segments(instructions, &mut code, cost_table);
+    let matched = match match_segments(calls, segments) {
+        Some(matched) => matched,
+        None => {
+            warn!(
+                "The instruction trace does not match the calls, fall back to the call level profile."
+            );
+            return Ok(ExecutionProfile {
+                samples: calls.iter().map(call_level_sample).collect(),
+                bytecode_level: false,
+            });
+        }
+    };
+    let mut samples = vec![];
+    for (call, segment) in calls.iter().zip(matched) {
+        let segment = match segment {
+            Some(segment) => segment,
+            None => {
+                samples.push(call_level_sample(call));
+                continue;
+            }
+        };
+        let costs: Vec<u64> = segment.samples.values().map(|(_, _, cost)| *cost).collect();
+        let gas = apportion(call.gas_used as u128, &costs);
+        let time = apportion(call.time.as_nanos(), &costs);
+        for ((((mut stack, offset), (instruction, count, _)), gas), time) in
+            segment.samples.into_iter().zip(gas).zip(time)
+        {
+            stack.insert(0, call.kind.to_string());
+            samples.push(ProfileSample {
+                stack,
+                offset: Some(offset),
+                instruction,
+                count,
+                gas: gas as u64,
+                time: Duration::from_nanos(time as u64),
+            });
+        }
+    }
+    Ok(ExecutionProfile {
+        samples,
+        bytecode_level: true,
+    })
+}
+
+/// The resources mutated by the call `idx` of the segment and its callees.
+fn mutated_resources(segment: &Segment, idx: usize, resources: &mut Vec<(ModuleId, Identifier)>) {
+    let call = &segment.calls[idx];
+    for resource in &call.mutated {
+        if !resources.contains(resource) {
+            resources.push(resource.clone());
+        }
+    }
+    for child in &call.children {
+        mutated_resources(segment, *child, resources);
+    }
+}
+
+/// The items of the `write_set` for the resources mutated by the call `idx` of the segment.
+fn call_writes(segment: &Segment, idx: usize, write_set: &WriteSet) -> Vec<(AccessPath, WriteOp)> {
+    let mut resources = vec![];
+    mutated_resources(segment, idx, &mut resources);
+    write_set
+        .iter()
+        .filter(|(access_path, _)| {
+            access_path.path.as_struct_tag().map_or(false, |tag| {
+                resources.iter().any(|(module, name)| {
+                    tag.address == *module.address()
+                        && tag.module.as_ident_str() == module.name()
+                        && &tag.name == name
+                })
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Convert the callees of the call `idx` of the segment to the nested calls of `call`,
+/// their gas and time are apportioned from `call` by the instruction costs.
+fn nested_calls(
+    segment: &Segment,
+    idx: usize,
+    call: &CallTrace,
+    root_cost: u64,
+    write_set: &WriteSet,
+) -> Vec<CallTrace> {
+    segment.calls[idx]
+        .children
+        .iter()
+        .map(|child| {
+            let node = &segment.calls[*child];
+            let (module, function) = match &node.frame.id {
+                Some(id) => (Some(id.module.clone()), Some(id.function.clone())),
+                None => (None, None),
+            };
+            let weight = |total: u128| total * node.cost as u128 / root_cost.max(1) as u128;
+            CallTrace {
+                kind: CallKind::Call,
+                module,
+                function,
+                ty_args: vec![],
+                args: vec![],
+                return_values: None,
+                gas_used: weight(call.gas_used as u128) as u64,
+                time: Duration::from_nanos(weight(call.time.as_nanos()) as u64),
+                reads: vec![],
+                writes: call_writes(segment, *child, write_set),
+                error: if node.aborted {
+                    call.error.clone()
+                } else {
+                    None
+                },
+                children: nested_calls(segment, *child, call, root_cost, write_set),
+            }
+        })
+        .collect()
+}
+
+/// Add the Move functions called inside the `calls` from the instruction trace read by `reader`,
+/// and attribute the items of the `write_set` to the calls which mutate the resources.
+/// The `calls` are unchanged if the instruction trace is not available or does not match them.
+pub(crate) fn build_call_tree(
+    state_view: &dyn StateView,
+    payload: Option<&TransactionPayload>,
+    cost_table: &CostTable,
+    calls: &mut [CallTrace],
+    write_set: &WriteSet,
+    reader: &InstructionTraceReader,
+) -> Result<()> {
+    let instructions = reader.read()?;
+    if instructions.is_empty() {
+        return Ok(());
+    }
+    let mut code = CodeResolver::new(state_view, payload);
+    let segments = split_segments(instructions, &mut code, cost_table);
+    let matched = match match_segments(calls, segments) {
+        Some(matched) => matched,
+        None => {
+            warn!("The instruction trace does not match the calls, the nested calls are omitted.");
+            return Ok(());
+        }
+    };
+    for (call, segment) in calls.iter_mut().zip(matched) {
+        if let Some(segment) = segment {
+            let root_cost = segment.calls[0].cost;
+            call.children = nested_calls(&segment, 0, call, root_cost, write_set);
+            call.writes = call_writes(&segment, 0, write_set);
+        }
+    }
+    Ok(())
+}
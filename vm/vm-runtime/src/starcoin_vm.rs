@@ -6,14 +6,17 @@ use crate::data_cache::{RemoteStorage, StateViewCache};
 use crate::errors::{
     convert_normal_success_epilogue_error, convert_prologue_runtime_error, error_split,
 };
+use crate::execution_trace::{CallKind, ExecutionTrace, ExecutionTracer, TracingStateView};
 use crate::metrics::VMMetrics;
+use crate::parallel_executor::ParallelExecutor;
+use crate::profiler::{self, ExecutionProfile, InstructionTraceReader};
 use anyhow::{format_err, Error, Result};
 use crypto::HashValue;
 use move_core_types::resolver::MoveResolver;
 use move_vm_runtime::move_vm::MoveVM;
 use move_vm_runtime::move_vm_adapter::{PublishModuleBundleOption, SessionAdapter};
 use move_vm_runtime::session::Session;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use starcoin_config::G_LATEST_GAS_SCHEDULE;
 use starcoin_logger::prelude::*;
 use starcoin_types::account_config::config_change::ConfigChangeEvent;
@@ -64,12 +67,15 @@ use starcoin_vm_types::{
     values::Value,
     vm_status::{StatusCode, VMStatus},
 };
+use std::cmp;
 use std::convert::{TryFrom, TryInto};
 use std::sync::Arc;
 
 static G_ZERO_COST_SCHEDULE: Lazy<CostTable> =
     Lazy::new(|| zero_cost_schedule(NativeCostIndex::NUMBER_OF_NATIVE_FUNCTIONS));
 
+static G_EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
+
 #[derive(Clone)]
 #[allow(clippy::upper_case_acronyms)]
 /// Wrapper of MoveVM
@@ -79,6 +85,8 @@ pub struct StarcoinVM {
     version: Option<Version>,
     move_version: Option<MoveLanguageVersion>,
     metrics: Option<VMMetrics>,
+    concurrency_level: usize,
+    tracer: Option<Arc<ExecutionTracer>>,
 }
 
 /// marking of stdlib version which includes vmconfig upgrades.
@@ -94,9 +102,28 @@ impl StarcoinVM {
             version: None,
             move_version: None,
             metrics,
+            concurrency_level: Self::get_concurrency_level(),
+            tracer: None,
         }
     }
 
+    /// Set the concurrency level of block execution for all the vm instances created later,
+    /// only the first call takes effect. Level 1 means sequential execution.
+    pub fn set_concurrency_level_once(concurrency_level: usize) {
+        G_EXECUTION_CONCURRENCY_LEVEL
+            .set(cmp::max(concurrency_level, 1))
+            .ok();
+    }
+
+    pub fn get_concurrency_level() -> usize {
+        G_EXECUTION_CONCURRENCY_LEVEL.get().copied().unwrap_or(1)
+    }
+
+    /// Set the concurrency level of block execution for this vm instance.
+    pub fn set_concurrency_level(&mut self, concurrency_level: usize) {
+        self.concurrency_level = cmp::max(concurrency_level, 1);
+    }
+
     pub fn load_configs(&mut self, state: &dyn StateView) -> Result<(), Error> {
         if state.is_genesis() {
             self.vm_config = Some(VMConfig {
@@ -518,7 +545,15 @@ impl StarcoinVM {
                     init_script.function(),
                     sender
                 );
-                session
+                self.trace_begin_call(
+                    CallKind::InitScript,
+                    Some(init_script.module()),
+                    Some(init_script.function()),
+                    init_script.ty_args(),
+                    init_script.args(),
+                    cost_strategy,
+                );
+                let result = session
                     .as_mut()
                     .execute_script_function(
                         init_script.module(),
@@ -528,7 +563,9 @@ impl StarcoinVM {
                         vec![sender],
                         cost_strategy,
                     )
-                    .map_err(|e| e.into_vm_status())?;
+                    .map_err(|e| e.into_vm_status());
+                self.trace_end_call(cost_strategy, &result, |_| vec![]);
+                result?;
             }
             charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
 
@@ -567,13 +604,21 @@ impl StarcoinVM {
             cost_strategy
                 .charge_intrinsic_gas(txn_data.transaction_size())
                 .map_err(|e| e.into_vm_status())?;
-            match payload {
+            let result = match payload {
                 TransactionPayload::Script(script) => {
                     // we only use the ok path, let move vm handle the wrong path.
                     if let Ok(s) = CompiledScript::deserialize(script.code()) {
                         self.check_move_version(s.version() as u64)?;
                     };
 
+                    self.trace_begin_call(
+                        CallKind::Script,
+                        None,
+                        None,
+                        script.ty_args(),
+                        script.args(),
+                        cost_strategy,
+                    );
                     session.as_mut().execute_script(
                         script.code().to_vec(),
                         script.ty_args().to_vec(),
@@ -583,6 +628,14 @@ impl StarcoinVM {
                     )
                 }
                 TransactionPayload::ScriptFunction(script_function) => {
+                    self.trace_begin_call(
+                        CallKind::ScriptFunction,
+                        Some(script_function.module()),
+                        Some(script_function.function()),
+                        script_function.ty_args(),
+                        script_function.args(),
+                        cost_strategy,
+                    );
                     session.as_mut().execute_script_function(
                         script_function.module(),
                         script_function.function(),
@@ -596,7 +649,9 @@ impl StarcoinVM {
                     return Err(VMStatus::Error(StatusCode::UNREACHABLE));
                 }
             }
-            .map_err(|e| e.into_vm_status())?;
+            .map_err(|e| e.into_vm_status());
+            self.trace_end_call(cost_strategy, &result, |_| vec![]);
+            result?;
 
             charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
 
@@ -646,29 +701,41 @@ impl StarcoinVM {
         };
 
         // Run prologue by genesis account
-        session
+        let ty_args = vec![gas_token_ty];
+        let args = serialize_values(&vec![
+            MoveValue::Signer(genesis_address),
+            MoveValue::Address(txn_data.sender),
+            MoveValue::U64(txn_sequence_number),
+            MoveValue::vector_u8(authentication_key_preimage),
+            MoveValue::U64(txn_gas_price),
+            MoveValue::U64(txn_max_gas_amount),
+            MoveValue::U64(txn_expiration_time),
+            MoveValue::U8(chain_id),
+            MoveValue::U8(payload_type.into()),
+            MoveValue::vector_u8(script_or_package_hash.to_vec()),
+            MoveValue::Address(package_address),
+        ]);
+        self.trace_begin_call(
+            CallKind::Prologue,
+            Some(&*account_config::G_TRANSACTION_MANAGER_MODULE),
+            Some(G_PROLOGUE_NAME.as_ident_str()),
+            &ty_args,
+            &args,
+            gas_status,
+        );
+        let result = session
             .as_mut()
             .execute_function(
                 &account_config::G_TRANSACTION_MANAGER_MODULE,
                 &G_PROLOGUE_NAME,
-                vec![gas_token_ty],
-                serialize_values(&vec![
-                    MoveValue::Signer(genesis_address),
-                    MoveValue::Address(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::vector_u8(authentication_key_preimage),
-                    MoveValue::U64(txn_gas_price),
-                    MoveValue::U64(txn_max_gas_amount),
-                    MoveValue::U64(txn_expiration_time),
-                    MoveValue::U8(chain_id),
-                    MoveValue::U8(payload_type.into()),
-                    MoveValue::vector_u8(script_or_package_hash.to_vec()),
-                    MoveValue::Address(package_address),
-                ]),
+                ty_args,
+                args,
                 gas_status,
             )
             .map(|_return_vals| ())
-            .or_else(convert_prologue_runtime_error)
+            .or_else(convert_prologue_runtime_error);
+        self.trace_end_call(gas_status, &result, |_| vec![]);
+        result
     }
 
     /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
@@ -742,17 +809,64 @@ impl StarcoinVM {
                 ]),
             )
         };
-        session
+        let ty_args = vec![gas_token_ty];
+        self.trace_begin_call(
+            CallKind::Epilogue,
+            Some(&*account_config::G_TRANSACTION_MANAGER_MODULE),
+            Some(function_name.as_ident_str()),
+            &ty_args,
+            &args,
+            gas_status,
+        );
+        let result = session
             .as_mut()
             .execute_function(
                 &account_config::G_TRANSACTION_MANAGER_MODULE,
                 function_name,
-                vec![gas_token_ty],
+                ty_args,
                 args,
                 gas_status,
             )
             .map(|_return_vals| ())
-            .or_else(convert_normal_success_epilogue_error)
+            .or_else(convert_normal_success_epilogue_error);
+        self.trace_end_call(gas_status, &result, |_| vec![]);
+        result
+    }
+
+    fn trace_begin_call(
+        &self,
+        kind: CallKind,
+        module: Option<&ModuleId>,
+        function: Option<&IdentStr>,
+        ty_args: &[TypeTag],
+        args: &[Vec<u8>],
+        gas_status: &GasStatus,
+    ) {
+        if let Some(tracer) = self.tracer.as_ref() {
+            tracer.begin_call(
+                kind,
+                module.cloned(),
+                function.map(|f| f.to_owned()),
+                ty_args.to_vec(),
+                args.to_vec(),
+                gas_status.remaining_gas().get(),
+            );
+        }
+    }
+
+    fn trace_end_call<T>(
+        &self,
+        gas_status: &GasStatus,
+        result: &Result<T, VMStatus>,
+        return_values: fn(&T) -> Vec<Vec<u8>>,
+    ) {
+        if let Some(tracer) = self.tracer.as_ref() {
+            tracer.end_call(
+                gas_status.remaining_gas().get(),
+                result.as_ref().ok().map(return_values),
+                result.as_ref().err().cloned(),
+            );
+        }
     }
 
     fn process_block_metadata(
@@ -815,7 +929,7 @@ impl StarcoinVM {
         )
     }
 
-    fn execute_user_transaction(
+    pub(crate) fn execute_user_transaction(
         &mut self,
         txn: SignedUserTransaction,
         remote_cache: &mut StateViewCache<'_>,
@@ -965,20 +1079,202 @@ impl StarcoinVM {
         })
     }
 
+    /// Dry run the transaction like `dry_run_transaction`, and return the execution trace.
+    pub fn trace_dry_run_transaction(
+        &mut self,
+        state_view: &dyn StateView,
+        txn: DryRunTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, ExecutionTrace)> {
+        let payload = txn.raw_txn.payload().clone();
+        let reader = InstructionTraceReader::new();
+        let tracer = Arc::new(ExecutionTracer::default());
+        let tracing_view = TracingStateView::new(state_view, &tracer);
+        self.tracer = Some(tracer.clone());
+        let result = self.dry_run_transaction(&tracing_view, txn);
+        self.tracer = None;
+        let (status, output) = result?;
+        let mut trace = tracer.take_trace();
+        self.build_call_tree(state_view, &payload, &mut trace, &output, &reader)?;
+        Ok((status, output, trace))
+    }
+
+    /// Execute the user transaction on `state_view`, and return the execution trace.
+    /// The output is not applied to the state.
+    pub fn trace_user_transaction(
+        &mut self,
+        state_view: &dyn StateView,
+        txn: SignedUserTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, ExecutionTrace)> {
+        let payload = txn.payload().clone();
+        let reader = InstructionTraceReader::new();
+        let tracer = Arc::new(ExecutionTracer::default());
+        let tracing_view = TracingStateView::new(state_view, &tracer);
+        let mut data_cache = StateViewCache::new(&tracing_view);
+        self.load_configs(&data_cache)?;
+        self.tracer = Some(tracer.clone());
+        let (status, output) = self.execute_user_transaction(txn, &mut data_cache);
+        self.tracer = None;
+        let mut trace = tracer.take_trace();
+        self.build_call_tree(state_view, &payload, &mut trace, &output, &reader)?;
+        Ok((status, output, trace))
+    }
+
+    /// Dry run the transaction like `dry_run_transaction`, and return the gas and time profile.
+    pub fn profile_dry_run_transaction(
+        &mut self,
+        state_view: &dyn StateView,
+        txn: DryRunTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, ExecutionProfile)> {
+        let payload = txn.raw_txn.payload().clone();
+        let reader = InstructionTraceReader::new();
+        let (status, output, trace) = self.trace_dry_run_transaction(state_view, txn)?;
+        let profile = self.build_profile(state_view, Some(&payload), &trace, reader)?;
+        Ok((status, output, profile))
+    }
+
+    /// Execute the user transaction on `state_view`, and return the gas and time profile.
+    /// The output is not applied to the state.
+    pub fn profile_user_transaction(
+        &mut self,
+        state_view: &dyn StateView,
+        txn: SignedUserTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, ExecutionProfile)> {
+        let payload = txn.payload().clone();
+        let reader = InstructionTraceReader::new();
+        let (status, output, trace) = self.trace_user_transaction(state_view, txn)?;
+        let profile = self.build_profile(state_view, Some(&payload), &trace, reader)?;
+        Ok((status, output, profile))
+    }
+
+    /// Execute the readonly function like `execute_readonly_function`, and return the profile.
+    /// Different from `execute_readonly_function`, the gas is charged by the on-chain gas schedule,
+    /// bounded by the `maximum_number_of_gas_units`.
+    pub fn profile_readonly_function(
+        &mut self,
+        state_view: &dyn StateView,
+        module: &ModuleId,
+        function_name: &IdentStr,
+        type_params: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> Result<(Vec<Vec<u8>>, ExecutionProfile)> {
+        let data_cache = StateViewCache::new(state_view);
+        self.load_configs(&data_cache)?;
+        let reader = InstructionTraceReader::new();
+        let tracer = Arc::new(ExecutionTracer::default());
+        self.tracer = Some(tracer.clone());
+        let result = self.execute_metered_readonly_function(
+            &data_cache,
+            module,
+            function_name,
+            type_params,
+            args,
+        );
+        self.tracer = None;
+        let trace = tracer.take_trace();
+        let result = result.map_err(|e| format_err!("Execute function failed: {:?}", e))?;
+        let profile = self.build_profile(state_view, None, &trace, reader)?;
+        Ok((result, profile))
+    }
+
+    fn execute_metered_readonly_function(
+        &self,
+        data_cache: &StateViewCache<'_>,
+        module: &ModuleId,
+        function_name: &IdentStr,
+        type_params: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>, VMStatus> {
+        let gas_schedule = self.get_gas_schedule()?;
+        let mut gas_status = GasStatus::new(
+            gas_schedule,
+            gas_schedule.gas_constants.maximum_number_of_gas_units,
+        );
+        let mut session = self.move_vm.new_session(data_cache);
+        self.trace_begin_call(
+            CallKind::Function,
+            Some(module),
+            Some(function_name),
+            &type_params,
+            &args,
+            &gas_status,
+        );
+        let result = session
+            .execute_function(module, function_name, type_params, args, &mut gas_status)
+            .map_err(|e| e.into_vm_status());
+        self.trace_end_call(&gas_status, &result, Clone::clone);
+        let result = result?;
+
+        let (changeset, events) = session.finish().map_err(|e| e.into_vm_status())?;
+        let (writeset, _events) = convert_changeset_and_events(changeset, events)?;
+        if !writeset.is_empty() {
+            warn!("Readonly function {} changes state", function_name);
+            return Err(VMStatus::Error(StatusCode::REJECTED_WRITE_SET));
+        }
+        Ok(result)
+    }
+
+    fn build_profile(
+        &self,
+        state_view: &dyn StateView,
+        payload: Option<&TransactionPayload>,
+        trace: &ExecutionTrace,
+        reader: InstructionTraceReader,
+    ) -> Result<ExecutionProfile> {
+        let cost_table = self.get_gas_schedule().unwrap_or(&*G_LATEST_GAS_SCHEDULE);
+        profiler::build_profile(state_view, payload, cost_table, &trace.calls, reader)
+    }
+
+    fn build_call_tree(
+        &self,
+        state_view: &dyn StateView,
+        payload: &TransactionPayload,
+        trace: &mut ExecutionTrace,
+        output: &TransactionOutput,
+        reader: &InstructionTraceReader,
+    ) -> Result<()> {
+        let cost_table = self.get_gas_schedule().unwrap_or(&*G_LATEST_GAS_SCHEDULE);
+        profiler::build_call_tree(
+            state_view,
+            Some(payload),
+            cost_table,
+            &mut trace.calls,
+            output.write_set(),
+            reader,
+        )
+    }
+
+    /// Reload the vm configs if the output contains reconfigure events, return whether reloaded.
     fn check_reconfigure(
         &mut self,
         state_view: &dyn StateView,
         output: &TransactionOutput,
-    ) -> Result<(), Error> {
+    ) -> Result<bool, Error> {
+        let mut reconfigured = false;
         for event in output.events() {
             if event.key().get_creator_address() == genesis_address()
                 && (event.is::<UpgradeEvent>() || event.is::<ConfigChangeEvent<Version>>())
             {
                 info!("Load vm configs trigger by reconfigure event. ");
                 self.load_configs(state_view)?;
+                reconfigured = true;
             }
         }
-        Ok(())
+        Ok(reconfigured)
+    }
+
+    /// Module publishing changes the code cache of the shared `MoveVM`,
+    /// so only the chunks without packages are executed in parallel.
+    fn can_execute_in_parallel(
+        &self,
+        state_view: &dyn StateView,
+        txns: &[SignedUserTransaction],
+    ) -> bool {
+        self.concurrency_level > 1
+            && txns.len() > 1
+            && !state_view.is_genesis()
+            && txns
+                .iter()
+                .all(|txn| !matches!(txn.payload(), TransactionPayload::Package(_)))
     }
 
     /// Execute a block transactions with gas_limit,
@@ -1000,6 +1296,51 @@ impl StarcoinVM {
         'outer: for block in blocks {
             let txn_type_name = block.type_name().to_string();
             match block {
+                TransactionBlock::UserTransaction(mut txns)
+                    if self.can_execute_in_parallel(&data_cache, &txns) =>
+                {
+                    while !txns.is_empty() {
+                        let timer = self.metrics.as_ref().map(|metrics| {
+                            metrics
+                                .vm_txn_exe_time
+                                .with_label_values(&["ParallelUserTransaction"])
+                                .start_timer()
+                        });
+                        let outputs = ParallelExecutor::new(self, self.concurrency_level)
+                            .execute_transactions(&data_cache, &txns)?;
+                        if let Some(timer) = timer {
+                            timer.observe_duration();
+                        }
+                        let mut remaining_txns = vec![];
+                        for (idx, (status, output)) in outputs.into_iter().enumerate() {
+                            match gas_left.checked_sub(output.gas_used()) {
+                                Some(l) => gas_left = l,
+                                None => break 'outer,
+                            }
+                            if let TransactionStatus::Keep(_) = output.status() {
+                                data_cache.push_write_set(output.write_set())
+                            }
+                            // the txns after reconfiguration must be executed with the new configs.
+                            let reconfigured = self.check_reconfigure(&data_cache, &output)?;
+                            if let Some(metrics) = self.metrics.as_ref() {
+                                metrics.vm_txn_gas_usage.observe(output.gas_used() as f64);
+                                metrics
+                                    .vm_txn_exe_total
+                                    .with_label_values(&[
+                                        txn_type_name.as_str(),
+                                        status.status_type().to_string().as_str(),
+                                    ])
+                                    .inc();
+                            }
+                            result.push((status, output));
+                            if reconfigured {
+                                remaining_txns = txns.split_off(idx + 1);
+                                break;
+                            }
+                        }
+                        txns = remaining_txns;
+                    }
+                }
                 TransactionBlock::UserTransaction(txns) => {
                     for transaction in txns {
                         let timer = self.metrics.as_ref().map(|metrics| {